@@ -0,0 +1,4 @@
+pub mod errors;
+pub mod data;
+pub mod ap_controller;
+pub mod validators;
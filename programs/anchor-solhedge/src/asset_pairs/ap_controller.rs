@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+use crate::asset_pairs::validators::*;
+use crate::asset_pairs::errors::AssetPairError;
+use crate::asset_pairs::data::{AssetPairConfig, SeriesIndex, SeriesEntry, SeriesKind};
+use crate::AssetPairConfigParams;
+use crate::insurance_funds::if_controller::init_insurance_fund_if_needed;
+use crate::fee_vaults::fv_controller::init_fee_vault_if_needed;
+use crate::fee_tiers::ft_controller::init_fee_tiers_if_needed;
+use crate::{
+    FREEZE_SECONDS,
+    MAX_MATURITY_FUTURE_SECONDS,
+    MAX_PROTOCOL_FEES_BPS,
+    DAILY_MATURITY_GRID_SECONDS,
+    WEEKLY_MATURITY_GRID_SECONDS
+};
+
+pub fn admin_create_asset_pair_config(ctx: Context<AdminCreateAssetPairConfig>,
+    params: AssetPairConfigParams
+) -> Result<()> {
+    check_asset_pair_config_params(&params)?;
+
+    ctx.accounts.asset_pair_config.base_asset = ctx.accounts.base_asset_mint.key();
+    ctx.accounts.asset_pair_config.quote_asset = ctx.accounts.quote_asset_mint.key();
+    write_asset_pair_config_params(&mut ctx.accounts.asset_pair_config, &params);
+
+    ctx.accounts.asset_pair_config.is_initialized = true;
+    msg!("AssetPairConfig initialized");
+
+    ctx.accounts.series_index.base_asset = ctx.accounts.base_asset_mint.key();
+    ctx.accounts.series_index.quote_asset = ctx.accounts.quote_asset_mint.key();
+    ctx.accounts.series_index.entries = Vec::new();
+    ctx.accounts.series_index.is_initialized = true;
+    msg!("SeriesIndex initialized");
+
+    let quote_asset = ctx.accounts.quote_asset_mint.key();
+    init_insurance_fund_if_needed(&mut ctx.accounts.insurance_fund_info, quote_asset);
+    init_fee_vault_if_needed(&mut ctx.accounts.fee_vault_info, quote_asset);
+    init_fee_tiers_if_needed(&mut ctx.accounts.fee_tiers_info, quote_asset);
+
+    Ok(())
+}
+
+pub fn admin_update_asset_pair_config(ctx: Context<AdminUpdateAssetPairConfig>,
+    params: AssetPairConfigParams
+) -> Result<()> {
+    check_asset_pair_config_params(&params)?;
+
+    write_asset_pair_config_params(&mut ctx.accounts.asset_pair_config, &params);
+    msg!("AssetPairConfig updated");
+
+    Ok(())
+}
+
+// Checks that a new vault factory for this pair respects the pair configuration. Vault factories
+// that do not use lots (such as binary options) pass None as lot_size
+pub fn check_asset_pair_allows_factory(asset_pair_config: &Account<AssetPairConfig>,
+    lot_size: Option<i8>,
+    strike: u64,
+    maturity: u64,
+    current_time: u64
+) -> Result<()> {
+    require!(
+        asset_pair_config.enabled,
+        AssetPairError::AssetPairDisabled
+    );
+
+    if let Some(lot_size) = lot_size {
+        require!(
+            lot_size >= asset_pair_config.min_lot_size && lot_size <= asset_pair_config.max_lot_size,
+            AssetPairError::LotSizeOutOfRange
+        );
+    }
+
+    require!(
+        strike >= asset_pair_config.min_strike && strike <= asset_pair_config.max_strike,
+        AssetPairError::StrikeOutOfRange
+    );
+
+    require!(
+        maturity <= current_time.checked_add(asset_pair_config.max_maturity_seconds).unwrap(),
+        AssetPairError::MaturityTooLate
+    );
+
+    if asset_pair_config.standardized {
+        require!(
+            strike % asset_pair_config.strike_tick == 0,
+            AssetPairError::OffGridStrike
+        );
+        require!(
+            maturity >= asset_pair_config.maturity_grid_offset_seconds &&
+            (maturity - asset_pair_config.maturity_grid_offset_seconds) % asset_pair_config.maturity_grid_seconds == 0,
+            AssetPairError::OffGridMaturity
+        );
+    }
+
+    Ok(())
+}
+
+// Lists a newly created vault factory in the series index of its pair. Series that already
+// matured are dropped first, since factories in emergency mode never get a settle price.
+// Instructions creating vault factories have already grown the index to fit one more series
+pub fn add_series_to_index(series_index: &mut Account<SeriesIndex>,
+    kind: SeriesKind,
+    factory: Pubkey,
+    strike: u64,
+    maturity: u64,
+    current_time: u64
+) {
+    series_index.entries.retain(|entry| entry.maturity > current_time);
+    series_index.entries.push(SeriesEntry {
+        maturity,
+        strike,
+        kind,
+        factory
+    });
+    msg!("Series {} listed in series index", factory);
+}
+
+// Removes a settled vault factory from the series index of its pair, if it is listed.
+// The last entry takes the place of the removed one
+pub fn remove_series_from_index(series_index: &mut Account<SeriesIndex>, factory: Pubkey) {
+    if let Some(i) = series_index.entries.iter().position(|entry| entry.factory == factory) {
+        series_index.entries.swap_remove(i);
+        msg!("Series {} removed from series index", factory);
+    }
+}
+
+fn check_asset_pair_config_params(params: &AssetPairConfigParams) -> Result<()> {
+    require!(
+        params.min_lot_size <= params.max_lot_size,
+        AssetPairError::InvalidLotSizeRange
+    );
+
+    require!(
+        params.min_strike > 0 && params.min_strike <= params.max_strike,
+        AssetPairError::InvalidStrikeRange
+    );
+
+    require!(
+        params.max_maturity_seconds > FREEZE_SECONDS && params.max_maturity_seconds <= MAX_MATURITY_FUTURE_SECONDS,
+        AssetPairError::InvalidMaxMaturity
+    );
+
+    require!(
+        params.protocol_fees_bps <= MAX_PROTOCOL_FEES_BPS,
+        AssetPairError::FeesTooHigh
+    );
+
+    if params.standardized {
+        require!(
+            params.strike_tick > 0 &&
+            (params.maturity_grid_seconds == DAILY_MATURITY_GRID_SECONDS || params.maturity_grid_seconds == WEEKLY_MATURITY_GRID_SECONDS) &&
+            params.maturity_grid_offset_seconds < params.maturity_grid_seconds,
+            AssetPairError::InvalidListingRules
+        );
+    }
+
+    Ok(())
+}
+
+fn write_asset_pair_config_params(asset_pair_config: &mut Account<AssetPairConfig>, params: &AssetPairConfigParams) {
+    asset_pair_config.enabled = params.enabled;
+    asset_pair_config.min_lot_size = params.min_lot_size;
+    asset_pair_config.max_lot_size = params.max_lot_size;
+    asset_pair_config.min_strike = params.min_strike;
+    asset_pair_config.max_strike = params.max_strike;
+    asset_pair_config.max_maturity_seconds = params.max_maturity_seconds;
+    asset_pair_config.price_source = params.price_source;
+    asset_pair_config.protocol_fees_bps = params.protocol_fees_bps;
+    asset_pair_config.standardized = params.standardized;
+    asset_pair_config.strike_tick = params.strike_tick;
+    asset_pair_config.maturity_grid_seconds = params.maturity_grid_seconds;
+    asset_pair_config.maturity_grid_offset_seconds = params.maturity_grid_offset_seconds;
+}
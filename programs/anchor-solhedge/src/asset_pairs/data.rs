@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct AssetPairConfig {
+    pub is_initialized: bool,
+    pub enabled: bool,              // vault factories can only be created for enabled pairs
+
+    pub base_asset: Pubkey,
+    pub quote_asset: Pubkey,
+
+    pub min_lot_size: i8,           // allowed lot_size range, 10^lot_size, for instance 0 means 1; -1 means 0.1; 2 means 100
+    pub max_lot_size: i8,
+    pub min_strike: u64,            // allowed strike range, in quote asset lamports
+    pub max_strike: u64,
+    pub max_maturity_seconds: u64,  // maturity of new vault factories is at most this far in the future (and never beyond MAX_MATURITY_FUTURE_SECONDS)
+    pub price_source: Pubkey,       // account the oracle reads prices for this pair from
+    pub protocol_fees_bps: u16,     // fees charged on premiums, in basis points, split between protocol and frontend
+
+    // Listing rules of standardized pairs, so that liquidity is not fragmented across near-identical series
+    pub standardized: bool,
+    pub strike_tick: u64,                   // strikes must be multiples of this, in quote asset lamports
+    pub maturity_grid_seconds: u64,         // DAILY_MATURITY_GRID_SECONDS or WEEKLY_MATURITY_GRID_SECONDS
+    pub maturity_grid_offset_seconds: u64   // expiry time inside the grid period, counted from the unix epoch (a Thursday, 00:00 UTC)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SeriesKind {
+    #[default]
+    Put,
+    Call,
+    BinaryPut,
+    BinaryCall
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct SeriesEntry {
+    pub maturity: u64,
+    pub strike: u64,
+    pub kind: SeriesKind,
+    pub factory: Pubkey             // PutOptionVaultFactoryInfo, CallOptionVaultFactoryInfo or BinaryOptionVaultFactoryInfo
+}
+
+impl SeriesEntry {
+    pub const SIZE: usize = 8 + 8 + 1 + 32;
+}
+
+// Live option series of an asset pair, so that clients can list them with a single account read.
+// The account grows (paid by whoever creates a vault factory) when a new series does not fit,
+// and the order of entries is not preserved on removal
+#[account]
+pub struct SeriesIndex {
+    pub is_initialized: bool,
+
+    pub base_asset: Pubkey,
+    pub quote_asset: Pubkey,
+
+    pub entries: Vec<SeriesEntry>
+}
+
+impl SeriesIndex {
+    // Account size (with discriminator) that holds num_entries entries
+    pub fn space(num_entries: usize) -> usize {
+        8 + 1 + 32 + 32 + 4 + num_entries*SeriesEntry::SIZE
+    }
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum AssetPairError {
+    #[msg("min_lot_size cannot be greater than max_lot_size")]
+    InvalidLotSizeRange,
+
+    #[msg("min_strike must be positive and not greater than max_strike")]
+    InvalidStrikeRange,
+
+    #[msg("max_maturity_seconds must be after the freeze period and at most MAX_MATURITY_FUTURE_SECONDS")]
+    InvalidMaxMaturity,
+
+    #[msg("Protocol fees are above the allowed maximum")]
+    FeesTooHigh,
+
+    #[msg("Asset pair is disabled")]
+    AssetPairDisabled,
+
+    #[msg("lot_size is outside the range allowed for this asset pair")]
+    LotSizeOutOfRange,
+
+    #[msg("strike is outside the range allowed for this asset pair")]
+    StrikeOutOfRange,
+
+    #[msg("maturity is too late for this asset pair")]
+    MaturityTooLate,
+
+    #[msg("Base and quote asset mints must belong to the same token program")]
+    TokenProgramMismatch,
+
+    #[msg("Standardized pairs need a positive strike tick, a daily or weekly maturity grid and an offset inside the grid period")]
+    InvalidListingRules,
+
+    #[msg("strike is not a multiple of the strike tick of this asset pair")]
+    OffGridStrike,
+
+    #[msg("maturity is not on the expiry grid of this asset pair")]
+    OffGridMaturity
+}
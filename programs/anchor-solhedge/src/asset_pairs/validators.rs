@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use crate::asset_pairs::data::{AssetPairConfig, SeriesIndex};
+use crate::insurance_funds::data::InsuranceFundInfo;
+use crate::fee_vaults::data::FeeVaultInfo;
+use crate::fee_tiers::data::FeeTiersInfo;
+use anchor_spl::token_interface::{Mint, TokenInterface, TokenAccount};
+use anchor_spl::associated_token::AssociatedToken;
+use crate::ADMIN_ADDRESS;
+use crate::asset_pairs::errors::AssetPairError;
+
+#[derive(Accounts)]
+pub struct AdminCreateAssetPairConfig<'info> {
+    #[account(
+        init,
+        seeds=["AssetPairConfig".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<AssetPairConfig>() + 8
+    )]
+    pub asset_pair_config: Account<'info, AssetPairConfig>,
+
+    #[account(
+        init,
+        seeds=["SeriesIndex".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        payer = initializer,
+        space = SeriesIndex::space(0)
+    )]
+    pub series_index: Box<Account<'info, SeriesIndex>>,
+
+    // Instructions charging fees take the insurance fund, fee vault and fee tiers of the quote asset,
+    // so they are created with the first pair of the quote asset
+    #[account(
+        init_if_needed,
+        seeds=["InsuranceFundInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<InsuranceFundInfo>() + 8
+    )]
+    pub insurance_fund_info: Box<Account<'info, InsuranceFundInfo>>,
+
+    #[account(
+        init_if_needed,
+        payer = initializer, // Payer will be initializer
+        associated_token::mint = quote_asset_mint, // Quote asset mint
+        associated_token::authority = insurance_fund_info // Authority set to PDA
+    )]
+    pub insurance_fund_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        seeds=["FeeVaultInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<FeeVaultInfo>() + 8
+    )]
+    pub fee_vault_info: Box<Account<'info, FeeVaultInfo>>,
+
+    #[account(
+        init_if_needed,
+        payer = initializer, // Payer will be initializer
+        associated_token::mint = quote_asset_mint, // Quote asset mint
+        associated_token::authority = fee_vault_info // Authority set to PDA
+    )]
+    pub fee_vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        seeds=["FeeTiersInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<FeeTiersInfo>() + 8
+    )]
+    pub fee_tiers_info: Box<Account<'info, FeeTiersInfo>>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset, vault instructions take a single token program
+    // so both mints must be SPL Token or both Token-2022
+    #[account(
+        constraint = quote_asset_mint.to_account_info().owner == base_asset_mint.to_account_info().owner @ AssetPairError::TokenProgramMismatch
+    )]
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // Check if initializer is signer, should also be the admin, mut is required to reduce lamports (fees)
+    #[account(
+        mut,
+        constraint = initializer.key() == ADMIN_ADDRESS
+    )]
+    pub initializer: Signer<'info>,
+
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to create the treasuries
+    pub token_program: Interface<'info, TokenInterface>,
+    // Associated Token Program required to create the treasuries
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct AdminUpdateAssetPairConfig<'info> {
+    #[account(
+        mut,
+        seeds=["AssetPairConfig".as_bytes().as_ref(), asset_pair_config.base_asset.as_ref(), asset_pair_config.quote_asset.as_ref()],
+        bump,
+        constraint = asset_pair_config.is_initialized == true
+    )]
+    pub asset_pair_config: Account<'info, AssetPairConfig>,
+
+    // Check if initializer is signer, should also be the admin
+    #[account(
+        constraint = initializer.key() == ADMIN_ADDRESS
+    )]
+    pub initializer: Signer<'info>
+}
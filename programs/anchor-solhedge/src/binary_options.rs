@@ -0,0 +1,4 @@
+pub mod errors;
+pub mod data;
+pub mod bo_controller;
+pub mod validators;
@@ -0,0 +1,939 @@
+use anchor_lang::{prelude::*, system_program};
+use crate::binary_options::validators::*;
+use crate::MakerCreateBinaryOptionParams;
+use crate::binary_options::errors::BinaryOptionError;
+use crate::{
+    FREEZE_SECONDS, 
+    MAX_MATURITY_FUTURE_SECONDS,
+    LAMPORTS_FOR_UPDATE_SETTLEPRICE_TICKET,
+    LAMPORTS_FOR_UPDATE_FAIRPRICE_TICKET,
+    MAX_SECONDS_FROM_LAST_FAIR_PRICE_UPDATE,
+    MAX_REMAINING_MAKERS
+};
+use anchor_spl::token_interface::{self, TransferChecked, TokenAccount};
+use crate::transfer_fees::amount_with_transfer_fee;
+use crate::anchor_solhedge::*;
+use crate::asset_pairs::ap_controller as ap;
+use crate::asset_pairs::data::SeriesKind;
+use crate::frontends::fe_controller::{frontend_fee_share_bps, record_frontend_trade};
+use crate::fee_tiers::ft_controller::init_taker_stats_if_needed;
+use crate::native_sol::{wrap_native_sol_if_needed, unwrap_native_sol_if_needed};
+use crate::emergency_mode::is_emergency_grace_period_over;
+use crate::remaining_accounts::{
+    has_duplicate_accounts,
+    load_writable_program_account,
+    load_writable_token_account,
+    is_program_address
+};
+use crate::binary_options::data::{
+    BinaryOptionVaultFactoryInfo,
+    BinaryOptionVaultInfo,
+    BinaryOptionMakerInfo
+};
+
+pub fn maker_next_binary_option_vault_id(ctx: Context<MakerNextBinaryOptionVaultId>,
+    params: MakerCreateBinaryOptionParams
+) -> Result<u64> {
+
+    require!(
+        params.strike > 0,
+        BinaryOptionError::StrikeZero
+    );
+
+    require!(
+        params.payout_per_lot > 0,
+        BinaryOptionError::PayoutZero
+    );
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        params.maturity > current_time.checked_add(FREEZE_SECONDS).unwrap(),
+        BinaryOptionError::MaturityTooEarly
+    );
+
+    require!(
+        params.maturity <= current_time.checked_add(MAX_MATURITY_FUTURE_SECONDS).unwrap(),
+        BinaryOptionError::MaturityTooLate
+    );
+
+
+    // Only asset pairs registered by the admin are allowed
+    ap::check_asset_pair_allows_factory(&ctx.accounts.asset_pair_config, None, params.strike, params.maturity, current_time)?;
+
+    // Initializing factory vault (BinaryOptionVaultFactoryInfo) if it has been just created
+    if !ctx.accounts.vault_factory_info.is_initialized {
+        ctx.accounts.vault_factory_info.next_vault_id = 1;
+        ctx.accounts.vault_factory_info.maturity = params.maturity;
+        ctx.accounts.vault_factory_info.matured = false;
+        ctx.accounts.vault_factory_info.strike = params.strike;
+        ctx.accounts.vault_factory_info.payout_per_lot = params.payout_per_lot;
+        ctx.accounts.vault_factory_info.is_put = params.is_put;
+        ctx.accounts.vault_factory_info.base_asset = ctx.accounts.base_asset_mint.key();
+        ctx.accounts.vault_factory_info.quote_asset = ctx.accounts.quote_asset_mint.key();
+        ctx.accounts.vault_factory_info.emergency_mode = false;
+        ctx.accounts.vault_factory_info.protocol_fees_bps = ctx.accounts.asset_pair_config.protocol_fees_bps;
+
+        ctx.accounts.vault_factory_info.is_initialized = true;
+        msg!("BinaryOptionVaultFactoryInfo initialized");
+
+        let kind = if params.is_put { SeriesKind::BinaryPut } else { SeriesKind::BinaryCall };
+        ap::add_series_to_index(
+            &mut ctx.accounts.series_index,
+            kind,
+            ctx.accounts.vault_factory_info.key(),
+            params.strike,
+            params.maturity,
+            current_time
+        );
+    }
+    let result = ctx.accounts.vault_factory_info.next_vault_id;
+    ctx.accounts.vault_factory_info.next_vault_id = ctx.accounts.vault_factory_info.next_vault_id.checked_add(1).unwrap();
+
+    Ok(result)
+}
+
+pub fn maker_create_binary_option_vault(ctx: Context<MakerCreateBinaryOptionVault>,
+    params: MakerCreateBinaryOptionParams, vault_id: u64
+) -> Result<()> {
+
+    require!(
+        params.max_makers > 0,
+        BinaryOptionError::MaxMakersZero
+    );
+
+    require!(
+        params.max_takers > 0,
+        BinaryOptionError::MaxTakersZero
+    );
+
+    require!(
+        params.num_lots_to_sell > 0,
+        BinaryOptionError::LotsToSellZero
+    );
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        params.maturity > current_time.checked_add(FREEZE_SECONDS).unwrap(),
+        BinaryOptionError::MaturityTooEarly
+    );
+
+
+    // Initializing this new vault (BinaryOptionVaultInfo)
+    // and updating number of vaults in factory
+    msg!("Started initialization of BinaryOptionVaultInfo");
+
+    // Makers collateralize the fixed payout of every lot they sell
+    let transfer_amount = params.num_lots_to_sell.checked_mul(ctx.accounts.vault_factory_info.payout_per_lot).ok_or(BinaryOptionError::Overflow)?;
+    msg!("num_lots_to_sell: {}", params.num_lots_to_sell);
+    msg!("payout_per_lot: {}", ctx.accounts.vault_factory_info.payout_per_lot);
+    msg!("Transfer amount is {}", transfer_amount);
+
+    wrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.maker_quote_asset_account.to_account_info(),
+        transfer_amount,
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    ctx.accounts.maker_quote_asset_account.reload()?;
+
+    require!(
+        ctx.accounts.maker_quote_asset_account.amount >= transfer_amount,
+        BinaryOptionError::InsufficientFunds
+    );
+
+    // Proceed to transfer (still initializing vault)
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.maker_quote_asset_account.to_account_info(),
+        mint: ctx.accounts.quote_asset_mint.to_account_info(),
+        to: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+        authority: ctx.accounts.initializer.to_account_info(),
+    };
+    let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
+    token_interface::transfer_checked(token_transfer_context, amount_with_transfer_fee(&ctx.accounts.quote_asset_mint.to_account_info(), transfer_amount)?, ctx.accounts.quote_asset_mint.decimals)?;
+    msg!("Transferred {} quote asset lamports to quote asset treasury", transfer_amount);
+
+    ctx.accounts.vault_info.factory_vault = ctx.accounts.vault_factory_info.key();
+    ctx.accounts.vault_info.ord = vault_id;
+    ctx.accounts.vault_info.max_makers = params.max_makers;
+    ctx.accounts.vault_info.max_takers = params.max_takers;
+    ctx.accounts.vault_info.makers_num = 1;
+    ctx.accounts.vault_info.next_maker_ord = 0;
+    ctx.accounts.vault_info.makers_total_pending_sell = transfer_amount;
+    ctx.accounts.vault_info.makers_total_pending_settle = transfer_amount;
+    ctx.accounts.vault_info.is_makers_full = ctx.accounts.vault_info.makers_num >= ctx.accounts.vault_info.max_makers; 
+    ctx.accounts.vault_info.takers_num = 0;
+    ctx.accounts.vault_info.takers_total_lots = 0;
+    ctx.accounts.vault_info.is_takers_full = false;
+    msg!("Finished initialization of BinaryOptionVaultInfo, now initializing BinaryOptionMakerInfo");
+
+    // Now initializing info about this maker in the vault (BinaryOptionMakerInfo)
+    ctx.accounts.binary_option_maker_info.ord = ctx.accounts.vault_info.take_maker_ord();
+    ctx.accounts.binary_option_maker_info.quote_asset_qty = transfer_amount;
+    ctx.accounts.binary_option_maker_info.volume_sold = 0;
+    ctx.accounts.binary_option_maker_info.is_all_sold = false;
+    ctx.accounts.binary_option_maker_info.is_settled = false;
+    ctx.accounts.binary_option_maker_info.premium_limit = params.premium_limit;
+    ctx.accounts.binary_option_maker_info.owner = ctx.accounts.maker_quote_asset_account.owner;
+    ctx.accounts.binary_option_maker_info.binary_option_vault = ctx.accounts.vault_info.key();
+    ctx.accounts.binary_option_maker_info.resting_since = current_time;
+    msg!("Vault initialization finished");
+    
+    Ok(())
+}
+
+pub fn maker_enter_binary_option_vault(ctx: Context<MakerEnterBinaryOptionVault>,     
+    num_lots_to_sell: u64,
+    premium_limit: u64
+) -> Result<()> {
+
+    require!(
+        num_lots_to_sell > 0,
+        BinaryOptionError::LotsToSellZero
+    );
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        ctx.accounts.vault_factory_info.maturity > current_time.checked_add(FREEZE_SECONDS).unwrap(),
+        BinaryOptionError::MaturityTooEarly
+    );
+
+    ctx.accounts.vault_info.makers_num = ctx.accounts.vault_info.makers_num.checked_add(1).unwrap();
+
+    if ctx.accounts.vault_info.makers_num >= ctx.accounts.vault_info.max_makers {
+        ctx.accounts.vault_info.is_makers_full = true;
+    }
+
+    let transfer_amount = num_lots_to_sell.checked_mul(ctx.accounts.vault_factory_info.payout_per_lot).ok_or(BinaryOptionError::Overflow)?;
+    msg!("num_lots_to_sell: {}", num_lots_to_sell);
+    msg!("Transfer amount is {}", transfer_amount);
+
+    wrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.maker_quote_asset_account.to_account_info(),
+        transfer_amount,
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    ctx.accounts.maker_quote_asset_account.reload()?;
+
+    require!(
+        ctx.accounts.maker_quote_asset_account.amount >= transfer_amount,
+        BinaryOptionError::InsufficientFunds
+    );
+
+    // Proceed to transfer 
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.maker_quote_asset_account.to_account_info(),
+        mint: ctx.accounts.quote_asset_mint.to_account_info(),
+        to: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+        authority: ctx.accounts.initializer.to_account_info(),
+    };
+    let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
+    token_interface::transfer_checked(token_transfer_context, amount_with_transfer_fee(&ctx.accounts.quote_asset_mint.to_account_info(), transfer_amount)?, ctx.accounts.quote_asset_mint.decimals)?;
+    msg!("Transferred {} quote asset lamports to quote asset treasury", transfer_amount);
+
+    // Updating vault_info ...
+    ctx.accounts.vault_info.makers_total_pending_sell = ctx.accounts.vault_info.makers_total_pending_sell.checked_add(transfer_amount).unwrap();
+    ctx.accounts.vault_info.makers_total_pending_settle = ctx.accounts.vault_info.makers_total_pending_settle.checked_add(transfer_amount).unwrap();
+    msg!("Finished transferring quote assets to vault quote asset treasury, now updating BinaryOptionMakerInfo");
+
+    // Now initializing info about this maker in the vault (BinaryOptionMakerInfo)
+    ctx.accounts.binary_option_maker_info.ord = ctx.accounts.vault_info.take_maker_ord();
+    ctx.accounts.binary_option_maker_info.quote_asset_qty = transfer_amount;
+    ctx.accounts.binary_option_maker_info.volume_sold = 0;
+    ctx.accounts.binary_option_maker_info.is_all_sold = false;
+    ctx.accounts.binary_option_maker_info.is_settled = false;
+    ctx.accounts.binary_option_maker_info.premium_limit = premium_limit;
+    ctx.accounts.binary_option_maker_info.owner = ctx.accounts.maker_quote_asset_account.owner;
+    ctx.accounts.binary_option_maker_info.binary_option_vault = ctx.accounts.vault_info.key();
+    ctx.accounts.binary_option_maker_info.resting_since = current_time;
+    msg!("Vault initialization finished");
+
+    Ok(())
+}
+
+pub fn maker_adjust_position_binary_option_vault(ctx: Context<MakerAdjustPositionBinaryOptionVault>,     
+    num_lots_to_sell: u64,
+    premium_limit: u64
+) -> Result<()> {
+
+    msg!("Entered maker_adjust_position_binary_option_vault");
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        ctx.accounts.vault_factory_info.maturity > current_time.checked_add(FREEZE_SECONDS).unwrap(),
+        BinaryOptionError::MaturityTooEarly
+    );
+
+    require!(
+        !ctx.accounts.binary_option_maker_info.is_settled,
+        BinaryOptionError::IllegalState
+    );
+
+    let payout_per_lot = ctx.accounts.vault_factory_info.payout_per_lot;
+    let wanted_amount = num_lots_to_sell.checked_mul(payout_per_lot).ok_or(BinaryOptionError::Overflow)?;
+
+    if wanted_amount > ctx.accounts.binary_option_maker_info.quote_asset_qty {
+        // Maker wants to increase her position in the vault
+        let increase_amount = wanted_amount.checked_sub(ctx.accounts.binary_option_maker_info.quote_asset_qty).unwrap();
+        wrap_native_sol_if_needed(
+            &ctx.accounts.quote_asset_mint.key(),
+            &ctx.accounts.initializer.to_account_info(),
+            &ctx.accounts.maker_quote_asset_account.to_account_info(),
+            increase_amount,
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.token_program.to_account_info()
+        )?;
+        // Proceed to transfer 
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.maker_quote_asset_account.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+            authority: ctx.accounts.initializer.to_account_info(),
+        };
+        let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
+
+        token_interface::transfer_checked(token_transfer_context, amount_with_transfer_fee(&ctx.accounts.quote_asset_mint.to_account_info(), increase_amount)?, ctx.accounts.quote_asset_mint.decimals)?;
+        msg!("Transferred {} quote asset lamports to quote asset treasury", increase_amount);
+        ctx.accounts.binary_option_maker_info.quote_asset_qty = ctx.accounts.binary_option_maker_info.quote_asset_qty.checked_add(increase_amount).unwrap();
+        ctx.accounts.binary_option_maker_info.is_all_sold = false;
+        ctx.accounts.binary_option_maker_info.resting_since = current_time;
+        ctx.accounts.vault_info.makers_total_pending_sell = ctx.accounts.vault_info.makers_total_pending_sell.checked_add(increase_amount).unwrap();
+        ctx.accounts.vault_info.makers_total_pending_settle = ctx.accounts.vault_info.makers_total_pending_settle.checked_add(increase_amount).unwrap();
+
+    } else if wanted_amount < ctx.accounts.binary_option_maker_info.quote_asset_qty {
+        // Maker wants to decrease her position in the vault
+        let decrease_amount = ctx.accounts.binary_option_maker_info.quote_asset_qty - wanted_amount;
+        let max_decrease = ctx.accounts.binary_option_maker_info.quote_asset_qty - ctx.accounts.binary_option_maker_info.volume_sold;
+        require!(
+            decrease_amount <= max_decrease,
+            BinaryOptionError::OversizedDecrease
+        );
+        // Proceed to transfer 
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.maker_quote_asset_account.to_account_info(),
+            authority: ctx.accounts.vault_info.to_account_info(),
+        };
+
+        // Preparing PDA signer
+        let auth_bump = *ctx.bumps.get("vault_info").unwrap();
+        let seeds = &[
+            "BinaryOptionVaultInfo".as_bytes().as_ref(), 
+            &ctx.accounts.vault_factory_info.key().to_bytes(),
+            &ctx.accounts.vault_info.ord.to_le_bytes(),
+            &[auth_bump],
+        ];
+        let signer = &[&seeds[..]];
+
+
+        let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+        token_interface::transfer_checked(token_transfer_context, decrease_amount, ctx.accounts.quote_asset_mint.decimals)?;
+        msg!("Transferred {} quote asset lamports from quote asset treasury to user", decrease_amount);
+        unwrap_native_sol_if_needed(
+            &ctx.accounts.quote_asset_mint.key(),
+            &ctx.accounts.initializer.to_account_info(),
+            &ctx.accounts.maker_quote_asset_account.to_account_info(),
+            &ctx.accounts.token_program.to_account_info()
+        )?;
+        ctx.accounts.binary_option_maker_info.quote_asset_qty = ctx.accounts.binary_option_maker_info.quote_asset_qty.checked_sub(decrease_amount).unwrap();
+        ctx.accounts.binary_option_maker_info.is_all_sold = ctx.accounts.binary_option_maker_info.quote_asset_qty.checked_sub(ctx.accounts.binary_option_maker_info.volume_sold).unwrap() < payout_per_lot;
+        ctx.accounts.vault_info.makers_total_pending_sell = ctx.accounts.vault_info.makers_total_pending_sell.checked_sub(decrease_amount).unwrap();
+        ctx.accounts.vault_info.makers_total_pending_settle = ctx.accounts.vault_info.makers_total_pending_settle.checked_sub(decrease_amount).unwrap();
+    }
+
+    ctx.accounts.binary_option_maker_info.premium_limit = premium_limit;    
+
+    require!(
+        ctx.accounts.binary_option_maker_info.quote_asset_qty >= ctx.accounts.binary_option_maker_info.volume_sold,
+        BinaryOptionError::IllegalState
+    );
+
+    Ok(())
+}
+
+pub fn maker_exit_binary_option_vault(ctx: Context<MakerExitBinaryOptionVault>) -> Result<u64> {
+
+    msg!("Entered maker_exit_binary_option_vault");
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        ctx.accounts.vault_factory_info.maturity > current_time.checked_add(FREEZE_SECONDS).unwrap(),
+        BinaryOptionError::MaturityTooEarly
+    );
+
+    require!(
+        ctx.accounts.binary_option_maker_info.quote_asset_qty >= ctx.accounts.binary_option_maker_info.volume_sold,
+        BinaryOptionError::IllegalState
+    );
+
+    // Maker gets back everything that has not been sold yet
+    let withdraw_amount = ctx.accounts.binary_option_maker_info.quote_asset_qty.checked_sub(ctx.accounts.binary_option_maker_info.volume_sold).unwrap();
+    if withdraw_amount > 0 {
+        // Proceed to transfer 
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.maker_quote_asset_account.to_account_info(),
+            authority: ctx.accounts.vault_info.to_account_info(),
+        };
+
+        // Preparing PDA signer
+        let auth_bump = *ctx.bumps.get("vault_info").unwrap();
+        let seeds = &[
+            "BinaryOptionVaultInfo".as_bytes().as_ref(), 
+            &ctx.accounts.vault_factory_info.key().to_bytes(),
+            &ctx.accounts.vault_info.ord.to_le_bytes(),
+            &[auth_bump],
+        ];
+        let signer = &[&seeds[..]];
+
+
+        let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+        token_interface::transfer_checked(token_transfer_context, withdraw_amount, ctx.accounts.quote_asset_mint.decimals)?;
+        msg!("Transferred {} quote asset lamports from quote asset treasury to user", withdraw_amount);
+        unwrap_native_sol_if_needed(
+            &ctx.accounts.quote_asset_mint.key(),
+            &ctx.accounts.initializer.to_account_info(),
+            &ctx.accounts.maker_quote_asset_account.to_account_info(),
+            &ctx.accounts.token_program.to_account_info()
+        )?;
+        ctx.accounts.binary_option_maker_info.quote_asset_qty = ctx.accounts.binary_option_maker_info.quote_asset_qty.checked_sub(withdraw_amount).unwrap();
+        ctx.accounts.vault_info.makers_total_pending_sell = ctx.accounts.vault_info.makers_total_pending_sell.checked_sub(withdraw_amount).unwrap();
+        ctx.accounts.vault_info.makers_total_pending_settle = ctx.accounts.vault_info.makers_total_pending_settle.checked_sub(withdraw_amount).unwrap();
+    }
+    ctx.accounts.binary_option_maker_info.is_all_sold = true;
+
+    if ctx.accounts.binary_option_maker_info.volume_sold == 0 {
+        // Nothing has been sold, so the maker has no commitments in this vault:
+        // we free her slot and give her back the rent of her BinaryOptionMakerInfo
+        ctx.accounts.vault_info.makers_num = ctx.accounts.vault_info.makers_num.checked_sub(1).unwrap();
+        ctx.accounts.vault_info.is_makers_full = ctx.accounts.vault_info.makers_num >= ctx.accounts.vault_info.max_makers;
+        ctx.accounts.binary_option_maker_info.close(ctx.accounts.initializer.to_account_info())?;
+        msg!("Maker has left the vault, BinaryOptionMakerInfo closed");
+    } else {
+        msg!("Maker still has {} quote asset lamports sold in options, she will stay until settlement", ctx.accounts.binary_option_maker_info.volume_sold);
+    }
+
+    Ok(withdraw_amount)
+}
+
+pub fn gen_update_binary_option_fair_price_ticket(ctx: Context<GenUpdateBinaryOptionFairPriceTicket>) -> Result<()> {
+    require!(
+        !ctx.accounts.binary_option_fair_price_ticket.is_used,
+        BinaryOptionError::UsedUpdateTicket
+    );
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        ctx.accounts.vault_factory_info.maturity > current_time.checked_add(FREEZE_SECONDS).unwrap(),
+        BinaryOptionError::MaturityTooEarly
+    );
+
+
+    msg!("Started transferring lamports to oracle");
+    let oracle_fee_transfer_cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        system_program::Transfer {
+            from: ctx.accounts.initializer.to_account_info(),
+            to: ctx.accounts.oracle_wallet.to_account_info()
+        }
+    );
+    system_program::transfer(oracle_fee_transfer_cpi_context, LAMPORTS_FOR_UPDATE_FAIRPRICE_TICKET)?;
+    msg!("Finished transferring lamports to oracle");
+
+    Ok(())
+}
+
+// For binary options the fair price is the premium of one lot, so it can never be above the lot payout
+pub fn oracle_update_binary_option_price(
+    ctx: Context<OracleUpdateBinaryOptionFairPrice>,
+    new_fair_price: u64
+) -> Result<()> {
+    require!(
+        new_fair_price > 0,
+        BinaryOptionError::PriceZero
+    );
+    require!(
+        new_fair_price <= ctx.accounts.vault_factory_info.payout_per_lot,
+        BinaryOptionError::PriceAbovePayout
+    );
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    if ctx.accounts.vault_factory_info.maturity > current_time.checked_add(FREEZE_SECONDS).unwrap() {
+        ctx.accounts.vault_factory_info.last_fair_price = new_fair_price;
+        ctx.accounts.vault_factory_info.ts_last_fair_price = current_time;
+    }
+    ctx.accounts.update_ticket.is_used = true;
+    Ok(())
+}
+
+//remember, oracle should have written last fair price at most MAX_SECONDS_FROM_LAST_FAIR_PRICE_UPDATE before
+pub fn taker_buy_lots_binary_option_vault<'info>(ctx: Context<'_, '_, '_, 'info, TakerBuyLotsBinaryOptionVault<'info>>,
+    max_fair_price: u64,
+    num_lots_to_buy: u64
+) -> Result<TakerBuyLotsBinaryOptionReturn> {
+
+    // Must pass BinaryOptionMakerInfo and corresponding quote asset ATAs (to receive premium) of potential sellers
+    // in remaining accounts
+    require!(
+        !ctx.remaining_accounts.is_empty(),
+        BinaryOptionError::EmptyRemainingAccounts
+    );
+
+    // Always in pairs, first the BinaryOptionMakerInfo, followed by the seller ATA
+    require!(
+        ctx.remaining_accounts.len() % 2 == 0,
+        BinaryOptionError::RemainingAccountsNumIsOdd
+    );      
+
+    require!(
+        ctx.remaining_accounts.len()/2 <= MAX_REMAINING_MAKERS,
+        BinaryOptionError::TooManyRemainingAccounts
+    );
+
+    require!(
+        !has_duplicate_accounts(ctx.remaining_accounts),
+        BinaryOptionError::AccountValidationError
+    );      
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    // Period to take options is already closed
+    require!(
+        ctx.accounts.vault_factory_info.maturity > current_time.checked_add(FREEZE_SECONDS).unwrap(),
+        BinaryOptionError::MaturityTooEarly
+    );
+
+    // If taker is entering the vault, we initialize her BinaryOptionTakerInfo
+    // If she already has a BinaryOptionTakerInfo, she is buying more binary options
+    if !ctx.accounts.binary_option_taker_info.is_initialized {
+        require!(
+            !ctx.accounts.vault_info.is_takers_full,
+            BinaryOptionError::TakersFull
+        );
+
+        ctx.accounts.vault_info.takers_num = ctx.accounts.vault_info.takers_num.checked_add(1).unwrap();
+        if ctx.accounts.vault_info.takers_num >= ctx.accounts.vault_info.max_takers {
+            ctx.accounts.vault_info.is_takers_full = true;
+        }
+
+        ctx.accounts.binary_option_taker_info.ord = ctx.accounts.vault_info.takers_num;
+        ctx.accounts.binary_option_taker_info.num_lots = 0;
+        ctx.accounts.binary_option_taker_info.is_settled = false;
+        ctx.accounts.binary_option_taker_info.owner = ctx.accounts.initializer.key();
+        ctx.accounts.binary_option_taker_info.binary_option_vault = ctx.accounts.vault_info.key();
+
+        ctx.accounts.binary_option_taker_info.is_initialized = true;
+    }
+
+    // We cannot have a timestamp for the last fair price in the future
+    require!(
+        ctx.accounts.vault_factory_info.ts_last_fair_price <= current_time,
+        BinaryOptionError::IllegalState
+    );
+
+    // We only sell if the option price has been updated recently
+    let seconds_from_update = current_time.checked_sub(ctx.accounts.vault_factory_info.ts_last_fair_price).unwrap();
+    require!(
+        seconds_from_update <= MAX_SECONDS_FROM_LAST_FAIR_PRICE_UPDATE,
+        BinaryOptionError::LastFairPriceUpdateTooOld
+    );
+
+    // We won't sell if the taker is not willing to pay the current fair price
+    require!(
+        max_fair_price >= ctx.accounts.vault_factory_info.last_fair_price,
+        BinaryOptionError::MaxFairPriceTooLow
+    );
+
+    let payout_per_lot = ctx.accounts.vault_factory_info.payout_per_lot;
+
+    // Fees are only shared with registered frontends
+    let frontend_fee_share = (frontend_fee_share_bps(&ctx.accounts.frontend_info) as f64)/10000.0;
+    // Fee tier of the taker based on her rolling volume before this purchase
+    init_taker_stats_if_needed(&mut ctx.accounts.taker_stats_info, ctx.accounts.initializer.key(), ctx.accounts.quote_asset_mint.key());
+    let fee_schedule = ctx.accounts.fee_tiers_info.schedule_for(ctx.accounts.taker_stats_info.rolling_volume(current_time));
+    let mut premiums_paid:u64 = 0;
+    let mut backend_fees:u64 = 0;
+    let mut frontend_fees:u64 = 0;
+    let mut total_lots_bought:u64 = 0;
+    for i in 0..(ctx.remaining_accounts.len()/2) {
+        let mut maker_info = load_binary_option_maker_info(&ctx.remaining_accounts[2*i], &ctx.accounts.vault_info)?;
+        let maker_ata = load_binary_option_maker_quote_asset_account(&ctx.remaining_accounts[2*i + 1], maker_info.owner, ctx.accounts.quote_asset_mint.key())?;
+
+        let maker_avbl_quote_asset = maker_info.quote_asset_qty.checked_sub(maker_info.volume_sold).unwrap();
+        let max_lots_from_this_maker = maker_avbl_quote_asset.checked_div(payout_per_lot).unwrap();
+        let lots_from_this_maker = std::cmp::min(max_lots_from_this_maker, num_lots_to_buy.checked_sub(total_lots_bought).unwrap());
+        if lots_from_this_maker > 0 {
+            let reserve_amount = lots_from_this_maker.checked_mul(payout_per_lot).unwrap();
+            maker_info.volume_sold = maker_info.volume_sold.checked_add(reserve_amount).unwrap();
+            ctx.accounts.vault_info.makers_total_pending_sell = ctx.accounts.vault_info.makers_total_pending_sell.checked_sub(reserve_amount).unwrap();
+            let new_avbl_quote_asset = maker_info.quote_asset_qty.checked_sub(maker_info.volume_sold).unwrap();
+            if new_avbl_quote_asset < payout_per_lot {
+                maker_info.is_all_sold = true;
+            }
+            // Now transfer the premium to the maker and protocol
+            let premium_to_maker_f64 = (ctx.accounts.vault_factory_info.last_fair_price as f64)*(lots_from_this_maker as f64);
+            require!(
+                premium_to_maker_f64.is_finite() && premium_to_maker_f64 > 0.0,
+                BinaryOptionError::IllegalState
+            );
+            let mut premium_to_maker = premium_to_maker_f64.round() as u64;
+            let total_fees = fee_schedule.total_fees(premium_to_maker_f64, ctx.accounts.vault_factory_info.protocol_fees_bps);
+            let backend_share = (total_fees*(1.0 - frontend_fee_share)).ceil() as u64;
+            let frontend_share = (total_fees*frontend_fee_share).ceil() as u64;
+            require!(
+                premium_to_maker > backend_share + frontend_share,
+                BinaryOptionError::OptionPremiumTooLow
+            );
+            premium_to_maker = premium_to_maker.checked_sub(backend_share).unwrap();
+            premium_to_maker = premium_to_maker.checked_sub(frontend_share).unwrap();
+            // Makers whose collateral has been resting for long get part of the protocol share back
+            let maker_rebate = fee_schedule.maker_rebate(backend_share, maker_info.resting_since, current_time);
+            premium_to_maker = premium_to_maker.checked_add(maker_rebate).unwrap();
+            let backend_share = backend_share - maker_rebate;
+
+            wrap_native_sol_if_needed(
+                &ctx.accounts.quote_asset_mint.key(),
+                &ctx.accounts.initializer.to_account_info(),
+                &ctx.accounts.taker_quote_asset_account.to_account_info(),
+                premium_to_maker + backend_share + frontend_share,
+                &ctx.accounts.system_program.to_account_info(),
+                &ctx.accounts.token_program.to_account_info()
+            )?;
+
+            {
+                let cpi_program = ctx.accounts.token_program.to_account_info();
+                msg!("Started transferring premium lamports in quote asset from taker to maker");                
+                let cpi_accounts = TransferChecked {
+                    from: ctx.accounts.taker_quote_asset_account.to_account_info(),
+                    mint: ctx.accounts.quote_asset_mint.to_account_info(),
+                    to: maker_ata.to_account_info(),
+                    authority: ctx.accounts.initializer.to_account_info(),
+                };
+                let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
+                token_interface::transfer_checked(token_transfer_context, premium_to_maker, ctx.accounts.quote_asset_mint.decimals)?;
+                msg!("Finished transferring premium quote asset lamports to maker");
+            }
+
+            backend_fees = backend_fees.checked_add(backend_share).unwrap();
+            frontend_fees = frontend_fees.checked_add(frontend_share).unwrap();
+            premiums_paid = premiums_paid.checked_add(premium_to_maker + backend_share + frontend_share).unwrap();
+            total_lots_bought = total_lots_bought.checked_add(lots_from_this_maker).unwrap();
+            { // Serializing maker info
+                let mut data = ctx.remaining_accounts[2*i].try_borrow_mut_data()?;
+                maker_info.try_serialize(&mut data.as_mut())?;    
+            }
+            if total_lots_bought >= num_lots_to_buy {
+                break;
+            }
+        }
+    }
+    require!(
+        total_lots_bought <= num_lots_to_buy,
+        BinaryOptionError::IllegalState
+    );
+
+    // Fees of all fills are paid to the fee vault of the quote asset at once
+    if backend_fees + frontend_fees > 0 {
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        msg!("Started transferring fee lamports to fee vault");
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.taker_quote_asset_account.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.fee_vault_quote_asset_treasury.to_account_info(),
+            authority: ctx.accounts.initializer.to_account_info(),
+        };
+        let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
+        token_interface::transfer_checked(token_transfer_context, amount_with_transfer_fee(&ctx.accounts.quote_asset_mint.to_account_info(), backend_fees + frontend_fees)?, ctx.accounts.quote_asset_mint.decimals)?;
+        msg!("Finished transferring fee lamports to fee vault");
+    }
+    ctx.accounts.fee_vault_info.accrue_backend_fees(backend_fees, &ctx.accounts.insurance_fund_info);
+    record_frontend_trade(&mut ctx.accounts.frontend_info, &mut ctx.accounts.fee_vault_info, premiums_paid, frontend_fees);
+    ctx.accounts.taker_stats_info.record_volume(current_time, premiums_paid);
+
+    ctx.accounts.binary_option_taker_info.num_lots = ctx.accounts.binary_option_taker_info.num_lots.checked_add(total_lots_bought).unwrap();
+    ctx.accounts.vault_info.takers_total_lots = ctx.accounts.vault_info.takers_total_lots.checked_add(total_lots_bought).unwrap();
+
+    let result = TakerBuyLotsBinaryOptionReturn {
+        num_lots_bought: total_lots_bought,
+        price: ctx.accounts.vault_factory_info.last_fair_price
+    };
+    Ok(result)
+}
+
+pub fn maker_activate_binary_option_emergency_mode(ctx: Context<MakerActivateBinaryOptionEmergencyMode>) -> Result<()> {
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        is_emergency_grace_period_over(ctx.accounts.vault_factory_info.maturity, current_time),
+        BinaryOptionError::EmergencyModeTooEarly
+    );
+
+    ctx.accounts.vault_factory_info.emergency_mode = true;
+
+    Ok(())
+}
+
+pub fn taker_activate_binary_option_emergency_mode(ctx: Context<TakerActivateBinaryOptionEmergencyMode>) -> Result<()> {
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        is_emergency_grace_period_over(ctx.accounts.vault_factory_info.maturity, current_time),
+        BinaryOptionError::EmergencyModeTooEarly
+    );
+
+    ctx.accounts.vault_factory_info.emergency_mode = true;
+
+    Ok(())
+}
+
+// Takers have deposited nothing but premiums, already paid to makers, so only makers exit:
+// they get all their collateral back, as if the options had ended out of the money
+pub fn maker_binary_option_emergency_exit(ctx: Context<MakerBinaryOptionEmergencyExit>) -> Result<()> {
+    if ctx.accounts.binary_option_maker_info.quote_asset_qty > 0 {
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.maker_quote_asset_account.to_account_info(),
+            authority: ctx.accounts.vault_info.to_account_info(),
+        };
+
+        // Preparing PDA signer
+        let auth_bump = *ctx.bumps.get("vault_info").unwrap();
+        let seeds = &[
+            "BinaryOptionVaultInfo".as_bytes().as_ref(), 
+            &ctx.accounts.vault_factory_info.key().to_bytes(),
+            &ctx.accounts.vault_info.ord.to_le_bytes(),
+            &[auth_bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token_interface::transfer_checked(token_transfer_context, ctx.accounts.binary_option_maker_info.quote_asset_qty, ctx.accounts.quote_asset_mint.decimals)?;
+    }
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.maker_quote_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+
+    ctx.accounts.binary_option_maker_info.quote_asset_qty = 0;
+    ctx.accounts.binary_option_maker_info.volume_sold = 0;
+    ctx.accounts.binary_option_maker_info.is_settled = true;
+
+    Ok(())
+}
+
+pub fn gen_settle_binary_option_price_ticket(ctx: Context<GenSettleBinaryOptionPriceTicket>) -> Result<()> {
+    require!(
+        !ctx.accounts.binary_option_settle_price_ticket.is_used,
+        BinaryOptionError::UsedUpdateTicket
+    );
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        ctx.accounts.vault_factory_info.maturity < current_time,
+        BinaryOptionError::MaturityTooLate
+    );
+
+    msg!("Started transferring lamports to oracle");
+    let oracle_fee_transfer_cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        system_program::Transfer {
+            from: ctx.accounts.initializer.to_account_info(),
+            to: ctx.accounts.oracle_wallet.to_account_info()
+        }
+    );
+    system_program::transfer(oracle_fee_transfer_cpi_context, LAMPORTS_FOR_UPDATE_SETTLEPRICE_TICKET)?;
+    msg!("Finished transferring lamports to oracle");
+
+    Ok(())
+}
+
+pub fn oracle_update_binary_option_settle_price(
+    ctx: Context<OracleUpdateBinaryOptionSettlePrice>,
+    settle_price: u64
+) -> Result<()> {
+    require!(
+        settle_price > 0,
+        BinaryOptionError::PriceZero
+    );
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        ctx.accounts.vault_factory_info.maturity < current_time,
+        BinaryOptionError::MaturityTooLate
+    );
+
+    if !ctx.accounts.vault_factory_info.matured {
+        ctx.accounts.vault_factory_info.settled_price = settle_price;
+        ctx.accounts.vault_factory_info.matured = true;
+        ap::remove_series_from_index(&mut ctx.accounts.series_index, ctx.accounts.vault_factory_info.key());
+    }
+
+    ctx.accounts.update_ticket.is_used = true;
+
+    Ok(())
+}
+
+// The maker pays the fixed payout for every lot she has sold if the option ends in the money,
+// and gets back the rest of her collateral
+pub fn maker_settle_binary_option(ctx: Context<MakerSettleBinaryOption>) -> Result<BinaryOptionSettleReturn> {
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        ctx.accounts.vault_factory_info.maturity < current_time,
+        BinaryOptionError::IllegalState  // should not have passed maturity test, must never happen
+    );
+
+    let in_the_money = is_binary_option_in_the_money(&ctx.accounts.vault_factory_info);
+    let mut transfer_quote_asset = ctx.accounts.binary_option_maker_info.quote_asset_qty;
+    if in_the_money {
+        msg!("Binary option ended in the money, maker pays the payout of the lots she sold");
+        transfer_quote_asset = transfer_quote_asset.checked_sub(ctx.accounts.binary_option_maker_info.volume_sold).unwrap();
+    } else {
+        msg!("Binary option ended out of the money, maker gets all her collateral back");
+    }
+
+    if transfer_quote_asset > 0 {
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.maker_quote_asset_account.to_account_info(),
+            authority: ctx.accounts.vault_info.to_account_info(),
+        };
+
+        // Preparing PDA signer
+        let auth_bump = *ctx.bumps.get("vault_info").unwrap();
+        let seeds = &[
+            "BinaryOptionVaultInfo".as_bytes().as_ref(), 
+            &ctx.accounts.vault_factory_info.key().to_bytes(),
+            &ctx.accounts.vault_info.ord.to_le_bytes(),
+            &[auth_bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token_interface::transfer_checked(token_transfer_context, transfer_quote_asset, ctx.accounts.quote_asset_mint.decimals)?;
+    }
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.maker_quote_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+
+    ctx.accounts.binary_option_maker_info.quote_asset_qty = 0;
+    ctx.accounts.binary_option_maker_info.volume_sold = 0;
+    ctx.accounts.binary_option_maker_info.is_settled = true;
+
+    let result = BinaryOptionSettleReturn {
+        in_the_money,
+        quote_asset_transfer: transfer_quote_asset
+    };
+    Ok(result)
+}
+
+pub fn taker_settle_binary_option(ctx: Context<TakerSettleBinaryOption>) -> Result<BinaryOptionSettleReturn> {
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        ctx.accounts.vault_factory_info.maturity < current_time,
+        BinaryOptionError::IllegalState  // should not have passed maturity test, must never happen
+    );
+
+    let in_the_money = is_binary_option_in_the_money(&ctx.accounts.vault_factory_info);
+    let mut payout:u64 = 0;
+    if in_the_money {
+        msg!("Binary option ended in the money, taker gets the payout of her lots");
+        payout = ctx.accounts.binary_option_taker_info.num_lots.checked_mul(ctx.accounts.vault_factory_info.payout_per_lot).ok_or(BinaryOptionError::Overflow)?;
+        if payout > 0 {
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+                mint: ctx.accounts.quote_asset_mint.to_account_info(),
+                to: ctx.accounts.taker_quote_asset_account.to_account_info(),
+                authority: ctx.accounts.vault_info.to_account_info(),
+            };
+
+            // Preparing PDA signer
+            let auth_bump = *ctx.bumps.get("vault_info").unwrap();
+            let seeds = &[
+                "BinaryOptionVaultInfo".as_bytes().as_ref(), 
+                &ctx.accounts.vault_factory_info.key().to_bytes(),
+                &ctx.accounts.vault_info.ord.to_le_bytes(),
+                &[auth_bump],
+            ];
+            let signer = &[&seeds[..]];
+
+            let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token_interface::transfer_checked(token_transfer_context, payout, ctx.accounts.quote_asset_mint.decimals)?;
+        }
+    } else {
+        msg!("Binary option ended out of the money, nothing to pay to the taker");
+    }
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.taker_quote_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+
+    ctx.accounts.binary_option_taker_info.is_settled = true;
+
+    let result = BinaryOptionSettleReturn {
+        in_the_money,
+        quote_asset_transfer: payout
+    };
+    Ok(result)
+}
+
+// A binary call pays if settled_price is above strike, a binary put if it is below strike.
+// Nothing is paid if settled_price is exactly at the strike
+fn is_binary_option_in_the_money(vault_factory_info: &Account<BinaryOptionVaultFactoryInfo>) -> bool {
+    if vault_factory_info.is_put {
+        vault_factory_info.settled_price < vault_factory_info.strike
+    } else {
+        vault_factory_info.settled_price > vault_factory_info.strike
+    }
+}
+
+// BinaryOptionMakerInfo of a maker of vault_info passed in remaining accounts: writable, owned by
+// this program and at the address derived from the vault and its owner
+fn load_binary_option_maker_info<'info>(info: &AccountInfo<'info>,
+    vault_info: &Account<BinaryOptionVaultInfo>
+) -> Result<Account<'info, BinaryOptionMakerInfo>> {
+    let maker_info: Account<BinaryOptionMakerInfo> = load_writable_program_account(info).ok_or(BinaryOptionError::AccountValidationError)?;
+    require!(
+        maker_info.binary_option_vault == vault_info.key() &&
+        is_program_address(info.key, &[
+            "BinaryOptionMakerInfo".as_bytes(),
+            vault_info.factory_vault.as_ref(),
+            &vault_info.ord.to_le_bytes(),
+            maker_info.owner.as_ref()
+        ]),
+        BinaryOptionError::AccountValidationError
+    );
+
+    Ok(maker_info)
+}
+
+// Quote asset account of a maker passed in remaining accounts to receive premiums
+fn load_binary_option_maker_quote_asset_account<'info>(info: &AccountInfo<'info>,
+    maker: Pubkey,
+    quote_asset_mint: Pubkey
+) -> Result<InterfaceAccount<'info, TokenAccount>> {
+    let maker_ata = load_writable_token_account(info).ok_or(BinaryOptionError::AccountValidationError)?;
+    require!(
+        maker_ata.owner == maker && maker_ata.mint == quote_asset_mint,
+        BinaryOptionError::AccountValidationError
+    );
+
+    Ok(maker_ata)
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct BinaryOptionVaultFactoryInfo {
+    pub is_initialized: bool,
+
+    pub next_vault_id: u64,
+    pub maturity: u64,
+    pub matured: bool,
+    pub strike: u64,
+    pub payout_per_lot: u64,        // fixed amount in quote asset lamports paid for each lot if the option ends in the money
+    pub is_put: bool,               // if true, pays when settled_price is below strike, otherwise when it is above strike
+    pub base_asset: Pubkey,
+    pub quote_asset: Pubkey,
+
+    pub last_fair_price: u64,       // price of one lot in quote asset lamports
+    pub ts_last_fair_price: u64,
+    pub settled_price: u64,
+    pub emergency_mode: bool,
+    pub protocol_fees_bps: u16      // fees on premiums, copied from the AssetPairConfig when the factory is created
+}
+
+#[account]
+pub struct BinaryOptionVaultInfo {
+    pub factory_vault: Pubkey,
+
+    pub ord: u64,
+    pub max_makers: u16,
+    pub max_takers: u16,
+
+    pub makers_num: u16,
+    pub next_maker_ord: u16,                // ord of the last maker that joined, makers_num goes down when makers exit
+    pub makers_total_pending_sell: u64,     // the amount of quote asset lamports that makers have deposited, but not yet sold
+    pub makers_total_pending_settle: u64,   // the amount of quote asset lamports that has been sold in options and has not been settled
+    pub is_makers_full: bool,
+
+    pub takers_num: u16,
+    pub takers_total_lots: u64,             // the number of lots bought by all takers
+    pub is_takers_full: bool
+}
+
+impl BinaryOptionVaultInfo {
+    // Ord of a maker joining the vault, never reused when makers exit
+    pub fn take_maker_ord(&mut self) -> u16 {
+        self.next_maker_ord = self.next_maker_ord.checked_add(1).unwrap();
+        self.next_maker_ord
+    }
+}
+
+#[account]
+pub struct BinaryOptionMakerInfo {
+    pub ord: u16,
+    pub quote_asset_qty: u64,       // total in quote asset lamports that has been deposited by the maker, always a multiple of payout_per_lot
+    pub volume_sold: u64,           // amount of quote asset lamports that has been sold in options by this maker (volume_sold <= quote_asset_qty)
+    pub is_all_sold: bool,          // if the available volume (quote_asset_qty - volume_sold) is worth less than 1 lot, all is sold
+    pub is_settled: bool,           // if the maker has already got his tokens after maturity
+    pub premium_limit: u64,         // minimum price for option premium he is willing to get, can be zero if he is ok of selling at whatever the fair price
+    pub owner: Pubkey,
+    pub binary_option_vault: Pubkey,
+    pub resting_since: u64          // when she last entered or added lots, for maker rebates on fees
+}
+
+#[account]
+pub struct BinaryOptionUpdateFairPriceTicketInfo {
+    pub is_used: bool,
+    pub factory_vault: Pubkey
+}
+
+#[account]
+pub struct BinaryOptionTakerInfo {
+    pub is_initialized: bool,
+
+    pub ord: u16,
+    pub num_lots: u64,              // how many lots she has bought, each one pays payout_per_lot if in the money
+    pub is_settled: bool,           // if the taker has already got her tokens after maturity
+    pub owner: Pubkey,
+    pub binary_option_vault: Pubkey
+}
+
+#[account]
+pub struct BinaryOptionSettlePriceTicketInfo {
+    pub is_used: bool,
+    pub factory_vault: Pubkey
+}
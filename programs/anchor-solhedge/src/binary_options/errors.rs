@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum BinaryOptionError {
+    #[msg("Number of max_makers cannot be zero")]
+    MaxMakersZero,
+
+    #[msg("Number of max_takers cannot be zero")]
+    MaxTakersZero,
+
+    #[msg("num_lots_to_sell cannot be zero")]
+    LotsToSellZero,
+
+    #[msg("strike cannot be zero")]
+    StrikeZero,
+
+    #[msg("payout_per_lot cannot be zero")]
+    PayoutZero,
+
+    #[msg("Price cannot be zero")]
+    PriceZero,
+
+    #[msg("Fair price cannot be above the payout of one lot")]
+    PriceAbovePayout,
+
+    #[msg("maturity is too early")]
+    MaturityTooEarly,
+
+    #[msg("maturity is too late")]
+    MaturityTooLate,
+
+    #[msg("Unable to decrease position given previous commitments")]
+    OversizedDecrease,
+
+    #[msg("Overflow error")]
+    Overflow,
+
+    #[msg("Illegal internal state")]
+    IllegalState,
+
+    #[msg("Update binary option fair price ticket is already used")]
+    UsedUpdateTicket,
+
+    #[msg("Not enough funds in source account")]
+    InsufficientFunds,
+
+    #[msg("No more takers are allowed in this vault")]
+    TakersFull,
+
+    #[msg("Last fair price update is too old. Please ask the oracle to make a new update")]
+    LastFairPriceUpdateTooOld,
+
+    #[msg("Your max fair price is below current fair price")]
+    MaxFairPriceTooLow,
+
+    #[msg("Remaining accounts are empty")]
+    EmptyRemainingAccounts,
+  
+    #[msg("Quantity of remaining accounts is odd, should be even")]
+    RemainingAccountsNumIsOdd,
+
+    #[msg("Account validation error")]
+    AccountValidationError,
+
+    #[msg("Option premium price is too low")]
+    OptionPremiumTooLow,
+
+    #[msg("Too many accounts in remaining accounts")]
+    TooManyRemainingAccounts,
+
+    #[msg("Insufficient time passed since maturity to activate emergency mode, please wait more")]
+    EmergencyModeTooEarly
+}
@@ -0,0 +1,929 @@
+use anchor_lang::prelude::*;
+use crate::insurance_funds::data::InsuranceFundInfo;
+use crate::fee_vaults::data::FeeVaultInfo;
+use crate::fee_tiers::data::{FeeTiersInfo, TakerStatsInfo};
+use crate::frontends::data::FrontendInfo;
+use crate::MakerCreateBinaryOptionParams;
+use crate::binary_options::data::{
+    BinaryOptionVaultFactoryInfo,
+    BinaryOptionVaultInfo,
+    BinaryOptionMakerInfo,
+    BinaryOptionUpdateFairPriceTicketInfo,
+    BinaryOptionTakerInfo,
+    BinaryOptionSettlePriceTicketInfo
+};
+use crate::asset_pairs::data::{AssetPairConfig, SeriesIndex};
+use anchor_spl::token_interface::{Mint, TokenInterface, TokenAccount};
+use anchor_spl::associated_token::AssociatedToken;
+use crate::ORACLE_ADDRESS;
+
+#[derive(Accounts)]
+#[instruction(
+    params: MakerCreateBinaryOptionParams
+)]
+pub struct MakerNextBinaryOptionVaultId<'info> {
+    #[account(
+        init_if_needed, 
+        seeds=["BinaryOptionVaultFactoryInfo".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref(), &params.maturity.to_le_bytes().as_ref(), &params.strike.to_le_bytes().as_ref(), &params.payout_per_lot.to_le_bytes().as_ref(), &[params.is_put as u8].as_ref()], 
+        bump, 
+        payer = initializer, 
+        space= std::mem::size_of::<BinaryOptionVaultFactoryInfo>() + 8,        
+        constraint = params.strike > 0,
+        constraint = params.payout_per_lot > 0
+    )]
+    pub vault_factory_info: Account<'info, BinaryOptionVaultFactoryInfo>,
+
+    #[account(
+        seeds=["AssetPairConfig".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = asset_pair_config.is_initialized == true
+    )]
+    pub asset_pair_config: Account<'info, AssetPairConfig>,
+
+    #[account(
+        mut,
+        seeds=["SeriesIndex".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        realloc = SeriesIndex::space(series_index.entries.len() + 1),
+        realloc::payer = initializer,
+        realloc::zero = false,
+        constraint = series_index.is_initialized == true
+    )]
+    pub series_index: Box<Account<'info, SeriesIndex>>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+#[instruction(
+    params: MakerCreateBinaryOptionParams, 
+    vault_id: u64
+)]
+pub struct MakerCreateBinaryOptionVault<'info> {
+    #[account(
+        seeds=["BinaryOptionVaultFactoryInfo".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref(), &params.maturity.to_le_bytes().as_ref(), &params.strike.to_le_bytes().as_ref(), &params.payout_per_lot.to_le_bytes().as_ref(), &[params.is_put as u8].as_ref()], 
+        bump, 
+        constraint = params.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.base_asset == base_asset_mint.key(),
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.maturity == params.maturity,
+        constraint = vault_factory_info.strike == params.strike,
+        constraint = vault_factory_info.payout_per_lot == params.payout_per_lot,
+        constraint = vault_factory_info.is_put == params.is_put,
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, BinaryOptionVaultFactoryInfo>,
+
+    #[account(
+        init,
+        seeds=[
+            "BinaryOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            &vault_id.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = initializer, 
+        constraint = vault_id < vault_factory_info.next_vault_id,
+        space= std::mem::size_of::<BinaryOptionVaultInfo>() + 8
+    )]
+    pub vault_info: Account<'info, BinaryOptionVaultInfo>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = initializer, // Payer will be initializer
+        associated_token::mint = quote_asset_mint, // Quote asset mint
+        associated_token::authority = vault_info // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        seeds=[
+            "BinaryOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            &vault_id.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<BinaryOptionMakerInfo>() + 8
+    )]
+    pub binary_option_maker_info: Account<'info, BinaryOptionMakerInfo>,
+
+    #[account(
+        mut,
+        constraint = maker_quote_asset_account.owner.key() == initializer.key(),
+        constraint = maker_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub maker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    num_lots_to_sell: u64,
+    premium_limit: u64
+)]
+pub struct MakerEnterBinaryOptionVault<'info> {
+    #[account(
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.base_asset == base_asset_mint.key(),
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, BinaryOptionVaultFactoryInfo>,
+
+    #[account(
+        mut,
+        seeds=[
+            "BinaryOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.is_makers_full == false,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Account<'info, BinaryOptionVaultInfo>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = vault_quote_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        seeds=[
+            "BinaryOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<BinaryOptionMakerInfo>() + 8
+    )]
+    pub binary_option_maker_info: Account<'info, BinaryOptionMakerInfo>,
+
+    #[account(
+        mut,
+        constraint = maker_quote_asset_account.owner.key() == initializer.key(),
+        constraint = maker_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub maker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+}
+
+#[derive(Accounts)]
+#[instruction(
+    num_lots_to_sell: u64,
+    premium_limit: u64
+)]
+pub struct MakerAdjustPositionBinaryOptionVault<'info> {
+    #[account(        
+        seeds=["BinaryOptionVaultFactoryInfo".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref(), vault_factory_info.maturity.to_le_bytes().as_ref(), vault_factory_info.strike.to_le_bytes().as_ref(), vault_factory_info.payout_per_lot.to_le_bytes().as_ref(), &[vault_factory_info.is_put as u8].as_ref()], 
+        bump, 
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.base_asset == base_asset_mint.key(),
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, BinaryOptionVaultFactoryInfo>,
+
+    #[account(
+        mut,
+        seeds=[
+            "BinaryOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Account<'info, BinaryOptionVaultInfo>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = vault_quote_asset_treasury.owner == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds=[
+            "BinaryOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        constraint = binary_option_maker_info.binary_option_vault == vault_info.key(),
+        constraint = binary_option_maker_info.owner == initializer.key()
+    )]
+    pub binary_option_maker_info: Account<'info, BinaryOptionMakerInfo>,
+
+    #[account(
+        mut,
+        constraint = maker_quote_asset_account.owner.key() == initializer.key(),
+        constraint = maker_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub maker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+}
+
+#[derive(Accounts)]
+pub struct MakerExitBinaryOptionVault<'info> {
+    #[account(        
+        seeds=["BinaryOptionVaultFactoryInfo".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref(), vault_factory_info.maturity.to_le_bytes().as_ref(), vault_factory_info.strike.to_le_bytes().as_ref(), vault_factory_info.payout_per_lot.to_le_bytes().as_ref(), &[vault_factory_info.is_put as u8].as_ref()], 
+        bump, 
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.base_asset == base_asset_mint.key(),
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, BinaryOptionVaultFactoryInfo>,
+
+    #[account(
+        mut,
+        seeds=[
+            "BinaryOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Account<'info, BinaryOptionVaultInfo>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = vault_quote_asset_treasury.owner == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // closed (rent goes back to initializer) if nothing has been sold
+    #[account(
+        mut,
+        seeds=[
+            "BinaryOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        constraint = binary_option_maker_info.binary_option_vault == vault_info.key(),
+        constraint = binary_option_maker_info.owner == initializer.key(),
+        constraint = !binary_option_maker_info.is_settled
+    )]
+    pub binary_option_maker_info: Account<'info, BinaryOptionMakerInfo>,
+
+    // unsold quote assets will be sent back to this account
+    #[account(
+        mut,
+        constraint = maker_quote_asset_account.owner.key() == initializer.key(),
+        constraint = maker_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub maker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+}
+
+#[derive(Accounts)]
+pub struct GenUpdateBinaryOptionFairPriceTicket<'info> {
+    #[account(
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, BinaryOptionVaultFactoryInfo>,
+
+    #[account(
+        init,
+        seeds=["BinaryOptionUpdateTicketInfo".as_bytes().as_ref(), vault_factory_info.key().as_ref(), initializer.key().as_ref()],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<BinaryOptionUpdateFairPriceTicketInfo>() + 8,
+    )]
+    pub binary_option_fair_price_ticket: Account<'info, BinaryOptionUpdateFairPriceTicketInfo>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = oracle_wallet.key() == ORACLE_ADDRESS
+    )]
+    pub oracle_wallet: SystemAccount<'info>,
+
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+#[instruction(
+    new_fair_price: u64
+)]
+pub struct OracleUpdateBinaryOptionFairPrice<'info> {
+    #[account(
+        mut,
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, BinaryOptionVaultFactoryInfo>,
+
+    #[account(
+        mut,
+        seeds=["BinaryOptionUpdateTicketInfo".as_bytes().as_ref(), vault_factory_info.key().as_ref(), ticket_owner.key().as_ref()],
+        bump,
+        close = ticket_owner,
+        constraint = update_ticket.is_used == false, 
+    )]
+    pub update_ticket: Account<'info, BinaryOptionUpdateFairPriceTicketInfo>,
+
+    #[account(
+        mut
+    )]
+    pub ticket_owner: SystemAccount<'info>,
+
+    // Check if initializer is signer, should also be the oracle, mut is required to reduce lamports (fees)
+    #[account(
+        mut,
+        constraint = initializer.key() == ORACLE_ADDRESS
+    )]
+    pub initializer: Signer<'info>,
+
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>
+
+}
+
+#[derive(Accounts)]
+#[instruction(
+    max_fair_price: u64,
+    num_lots_to_buy: u64
+)]
+pub struct TakerBuyLotsBinaryOptionVault<'info> {
+    #[account(
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.base_asset == base_asset_mint.key(),
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, BinaryOptionVaultFactoryInfo>,
+
+    #[account(
+        mut,
+        seeds=[
+            "BinaryOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Account<'info, BinaryOptionVaultInfo>,
+
+    #[account(
+        init_if_needed,
+        seeds=[
+            "BinaryOptionTakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<BinaryOptionTakerInfo>() + 8,
+        constraint = !binary_option_taker_info.is_settled
+    )]
+    pub binary_option_taker_info: Account<'info, BinaryOptionTakerInfo>,
+
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // to pay the option premium (fair price)
+    #[account(
+        mut,
+        constraint = taker_quote_asset_account.owner.key() == initializer.key(),
+        constraint = taker_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub taker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // fees accrue in the fee vault of the quote asset, part of the backend
+    // share for the insurance fund of the quote asset
+    #[account(
+        mut,
+        seeds=["FeeVaultInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = fee_vault_info.is_initialized == true
+    )]
+    pub fee_vault_info: Box<Account<'info, FeeVaultInfo>>,
+
+    #[account(
+        mut,
+        constraint = fee_vault_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = fee_vault_quote_asset_treasury.owner == fee_vault_info.key() // Authority set to fee vault PDA
+    )]
+    pub fee_vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds=["InsuranceFundInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = insurance_fund_info.is_initialized == true
+    )]
+    pub insurance_fund_info: Box<Account<'info, InsuranceFundInfo>>,
+
+    // registered frontend of the taker, if any, which earns its share of fees
+    #[account(
+        mut,
+        constraint = frontend_info.quote_asset == quote_asset_mint.key()
+    )]
+    pub frontend_info: Option<Box<Account<'info, FrontendInfo>>>,
+
+    // fee tiers of takers of the quote asset and rebates of makers
+    #[account(
+        seeds=["FeeTiersInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = fee_tiers_info.is_initialized == true
+    )]
+    pub fee_tiers_info: Box<Account<'info, FeeTiersInfo>>,
+
+    // rolling volume of the taker in the quote asset, for her fee tier
+    #[account(
+        init_if_needed,
+        seeds=["TakerStatsInfo".as_bytes().as_ref(), initializer.key().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<TakerStatsInfo>() + 8
+    )]
+    pub taker_stats_info: Box<Account<'info, TakerStatsInfo>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+}
+
+#[derive(Accounts)]
+pub struct GenSettleBinaryOptionPriceTicket<'info> {
+    #[account(
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, BinaryOptionVaultFactoryInfo>,
+
+    #[account(
+        init,
+        seeds=["BinaryOptionSettlePriceTicketInfo".as_bytes().as_ref(), vault_factory_info.key().as_ref(), initializer.key().as_ref()],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<BinaryOptionSettlePriceTicketInfo>() + 8,
+    )]
+    pub binary_option_settle_price_ticket: Account<'info, BinaryOptionSettlePriceTicketInfo>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = oracle_wallet.key() == ORACLE_ADDRESS
+    )]
+    pub oracle_wallet: SystemAccount<'info>,
+
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>
+
+}
+
+#[derive(Accounts)]
+#[instruction(
+    settle_price: u64
+)]
+pub struct OracleUpdateBinaryOptionSettlePrice<'info> {
+    #[account(
+        mut,
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, BinaryOptionVaultFactoryInfo>,
+
+    #[account(
+        mut,
+        seeds=["SeriesIndex".as_bytes().as_ref(), vault_factory_info.base_asset.as_ref(), vault_factory_info.quote_asset.as_ref()],
+        bump,
+        constraint = series_index.is_initialized == true
+    )]
+    pub series_index: Box<Account<'info, SeriesIndex>>,
+
+    #[account(
+        mut,
+        seeds=["BinaryOptionSettlePriceTicketInfo".as_bytes().as_ref(), vault_factory_info.key().as_ref(), ticket_owner.key().as_ref()],
+        bump,
+        close = ticket_owner,
+        constraint = update_ticket.is_used == false, 
+    )]
+    pub update_ticket: Account<'info, BinaryOptionSettlePriceTicketInfo>,
+
+    #[account(
+        mut
+    )]
+    pub ticket_owner: SystemAccount<'info>,
+
+    // Check if initializer is signer, should also be the oracle, mut is required to reduce lamports (fees)
+    #[account(
+        mut,
+        constraint = initializer.key() == ORACLE_ADDRESS
+    )]
+    pub initializer: Signer<'info>,
+
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>
+
+}
+
+#[derive(Accounts)]
+pub struct MakerSettleBinaryOption<'info> {
+    #[account(
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == true,
+        constraint = vault_factory_info.settled_price > 0,
+        constraint = vault_factory_info.base_asset == base_asset_mint.key(),
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, BinaryOptionVaultFactoryInfo>,
+
+    #[account(
+        mut,
+        seeds=[
+            "BinaryOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Account<'info, BinaryOptionVaultInfo>,
+
+    #[account(
+        mut,
+        seeds=[
+            "BinaryOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        constraint = !binary_option_maker_info.is_settled
+    )]
+    pub binary_option_maker_info: Account<'info, BinaryOptionMakerInfo>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // quote asset mint
+        constraint = vault_quote_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // maker will get back the collateral that has not been paid to takers at this account
+    #[account(
+        mut,
+        constraint = maker_quote_asset_account.owner.key() == initializer.key(),
+        constraint = maker_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub maker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>
+}
+
+#[derive(Accounts)]
+pub struct TakerSettleBinaryOption<'info> {
+    #[account(
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == true,
+        constraint = vault_factory_info.settled_price > 0,
+        constraint = vault_factory_info.base_asset == base_asset_mint.key(),
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, BinaryOptionVaultFactoryInfo>,
+
+    #[account(
+        mut,
+        seeds=[
+            "BinaryOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Account<'info, BinaryOptionVaultInfo>,
+
+    #[account(
+        mut,
+        seeds=[
+            "BinaryOptionTakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        constraint = !binary_option_taker_info.is_settled
+    )]
+    pub binary_option_taker_info: Account<'info, BinaryOptionTakerInfo>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // quote asset mint
+        constraint = vault_quote_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // if binary option ends in the money, taker will get the payout at this account
+    #[account(
+        mut,
+        constraint = taker_quote_asset_account.owner.key() == initializer.key(),
+        constraint = taker_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub taker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>
+}
+
+#[derive(Accounts)]
+pub struct MakerActivateBinaryOptionEmergencyMode<'info> {
+    #[account(
+        mut,
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, BinaryOptionVaultFactoryInfo>,
+
+    #[account(
+        seeds=[
+            "BinaryOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Account<'info, BinaryOptionVaultInfo>,
+
+    #[account(
+        seeds=[
+            "BinaryOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        constraint = !binary_option_maker_info.is_settled
+    )]
+    pub binary_option_maker_info: Account<'info, BinaryOptionMakerInfo>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+
+}
+
+#[derive(Accounts)]
+pub struct TakerActivateBinaryOptionEmergencyMode<'info> {
+    #[account(
+        mut,
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, BinaryOptionVaultFactoryInfo>,
+
+    #[account(
+        seeds=[
+            "BinaryOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Account<'info, BinaryOptionVaultInfo>,
+
+    #[account(
+        seeds=[
+            "BinaryOptionTakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        constraint = !binary_option_taker_info.is_settled
+    )]
+    pub binary_option_taker_info: Account<'info, BinaryOptionTakerInfo>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+
+}
+
+#[derive(Accounts)]
+pub struct MakerBinaryOptionEmergencyExit<'info> {
+    #[account(
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.emergency_mode == true
+    )]
+    pub vault_factory_info: Account<'info, BinaryOptionVaultFactoryInfo>,
+
+    #[account(
+        seeds=[
+            "BinaryOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Account<'info, BinaryOptionVaultInfo>,
+
+    #[account(
+        mut,
+        seeds=[
+            "BinaryOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        constraint = !binary_option_maker_info.is_settled
+    )]
+    pub binary_option_maker_info: Account<'info, BinaryOptionMakerInfo>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // quote asset mint
+        constraint = vault_quote_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = maker_quote_asset_account.owner.key() == initializer.key(),
+        constraint = maker_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub maker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>
+}
@@ -5,18 +5,113 @@ use crate::call_options::errors::CallOptionError;
 use crate::{
     FREEZE_SECONDS, 
     MAX_MATURITY_FUTURE_SECONDS,
+    LAMPORTS_FOR_UPDATE_SETTLEPRICE_TICKET,
     LAMPORTS_FOR_UPDATE_FAIRPRICE_TICKET,
     MAX_SECONDS_FROM_LAST_FAIR_PRICE_UPDATE,
-    PROTOCOL_TOTAL_FEES,
-    FRONTEND_SHARE
+    MAX_REMAINING_MAKERS
 };
-use anchor_spl::token::{self, Transfer, TokenAccount};
+use anchor_spl::token_interface::{self, TransferChecked, TokenAccount, Mint};
+use crate::transfer_fees::amount_with_transfer_fee;
 use crate::anchor_solhedge::*;
-use crate::call_options::data::CallOptionMakerInfo;
+use crate::asset_pairs::ap_controller as ap;
+use crate::asset_pairs::data::{AssetPairConfig, SeriesIndex, SeriesKind};
+use crate::frontends::fe_controller::{frontend_fee_share_bps, record_frontend_trade};
+use crate::fee_tiers::ft_controller::init_taker_stats_if_needed;
+use crate::native_sol::{wrap_native_sol_if_needed, unwrap_native_sol_if_needed};
+use crate::emergency_mode::is_emergency_grace_period_over;
+use crate::remaining_accounts::{
+    has_duplicate_accounts,
+    load_writable_program_account,
+    load_writable_token_account,
+    is_program_address
+};
+use crate::call_options::data::{
+    CallOptionVaultFactoryInfo,
+    CallOptionVaultInfo,
+    CallOptionMakerInfo
+};
 
 pub fn maker_next_call_option_vault_id(ctx: Context<MakerNextCallOptionVaultId>,
     params: MakerCreateCallOptionParams
 ) -> Result<u64> {
+    let base_asset = ctx.accounts.base_asset_mint.key();
+    let quote_asset = ctx.accounts.quote_asset_mint.key();
+    allocate_call_option_vault_id(
+        &mut ctx.accounts.vault_factory_info,
+        &ctx.accounts.asset_pair_config,
+        &mut ctx.accounts.series_index,
+        base_asset,
+        quote_asset,
+        &params
+    )
+}
+
+pub fn maker_create_call_option_vault(ctx: Context<MakerCreateCallOptionVault>,
+    params: MakerCreateCallOptionParams, vault_id: u64
+) -> Result<()> {
+    let payer = CallOptionBasePayer {
+        base_asset_account: &ctx.accounts.maker_base_asset_account,
+        base_asset_mint: &ctx.accounts.base_asset_mint,
+        initializer: ctx.accounts.initializer.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info()
+    };
+    fund_new_call_option_vault(
+        ctx.accounts.vault_factory_info.key(),
+        &mut ctx.accounts.vault_info,
+        &mut ctx.accounts.call_option_maker_info,
+        ctx.accounts.vault_base_asset_treasury.to_account_info(),
+        &payer,
+        &params,
+        vault_id
+    )
+}
+
+// Same as maker_next_call_option_vault_id followed by maker_create_call_option_vault, without
+// a separate transaction reserving the vault id. Returns the id of the new vault
+pub fn maker_open_call_option_vault(ctx: Context<MakerOpenCallOptionVault>,
+    params: MakerCreateCallOptionParams
+) -> Result<u64> {
+    let base_asset = ctx.accounts.base_asset_mint.key();
+    let quote_asset = ctx.accounts.quote_asset_mint.key();
+    let vault_id = allocate_call_option_vault_id(
+        &mut ctx.accounts.vault_factory_info,
+        &ctx.accounts.asset_pair_config,
+        &mut ctx.accounts.series_index,
+        base_asset,
+        quote_asset,
+        &params
+    )?;
+
+    let payer = CallOptionBasePayer {
+        base_asset_account: &ctx.accounts.maker_base_asset_account,
+        base_asset_mint: &ctx.accounts.base_asset_mint,
+        initializer: ctx.accounts.initializer.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info()
+    };
+    fund_new_call_option_vault(
+        ctx.accounts.vault_factory_info.key(),
+        &mut ctx.accounts.vault_info,
+        &mut ctx.accounts.call_option_maker_info,
+        ctx.accounts.vault_base_asset_treasury.to_account_info(),
+        &payer,
+        &params,
+        vault_id
+    )?;
+
+    Ok(vault_id)
+}
+
+// Checks the series against the asset pair configuration, initializes its vault factory
+// if it has just been created and reserves the id of a new vault in it
+fn allocate_call_option_vault_id(vault_factory_info: &mut Account<CallOptionVaultFactoryInfo>,
+    asset_pair_config: &Account<AssetPairConfig>,
+    series_index: &mut Account<SeriesIndex>,
+    base_asset: Pubkey,
+    quote_asset: Pubkey,
+    params: &MakerCreateCallOptionParams
+) -> Result<u64> {
 
     require!(
         params.strike > 0,
@@ -35,27 +130,59 @@ pub fn maker_next_call_option_vault_id(ctx: Context<MakerNextCallOptionVaultId>,
     );
 
 
+    // Only asset pairs registered by the admin are allowed
+    ap::check_asset_pair_allows_factory(asset_pair_config, Some(params.lot_size), params.strike, params.maturity, current_time)?;
+
     // Initializing factory vault (CallOptionVaultFactoryInfo) if it has been just created
-    if !ctx.accounts.vault_factory_info.is_initialized {
-        ctx.accounts.vault_factory_info.next_vault_id = 1;
-        ctx.accounts.vault_factory_info.maturity = params.maturity;
-        ctx.accounts.vault_factory_info.matured = false;
-        ctx.accounts.vault_factory_info.strike = params.strike;
-        ctx.accounts.vault_factory_info.base_asset = ctx.accounts.base_asset_mint.key();
-        ctx.accounts.vault_factory_info.quote_asset = ctx.accounts.quote_asset_mint.key();
-        ctx.accounts.vault_factory_info.emergency_mode = false;
-
-        ctx.accounts.vault_factory_info.is_initialized = true;
-        msg!("CallOptionVaultFactoryInfo initialized");
+    if !vault_factory_info.is_initialized {
+        init_call_option_vault_factory_info(
+            vault_factory_info, 
+            base_asset, 
+            quote_asset, 
+            params,
+            asset_pair_config.protocol_fees_bps
+        );
+        ap::add_series_to_index(
+            series_index,
+            SeriesKind::Call,
+            vault_factory_info.key(),
+            params.strike,
+            params.maturity,
+            current_time
+        );
     }
-    let result = ctx.accounts.vault_factory_info.next_vault_id;
-    ctx.accounts.vault_factory_info.next_vault_id = ctx.accounts.vault_factory_info.next_vault_id.checked_add(1).unwrap();
+    require!(
+        vault_factory_info.cash_settled == params.cash_settled,
+        CallOptionError::SettlementModeMismatch
+    );
+    require!(
+        vault_factory_info.barrier == params.barrier && vault_factory_info.barrier_up == params.barrier_up && vault_factory_info.knock_in == params.knock_in,
+        CallOptionError::BarrierMismatch
+    );
+    let result = vault_factory_info.next_vault_id;
+    vault_factory_info.next_vault_id = vault_factory_info.next_vault_id.checked_add(1).unwrap();
 
     Ok(result)
 }
 
-pub fn maker_create_call_option_vault(ctx: Context<MakerCreateCallOptionVault>,
-    params: MakerCreateCallOptionParams, vault_id: u64
+// Accounts of the signer depositing base assets (collateral) in the helpers below
+struct CallOptionBasePayer<'a, 'info> {
+    base_asset_account: &'a InterfaceAccount<'info, TokenAccount>,
+    base_asset_mint: &'a InterfaceAccount<'info, Mint>,
+    initializer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    token_program: AccountInfo<'info>
+}
+
+// Deposits the collateral of the first maker (the payer) of a new vault, for params.num_lots_to_sell lots,
+// and initializes the vault
+fn fund_new_call_option_vault<'info>(vault_factory_key: Pubkey,
+    vault_info: &mut Account<CallOptionVaultInfo>,
+    call_option_maker_info: &mut Account<CallOptionMakerInfo>,
+    vault_base_asset_treasury: AccountInfo<'info>,
+    payer: &CallOptionBasePayer<'_, 'info>,
+    params: &MakerCreateCallOptionParams,
+    vault_id: u64
 ) -> Result<()> {
 
     let current_time = Clock::get().unwrap().unix_timestamp as u64;
@@ -64,37 +191,33 @@ pub fn maker_create_call_option_vault(ctx: Context<MakerCreateCallOptionVault>,
         CallOptionError::MaturityTooEarly
     );
 
+    // The auction price never goes below the fair price, which is what takers pay once it is over
+    require!(
+        params.auction_seconds == 0 || (params.auction_floor_multiple_bps >= 10000 && params.auction_floor_multiple_bps <= params.auction_start_multiple_bps),
+        CallOptionError::InvalidAuctionParams
+    );
+
 
     // Initializing this new vault (CallOptionVaultInfo)
     // and updating number of vaults in factory
     msg!("Started initialization of CallOptionVaultInfo");
-    ctx.accounts.vault_info.factory_vault = ctx.accounts.vault_factory_info.key();
-    ctx.accounts.vault_info.ord = vault_id;
-    ctx.accounts.vault_info.max_makers = params.max_makers;
-    ctx.accounts.vault_info.max_takers = params.max_takers;
-    ctx.accounts.vault_info.lot_size = params.lot_size;
 
     // Proceed to transfer (still initializing vault)
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.maker_base_asset_account.to_account_info(),
-        to: ctx.accounts.vault_base_asset_treasury.to_account_info(),
-        authority: ctx.accounts.initializer.to_account_info(),
+    let cpi_program = payer.token_program.clone();
+    let cpi_accounts = TransferChecked {
+        from: payer.base_asset_account.to_account_info(),
+        mint: payer.base_asset_mint.to_account_info(),
+        to: vault_base_asset_treasury,
+        authority: payer.initializer.clone(),
     };
     let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
 
     let lot_multiplier:f64 = 10.0f64.powf(params.lot_size as f64);
-    let lot_lamports_qty = lot_multiplier*10.0f64.powf(ctx.accounts.base_asset_mint.decimals as f64);
+    let lot_lamports_qty = lot_multiplier*10.0f64.powf(payer.base_asset_mint.decimals as f64);
     let rounded_lamports_qty = lot_lamports_qty.ceil() as u64;
 
 
     let transfer_amount_f64 = (params.num_lots_to_sell as f64)*(rounded_lamports_qty as f64);
-    msg!("params.lot_size: {}", params.lot_size);
-    msg!("num_lots_to_sell: {}", params.num_lots_to_sell);
-    msg!("lot_multiplier: {}", lot_multiplier);
-    msg!("strike: {}", params.strike);
-
-    msg!("Transfer amount is {}", transfer_amount_f64);
     require!(
         transfer_amount_f64.is_finite(),
         CallOptionError::Overflow
@@ -106,38 +229,36 @@ pub fn maker_create_call_option_vault(ctx: Context<MakerCreateCallOptionVault>,
     
     let transfer_amount = transfer_amount_f64.ceil() as u64;
 
+    wrap_native_sol_if_needed(
+        &payer.base_asset_account.mint,
+        &payer.initializer,
+        &payer.base_asset_account.to_account_info(),
+        transfer_amount,
+        &payer.system_program,
+        &payer.token_program
+    )?;
+    // balance may have changed in this instruction (SOL wrapped)
     require!(
-        ctx.accounts.maker_base_asset_account.amount >= transfer_amount,
+        token_interface::accessor::amount(&payer.base_asset_account.to_account_info())? >= transfer_amount,
         CallOptionError::InsufficientFunds
     );
 
-    token::transfer(token_transfer_context, transfer_amount)?;
+    token_interface::transfer_checked(token_transfer_context, amount_with_transfer_fee(&payer.base_asset_mint.to_account_info(), transfer_amount)?, payer.base_asset_mint.decimals)?;
     msg!("Transferred {} base asset lamports to base asset treasury", transfer_amount);
 
-    // Continuing to initialize vault...
-    ctx.accounts.vault_info.makers_num = 1;
-    ctx.accounts.vault_info.makers_total_pending_sell = transfer_amount;
-    ctx.accounts.vault_info.makers_total_pending_settle = transfer_amount;
-    ctx.accounts.vault_info.is_makers_full = ctx.accounts.vault_info.makers_num >= ctx.accounts.vault_info.max_makers; 
-    ctx.accounts.vault_info.takers_num = 0;
-    ctx.accounts.vault_info.takers_total_deposited = 0;
-    ctx.accounts.vault_info.is_takers_full = ctx.accounts.vault_info.takers_num >= ctx.accounts.vault_info.max_takers;
-    ctx.accounts.vault_info.bonus_not_exercised = 0;
-    msg!("Finished initialization of CallOptionVaultInfo, now initializing CallOptionMakerInfo");
-
-    // Now initializing info about this maker in the vault (CallOptionMakerInfo)
-    ctx.accounts.call_option_maker_info.ord = ctx.accounts.vault_info.makers_num;
-    ctx.accounts.call_option_maker_info.base_asset_qty = transfer_amount;
-    ctx.accounts.call_option_maker_info.volume_sold = 0;
-    ctx.accounts.call_option_maker_info.is_settled = false;
-    ctx.accounts.call_option_maker_info.is_all_sold = false;
+    init_call_option_vault_with_first_maker(
+        vault_factory_key,
+        vault_info,
+        call_option_maker_info,
+        payer.base_asset_account.owner,
+        params,
+        vault_id,
+        transfer_amount
+    );
     require!(
-        ctx.accounts.call_option_maker_info.base_asset_qty >= rounded_lamports_qty,
+        call_option_maker_info.base_asset_qty >= rounded_lamports_qty,
         CallOptionError::IllegalState
     );
-    ctx.accounts.call_option_maker_info.premium_limit = params.premium_limit;
-    ctx.accounts.call_option_maker_info.owner = ctx.accounts.maker_base_asset_account.owner;
-    ctx.accounts.call_option_maker_info.call_option_vault = ctx.accounts.vault_info.key();
     msg!("Vault initialization finished");
     
     Ok(())
@@ -167,8 +288,9 @@ pub fn maker_enter_call_option_vault(ctx: Context<MakerEnterCallOptionVault>,
 
     // Proceed to transfer 
     let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_accounts = Transfer {
+    let cpi_accounts = TransferChecked {
         from: ctx.accounts.maker_base_asset_account.to_account_info(),
+        mint: ctx.accounts.base_asset_mint.to_account_info(),
         to: ctx.accounts.vault_base_asset_treasury.to_account_info(),
         authority: ctx.accounts.initializer.to_account_info(),
     };
@@ -197,12 +319,22 @@ pub fn maker_enter_call_option_vault(ctx: Context<MakerEnterCallOptionVault>,
     
     let transfer_amount = transfer_amount_f64.ceil() as u64;
 
+    wrap_native_sol_if_needed(
+        &ctx.accounts.base_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.maker_base_asset_account.to_account_info(),
+        transfer_amount,
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    ctx.accounts.maker_base_asset_account.reload()?;
+
     require!(
         ctx.accounts.maker_base_asset_account.amount >= transfer_amount,
         CallOptionError::InsufficientFunds
     );
 
-    token::transfer(token_transfer_context, transfer_amount)?;
+    token_interface::transfer_checked(token_transfer_context, amount_with_transfer_fee(&ctx.accounts.base_asset_mint.to_account_info(), transfer_amount)?, ctx.accounts.base_asset_mint.decimals)?;
     msg!("Transferred {} base asset lamports to base asset treasury", transfer_amount);
 
     // Updating vault_info ...
@@ -211,7 +343,7 @@ pub fn maker_enter_call_option_vault(ctx: Context<MakerEnterCallOptionVault>,
     msg!("Finished transferring base assets to vault base asset treasury, now updating CallOptionMakerInfo");
 
     // Now initializing info about this maker in the vault (CallOptionMakerInfo)
-    ctx.accounts.call_option_maker_info.ord = ctx.accounts.vault_info.makers_num;
+    ctx.accounts.call_option_maker_info.ord = ctx.accounts.vault_info.take_maker_ord();
     ctx.accounts.call_option_maker_info.base_asset_qty = transfer_amount;
     ctx.accounts.call_option_maker_info.volume_sold = 0;
     ctx.accounts.call_option_maker_info.is_all_sold = false;
@@ -223,6 +355,7 @@ pub fn maker_enter_call_option_vault(ctx: Context<MakerEnterCallOptionVault>,
     ctx.accounts.call_option_maker_info.premium_limit = premium_limit;
     ctx.accounts.call_option_maker_info.owner = ctx.accounts.maker_base_asset_account.owner;
     ctx.accounts.call_option_maker_info.call_option_vault = ctx.accounts.vault_info.key();
+    ctx.accounts.call_option_maker_info.resting_since = current_time;
     msg!("Vault initialization finished");
 
 
@@ -258,16 +391,25 @@ pub fn maker_adjust_position_call_option_vault(ctx: Context<MakerAdjustPositionC
         // Maker wants to increase her position in the vault
 
         let increase_amount = wanted_amount.checked_sub(ctx.accounts.call_option_maker_info.base_asset_qty).unwrap();
+        wrap_native_sol_if_needed(
+            &ctx.accounts.base_asset_mint.key(),
+            &ctx.accounts.initializer.to_account_info(),
+            &ctx.accounts.maker_base_asset_account.to_account_info(),
+            increase_amount,
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.token_program.to_account_info()
+        )?;
         // Proceed to transfer 
         let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_accounts = Transfer {
+        let cpi_accounts = TransferChecked {
             from: ctx.accounts.maker_base_asset_account.to_account_info(),
+            mint: ctx.accounts.base_asset_mint.to_account_info(),
             to: ctx.accounts.vault_base_asset_treasury.to_account_info(),
             authority: ctx.accounts.initializer.to_account_info(),
         };
         let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
 
-        token::transfer(token_transfer_context, increase_amount)?;
+        token_interface::transfer_checked(token_transfer_context, amount_with_transfer_fee(&ctx.accounts.base_asset_mint.to_account_info(), increase_amount)?, ctx.accounts.base_asset_mint.decimals)?;
         msg!("Transferred {} base asset lamports to base asset treasury", increase_amount);
         ctx.accounts.call_option_maker_info.base_asset_qty = ctx.accounts.call_option_maker_info.base_asset_qty.checked_add(increase_amount).unwrap();
         require!(
@@ -275,6 +417,7 @@ pub fn maker_adjust_position_call_option_vault(ctx: Context<MakerAdjustPositionC
             CallOptionError::IllegalState
         );
         ctx.accounts.call_option_maker_info.is_all_sold = false;
+        ctx.accounts.call_option_maker_info.resting_since = current_time;
         ctx.accounts.vault_info.makers_total_pending_sell = ctx.accounts.vault_info.makers_total_pending_sell.checked_add(increase_amount).unwrap();
         ctx.accounts.vault_info.makers_total_pending_settle = ctx.accounts.vault_info.makers_total_pending_settle.checked_add(increase_amount).unwrap();
 
@@ -288,8 +431,9 @@ pub fn maker_adjust_position_call_option_vault(ctx: Context<MakerAdjustPositionC
         );
         // Proceed to transfer 
         let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_accounts = Transfer {
+        let cpi_accounts = TransferChecked {
             from: ctx.accounts.vault_base_asset_treasury.to_account_info(),
+            mint: ctx.accounts.base_asset_mint.to_account_info(),
             to: ctx.accounts.maker_base_asset_account.to_account_info(),
             authority: ctx.accounts.vault_info.to_account_info(),
         };
@@ -307,8 +451,14 @@ pub fn maker_adjust_position_call_option_vault(ctx: Context<MakerAdjustPositionC
 
         let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
 
-        token::transfer(token_transfer_context, decrease_amount)?;
+        token_interface::transfer_checked(token_transfer_context, decrease_amount, ctx.accounts.base_asset_mint.decimals)?;
         msg!("Transferred {} base asset lamports from base asset treasury to user", decrease_amount);
+        unwrap_native_sol_if_needed(
+            &ctx.accounts.base_asset_mint.key(),
+            &ctx.accounts.initializer.to_account_info(),
+            &ctx.accounts.maker_base_asset_account.to_account_info(),
+            &ctx.accounts.token_program.to_account_info()
+        )?;
         ctx.accounts.call_option_maker_info.base_asset_qty = ctx.accounts.call_option_maker_info.base_asset_qty.checked_sub(decrease_amount).unwrap();
         ctx.accounts.call_option_maker_info.is_all_sold = ctx.accounts.call_option_maker_info.base_asset_qty.checked_sub(ctx.accounts.call_option_maker_info.volume_sold).unwrap() < rounded_lamports_qty;
         ctx.accounts.vault_info.makers_total_pending_sell = ctx.accounts.vault_info.makers_total_pending_sell.checked_sub(decrease_amount).unwrap();
@@ -327,6 +477,73 @@ pub fn maker_adjust_position_call_option_vault(ctx: Context<MakerAdjustPositionC
 
 }
 
+pub fn maker_exit_call_option_vault(ctx: Context<MakerExitCallOptionVault>) -> Result<u64> {
+
+    msg!("Entered maker_exit_call_option_vault");
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        ctx.accounts.vault_factory_info.maturity > current_time.checked_add(FREEZE_SECONDS).unwrap(),
+        CallOptionError::MaturityTooEarly
+    );
+
+    require!(
+        ctx.accounts.call_option_maker_info.base_asset_qty >= ctx.accounts.call_option_maker_info.volume_sold,
+        CallOptionError::IllegalState
+    );
+
+    // Maker gets back everything that has not been sold yet
+    let withdraw_amount = ctx.accounts.call_option_maker_info.base_asset_qty.checked_sub(ctx.accounts.call_option_maker_info.volume_sold).unwrap();
+    if withdraw_amount > 0 {
+        // Proceed to transfer 
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_base_asset_treasury.to_account_info(),
+            mint: ctx.accounts.base_asset_mint.to_account_info(),
+            to: ctx.accounts.maker_base_asset_account.to_account_info(),
+            authority: ctx.accounts.vault_info.to_account_info(),
+        };
+
+        // Preparing PDA signer
+        let auth_bump = *ctx.bumps.get("vault_info").unwrap();
+        let seeds = &[
+            "CallOptionVaultInfo".as_bytes().as_ref(), 
+            &ctx.accounts.vault_factory_info.key().to_bytes(),
+            &ctx.accounts.vault_info.ord.to_le_bytes(),
+            &[auth_bump],
+        ];
+        let signer = &[&seeds[..]];
+
+
+        let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+        token_interface::transfer_checked(token_transfer_context, withdraw_amount, ctx.accounts.base_asset_mint.decimals)?;
+        msg!("Transferred {} base asset lamports from base asset treasury to user", withdraw_amount);
+        unwrap_native_sol_if_needed(
+            &ctx.accounts.base_asset_mint.key(),
+            &ctx.accounts.initializer.to_account_info(),
+            &ctx.accounts.maker_base_asset_account.to_account_info(),
+            &ctx.accounts.token_program.to_account_info()
+        )?;
+        ctx.accounts.call_option_maker_info.base_asset_qty = ctx.accounts.call_option_maker_info.base_asset_qty.checked_sub(withdraw_amount).unwrap();
+        ctx.accounts.vault_info.makers_total_pending_sell = ctx.accounts.vault_info.makers_total_pending_sell.checked_sub(withdraw_amount).unwrap();
+        ctx.accounts.vault_info.makers_total_pending_settle = ctx.accounts.vault_info.makers_total_pending_settle.checked_sub(withdraw_amount).unwrap();
+    }
+    ctx.accounts.call_option_maker_info.is_all_sold = true;
+
+    if ctx.accounts.call_option_maker_info.volume_sold == 0 {
+        // Nothing has been sold, so the maker has no commitments in this vault:
+        // we free her slot and give her back the rent of her CallOptionMakerInfo
+        ctx.accounts.vault_info.makers_num = ctx.accounts.vault_info.makers_num.checked_sub(1).unwrap();
+        ctx.accounts.vault_info.is_makers_full = ctx.accounts.vault_info.makers_num >= ctx.accounts.vault_info.max_makers;
+        ctx.accounts.call_option_maker_info.close(ctx.accounts.initializer.to_account_info())?;
+        msg!("Maker has left the vault, CallOptionMakerInfo closed");
+    } else {
+        msg!("Maker still has {} base asset lamports sold in options, she will stay until settlement", ctx.accounts.call_option_maker_info.volume_sold);
+    }
+
+    Ok(withdraw_amount)
+}
+
 pub fn gen_update_call_option_fair_price_ticket(ctx: Context<GenUpdateCallOptionFairPriceTicket>) -> Result<()> {
     require!(
         ctx.accounts.call_option_fair_price_ticket.is_used == false,
@@ -372,6 +589,25 @@ pub fn oracle_update_call_option_price(
     Ok(())
 }
 
+// Same as oracle_update_call_option_price, also recording a spot price observation for barrier options
+pub fn oracle_update_call_option_price_with_spot(
+    ctx: Context<OracleUpdateCallOptionFairPrice>,
+    new_fair_price: u64,
+    spot_price: u64
+) -> Result<()> {
+    require!(
+        spot_price > 0,
+        CallOptionError::PriceZero
+    );
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    // Barrier is monitored on every spot price observation up to maturity
+    if ctx.accounts.vault_factory_info.maturity > current_time {
+        ctx.accounts.vault_factory_info.observe_barrier(spot_price);
+    }
+    oracle_update_call_option_price(ctx, new_fair_price)
+}
+
 //remember, oracle should have written last fair price at most MAX_SECONDS_FROM_LAST_FAIR_PRICE_UPDATE before
 pub fn taker_buy_lots_call_option_vault<'info>(ctx: Context<'_, '_, '_, 'info, TakerBuyLotsCallOptionVault<'info>>,
     max_fair_price: u64,
@@ -391,6 +627,16 @@ pub fn taker_buy_lots_call_option_vault<'info>(ctx: Context<'_, '_, '_, 'info, T
     require!(
         ctx.remaining_accounts.len() % 2 == 0,
         CallOptionError::RemainingAccountsNumIsOdd
+    );
+
+    require!(
+        ctx.remaining_accounts.len()/2 <= MAX_REMAINING_MAKERS,
+        CallOptionError::TooManyRemainingAccounts
+    );
+
+    require!(
+        !has_duplicate_accounts(ctx.remaining_accounts),
+        CallOptionError::AccountValidationError
     );      
 
 
@@ -437,12 +683,19 @@ pub fn taker_buy_lots_call_option_vault<'info>(ctx: Context<'_, '_, '_, 'info, T
         CallOptionError::LastFairPriceUpdateTooOld
     );
 
-    // We won't sell if the taker is not willing to pay the current fair price
+    // We won't sell if the taker is not willing to pay the current fair price (or auction price)
+    let premium_price = ctx.accounts.vault_info.premium_price(ctx.accounts.vault_factory_info.last_fair_price, current_time);
     require!(
-        max_fair_price >= ctx.accounts.vault_factory_info.last_fair_price,
+        max_fair_price >= premium_price,
         CallOptionError::MaxFairPriceTooLow
     );
 
+    // Takers never fund cash-settled options
+    require!(
+        !ctx.accounts.vault_factory_info.cash_settled || initial_funding == 0,
+        CallOptionError::CashSettledNoFunding
+    );
+
     let lot_multiplier:f64 = 10.0f64.powf(ctx.accounts.vault_info.lot_size as f64);
     require!(
         lot_multiplier.is_finite(),
@@ -472,25 +725,18 @@ pub fn taker_buy_lots_call_option_vault<'info>(ctx: Context<'_, '_, '_, 'info, T
 
     let lot_in_base_lamports = lot_in_base_lamports_f64.ceil() as u64;
 
+    // Fees are only shared with registered frontends
+    let frontend_fee_share = (frontend_fee_share_bps(&ctx.accounts.frontend_info) as f64)/10000.0;
+    // Fee tier of the taker based on her rolling volume before this purchase
+    init_taker_stats_if_needed(&mut ctx.accounts.taker_stats_info, ctx.accounts.initializer.key(), ctx.accounts.quote_asset_mint.key());
+    let fee_schedule = ctx.accounts.fee_tiers_info.schedule_for(ctx.accounts.taker_stats_info.rolling_volume(current_time));
+    let mut premiums_paid:u64 = 0;
+    let mut backend_fees:u64 = 0;
+    let mut frontend_fees:u64 = 0;
     let mut total_lots_bought:u64 = 0;
     for i in 0..(ctx.remaining_accounts.len()/2) {
-        let mut maker_info: Account<CallOptionMakerInfo> = CallOptionMakerInfo::from(&ctx.remaining_accounts[2*i]);
-        let maker_ata:Account<TokenAccount> = Account::try_from(&ctx.remaining_accounts[2*i + 1])?;
-
-        require!(
-            maker_info.owner == maker_ata.owner,
-            CallOptionError::AccountValidationError
-        );
-
-        require!(
-            maker_info.call_option_vault == ctx.accounts.vault_info.key(),
-            CallOptionError::AccountValidationError
-        );
-
-        require!(
-            maker_ata.mint == ctx.accounts.quote_asset_mint.key(),
-            CallOptionError::AccountValidationError
-        );
+        let mut maker_info = load_call_option_maker_info(&ctx.remaining_accounts[2*i], &ctx.accounts.vault_info)?;
+        let maker_ata = load_call_option_maker_quote_asset_account(&ctx.remaining_accounts[2*i + 1], maker_info.owner, ctx.accounts.quote_asset_mint.key())?;
         
         let maker_avbl_base_asset = maker_info.base_asset_qty.checked_sub(maker_info.volume_sold).unwrap();
         
@@ -506,64 +752,52 @@ pub fn taker_buy_lots_call_option_vault<'info>(ctx: Context<'_, '_, '_, 'info, T
                 maker_info.is_all_sold = true;
             }
             // Now transfer the premium to the maker and protocol
-            let premium_to_maker_f64 = (ctx.accounts.vault_factory_info.last_fair_price as f64)*lot_multiplier*(lots_from_this_maker as f64);
+            let premium_to_maker_f64 = (premium_price as f64)*lot_multiplier*(lots_from_this_maker as f64);
             require!(
                 premium_to_maker_f64.is_finite() && premium_to_maker_f64 > 0.0,
                 CallOptionError::IllegalState
             );
             let mut premium_to_maker = premium_to_maker_f64.round() as u64;
-            let total_fees = premium_to_maker_f64*PROTOCOL_TOTAL_FEES;
-            let backend_share = (total_fees*(1.0 - FRONTEND_SHARE)).ceil() as u64;
-            let frontend_share = (total_fees*(FRONTEND_SHARE)).ceil() as u64;
+            let total_fees = fee_schedule.total_fees(premium_to_maker_f64, ctx.accounts.vault_factory_info.protocol_fees_bps);
+            let backend_share = (total_fees*(1.0 - frontend_fee_share)).ceil() as u64;
+            let frontend_share = (total_fees*frontend_fee_share).ceil() as u64;
             require!(
                 premium_to_maker > backend_share + frontend_share,
                 CallOptionError::OptionPremiumTooLow
             );
             premium_to_maker = premium_to_maker.checked_sub(backend_share).unwrap();
             premium_to_maker = premium_to_maker.checked_sub(frontend_share).unwrap();
-
-
+            // Makers whose collateral has been resting for long get part of the protocol share back
+            let maker_rebate = fee_schedule.maker_rebate(backend_share, maker_info.resting_since, current_time);
+            premium_to_maker = premium_to_maker.checked_add(maker_rebate).unwrap();
+            let backend_share = backend_share - maker_rebate;
+
+            wrap_native_sol_if_needed(
+                &ctx.accounts.quote_asset_mint.key(),
+                &ctx.accounts.initializer.to_account_info(),
+                &ctx.accounts.taker_quote_asset_account.to_account_info(),
+                premium_to_maker + backend_share + frontend_share,
+                &ctx.accounts.system_program.to_account_info(),
+                &ctx.accounts.token_program.to_account_info()
+            )?;
 
             {
                 let cpi_program = ctx.accounts.token_program.to_account_info();
                 msg!("Started transferring premium lamports in quote asset from taker to maker");                
-                let cpi_accounts = Transfer {
+                let cpi_accounts = TransferChecked {
                     from: ctx.accounts.taker_quote_asset_account.to_account_info(),
+                    mint: ctx.accounts.quote_asset_mint.to_account_info(),
                     to: maker_ata.to_account_info(),
                     authority: ctx.accounts.initializer.to_account_info(),
                 };
                 let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
-                token::transfer(token_transfer_context, premium_to_maker)?;
+                token_interface::transfer_checked(token_transfer_context, premium_to_maker, ctx.accounts.quote_asset_mint.decimals)?;
                 msg!("Finished transferring premium quote asset lamports to maker");
             }
 
-            {
-                let cpi_program = ctx.accounts.token_program.to_account_info();
-                msg!("Started transferring backend fee lamports to protocol");
-                let cpi_accounts = Transfer {
-                    from: ctx.accounts.taker_quote_asset_account.to_account_info(),
-                    to: ctx.accounts.protocol_quote_asset_treasury.to_account_info(),
-                    authority: ctx.accounts.initializer.to_account_info(),
-                };
-                let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
-                token::transfer(token_transfer_context, backend_share)?;
-                msg!("Finished transferring backend fee lamports to protocol");
-            }
-
-            {
-                let cpi_program = ctx.accounts.token_program.to_account_info();
-                msg!("Started transferring frontend fee lamports to protocol");
-                let cpi_accounts = Transfer {
-                    from: ctx.accounts.taker_quote_asset_account.to_account_info(),
-                    to: ctx.accounts.frontend_quote_asset_treasury.to_account_info(),
-                    authority: ctx.accounts.initializer.to_account_info(),
-                };
-                let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
-                token::transfer(token_transfer_context, frontend_share)?;
-                msg!("Finished transferring frontend fee lamports to protocol");
-
-            }
-        
+            backend_fees = backend_fees.checked_add(backend_share).unwrap();
+            frontend_fees = frontend_fees.checked_add(frontend_share).unwrap();
+            premiums_paid = premiums_paid.checked_add(premium_to_maker + backend_share + frontend_share).unwrap();
             total_lots_bought = total_lots_bought.checked_add(lots_from_this_maker).unwrap();
             { // Serializing maker info
                 let mut data = ctx.remaining_accounts[2*i].try_borrow_mut_data()?;
@@ -579,6 +813,24 @@ pub fn taker_buy_lots_call_option_vault<'info>(ctx: Context<'_, '_, '_, 'info, T
         CallOptionError::IllegalState
     );
 
+    // Fees of all fills are paid to the fee vault of the quote asset at once
+    if backend_fees + frontend_fees > 0 {
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        msg!("Started transferring fee lamports to fee vault");
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.taker_quote_asset_account.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.fee_vault_quote_asset_treasury.to_account_info(),
+            authority: ctx.accounts.initializer.to_account_info(),
+        };
+        let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
+        token_interface::transfer_checked(token_transfer_context, amount_with_transfer_fee(&ctx.accounts.quote_asset_mint.to_account_info(), backend_fees + frontend_fees)?, ctx.accounts.quote_asset_mint.decimals)?;
+        msg!("Finished transferring fee lamports to fee vault");
+    }
+    ctx.accounts.fee_vault_info.accrue_backend_fees(backend_fees, &ctx.accounts.insurance_fund_info);
+    record_frontend_trade(&mut ctx.accounts.frontend_info, &mut ctx.accounts.fee_vault_info, premiums_paid, frontend_fees);
+    ctx.accounts.taker_stats_info.record_volume(current_time, premiums_paid);
+
     let mut quote_asset_transfer_qty:u64 = 0;
     if total_lots_bought > 0 {
         let max_initial_funding_quote_lamports = total_lots_bought.checked_mul(lot_price_in_quote_lamports).unwrap();
@@ -587,16 +839,25 @@ pub fn taker_buy_lots_call_option_vault<'info>(ctx: Context<'_, '_, '_, 'info, T
             let missing_funding = ctx.accounts.call_option_taker_info.max_quote_asset.checked_sub(ctx.accounts.call_option_taker_info.qty_deposited).unwrap();
             quote_asset_transfer_qty = std::cmp::min(initial_funding, missing_funding);
             
+            wrap_native_sol_if_needed(
+                &ctx.accounts.quote_asset_mint.key(),
+                &ctx.accounts.initializer.to_account_info(),
+                &ctx.accounts.taker_quote_asset_account.to_account_info(),
+                quote_asset_transfer_qty,
+                &ctx.accounts.system_program.to_account_info(),
+                &ctx.accounts.token_program.to_account_info()
+            )?;
             {
                 let cpi_program = ctx.accounts.token_program.to_account_info();
                 msg!("Started transferring quote assets to fund option");
-                let cpi_accounts = Transfer {
+                let cpi_accounts = TransferChecked {
                     from: ctx.accounts.taker_quote_asset_account.to_account_info(),
+                    mint: ctx.accounts.quote_asset_mint.to_account_info(),
                     to: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
                     authority: ctx.accounts.initializer.to_account_info(),
                 };
                 let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
-                token::transfer(token_transfer_context, quote_asset_transfer_qty)?;
+                token_interface::transfer_checked(token_transfer_context, amount_with_transfer_fee(&ctx.accounts.quote_asset_mint.to_account_info(), quote_asset_transfer_qty)?, ctx.accounts.quote_asset_mint.decimals)?;
                 msg!("Finished transferring quote assets to fund option")
             }            
             ctx.accounts.vault_info.takers_total_deposited = ctx.accounts.vault_info.takers_total_deposited.checked_add(quote_asset_transfer_qty).unwrap();
@@ -612,8 +873,643 @@ pub fn taker_buy_lots_call_option_vault<'info>(ctx: Context<'_, '_, '_, 'info, T
 
     let result = TakerBuyLotsCallOptionReturn {
         num_lots_bought: total_lots_bought,
-        price: ctx.accounts.vault_factory_info.last_fair_price,
+        price: premium_price,
         funding_added: quote_asset_transfer_qty
     };
     Ok(result)
 }
+
+pub fn gen_settle_call_option_price_ticket(ctx: Context<GenSettleCallOptionPriceTicket>) -> Result<()> {
+    require!(
+        ctx.accounts.call_option_settle_price_ticket.is_used == false,
+        CallOptionError::UsedUpdateTicket
+    );
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        ctx.accounts.vault_factory_info.maturity < current_time,
+        CallOptionError::MaturityTooLate
+    );
+
+    msg!("Started transferring lamports to oracle");
+    let oracle_fee_transfer_cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        system_program::Transfer {
+            from: ctx.accounts.initializer.to_account_info(),
+            to: ctx.accounts.oracle_wallet.to_account_info()
+        }
+    );
+    system_program::transfer(oracle_fee_transfer_cpi_context, LAMPORTS_FOR_UPDATE_SETTLEPRICE_TICKET)?;
+    msg!("Finished transferring lamports to oracle");
+
+
+    Ok(())
+}
+
+pub fn oracle_update_call_option_settle_price(
+    ctx: Context<OracleUpdateCallOptionSettlePrice>,
+    settle_price: u64
+) -> Result<()> {
+    require!(
+        settle_price > 0,
+        CallOptionError::PriceZero
+    );
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        ctx.accounts.vault_factory_info.maturity < current_time,
+        CallOptionError::MaturityTooLate
+    );
+
+    if !ctx.accounts.vault_factory_info.matured {
+        ctx.accounts.vault_factory_info.settled_price = settle_price;
+        ctx.accounts.vault_factory_info.matured = true;
+        ap::remove_series_from_index(&mut ctx.accounts.series_index, ctx.accounts.vault_factory_info.key());
+    }
+
+    ctx.accounts.update_ticket.is_used = true;
+
+    Ok(())
+
+}
+
+pub fn maker_activate_call_option_emergency_mode(ctx: Context<MakerActivateCallOptionEmergencyMode>) -> Result<()> {
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        is_emergency_grace_period_over(ctx.accounts.vault_factory_info.maturity, current_time),
+        CallOptionError::EmergencyModeTooEarly
+    );
+
+    ctx.accounts.vault_factory_info.emergency_mode = true;
+
+    Ok(())
+}
+
+pub fn taker_activate_call_option_emergency_mode(ctx: Context<TakerActivateCallOptionEmergencyMode>) -> Result<()> {
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        is_emergency_grace_period_over(ctx.accounts.vault_factory_info.maturity, current_time),
+        CallOptionError::EmergencyModeTooEarly
+    );
+
+    ctx.accounts.vault_factory_info.emergency_mode = true;
+
+    Ok(())
+}
+
+pub fn maker_call_option_emergency_exit(ctx: Context<MakerCallOptionEmergencyExit>) -> Result<()> {
+    // Maker gets her deposited base assets back, as if the options had not been exercised
+    transfer_call_option_emergency_collateral(
+        &ctx.accounts.vault_info,
+        *ctx.bumps.get("vault_info").unwrap(),
+        &ctx.accounts.call_option_maker_info,
+        &ctx.accounts.vault_base_asset_treasury,
+        &ctx.accounts.base_asset_mint,
+        ctx.accounts.maker_base_asset_account.to_account_info(),
+        ctx.accounts.token_program.to_account_info()
+    )?;
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.base_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.maker_base_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    ctx.accounts.call_option_maker_info.base_asset_qty = 0;
+    ctx.accounts.call_option_maker_info.volume_sold = 0;
+    ctx.accounts.call_option_maker_info.is_settled = true;
+
+    Ok(())
+}
+
+pub fn taker_call_option_emergency_exit(ctx: Context<TakerCallOptionEmergencyExit>) -> Result<()> {
+    // Taker gets her deposited quote assets back
+    if ctx.accounts.call_option_taker_info.qty_deposited > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.taker_quote_asset_account.to_account_info(),
+            authority: ctx.accounts.vault_info.to_account_info(),
+        };
+
+        // Preparing PDA signer
+        let auth_bump = *ctx.bumps.get("vault_info").unwrap();
+        let vault_factory_key = ctx.accounts.vault_factory_info.key();
+        let vault_ord = ctx.accounts.vault_info.ord.to_le_bytes();
+        let seeds = &[
+            "CallOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_key.as_ref(),
+            vault_ord.as_ref(),
+            &[auth_bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let token_transfer_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::transfer_checked(token_transfer_context, ctx.accounts.call_option_taker_info.qty_deposited, ctx.accounts.quote_asset_mint.decimals)?;
+    }
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.taker_quote_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    ctx.accounts.call_option_taker_info.qty_deposited = 0;
+    ctx.accounts.call_option_taker_info.is_settled = true;
+
+    Ok(())
+}
+
+// Transfers the base assets deposited by a maker back to her (or to the roll vault that is the maker),
+// callers must have checked that the vault factory is in emergency mode
+pub fn transfer_call_option_emergency_collateral<'info>(vault_info: &Account<'info, CallOptionVaultInfo>,
+    vault_bump: u8,
+    call_option_maker_info: &Account<'info, CallOptionMakerInfo>,
+    vault_base_asset_treasury: &InterfaceAccount<'info, TokenAccount>,
+    base_asset_mint: &InterfaceAccount<'info, Mint>,
+    to: AccountInfo<'info>,
+    token_program: AccountInfo<'info>
+) -> Result<()> {
+    if call_option_maker_info.base_asset_qty == 0 {
+        return Ok(());
+    }
+
+    // Preparing PDA signer
+    let vault_factory_key = vault_info.factory_vault;
+    let vault_ord = vault_info.ord.to_le_bytes();
+    let seeds = &[
+        "CallOptionVaultInfo".as_bytes().as_ref(), 
+        vault_factory_key.as_ref(),
+        vault_ord.as_ref(),
+        &[vault_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = TransferChecked {
+        from: vault_base_asset_treasury.to_account_info(),
+        mint: base_asset_mint.to_account_info(),
+        to,
+        authority: vault_info.to_account_info(),
+    };
+    let token_transfer_context = CpiContext::new_with_signer(token_program, cpi_accounts, signer);
+    token_interface::transfer_checked(token_transfer_context, call_option_maker_info.base_asset_qty, base_asset_mint.decimals)?;
+    msg!("Transferred {} base asset lamports back to maker in emergency mode", call_option_maker_info.base_asset_qty);
+
+    Ok(())
+}
+
+pub fn maker_settle_call_option(ctx: Context<MakerSettleCallOption>) -> Result<CallOptionSettleReturn> {
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        ctx.accounts.vault_factory_info.maturity < current_time,
+        CallOptionError::IllegalState  // should not have passed maturity test, must never happen
+    );
+
+    if ctx.accounts.vault_factory_info.cash_settled {
+        return maker_settle_cash_settled_call_option(ctx);
+    }
+
+    let result = compute_call_option_maker_physical_settlement(
+        &ctx.accounts.vault_factory_info,
+        &mut ctx.accounts.vault_info,
+        &ctx.accounts.call_option_maker_info,
+        ctx.accounts.base_asset_mint.decimals
+    )?;
+
+    // Preparing PDA signer
+    let auth_bump = *ctx.bumps.get("vault_info").unwrap();
+    let vault_factory_key = ctx.accounts.vault_factory_info.key();
+    let vault_ord = ctx.accounts.vault_info.ord.to_le_bytes();
+    let seeds = &[
+        "CallOptionVaultInfo".as_bytes().as_ref(), 
+        vault_factory_key.as_ref(),
+        vault_ord.as_ref(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    if result.base_asset_transfer > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_base_asset_treasury.to_account_info(),
+            mint: ctx.accounts.base_asset_mint.to_account_info(),
+            to: ctx.accounts.maker_base_asset_account.to_account_info(),
+            authority: ctx.accounts.vault_info.to_account_info(),
+        };
+        let token_transfer_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::transfer_checked(token_transfer_context, result.base_asset_transfer, ctx.accounts.base_asset_mint.decimals)?;
+    }
+    if result.quote_asset_transfer > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.maker_quote_asset_account.to_account_info(),
+            authority: ctx.accounts.vault_info.to_account_info(),
+        };
+        let token_transfer_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::transfer_checked(token_transfer_context, result.quote_asset_transfer, ctx.accounts.quote_asset_mint.decimals)?;
+    }
+
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.base_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.maker_base_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.maker_quote_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    ctx.accounts.call_option_maker_info.base_asset_qty = 0;
+    ctx.accounts.call_option_maker_info.volume_sold = 0;
+    ctx.accounts.call_option_maker_info.is_settled = true;
+
+    Ok(result)
+}
+
+pub fn taker_settle_call_option(ctx: Context<TakerSettleCallOption>) -> Result<CallOptionSettleReturn> {
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        ctx.accounts.vault_factory_info.maturity < current_time,
+        CallOptionError::IllegalState  // should not have passed maturity test, must never happen
+    );
+
+    if ctx.accounts.vault_factory_info.cash_settled {
+        return taker_settle_cash_settled_call_option(ctx);
+    }
+
+    let mut result = CallOptionSettleReturn {
+        base_asset_transfer: 0,
+        quote_asset_transfer: 0,
+        settle_result: CallOptionSettleResult::NotExercised
+    };
+
+    // Preparing PDA signer
+    let auth_bump = *ctx.bumps.get("vault_info").unwrap();
+    let vault_factory_key = ctx.accounts.vault_factory_info.key();
+    let vault_ord = ctx.accounts.vault_info.ord.to_le_bytes();
+    let seeds = &[
+        "CallOptionVaultInfo".as_bytes().as_ref(), 
+        vault_factory_key.as_ref(),
+        vault_ord.as_ref(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    if ctx.accounts.vault_factory_info.settled_price <= ctx.accounts.vault_factory_info.strike || ctx.accounts.vault_factory_info.is_barrier_void() {
+        msg!("Call option is not favorable to taker, will NOT be exercised");
+        // i.e. taker gets her deposited quote assets back
+        result.settle_result = CallOptionSettleResult::NotExercised;
+        if ctx.accounts.call_option_taker_info.qty_deposited > 0 {
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+                mint: ctx.accounts.quote_asset_mint.to_account_info(),
+                to: ctx.accounts.taker_quote_asset_account.to_account_info(),
+                authority: ctx.accounts.vault_info.to_account_info(),
+            };
+            let token_transfer_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+            token_interface::transfer_checked(token_transfer_context, ctx.accounts.call_option_taker_info.qty_deposited, ctx.accounts.quote_asset_mint.decimals)?;
+            result.base_asset_transfer = 0;
+            result.quote_asset_transfer = ctx.accounts.call_option_taker_info.qty_deposited;
+        }
+    } else {
+        msg!("Call option is favorable to taker, WILL be exercised");
+        // i.e. buy with qty_deposited at strike price
+        result.settle_result = CallOptionSettleResult::PartiallyExercised;
+        if ctx.accounts.call_option_taker_info.qty_deposited > 0 {
+            if ctx.accounts.call_option_taker_info.qty_deposited == ctx.accounts.call_option_taker_info.max_quote_asset {
+                result.settle_result = CallOptionSettleResult::FullyExercised;
+            }
+            let qty_deposited_base_lamports_value_f64 = (ctx.accounts.call_option_taker_info.qty_deposited as f64) / (ctx.accounts.vault_factory_info.strike as f64) * 10.0f64.powf(ctx.accounts.base_asset_mint.decimals as f64);
+            require!(
+                qty_deposited_base_lamports_value_f64.is_finite(),
+                CallOptionError::Overflow
+            );
+            let qty_deposited_base_lamports_value = qty_deposited_base_lamports_value_f64.floor() as u64;
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.vault_base_asset_treasury.to_account_info(),
+                mint: ctx.accounts.base_asset_mint.to_account_info(),
+                to: ctx.accounts.taker_base_asset_account.to_account_info(),
+                authority: ctx.accounts.vault_info.to_account_info(),
+            };
+            let token_transfer_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+            token_interface::transfer_checked(token_transfer_context, qty_deposited_base_lamports_value, ctx.accounts.base_asset_mint.decimals)?;
+            result.base_asset_transfer = qty_deposited_base_lamports_value;
+            result.quote_asset_transfer = 0;
+        }
+    }
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.base_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.taker_base_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.taker_quote_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    ctx.accounts.call_option_taker_info.qty_deposited = 0;
+    ctx.accounts.call_option_taker_info.is_settled = true;
+
+    Ok(result)
+}
+
+// In a cash-settled call the maker pays max(settled_price - strike, 0) for each base asset unit
+// she has sold options for. As her collateral is in base asset, she pays it in base asset
+// at the settled price, and gets the rest back
+fn maker_settle_cash_settled_call_option(ctx: Context<MakerSettleCallOption>) -> Result<CallOptionSettleReturn> {
+    let mut result = CallOptionSettleReturn {
+        base_asset_transfer: 0,
+        quote_asset_transfer: 0,
+        settle_result: CallOptionSettleResult::NotExercised
+    };
+
+    let mut payout:u64 = 0;
+    if ctx.accounts.vault_factory_info.settled_price > ctx.accounts.vault_factory_info.strike && !ctx.accounts.vault_factory_info.is_barrier_void() {
+        msg!("Cash-settled call option is favorable to taker, maker will pay the difference to strike");
+        let price_difference = ctx.accounts.vault_factory_info.settled_price.checked_sub(ctx.accounts.vault_factory_info.strike).unwrap();
+        let payout_f64 = (ctx.accounts.call_option_maker_info.volume_sold as f64) * (price_difference as f64) / (ctx.accounts.vault_factory_info.settled_price as f64);
+        require!(
+            payout_f64.is_finite() && payout_f64 >= 0.0,
+            CallOptionError::Overflow
+        );
+        // rounding in favor of takers, so the vault always has enough to pay them
+        payout = std::cmp::min(payout_f64.ceil() as u64, ctx.accounts.call_option_maker_info.volume_sold);
+        if payout > 0 {
+            result.settle_result = CallOptionSettleResult::FullyExercised;
+        }
+    } else {
+        msg!("Cash-settled call option is not favorable to taker, will NOT be exercised");
+    }
+
+    let transfer_base_asset = ctx.accounts.call_option_maker_info.base_asset_qty.checked_sub(payout).unwrap();
+    if transfer_base_asset > 0 {
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_base_asset_treasury.to_account_info(),
+            mint: ctx.accounts.base_asset_mint.to_account_info(),
+            to: ctx.accounts.maker_base_asset_account.to_account_info(),
+            authority: ctx.accounts.vault_info.to_account_info(),
+        };
+
+        // Preparing PDA signer
+        let auth_bump = *ctx.bumps.get("vault_info").unwrap();
+        let seeds = &[
+            "CallOptionVaultInfo".as_bytes().as_ref(), 
+            &ctx.accounts.vault_factory_info.key().to_bytes(),
+            &ctx.accounts.vault_info.ord.to_le_bytes(),
+            &[auth_bump],
+        ];
+        let signer = &[&seeds[..]];
+
+
+        let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+        token_interface::transfer_checked(token_transfer_context, transfer_base_asset, ctx.accounts.base_asset_mint.decimals)?;
+    }
+    result.base_asset_transfer = transfer_base_asset;
+
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.base_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.maker_base_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.maker_quote_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    ctx.accounts.call_option_maker_info.base_asset_qty = 0;
+    ctx.accounts.call_option_maker_info.volume_sold = 0;
+    ctx.accounts.call_option_maker_info.is_settled = true;
+
+    Ok(result)
+}
+
+// In a cash-settled call the taker gets max(settled_price - strike, 0) for each base asset unit
+// she has bought options for, paid in base asset at the settled price
+fn taker_settle_cash_settled_call_option(ctx: Context<TakerSettleCallOption>) -> Result<CallOptionSettleReturn> {
+    let mut result = CallOptionSettleReturn {
+        base_asset_transfer: 0,
+        quote_asset_transfer: 0,
+        settle_result: CallOptionSettleResult::NotExercised
+    };
+
+    if ctx.accounts.vault_factory_info.settled_price > ctx.accounts.vault_factory_info.strike && !ctx.accounts.vault_factory_info.is_barrier_void() {
+        msg!("Cash-settled call option is favorable to taker, WILL be exercised");
+        let price_difference = ctx.accounts.vault_factory_info.settled_price.checked_sub(ctx.accounts.vault_factory_info.strike).unwrap();
+        // max_quote_asset is strike*quantity
+        let payoff_f64 = (ctx.accounts.call_option_taker_info.max_quote_asset as f64) / (ctx.accounts.vault_factory_info.strike as f64) * 10.0f64.powf(ctx.accounts.base_asset_mint.decimals as f64) * (price_difference as f64) / (ctx.accounts.vault_factory_info.settled_price as f64);
+        require!(
+            payoff_f64.is_finite() && payoff_f64 >= 0.0,
+            CallOptionError::Overflow
+        );
+        let payoff = payoff_f64.floor() as u64;
+        if payoff > 0 {
+            result.settle_result = CallOptionSettleResult::FullyExercised;
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.vault_base_asset_treasury.to_account_info(),
+                mint: ctx.accounts.base_asset_mint.to_account_info(),
+                to: ctx.accounts.taker_base_asset_account.to_account_info(),
+                authority: ctx.accounts.vault_info.to_account_info(),
+            };
+
+            // Preparing PDA signer
+            let auth_bump = *ctx.bumps.get("vault_info").unwrap();
+            let seeds = &[
+                "CallOptionVaultInfo".as_bytes().as_ref(), 
+                &ctx.accounts.vault_factory_info.key().to_bytes(),
+                &ctx.accounts.vault_info.ord.to_le_bytes(),
+                &[auth_bump],
+            ];
+            let signer = &[&seeds[..]];
+    
+
+            let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+            token_interface::transfer_checked(token_transfer_context, payoff, ctx.accounts.base_asset_mint.decimals)?;
+            result.base_asset_transfer = payoff;
+        }
+    } else {
+        msg!("Cash-settled call option is not favorable to taker, will NOT be exercised");
+    }
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.base_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.taker_base_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+
+    ctx.accounts.call_option_taker_info.is_settled = true;
+
+    Ok(result)
+}
+
+// Computes what a maker gets back when a physically settled call option vault is settled: her unsold (or not exercised)
+// base assets and the quote assets, at strike, for what has been exercised. Updates the vault bonus_not_exercised,
+// the transfers are up to the caller
+pub fn compute_call_option_maker_physical_settlement(vault_factory_info: &Account<CallOptionVaultFactoryInfo>,
+    vault_info: &mut Account<CallOptionVaultInfo>,
+    call_option_maker_info: &Account<CallOptionMakerInfo>,
+    base_asset_decimals: u8
+) -> Result<CallOptionSettleReturn> {
+    let mut result = CallOptionSettleReturn {
+        base_asset_transfer: 0,
+        quote_asset_transfer: 0,
+        settle_result: CallOptionSettleResult::NotExercised
+    };
+
+    if vault_factory_info.settled_price <= vault_factory_info.strike || vault_factory_info.is_barrier_void() {
+        msg!("Call option is not favorable to taker, will NOT be exercised");
+        // i.e. maker gets her deposited base assets back
+        result.settle_result = CallOptionSettleResult::NotExercised;
+        result.base_asset_transfer = call_option_maker_info.base_asset_qty;
+        result.quote_asset_transfer = 0;
+    } else {
+        msg!("Call option is favorable to taker, WILL be exercised");
+        // maker will sell up to the limit of call_option_maker_info.volume_sold
+        // however as takers may have insufficiently funded their options, the maker
+        // may eventually sell less, in a first settle first served base
+
+        let total_deposited_base_lamports_value_f64 = (vault_info.takers_total_deposited as f64) / (vault_factory_info.strike as f64) * 10.0f64.powf(base_asset_decimals as f64);
+        require!(
+            total_deposited_base_lamports_value_f64.is_finite(),
+            CallOptionError::Overflow
+        );
+        let total_deposited_base_lamports_value = total_deposited_base_lamports_value_f64.floor() as u64;
+        // base asset lamports that have been sold in options by all makers in this vault
+        let total_sold = vault_info.makers_total_pending_settle.checked_sub(vault_info.makers_total_pending_sell).unwrap();
+        let total_bonus = total_sold.saturating_sub(total_deposited_base_lamports_value);
+        let max_bonus = total_bonus.saturating_sub(vault_info.bonus_not_exercised);
+
+        let maker_bonus = std::cmp::min(max_bonus, call_option_maker_info.volume_sold);
+        let maker_sell_amount = call_option_maker_info.volume_sold.checked_sub(maker_bonus).unwrap();
+        let mut transfer_base_asset = call_option_maker_info.base_asset_qty.checked_sub(call_option_maker_info.volume_sold).unwrap(); // initially unsold base assets
+        if maker_bonus > 0 {
+            transfer_base_asset = transfer_base_asset.checked_add(maker_bonus).unwrap();
+            vault_info.bonus_not_exercised = vault_info.bonus_not_exercised.checked_add(maker_bonus).unwrap();
+        }
+        if transfer_base_asset > 0 {
+            msg!("Lucky maker! Will only be partially exercised!");
+            result.settle_result = CallOptionSettleResult::PartiallyExercised;
+            result.base_asset_transfer = transfer_base_asset;
+        } else {
+            msg!("Maker will be fully exercised!");
+            result.settle_result = CallOptionSettleResult::FullyExercised;
+            result.base_asset_transfer = 0;
+        }
+        if maker_sell_amount > 0 {
+            let quote_lamports_f64 = (maker_sell_amount as f64) / 10.0f64.powf(base_asset_decimals as f64) * (vault_factory_info.strike as f64);
+            require!(
+                quote_lamports_f64.is_finite(),
+                CallOptionError::Overflow
+            );
+            result.quote_asset_transfer = quote_lamports_f64.floor() as u64;
+        }
+    }
+
+    Ok(result)
+}
+
+pub fn init_call_option_vault_factory_info(vault_factory_info: &mut Account<CallOptionVaultFactoryInfo>,
+    base_asset: Pubkey,
+    quote_asset: Pubkey,
+    params: &MakerCreateCallOptionParams,
+    protocol_fees_bps: u16
+) {
+    vault_factory_info.next_vault_id = 1;
+    vault_factory_info.maturity = params.maturity;
+    vault_factory_info.matured = false;
+    vault_factory_info.strike = params.strike;
+    vault_factory_info.base_asset = base_asset;
+    vault_factory_info.quote_asset = quote_asset;
+    vault_factory_info.emergency_mode = false;
+    vault_factory_info.cash_settled = params.cash_settled;
+    vault_factory_info.protocol_fees_bps = protocol_fees_bps;
+    vault_factory_info.barrier = params.barrier;
+    vault_factory_info.barrier_up = params.barrier_up;
+    vault_factory_info.knock_in = params.knock_in;
+    vault_factory_info.knocked = false;
+
+    vault_factory_info.is_initialized = true;
+    msg!("CallOptionVaultFactoryInfo initialized");
+}
+
+// CallOptionMakerInfo of a maker of vault_info passed in remaining accounts: writable, owned by
+// this program and at the address derived from the vault and its owner
+fn load_call_option_maker_info<'info>(info: &AccountInfo<'info>,
+    vault_info: &Account<CallOptionVaultInfo>
+) -> Result<Account<'info, CallOptionMakerInfo>> {
+    let maker_info: Account<CallOptionMakerInfo> = load_writable_program_account(info).ok_or(CallOptionError::AccountValidationError)?;
+    require!(
+        maker_info.call_option_vault == vault_info.key() &&
+        is_program_address(info.key, &[
+            "CallOptionMakerInfo".as_bytes(),
+            vault_info.factory_vault.as_ref(),
+            &vault_info.ord.to_le_bytes(),
+            maker_info.owner.as_ref()
+        ]),
+        CallOptionError::AccountValidationError
+    );
+
+    Ok(maker_info)
+}
+
+// Quote asset account of a maker passed in remaining accounts to receive premiums
+fn load_call_option_maker_quote_asset_account<'info>(info: &AccountInfo<'info>,
+    maker: Pubkey,
+    quote_asset_mint: Pubkey
+) -> Result<InterfaceAccount<'info, TokenAccount>> {
+    let maker_ata = load_writable_token_account(info).ok_or(CallOptionError::AccountValidationError)?;
+    require!(
+        maker_ata.owner == maker && maker_ata.mint == quote_asset_mint,
+        CallOptionError::AccountValidationError
+    );
+
+    Ok(maker_ata)
+}
+
+// Initializes a new vault (CallOptionVaultInfo) whose first maker (CallOptionMakerInfo) has
+// already deposited base_asset_qty in the vault base asset treasury
+pub fn init_call_option_vault_with_first_maker(vault_factory_key: Pubkey,
+    vault_info: &mut Account<CallOptionVaultInfo>,
+    call_option_maker_info: &mut Account<CallOptionMakerInfo>,
+    maker: Pubkey,
+    params: &MakerCreateCallOptionParams,
+    vault_id: u64,
+    base_asset_qty: u64
+) {
+    vault_info.factory_vault = vault_factory_key;
+    vault_info.ord = vault_id;
+    vault_info.max_makers = params.max_makers;
+    vault_info.max_takers = params.max_takers;
+    vault_info.lot_size = params.lot_size;
+    vault_info.makers_num = 1;
+    vault_info.next_maker_ord = 0;
+    vault_info.makers_total_pending_sell = base_asset_qty;
+    vault_info.makers_total_pending_settle = base_asset_qty;
+    vault_info.is_makers_full = vault_info.makers_num >= vault_info.max_makers; 
+    vault_info.takers_num = 0;
+    vault_info.takers_total_deposited = 0;
+    vault_info.is_takers_full = vault_info.takers_num >= vault_info.max_takers;
+    vault_info.bonus_not_exercised = 0;
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    vault_info.auction_start = current_time;
+    vault_info.auction_end = if params.auction_seconds > 0 { current_time.checked_add(params.auction_seconds).unwrap() } else { 0 };
+    vault_info.auction_start_multiple_bps = params.auction_start_multiple_bps;
+    vault_info.auction_floor_multiple_bps = params.auction_floor_multiple_bps;
+    msg!("Finished initialization of CallOptionVaultInfo, now initializing CallOptionMakerInfo");
+
+    // Now initializing info about this maker in the vault (CallOptionMakerInfo)
+    call_option_maker_info.ord = vault_info.take_maker_ord();
+    call_option_maker_info.base_asset_qty = base_asset_qty;
+    call_option_maker_info.volume_sold = 0;
+    call_option_maker_info.is_settled = false;
+    call_option_maker_info.is_all_sold = false;
+    call_option_maker_info.premium_limit = params.premium_limit;
+    call_option_maker_info.owner = maker;
+    call_option_maker_info.call_option_vault = vault_info.key();
+    call_option_maker_info.resting_since = current_time;
+}
@@ -14,7 +14,37 @@ pub struct CallOptionVaultFactoryInfo {
     pub last_fair_price: u64,
     pub ts_last_fair_price: u64,
     pub settled_price: u64,
-    pub emergency_mode: bool
+    pub emergency_mode: bool,
+    pub cash_settled: bool,         // if true, options in this factory are settled in cash: takers never deposit anything
+    pub protocol_fees_bps: u16,     // fees on premiums, copied from the AssetPairConfig when the factory is created
+
+    pub barrier: u64,               // 0 for vanilla options, otherwise the barrier level in quote asset lamports
+    pub barrier_up: bool,           // if true, knocked when the spot price goes up to the barrier, otherwise when it goes down to it
+    pub knock_in: bool,             // if true, options are only exercised if knocked (knock-in), otherwise only if never knocked (knock-out)
+    pub knocked: bool               // permanently set by the first oracle spot price observation that crosses the barrier before maturity
+}
+
+impl CallOptionVaultFactoryInfo {
+    // Records an oracle spot price observation, the knocked flag can never be unset
+    pub fn observe_barrier(&mut self, spot_price: u64) {
+        if self.barrier > 0 && !self.knocked {
+            let crossed = if self.barrier_up { spot_price >= self.barrier } else { spot_price <= self.barrier };
+            if crossed {
+                self.knocked = true;
+                msg!("Spot price {} crossed barrier {}, options are now knocked", spot_price, self.barrier);
+            }
+        }
+    }
+
+    // Knocked-out options, and knock-in options that have never been knocked, are never exercised
+    pub fn is_barrier_void(&self) -> bool {
+        self.barrier > 0 && self.knock_in != self.knocked
+    }
+
+    // Id the next vault created in this factory gets, vault ids of a new factory start at 1
+    pub fn upcoming_vault_id(&self) -> u64 {
+        if self.is_initialized { self.next_vault_id } else { 1 }
+    }
 }
 
 #[account]
@@ -27,6 +57,7 @@ pub struct CallOptionVaultInfo {
     pub lot_size: i8, //10^lot_size, for instance 0 means 1; -1 means 0.1; 2 means 100
 
     pub makers_num: u16,
+    pub next_maker_ord: u16,                // ord of the last maker that joined, makers_num goes down when makers exit
     pub makers_total_pending_sell: u64,     // the amount of base asset lamports that makers have deposited, but not yet sold
     pub makers_total_pending_settle: u64,   // the amount of base asset lamports that has been sold in options and has not been settled
     pub is_makers_full: bool,
@@ -34,8 +65,37 @@ pub struct CallOptionVaultInfo {
     pub takers_num: u16,
     pub takers_total_deposited: u64,        // the amount that takers have funded
     pub is_takers_full: bool,
-    pub bonus_not_exercised: u64            // the amount of bonus that has been given to early settlers (makers) when the option was exercised
+    pub bonus_not_exercised: u64,           // the amount of bonus that has been given to early settlers (makers) when the option was exercised
                                         // but the takers have not fully funded what they had bought.
+
+    // Optional Dutch auction when the vault opens: takers pay the fair price times a multiple that decays
+    // linearly from auction_start_multiple_bps to auction_floor_multiple_bps until auction_end
+    pub auction_start: u64,
+    pub auction_end: u64,                   // 0 if the vault has no auction
+    pub auction_start_multiple_bps: u16,
+    pub auction_floor_multiple_bps: u16
+}
+
+impl CallOptionVaultInfo {
+    // Premium per unit of base asset takers pay at current_time, the fair price itself once the auction is over
+    pub fn premium_price(&self, fair_price: u64, current_time: u64) -> u64 {
+        if current_time >= self.auction_end {
+            return fair_price;
+        }
+        let duration = self.auction_end.saturating_sub(self.auction_start) as u128;
+        let elapsed = current_time.saturating_sub(self.auction_start) as u128;
+        let decay_bps = (self.auction_start_multiple_bps.saturating_sub(self.auction_floor_multiple_bps) as u128)*elapsed/duration;
+        let multiple_bps = (self.auction_start_multiple_bps as u128).saturating_sub(decay_bps);
+        ((fair_price as u128)*multiple_bps/10000) as u64
+    }
+}
+
+impl CallOptionVaultInfo {
+    // Ord of a maker joining the vault, never reused when makers exit
+    pub fn take_maker_ord(&mut self) -> u16 {
+        self.next_maker_ord = self.next_maker_ord.checked_add(1).unwrap();
+        self.next_maker_ord
+    }
 }
 
 #[account]
@@ -47,13 +107,8 @@ pub struct CallOptionMakerInfo {
     pub is_settled: bool,           // if the maker has already got his tokens after maturity
     pub premium_limit: u64,         // minimum price for option premium he is willing to get, can be zero if he is ok of selling at whatever the fair price
     pub owner: Pubkey,
-    pub call_option_vault: Pubkey
-}
-
-impl CallOptionMakerInfo {
-    pub fn from<'info>(info: &AccountInfo<'info>) -> Account<'info, Self> {
-        Account::try_from(info).unwrap()
-    }
+    pub call_option_vault: Pubkey,
+    pub resting_since: u64          // when she last entered or added lots, for maker rebates on fees
 }
 
 
@@ -74,3 +129,9 @@ pub struct CallOptionTakerInfo {
     pub owner: Pubkey,
     pub call_option_vault: Pubkey
 }
+
+#[account]
+pub struct CallOptionSettlePriceTicketInfo {
+    pub is_used: bool,
+    pub factory_vault: Pubkey
+}
@@ -60,5 +60,20 @@ pub enum CallOptionError {
     OptionPremiumTooLow,
 
     #[msg("Insufficient time passed since maturity to activate emergency mode, please wait more")]
-    EmergencyModeTooEarly
-}    
+    EmergencyModeTooEarly,
+
+    #[msg("Vault factory already exists with a different settlement mode (cash or physical)")]
+    SettlementModeMismatch,
+
+    #[msg("Cash-settled options are not funded by takers")]
+    CashSettledNoFunding,
+
+    #[msg("Vault factory already exists with a different barrier")]
+    BarrierMismatch,
+
+    #[msg("Auction floor must be at least the fair price (10000 bps) and not above the starting multiple")]
+    InvalidAuctionParams,
+
+    #[msg("Too many accounts in remaining accounts")]
+    TooManyRemainingAccounts
+}
@@ -1,15 +1,21 @@
 use anchor_lang::prelude::*;
+use crate::insurance_funds::data::InsuranceFundInfo;
+use crate::fee_vaults::data::FeeVaultInfo;
+use crate::fee_tiers::data::{FeeTiersInfo, TakerStatsInfo};
+use crate::frontends::data::FrontendInfo;
 use crate::MakerCreateCallOptionParams;
 use crate::call_options::data::{
     CallOptionVaultFactoryInfo,
     CallOptionVaultInfo,
     CallOptionMakerInfo,
     CallOptionUpdateFairPriceTicketInfo,
-    CallOptionTakerInfo
+    CallOptionTakerInfo,
+    CallOptionSettlePriceTicketInfo
 };
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::asset_pairs::data::{AssetPairConfig, SeriesIndex};
+use anchor_spl::token_interface::{Mint, TokenInterface, TokenAccount};
 use anchor_spl::associated_token::AssociatedToken;
-use crate::{PROTOCOL_FEES_ADDRESS, ORACLE_ADDRESS};
+use crate::ORACLE_ADDRESS;
 
 #[derive(Accounts)]
 #[instruction(
@@ -26,11 +32,29 @@ pub struct MakerNextCallOptionVaultId<'info> {
     )]
     pub vault_factory_info: Account<'info, CallOptionVaultFactoryInfo>,
 
+    #[account(
+        seeds=["AssetPairConfig".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = asset_pair_config.is_initialized == true
+    )]
+    pub asset_pair_config: Account<'info, AssetPairConfig>,
+
+    #[account(
+        mut,
+        seeds=["SeriesIndex".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        realloc = SeriesIndex::space(series_index.entries.len() + 1),
+        realloc::payer = initializer,
+        realloc::zero = false,
+        constraint = series_index.is_initialized == true
+    )]
+    pub series_index: Box<Account<'info, SeriesIndex>>,
+
     // mint for the base_asset
-    pub base_asset_mint: Account<'info, Mint>,
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
 
     // mint for the quote asset
-    pub quote_asset_mint: Account<'info, Mint>,
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
 
     // Check if initializer is signer, mut is required to reduce lamports (fees)
     #[account(mut)]
@@ -54,6 +78,10 @@ pub struct MakerCreateCallOptionVault<'info> {
         constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
         constraint = vault_factory_info.maturity == params.maturity,
         constraint = vault_factory_info.strike == params.strike,
+        constraint = vault_factory_info.cash_settled == params.cash_settled,
+        constraint = vault_factory_info.barrier == params.barrier,
+        constraint = vault_factory_info.barrier_up == params.barrier_up,
+        constraint = vault_factory_info.knock_in == params.knock_in,
         constraint = vault_factory_info.emergency_mode == false
     )]
     pub vault_factory_info: Account<'info, CallOptionVaultFactoryInfo>,
@@ -73,10 +101,10 @@ pub struct MakerCreateCallOptionVault<'info> {
     pub vault_info: Account<'info, CallOptionVaultInfo>,
 
     // mint for the base_asset
-    pub base_asset_mint: Account<'info, Mint>,
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
 
     // mint for the quote asset
-    pub quote_asset_mint: Account<'info, Mint>,
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init,
@@ -84,7 +112,7 @@ pub struct MakerCreateCallOptionVault<'info> {
         associated_token::mint = base_asset_mint, 
         associated_token::authority = vault_info // Authority set to PDA
     )]
-    pub vault_base_asset_treasury: Box<Account<'info, TokenAccount>>,
+    pub vault_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init,
@@ -92,7 +120,7 @@ pub struct MakerCreateCallOptionVault<'info> {
         associated_token::mint = quote_asset_mint, // Quote asset mint
         associated_token::authority = vault_info // Authority set to vault PDA
     )]
-    pub vault_quote_asset_treasury: Box<Account<'info, TokenAccount>>,
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init,
@@ -113,7 +141,110 @@ pub struct MakerCreateCallOptionVault<'info> {
         constraint = maker_base_asset_account.owner.key() == initializer.key(),
         constraint = maker_base_asset_account.mint == base_asset_mint.key()
     )]
-    pub maker_base_asset_account: Box<Account<'info, TokenAccount>>,
+    pub maker_base_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+// Creates the vault factory if needed and a new vault in it in a single instruction,
+// the vault gets the next id of the factory
+#[derive(Accounts)]
+#[instruction(
+    params: MakerCreateCallOptionParams
+)]
+pub struct MakerOpenCallOptionVault<'info> {
+    #[account(
+        init_if_needed, 
+        seeds=["CallOptionVaultFactoryInfo".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref(), &params.maturity.to_le_bytes().as_ref(), &params.strike.to_le_bytes().as_ref()], 
+        bump, 
+        payer = initializer, 
+        space= std::mem::size_of::<CallOptionVaultFactoryInfo>() + 8,        
+        constraint = params.strike > 0,
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Box<Account<'info, CallOptionVaultFactoryInfo>>,
+
+    #[account(
+        init,
+        seeds=[
+            "CallOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            &vault_factory_info.upcoming_vault_id().to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = initializer, 
+        space= std::mem::size_of::<CallOptionVaultInfo>() + 8
+    )]
+    pub vault_info: Box<Account<'info, CallOptionVaultInfo>>,
+
+    #[account(
+        seeds=["AssetPairConfig".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = asset_pair_config.is_initialized == true
+    )]
+    pub asset_pair_config: Box<Account<'info, AssetPairConfig>>,
+
+    #[account(
+        mut,
+        seeds=["SeriesIndex".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        realloc = SeriesIndex::space(series_index.entries.len() + 1),
+        realloc::payer = initializer,
+        realloc::zero = false,
+        constraint = series_index.is_initialized == true
+    )]
+    pub series_index: Box<Account<'info, SeriesIndex>>,
+
+    // mint for the base_asset
+    pub base_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = initializer, // Payer will be initializer
+        associated_token::mint = base_asset_mint, 
+        associated_token::authority = vault_info // Authority set to PDA
+    )]
+    pub vault_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = initializer, // Payer will be initializer
+        associated_token::mint = quote_asset_mint, // Quote asset mint
+        associated_token::authority = vault_info // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        seeds=[
+            "CallOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            &vault_factory_info.upcoming_vault_id().to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<CallOptionMakerInfo>() + 8
+    )]
+    pub call_option_maker_info: Box<Account<'info, CallOptionMakerInfo>>,
+
+    #[account(
+        mut,
+        constraint = maker_base_asset_account.owner.key() == initializer.key(),
+        constraint = maker_base_asset_account.mint == base_asset_mint.key()
+    )]
+    pub maker_base_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     // Check if initializer is signer, mut is required to reduce lamports (fees)
     #[account(mut)]
@@ -122,7 +253,7 @@ pub struct MakerCreateCallOptionVault<'info> {
     // System Program requred for deduction of lamports (fees)
     pub system_program: Program<'info, System>,
     // Token Program required to call transfer instruction
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
@@ -155,17 +286,17 @@ pub struct MakerEnterCallOptionVault<'info> {
     pub vault_info: Account<'info, CallOptionVaultInfo>,
 
     // mint for the base_asset
-    pub base_asset_mint: Account<'info, Mint>,
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
 
     // mint for the quote asset
-    pub quote_asset_mint: Account<'info, Mint>,
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         constraint = vault_base_asset_treasury.mint == base_asset_mint.key(), // Base asset mint
         constraint = vault_base_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
     )]
-    pub vault_base_asset_treasury: Box<Account<'info, TokenAccount>>,
+    pub vault_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init,
@@ -186,7 +317,7 @@ pub struct MakerEnterCallOptionVault<'info> {
         constraint = maker_base_asset_account.owner.key() == initializer.key(),
         constraint = maker_base_asset_account.mint == base_asset_mint.key()
     )]
-    pub maker_base_asset_account: Box<Account<'info, TokenAccount>>,
+    pub maker_base_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
 
     // Check if initializer is signer, mut is required to reduce lamports (fees)
@@ -196,7 +327,7 @@ pub struct MakerEnterCallOptionVault<'info> {
     // System Program requred for deduction of lamports (fees)
     pub system_program: Program<'info, System>,
     // Token Program required to call transfer instruction
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 
 }
@@ -231,17 +362,17 @@ pub struct MakerAdjustPositionCallOptionVault<'info> {
     pub vault_info: Account<'info, CallOptionVaultInfo>,
 
     // mint for the base_asset
-    pub base_asset_mint: Account<'info, Mint>,
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
 
     // mint for the quote asset
-    pub quote_asset_mint: Account<'info, Mint>,
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         constraint = vault_base_asset_treasury.mint == base_asset_mint.key(), // Base asset mint
         constraint = vault_base_asset_treasury.owner == vault_info.key() // Authority set to vault PDA
     )]
-    pub vault_base_asset_treasury: Box<Account<'info, TokenAccount>>,
+    pub vault_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         mut,
@@ -262,7 +393,82 @@ pub struct MakerAdjustPositionCallOptionVault<'info> {
         constraint = maker_base_asset_account.owner.key() == initializer.key(),
         constraint = maker_base_asset_account.mint == base_asset_mint.key()
     )]
-    pub maker_base_asset_account: Box<Account<'info, TokenAccount>>,
+    pub maker_base_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+}
+
+#[derive(Accounts)]
+pub struct MakerExitCallOptionVault<'info> {
+    #[account(        
+        seeds=["CallOptionVaultFactoryInfo".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref(), vault_factory_info.maturity.to_le_bytes().as_ref(), vault_factory_info.strike.to_le_bytes().as_ref()], 
+        bump, 
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.base_asset == base_asset_mint.key(),
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, CallOptionVaultFactoryInfo>,
+
+    #[account(
+        mut,
+        seeds=[
+            "CallOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Account<'info, CallOptionVaultInfo>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault_base_asset_treasury.mint == base_asset_mint.key(), // Base asset mint
+        constraint = vault_base_asset_treasury.owner == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // closed (rent goes back to initializer) if nothing has been sold
+    #[account(
+        mut,
+        seeds=[
+            "CallOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        constraint = call_option_maker_info.call_option_vault == vault_info.key(),
+        constraint = call_option_maker_info.owner == initializer.key(),
+        constraint = !call_option_maker_info.is_settled
+    )]
+    pub call_option_maker_info: Account<'info, CallOptionMakerInfo>,
+
+    // unsold base assets will be sent back to this account
+    #[account(
+        mut,
+        constraint = maker_base_asset_account.owner.key() == initializer.key(),
+        constraint = maker_base_asset_account.mint == base_asset_mint.key()
+    )]
+    pub maker_base_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
 
     // Check if initializer is signer, mut is required to reduce lamports (fees)
@@ -272,7 +478,7 @@ pub struct MakerAdjustPositionCallOptionVault<'info> {
     // System Program requred for deduction of lamports (fees)
     pub system_program: Program<'info, System>,
     // Token Program required to call transfer instruction
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 
 }
@@ -394,17 +600,17 @@ pub struct TakerBuyLotsCallOptionVault<'info> {
 
 
     // mint for the base_asset
-    pub base_asset_mint: Account<'info, Mint>,
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
 
     // mint for the quote asset
-    pub quote_asset_mint: Account<'info, Mint>,
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
         constraint = vault_quote_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
     )]
-    pub vault_quote_asset_treasury: Box<Account<'info, TokenAccount>>,
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
 
     // to pay the option premium (fair price) and fund call option
     #[account(
@@ -412,22 +618,56 @@ pub struct TakerBuyLotsCallOptionVault<'info> {
         constraint = taker_quote_asset_account.owner.key() == initializer.key(),
         constraint = taker_quote_asset_account.mint == quote_asset_mint.key()
     )]
-    pub taker_quote_asset_account: Box<Account<'info, TokenAccount>>,
+    pub taker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // fees accrue in the fee vault of the quote asset, part of the backend
+    // share for the insurance fund of the quote asset
+    #[account(
+        mut,
+        seeds=["FeeVaultInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = fee_vault_info.is_initialized == true
+    )]
+    pub fee_vault_info: Box<Account<'info, FeeVaultInfo>>,
 
-    // protocol fees will be paid here
     #[account(
         mut,
-        constraint = protocol_quote_asset_treasury.owner.key() == PROTOCOL_FEES_ADDRESS,
-        constraint = protocol_quote_asset_treasury.mint == quote_asset_mint.key()
+        constraint = fee_vault_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = fee_vault_quote_asset_treasury.owner == fee_vault_info.key() // Authority set to fee vault PDA
+    )]
+    pub fee_vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds=["InsuranceFundInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = insurance_fund_info.is_initialized == true
     )]
-    pub protocol_quote_asset_treasury: Box<Account<'info, TokenAccount>>,
+    pub insurance_fund_info: Box<Account<'info, InsuranceFundInfo>>,
 
-    // frontend fees will be paid here
+    // registered frontend of the taker, if any, which earns its share of fees
     #[account(
         mut,
-        constraint = frontend_quote_asset_treasury.mint == quote_asset_mint.key()
+        constraint = frontend_info.quote_asset == quote_asset_mint.key()
+    )]
+    pub frontend_info: Option<Box<Account<'info, FrontendInfo>>>,
+
+    // fee tiers of takers of the quote asset and rebates of makers
+    #[account(
+        seeds=["FeeTiersInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = fee_tiers_info.is_initialized == true
+    )]
+    pub fee_tiers_info: Box<Account<'info, FeeTiersInfo>>,
+
+    // rolling volume of the taker in the quote asset, for her fee tier
+    #[account(
+        init_if_needed,
+        seeds=["TakerStatsInfo".as_bytes().as_ref(), initializer.key().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<TakerStatsInfo>() + 8
     )]
-    pub frontend_quote_asset_treasury: Box<Account<'info, TokenAccount>>,
+    pub taker_stats_info: Box<Account<'info, TakerStatsInfo>>,
 
     // Check if initializer is signer, mut is required to reduce lamports (fees)
     #[account(mut)]
@@ -436,7 +676,468 @@ pub struct TakerBuyLotsCallOptionVault<'info> {
     // System Program requred for deduction of lamports (fees)
     pub system_program: Program<'info, System>,
     // Token Program required to call transfer instruction
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 
 }
+
+#[derive(Accounts)]
+pub struct GenSettleCallOptionPriceTicket<'info> {
+    #[account(
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, CallOptionVaultFactoryInfo>,
+
+    #[account(
+        init,
+        seeds=["CallOptionSettlePriceTicketInfo".as_bytes().as_ref(), vault_factory_info.key().as_ref(), initializer.key().as_ref()],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<CallOptionSettlePriceTicketInfo>() + 8,
+    )]
+    pub call_option_settle_price_ticket: Account<'info, CallOptionSettlePriceTicketInfo>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = oracle_wallet.key() == ORACLE_ADDRESS
+    )]
+    pub oracle_wallet: SystemAccount<'info>,
+
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>
+
+}
+
+#[derive(Accounts)]
+#[instruction(
+    settle_price: u64
+)]
+pub struct OracleUpdateCallOptionSettlePrice<'info> {
+    #[account(
+        mut,
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, CallOptionVaultFactoryInfo>,
+
+    #[account(
+        mut,
+        seeds=["SeriesIndex".as_bytes().as_ref(), vault_factory_info.base_asset.as_ref(), vault_factory_info.quote_asset.as_ref()],
+        bump,
+        constraint = series_index.is_initialized == true
+    )]
+    pub series_index: Box<Account<'info, SeriesIndex>>,
+
+    #[account(
+        mut,
+        seeds=["CallOptionSettlePriceTicketInfo".as_bytes().as_ref(), vault_factory_info.key().as_ref(), ticket_owner.key().as_ref()],
+        bump,
+        close = ticket_owner,
+        constraint = update_ticket.is_used == false, 
+    )]
+    pub update_ticket: Account<'info, CallOptionSettlePriceTicketInfo>,
+
+    #[account(
+        mut
+    )]
+    pub ticket_owner: SystemAccount<'info>,
+
+    // Check if initializer is signer, should also be the oracle, mut is required to reduce lamports (fees)
+    #[account(
+        mut,
+        constraint = initializer.key() == ORACLE_ADDRESS
+    )]
+    pub initializer: Signer<'info>,
+
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>
+
+}
+
+#[derive(Accounts)]
+pub struct MakerSettleCallOption<'info> {
+    #[account(
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == true,
+        constraint = vault_factory_info.settled_price > 0,
+        constraint = vault_factory_info.base_asset == base_asset_mint.key(),
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, CallOptionVaultFactoryInfo>,
+
+    #[account(
+        mut,
+        seeds=[
+            "CallOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Account<'info, CallOptionVaultInfo>,
+
+    #[account(
+        mut,
+        seeds=[
+            "CallOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        constraint = !call_option_maker_info.is_settled
+    )]
+    pub call_option_maker_info: Account<'info, CallOptionMakerInfo>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault_base_asset_treasury.mint == base_asset_mint.key(), // Base asset mint
+        constraint = vault_base_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // quote asset mint
+        constraint = vault_quote_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+
+    // if call option is not exercised, maker will get her base tokens back at this account
+    #[account(
+        mut,
+        constraint = maker_base_asset_account.owner.key() == initializer.key(),
+        constraint = maker_base_asset_account.mint == base_asset_mint.key()
+    )]
+    pub maker_base_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // if call option is exercised, maker will get the quote tokens for the base tokens she sold at strike price at this account
+    #[account(
+        mut,
+        constraint = maker_quote_asset_account.owner.key() == initializer.key(),
+        constraint = maker_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub maker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>
+}
+
+#[derive(Accounts)]
+pub struct TakerSettleCallOption<'info> {
+    #[account(
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == true,
+        constraint = vault_factory_info.settled_price > 0,
+        constraint = vault_factory_info.base_asset == base_asset_mint.key(),
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, CallOptionVaultFactoryInfo>,
+
+    #[account(
+        mut,
+        seeds=[
+            "CallOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Account<'info, CallOptionVaultInfo>,
+
+    #[account(
+        mut,
+        seeds=[
+            "CallOptionTakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        constraint = !call_option_taker_info.is_settled
+    )]
+    pub call_option_taker_info: Account<'info, CallOptionTakerInfo>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault_base_asset_treasury.mint == base_asset_mint.key(), // Base asset mint
+        constraint = vault_base_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // quote asset mint
+        constraint = vault_quote_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+
+    // if call option is exercised, taker will get the base tokens she bought at this account
+    #[account(
+        mut,
+        constraint = taker_base_asset_account.owner.key() == initializer.key(),
+        constraint = taker_base_asset_account.mint == base_asset_mint.key()
+    )]
+    pub taker_base_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // if call option is not exercised, taker will get her quote tokens back at this account
+    #[account(
+        mut,
+        constraint = taker_quote_asset_account.owner.key() == initializer.key(),
+        constraint = taker_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub taker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>
+}
+
+#[derive(Accounts)]
+pub struct MakerActivateCallOptionEmergencyMode<'info> {
+    #[account(
+        mut,
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, CallOptionVaultFactoryInfo>,
+
+    #[account(
+        seeds=[
+            "CallOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Account<'info, CallOptionVaultInfo>,
+
+    #[account(
+        seeds=[
+            "CallOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        constraint = !call_option_maker_info.is_settled
+    )]
+    pub call_option_maker_info: Account<'info, CallOptionMakerInfo>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+
+}
+
+#[derive(Accounts)]
+pub struct TakerActivateCallOptionEmergencyMode<'info> {
+    #[account(
+        mut,
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, CallOptionVaultFactoryInfo>,
+
+    #[account(
+        seeds=[
+            "CallOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Account<'info, CallOptionVaultInfo>,
+
+    #[account(
+        seeds=[
+            "CallOptionTakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        constraint = !call_option_taker_info.is_settled
+    )]
+    pub call_option_taker_info: Account<'info, CallOptionTakerInfo>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+
+}
+
+#[derive(Accounts)]
+pub struct MakerCallOptionEmergencyExit<'info> {
+    #[account(
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.base_asset == base_asset_mint.key(),
+        constraint = vault_factory_info.emergency_mode == true
+    )]
+    pub vault_factory_info: Account<'info, CallOptionVaultFactoryInfo>,
+
+    #[account(
+        seeds=[
+            "CallOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Account<'info, CallOptionVaultInfo>,
+
+    #[account(
+        mut,
+        seeds=[
+            "CallOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        constraint = !call_option_maker_info.is_settled
+    )]
+    pub call_option_maker_info: Account<'info, CallOptionMakerInfo>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault_base_asset_treasury.mint == base_asset_mint.key(), // Base asset mint
+        constraint = vault_base_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = maker_base_asset_account.owner.key() == initializer.key(),
+        constraint = maker_base_asset_account.mint == base_asset_mint.key()
+    )]
+    pub maker_base_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>
+}
+
+#[derive(Accounts)]
+pub struct TakerCallOptionEmergencyExit<'info> {
+    #[account(
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.emergency_mode == true
+    )]
+    pub vault_factory_info: Account<'info, CallOptionVaultFactoryInfo>,
+
+    #[account(
+        seeds=[
+            "CallOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Account<'info, CallOptionVaultInfo>,
+
+    #[account(
+        mut,
+        seeds=[
+            "CallOptionTakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        constraint = !call_option_taker_info.is_settled
+    )]
+    pub call_option_taker_info: Account<'info, CallOptionTakerInfo>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // quote asset mint
+        constraint = vault_quote_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = taker_quote_asset_account.owner.key() == initializer.key(),
+        constraint = taker_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub taker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>
+}
@@ -0,0 +1,7 @@
+use crate::EMERGENCY_MODE_GRACE_PERIOD;
+
+// Emergency mode can only be activated when the grace period after maturity has passed
+// and the oracle has still not written the settle price
+pub fn is_emergency_grace_period_over(maturity: u64, current_time: u64) -> bool {
+    current_time.saturating_sub(maturity) > EMERGENCY_MODE_GRACE_PERIOD
+}
@@ -0,0 +1,4 @@
+pub mod errors;
+pub mod data;
+pub mod ft_controller;
+pub mod validators;
\ No newline at end of file
@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use crate::FEE_TIER_VOLUME_WINDOW_DAYS;
+
+const SECONDS_PER_DAY: u64 = 24*60*60;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct FeeTier {
+    pub min_volume: u64,            // rolling volume of premiums, in quote asset lamports, from which the tier applies
+    pub discount_bps: u16           // discount on fees on premiums, in basis points, 0 for an unused tier
+}
+
+// Fee tiers of takers of a quote asset, based on their rolling volume, and the rebate paid back
+// to makers out of the protocol share of fees when their collateral has been resting for long
+#[account]
+pub struct FeeTiersInfo {
+    pub is_initialized: bool,
+
+    pub quote_asset: Pubkey,
+    pub tiers: [FeeTier; 4],            // MAX_FEE_TIERS
+    pub maker_rebate_bps: u16,          // share of the protocol share of fees paid back to makers, 0 for no rebate
+    pub maker_rebate_min_seconds: u64   // how long the collateral of a maker must have been resting to get the rebate
+}
+
+impl FeeTiersInfo {
+    // Highest tier reached with volume, tiers with increasing minimum volumes
+    pub fn schedule_for(&self, volume: u64) -> FeeSchedule {
+        let discount_bps = self.tiers.iter()
+            .rev()
+            .find(|tier| tier.discount_bps > 0 && volume >= tier.min_volume)
+            .map_or(0, |tier| tier.discount_bps);
+
+        FeeSchedule {
+            discount_bps,
+            maker_rebate_bps: self.maker_rebate_bps,
+            maker_rebate_min_seconds: self.maker_rebate_min_seconds
+        }
+    }
+}
+
+// Fees applying to a purchase of a taker
+#[derive(Clone, Copy, Default)]
+pub struct FeeSchedule {
+    pub discount_bps: u16,
+    pub maker_rebate_bps: u16,
+    pub maker_rebate_min_seconds: u64
+}
+
+impl FeeSchedule {
+    // Fees on a premium at protocol_fees_bps, after the discount of the tier of the taker
+    pub fn total_fees(&self, premium: f64, protocol_fees_bps: u16) -> f64 {
+        premium*(protocol_fees_bps as f64)/10000.0*((10000 - self.discount_bps) as f64)/10000.0
+    }
+
+    // Part of backend_share paid back to a maker whose collateral has been resting since resting_since
+    pub fn maker_rebate(&self, backend_share: u64, resting_since: u64, current_time: u64) -> u64 {
+        if self.maker_rebate_bps == 0 || current_time < resting_since.saturating_add(self.maker_rebate_min_seconds) {
+            return 0;
+        }
+        ((backend_share as u128)*(self.maker_rebate_bps as u128)/10000) as u64
+    }
+}
+
+// Premiums paid by a taker in a quote asset, per day over the last FEE_TIER_VOLUME_WINDOW_DAYS days
+// (indexed by day modulo the window), for her fee tier
+#[account]
+pub struct TakerStatsInfo {
+    pub is_initialized: bool,
+
+    pub owner: Pubkey,
+    pub quote_asset: Pubkey,
+    pub daily_volume: [u64; 30],    // FEE_TIER_VOLUME_WINDOW_DAYS
+    pub last_day: u64,              // day (since the unix epoch) of the last purchase
+    pub total_volume: u64
+}
+
+impl TakerStatsInfo {
+    pub fn rolling_volume(&self, current_time: u64) -> u64 {
+        let today = current_time/SECONDS_PER_DAY;
+        let first_day = today.saturating_sub(FEE_TIER_VOLUME_WINDOW_DAYS as u64 - 1);
+        // Days after last_day have not been written since they were last in the window
+        (first_day..=std::cmp::min(today, self.last_day))
+            .map(|day| self.daily_volume[(day % FEE_TIER_VOLUME_WINDOW_DAYS as u64) as usize])
+            .fold(0, |volume, day_volume| volume.saturating_add(day_volume))
+    }
+
+    pub fn record_volume(&mut self, current_time: u64, premium: u64) {
+        let today = current_time/SECONDS_PER_DAY;
+        if today > self.last_day {
+            let first_stale_day = std::cmp::max(self.last_day + 1, today.saturating_sub(FEE_TIER_VOLUME_WINDOW_DAYS as u64 - 1));
+            for day in first_stale_day..=today {
+                self.daily_volume[(day % FEE_TIER_VOLUME_WINDOW_DAYS as u64) as usize] = 0;
+            }
+            self.last_day = today;
+        }
+        let bucket = (today % FEE_TIER_VOLUME_WINDOW_DAYS as u64) as usize;
+        self.daily_volume[bucket] = self.daily_volume[bucket].saturating_add(premium);
+        self.total_volume = self.total_volume.saturating_add(premium);
+    }
+}
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum FeeTierError {
+    #[msg("Minimum volumes of fee tiers must be increasing")]
+    TiersNotIncreasing,
+
+    #[msg("Fee discount of a tier cannot be above 10000 basis points")]
+    DiscountTooHigh,
+
+    #[msg("Maker rebate cannot be above 10000 basis points")]
+    MakerRebateTooHigh
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::fee_tiers::validators::*;
+use crate::fee_tiers::errors::FeeTierError;
+use crate::fee_tiers::data::{FeeTiersInfo, TakerStatsInfo};
+use crate::FeeTiersParams;
+
+// Fee tiers created with the first asset pair of their quote asset give no discounts nor
+// maker rebates until the admin updates them
+pub fn init_fee_tiers_if_needed(fee_tiers_info: &mut FeeTiersInfo, quote_asset: Pubkey) {
+    if !fee_tiers_info.is_initialized {
+        fee_tiers_info.quote_asset = quote_asset;
+        fee_tiers_info.tiers = Default::default();
+        fee_tiers_info.maker_rebate_bps = 0;
+        fee_tiers_info.maker_rebate_min_seconds = 0;
+        fee_tiers_info.is_initialized = true;
+        msg!("FeeTiersInfo initialized");
+    }
+}
+
+pub fn admin_update_fee_tiers(ctx: Context<AdminUpdateFeeTiers>,
+    params: FeeTiersParams
+) -> Result<()> {
+    set_fee_tiers(&mut ctx.accounts.fee_tiers_info, &params)?;
+    msg!("FeeTiersInfo updated, makers get {} bps of protocol fees back after {} seconds", params.maker_rebate_bps, params.maker_rebate_min_seconds);
+
+    Ok(())
+}
+
+fn set_fee_tiers(fee_tiers_info: &mut FeeTiersInfo, params: &FeeTiersParams) -> Result<()> {
+    let mut last_min_volume: Option<u64> = None;
+    for tier in params.tiers.iter().filter(|tier| tier.discount_bps > 0) {
+        require!(
+            tier.discount_bps <= 10000,
+            FeeTierError::DiscountTooHigh
+        );
+        if let Some(min_volume) = last_min_volume {
+            require!(
+                tier.min_volume > min_volume,
+                FeeTierError::TiersNotIncreasing
+            );
+        }
+        last_min_volume = Some(tier.min_volume);
+    }
+    require!(
+        params.maker_rebate_bps <= 10000,
+        FeeTierError::MakerRebateTooHigh
+    );
+
+    fee_tiers_info.tiers = params.tiers;
+    fee_tiers_info.maker_rebate_bps = params.maker_rebate_bps;
+    fee_tiers_info.maker_rebate_min_seconds = params.maker_rebate_min_seconds;
+
+    Ok(())
+}
+
+// TakerStatsInfo accounts are created the first time a taker buys options in a quote asset
+pub fn init_taker_stats_if_needed(taker_stats_info: &mut TakerStatsInfo,
+    owner: Pubkey,
+    quote_asset: Pubkey
+) {
+    if !taker_stats_info.is_initialized {
+        taker_stats_info.owner = owner;
+        taker_stats_info.quote_asset = quote_asset;
+        taker_stats_info.is_initialized = true;
+    }
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use crate::fee_tiers::data::FeeTiersInfo;
+use crate::ADMIN_ADDRESS;
+
+#[derive(Accounts)]
+pub struct AdminUpdateFeeTiers<'info> {
+    #[account(
+        mut,
+        seeds=["FeeTiersInfo".as_bytes().as_ref(), fee_tiers_info.quote_asset.as_ref()],
+        bump,
+        constraint = fee_tiers_info.is_initialized == true
+    )]
+    pub fee_tiers_info: Account<'info, FeeTiersInfo>,
+
+    // Check if initializer is signer, should also be the admin
+    #[account(
+        constraint = initializer.key() == ADMIN_ADDRESS
+    )]
+    pub initializer: Signer<'info>
+}
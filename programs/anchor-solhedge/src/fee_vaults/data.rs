@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::insurance_funds::data::InsuranceFundInfo;
+
+// Fees on premiums of a quote asset accrue in the quote asset treasury of this PDA (its associated
+// token account) with a ledger per recipient, instead of being transferred to each of them on every fill.
+// Frontend balances are kept in each FrontendInfo, frontend_fees is their total
+#[account]
+pub struct FeeVaultInfo {
+    pub is_initialized: bool,
+
+    pub quote_asset: Pubkey,
+    pub protocol_fees: u64,         // claimable by the protocol fees address
+    pub insurance_fees: u64,        // moved to the insurance fund of the quote asset when protocol fees are claimed
+    pub frontend_fees: u64          // claimable by registered frontends
+}
+
+impl FeeVaultInfo {
+    // Accrues the backend share of fees, part of it for the insurance fund of the quote asset
+    pub fn accrue_backend_fees(&mut self, backend_fees: u64, insurance_fund_info: &InsuranceFundInfo) {
+        let insurance_share = insurance_fund_info.share_of(backend_fees);
+        self.insurance_fees = self.insurance_fees.checked_add(insurance_share).unwrap();
+        self.protocol_fees = self.protocol_fees.checked_add(backend_fees - insurance_share).unwrap();
+    }
+}
@@ -0,0 +1,7 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum FeeVaultError {
+    #[msg("There are no fees to claim")]
+    NothingToClaim
+}
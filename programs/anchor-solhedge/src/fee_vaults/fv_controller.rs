@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, TransferChecked};
+use crate::fee_vaults::validators::*;
+use crate::fee_vaults::errors::FeeVaultError;
+use crate::fee_vaults::data::FeeVaultInfo;
+
+// Fee vaults are created with the first asset pair of their quote asset
+pub fn init_fee_vault_if_needed(fee_vault_info: &mut FeeVaultInfo, quote_asset: Pubkey) {
+    if !fee_vault_info.is_initialized {
+        fee_vault_info.quote_asset = quote_asset;
+        fee_vault_info.protocol_fees = 0;
+        fee_vault_info.insurance_fees = 0;
+        fee_vault_info.frontend_fees = 0;
+        fee_vault_info.is_initialized = true;
+        msg!("FeeVaultInfo initialized");
+    }
+}
+
+// Pays the protocol fees accrued in the fee vault to the protocol and moves the insurance
+// share to the insurance fund of the quote asset. Returns the protocol fees paid
+pub fn claim_protocol_fees(ctx: Context<ClaimProtocolFees>) -> Result<u64> {
+    let protocol_fees = ctx.accounts.fee_vault_info.protocol_fees;
+    let insurance_fees = ctx.accounts.fee_vault_info.insurance_fees;
+    require!(
+        protocol_fees > 0 || insurance_fees > 0,
+        FeeVaultError::NothingToClaim
+    );
+
+    // Preparing PDA signer
+    let auth_bump = *ctx.bumps.get("fee_vault_info").unwrap();
+    let seeds = &[
+        "FeeVaultInfo".as_bytes().as_ref(),
+        &ctx.accounts.quote_asset_mint.key().to_bytes(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let payments = [
+        (ctx.accounts.protocol_quote_asset_treasury.to_account_info(), protocol_fees),
+        (ctx.accounts.insurance_fund_quote_asset_treasury.to_account_info(), insurance_fees)
+    ];
+    for (destination, amount) in payments {
+        if amount == 0 {
+            continue;
+        }
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.fee_vault_quote_asset_treasury.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: destination,
+            authority: ctx.accounts.fee_vault_info.to_account_info(),
+        };
+        let token_transfer_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::transfer_checked(token_transfer_context, amount, ctx.accounts.quote_asset_mint.decimals)?;
+    }
+    msg!("Claimed {} USDC lamports of protocol fees, {} went to the insurance fund", protocol_fees, insurance_fees);
+
+    ctx.accounts.fee_vault_info.protocol_fees = 0;
+    ctx.accounts.fee_vault_info.insurance_fees = 0;
+
+    Ok(protocol_fees)
+}
+
+// Pays a registered frontend the fees accrued for it in the fee vault. Returns the fees paid
+pub fn claim_frontend_fees(ctx: Context<ClaimFrontendFees>) -> Result<u64> {
+    let frontend_fees = ctx.accounts.frontend_info.fees_accrued;
+    require!(
+        frontend_fees > 0,
+        FeeVaultError::NothingToClaim
+    );
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.fee_vault_quote_asset_treasury.to_account_info(),
+        mint: ctx.accounts.quote_asset_mint.to_account_info(),
+        to: ctx.accounts.frontend_payout_account.to_account_info(),
+        authority: ctx.accounts.fee_vault_info.to_account_info(),
+    };
+
+    // Preparing PDA signer
+    let auth_bump = *ctx.bumps.get("fee_vault_info").unwrap();
+    let seeds = &[
+        "FeeVaultInfo".as_bytes().as_ref(),
+        &ctx.accounts.quote_asset_mint.key().to_bytes(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let token_transfer_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+    token_interface::transfer_checked(token_transfer_context, frontend_fees, ctx.accounts.quote_asset_mint.decimals)?;
+    msg!("Claimed {} USDC lamports of frontend fees", frontend_fees);
+
+    ctx.accounts.frontend_info.fees_accrued = 0;
+    ctx.accounts.fee_vault_info.frontend_fees = ctx.accounts.fee_vault_info.frontend_fees.checked_sub(frontend_fees).unwrap();
+
+    Ok(frontend_fees)
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use crate::fee_vaults::data::FeeVaultInfo;
+use crate::insurance_funds::data::InsuranceFundInfo;
+use crate::frontends::data::FrontendInfo;
+use anchor_spl::token_interface::{Mint, TokenInterface, TokenAccount};
+use crate::{ADMIN_ADDRESS, PROTOCOL_FEES_ADDRESS};
+
+#[derive(Accounts)]
+pub struct ClaimProtocolFees<'info> {
+    #[account(
+        mut,
+        seeds=["FeeVaultInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = fee_vault_info.is_initialized == true
+    )]
+    pub fee_vault_info: Box<Account<'info, FeeVaultInfo>>,
+
+    #[account(
+        mut,
+        constraint = fee_vault_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = fee_vault_quote_asset_treasury.owner == fee_vault_info.key() // Authority set to fee vault PDA
+    )]
+    pub fee_vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds=["InsuranceFundInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = insurance_fund_info.is_initialized == true
+    )]
+    pub insurance_fund_info: Box<Account<'info, InsuranceFundInfo>>,
+
+    #[account(
+        mut,
+        constraint = insurance_fund_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = insurance_fund_quote_asset_treasury.owner == insurance_fund_info.key() // Authority set to insurance fund PDA
+    )]
+    pub insurance_fund_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // protocol fees will be paid here
+    #[account(
+        mut,
+        constraint = protocol_quote_asset_treasury.owner.key() == PROTOCOL_FEES_ADDRESS,
+        constraint = protocol_quote_asset_treasury.mint == quote_asset_mint.key()
+    )]
+    pub protocol_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // Check if initializer is signer, should also be the admin
+    #[account(
+        constraint = initializer.key() == ADMIN_ADDRESS
+    )]
+    pub initializer: Signer<'info>,
+
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimFrontendFees<'info> {
+    #[account(
+        mut,
+        seeds=["FrontendInfo".as_bytes().as_ref(), initializer.key().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = frontend_info.is_initialized == true
+    )]
+    pub frontend_info: Box<Account<'info, FrontendInfo>>,
+
+    #[account(
+        mut,
+        seeds=["FeeVaultInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = fee_vault_info.is_initialized == true
+    )]
+    pub fee_vault_info: Box<Account<'info, FeeVaultInfo>>,
+
+    #[account(
+        mut,
+        constraint = fee_vault_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = fee_vault_quote_asset_treasury.owner == fee_vault_info.key() // Authority set to fee vault PDA
+    )]
+    pub fee_vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // frontend fees will be paid here
+    #[account(
+        mut,
+        constraint = frontend_payout_account.key() == frontend_info.payout_account
+    )]
+    pub frontend_payout_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // Check if initializer is signer, should also be the owner of the frontend
+    pub initializer: Signer<'info>,
+
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+}
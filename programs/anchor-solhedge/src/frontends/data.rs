@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+// Frontend (referrer) registered by the admin for a quote asset, with the share of fees
+// on premiums negotiated with it. Fees of takers coming from unregistered frontends go to the protocol
+#[account]
+pub struct FrontendInfo {
+    pub is_initialized: bool,
+    pub enabled: bool,
+
+    pub owner: Pubkey,
+    pub quote_asset: Pubkey,
+    pub payout_account: Pubkey,     // quote asset token account where the frontend claims its fees
+    pub fee_share_bps: u16,         // share of fees on premiums paid to the frontend, in basis points
+
+    pub volume: u64,                // premiums paid by takers through this frontend, in quote asset lamports
+    pub num_trades: u64,
+    pub fees_accrued: u64           // share of fees accrued in the FeeVaultInfo of the quote asset, not claimed yet
+}
+
+impl FrontendInfo {
+    // Fees are only shared with enabled frontends
+    pub fn earns_fees(&self) -> bool {
+        self.is_initialized && self.enabled
+    }
+}
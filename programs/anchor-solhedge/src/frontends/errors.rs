@@ -0,0 +1,7 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum FrontendError {
+    #[msg("Fee share of a frontend cannot be above 10000 basis points")]
+    FeeShareTooHigh
+}
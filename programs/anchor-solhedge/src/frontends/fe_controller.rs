@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::frontends::validators::*;
+use crate::frontends::errors::FrontendError;
+use crate::frontends::data::FrontendInfo;
+use crate::fee_vaults::data::FeeVaultInfo;
+
+pub fn admin_register_frontend(ctx: Context<AdminRegisterFrontend>,
+    fee_share_bps: u16
+) -> Result<()> {
+    require!(
+        fee_share_bps <= 10000,
+        FrontendError::FeeShareTooHigh
+    );
+
+    ctx.accounts.frontend_info.owner = ctx.accounts.frontend_owner.key();
+    ctx.accounts.frontend_info.quote_asset = ctx.accounts.quote_asset_mint.key();
+    ctx.accounts.frontend_info.payout_account = ctx.accounts.frontend_payout_account.key();
+    ctx.accounts.frontend_info.fee_share_bps = fee_share_bps;
+    ctx.accounts.frontend_info.volume = 0;
+    ctx.accounts.frontend_info.num_trades = 0;
+    ctx.accounts.frontend_info.fees_accrued = 0;
+    ctx.accounts.frontend_info.enabled = true;
+    ctx.accounts.frontend_info.is_initialized = true;
+    msg!("FrontendInfo initialized");
+
+    Ok(())
+}
+
+pub fn admin_update_frontend(ctx: Context<AdminUpdateFrontend>,
+    fee_share_bps: u16,
+    enabled: bool
+) -> Result<()> {
+    require!(
+        fee_share_bps <= 10000,
+        FrontendError::FeeShareTooHigh
+    );
+
+    ctx.accounts.frontend_info.fee_share_bps = fee_share_bps;
+    ctx.accounts.frontend_info.enabled = enabled;
+    msg!("FrontendInfo updated");
+
+    Ok(())
+}
+
+pub fn frontend_update_payout_account(ctx: Context<FrontendUpdatePayoutAccount>) -> Result<()> {
+    ctx.accounts.frontend_info.payout_account = ctx.accounts.frontend_payout_account.key();
+    msg!("Frontend fees will be paid to {}", ctx.accounts.frontend_info.payout_account);
+
+    Ok(())
+}
+
+// Share of fees on premiums going to the frontend passed by the taker, in basis points:
+// zero unless it is a registered frontend that is enabled
+pub fn frontend_fee_share_bps(frontend_info: &Option<Box<Account<FrontendInfo>>>) -> u16 {
+    match frontend_info {
+        Some(frontend_info) if frontend_info.earns_fees() => frontend_info.fee_share_bps,
+        _ => 0
+    }
+}
+
+// Accrues the frontend share of fees of a trade in the fee vault and accumulates the premiums
+// paid through the frontend, for incentive programs
+pub fn record_frontend_trade(frontend_info: &mut Option<Box<Account<FrontendInfo>>>,
+    fee_vault_info: &mut FeeVaultInfo,
+    premium: u64,
+    frontend_fees: u64
+) {
+    if let Some(frontend_info) = frontend_info {
+        if frontend_info.earns_fees() && premium > 0 {
+            frontend_info.volume = frontend_info.volume.saturating_add(premium);
+            frontend_info.num_trades = frontend_info.num_trades.checked_add(1).unwrap();
+            frontend_info.fees_accrued = frontend_info.fees_accrued.checked_add(frontend_fees).unwrap();
+            fee_vault_info.frontend_fees = fee_vault_info.frontend_fees.checked_add(frontend_fees).unwrap();
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use crate::frontends::data::FrontendInfo;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use crate::ADMIN_ADDRESS;
+
+#[derive(Accounts)]
+pub struct AdminRegisterFrontend<'info> {
+    #[account(
+        init,
+        seeds=["FrontendInfo".as_bytes().as_ref(), frontend_owner.key().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<FrontendInfo>() + 8
+    )]
+    pub frontend_info: Account<'info, FrontendInfo>,
+
+    pub frontend_owner: SystemAccount<'info>,
+
+    #[account(
+        constraint = frontend_payout_account.mint == quote_asset_mint.key()
+    )]
+    pub frontend_payout_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // Check if initializer is signer, should also be the admin, mut is required to reduce lamports (fees)
+    #[account(
+        mut,
+        constraint = initializer.key() == ADMIN_ADDRESS
+    )]
+    pub initializer: Signer<'info>,
+
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct AdminUpdateFrontend<'info> {
+    #[account(
+        mut,
+        seeds=["FrontendInfo".as_bytes().as_ref(), frontend_info.owner.as_ref(), frontend_info.quote_asset.as_ref()],
+        bump,
+        constraint = frontend_info.is_initialized == true
+    )]
+    pub frontend_info: Account<'info, FrontendInfo>,
+
+    // Check if initializer is signer, should also be the admin
+    #[account(
+        constraint = initializer.key() == ADMIN_ADDRESS
+    )]
+    pub initializer: Signer<'info>
+}
+
+#[derive(Accounts)]
+pub struct FrontendUpdatePayoutAccount<'info> {
+    #[account(
+        mut,
+        seeds=["FrontendInfo".as_bytes().as_ref(), initializer.key().as_ref(), frontend_info.quote_asset.as_ref()],
+        bump,
+        constraint = frontend_info.is_initialized == true
+    )]
+    pub frontend_info: Account<'info, FrontendInfo>,
+
+    #[account(
+        constraint = frontend_payout_account.mint == frontend_info.quote_asset
+    )]
+    pub frontend_payout_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Check if initializer is signer, should also be the owner of the frontend
+    pub initializer: Signer<'info>
+}
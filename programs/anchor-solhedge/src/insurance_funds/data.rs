@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+// Insurance fund of a quote asset, fed by part of the backend share of fees on premiums.
+// Its quote asset treasury is the associated token account of this PDA
+#[account]
+pub struct InsuranceFundInfo {
+    pub is_initialized: bool,
+
+    pub quote_asset: Pubkey,
+    pub fee_share_bps: u16,         // part of the backend share of fees routed to the fund, in basis points
+
+    pub num_draws: u64,             // draws are recorded in InsuranceFundDrawInfo accounts numbered from 1
+    pub total_drawn: u64            // in quote asset lamports
+}
+
+// Draws only go to the safety fund of margined vaults, the only vaults that can be short of
+// what they owe. Kept as an enum so records of other kinds of draws can be added
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InsuranceFundDrawReason {
+    #[default]
+    MarginShortfall
+}
+
+// Record of a governance draw from an insurance fund to cover a shortfall in a vault
+#[account]
+pub struct InsuranceFundDrawInfo {
+    pub is_initialized: bool,
+
+    pub insurance_fund: Pubkey,
+    pub ord: u64,
+    pub vault: Pubkey,              // vault whose quote asset treasury received the draw
+    pub amount: u64,                // in quote asset lamports
+    pub reason: InsuranceFundDrawReason,
+    pub drawn_at: u64
+}
+
+impl InsuranceFundInfo {
+    // Ord of the InsuranceFundDrawInfo recording the next draw
+    pub fn upcoming_draw_ord(&self) -> u64 {
+        self.num_draws.checked_add(1).unwrap()
+    }
+
+    // Part of backend_share that goes to the fund, rounded down
+    pub fn share_of(&self, backend_share: u64) -> u64 {
+        ((backend_share as u128)*(self.fee_share_bps as u128)/10000) as u64
+    }
+}
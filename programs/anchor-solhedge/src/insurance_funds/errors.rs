@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum InsuranceFundError {
+    #[msg("Fee share of the insurance fund cannot be above 10000 basis points")]
+    FeeShareTooHigh,
+
+    #[msg("Draw amount cannot be zero")]
+    DrawAmountZero,
+
+    #[msg("Not enough funds in the insurance fund")]
+    InsufficientFunds,
+
+    #[msg("Insurance fund draws only go to the safety fund of margined vaults")]
+    VaultNotMargined,
+
+    #[msg("Takers of the vault have started settling, its safety fund cannot grow anymore")]
+    TakersSettling
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, TransferChecked};
+use crate::insurance_funds::validators::*;
+use crate::insurance_funds::errors::InsuranceFundError;
+use crate::insurance_funds::data::{InsuranceFundInfo, InsuranceFundDrawReason};
+
+// Insurance funds created with the first asset pair of their quote asset get no share of fees
+// until the admin updates them
+pub fn init_insurance_fund_if_needed(insurance_fund_info: &mut InsuranceFundInfo, quote_asset: Pubkey) {
+    if !insurance_fund_info.is_initialized {
+        insurance_fund_info.quote_asset = quote_asset;
+        insurance_fund_info.fee_share_bps = 0;
+        insurance_fund_info.num_draws = 0;
+        insurance_fund_info.total_drawn = 0;
+        insurance_fund_info.is_initialized = true;
+        msg!("InsuranceFundInfo initialized");
+    }
+}
+
+pub fn admin_update_insurance_fund(ctx: Context<AdminUpdateInsuranceFund>,
+    fee_share_bps: u16
+) -> Result<()> {
+    require!(
+        fee_share_bps <= 10000,
+        InsuranceFundError::FeeShareTooHigh
+    );
+
+    ctx.accounts.insurance_fund_info.fee_share_bps = fee_share_bps;
+    msg!("InsuranceFundInfo updated, {} bps of backend fees go to the fund", fee_share_bps);
+
+    Ok(())
+}
+
+// Moves amount from the insurance fund to the quote asset treasury of a margined put option vault,
+// recording the draw. It is credited to the safety fund of the vault, which covers margin shortfalls:
+// other vaults pay makers and takers what they are owed, so nothing would ever pay a draw out.
+// Draws are possible until the first taker settles, what is not needed goes back to the fund
+// with crank_return_put_option_safety_fund
+pub fn admin_draw_insurance_fund_to_put_option_vault(ctx: Context<AdminDrawInsuranceFundToPutOptionVault>,
+    amount: u64,
+    reason: InsuranceFundDrawReason
+) -> Result<()> {
+    require!(
+        amount > 0,
+        InsuranceFundError::DrawAmountZero
+    );
+    require!(
+        ctx.accounts.vault_info.is_margined(),
+        InsuranceFundError::VaultNotMargined
+    );
+    // Takers already paid could not get their share of it
+    require!(
+        ctx.accounts.vault_info.takers_settled == 0,
+        InsuranceFundError::TakersSettling
+    );
+    require!(
+        ctx.accounts.insurance_fund_quote_asset_treasury.amount >= amount,
+        InsuranceFundError::InsufficientFunds
+    );
+
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.insurance_fund_quote_asset_treasury.to_account_info(),
+        mint: ctx.accounts.quote_asset_mint.to_account_info(),
+        to: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+        authority: ctx.accounts.insurance_fund_info.to_account_info(),
+    };
+
+    // Preparing PDA signer
+    let auth_bump = *ctx.bumps.get("insurance_fund_info").unwrap();
+    let seeds = &[
+        "InsuranceFundInfo".as_bytes().as_ref(),
+        &ctx.accounts.quote_asset_mint.key().to_bytes(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token_interface::transfer_checked(token_transfer_context, amount, ctx.accounts.quote_asset_mint.decimals)?;
+    msg!("Transferred {} USDC lamports from insurance fund to vault quote asset treasury", amount);
+
+    ctx.accounts.vault_info.safety_fund = ctx.accounts.vault_info.safety_fund.checked_add(amount).unwrap();
+    // Makers that have already settled may have left a shortfall this covers
+    let covered = ctx.accounts.vault_info.cover_margin_shortfall();
+    msg!("Safety fund of the vault now holds {} USDC lamports, {} went to the margin shortfall", ctx.accounts.vault_info.safety_fund, covered);
+
+    let ord = ctx.accounts.insurance_fund_info.upcoming_draw_ord();
+    ctx.accounts.insurance_fund_draw_info.insurance_fund = ctx.accounts.insurance_fund_info.key();
+    ctx.accounts.insurance_fund_draw_info.ord = ord;
+    ctx.accounts.insurance_fund_draw_info.vault = ctx.accounts.vault_info.key();
+    ctx.accounts.insurance_fund_draw_info.amount = amount;
+    ctx.accounts.insurance_fund_draw_info.reason = reason;
+    ctx.accounts.insurance_fund_draw_info.drawn_at = Clock::get().unwrap().unix_timestamp as u64;
+    ctx.accounts.insurance_fund_draw_info.is_initialized = true;
+
+    ctx.accounts.insurance_fund_info.num_draws = ord;
+    ctx.accounts.insurance_fund_info.total_drawn = ctx.accounts.insurance_fund_info.total_drawn.checked_add(amount).unwrap();
+
+    Ok(())
+}
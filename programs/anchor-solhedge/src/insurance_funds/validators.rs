@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use crate::insurance_funds::data::{InsuranceFundInfo, InsuranceFundDrawInfo};
+use crate::put_options::data::{PutOptionVaultFactoryInfo, PutOptionVaultInfo};
+use anchor_spl::token_interface::{Mint, TokenInterface, TokenAccount};
+use crate::ADMIN_ADDRESS;
+
+#[derive(Accounts)]
+pub struct AdminUpdateInsuranceFund<'info> {
+    #[account(
+        mut,
+        seeds=["InsuranceFundInfo".as_bytes().as_ref(), insurance_fund_info.quote_asset.as_ref()],
+        bump,
+        constraint = insurance_fund_info.is_initialized == true
+    )]
+    pub insurance_fund_info: Account<'info, InsuranceFundInfo>,
+
+    // Check if initializer is signer, should also be the admin
+    #[account(
+        constraint = initializer.key() == ADMIN_ADDRESS
+    )]
+    pub initializer: Signer<'info>
+}
+
+#[derive(Accounts)]
+pub struct AdminDrawInsuranceFundToPutOptionVault<'info> {
+    #[account(
+        mut,
+        seeds=["InsuranceFundInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = insurance_fund_info.is_initialized == true
+    )]
+    pub insurance_fund_info: Box<Account<'info, InsuranceFundInfo>>,
+
+    #[account(
+        mut,
+        constraint = insurance_fund_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = insurance_fund_quote_asset_treasury.owner == insurance_fund_info.key() // Authority set to insurance fund PDA
+    )]
+    pub insurance_fund_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        seeds=[
+            "InsuranceFundDrawInfo".as_bytes().as_ref(),
+            insurance_fund_info.key().as_ref(),
+            insurance_fund_info.upcoming_draw_ord().to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<InsuranceFundDrawInfo>() + 8
+    )]
+    pub insurance_fund_draw_info: Box<Account<'info, InsuranceFundDrawInfo>>,
+
+    #[account(
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key()
+    )]
+    pub vault_factory_info: Box<Account<'info, PutOptionVaultFactoryInfo>>,
+
+    #[account(
+        mut,
+        seeds=[
+            "PutOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Box<Account<'info, PutOptionVaultInfo>>,
+
+    #[account(
+        mut,
+        constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = vault_quote_asset_treasury.owner == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // Check if initializer is signer, should also be the admin, mut is required to reduce lamports (fees)
+    #[account(
+        mut,
+        constraint = initializer.key() == ADMIN_ADDRESS
+    )]
+    pub initializer: Signer<'info>,
+
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+}
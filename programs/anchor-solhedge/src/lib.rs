@@ -20,12 +20,43 @@ use solana_program::{pubkey, pubkey::Pubkey};
 
 use put_options::validators::*;
 use call_options::validators::*;
+use roll_vaults::validators::*;
+use binary_options::validators::*;
+use asset_pairs::validators::*;
+use rfqs::validators::*;
+use insurance_funds::validators::*;
+use frontends::validators::*;
+use fee_vaults::validators::*;
+use fee_tiers::validators::*;
 use put_options::po_controller as po;
 use call_options::co_controller as co;
+use roll_vaults::rv_controller as rv;
+use binary_options::bo_controller as bo;
+use asset_pairs::ap_controller as ap;
+use rfqs::rfq_controller as rfq;
+use insurance_funds::if_controller as insurance;
+use frontends::fe_controller as fe;
+use fee_vaults::fv_controller as fv;
+use fee_tiers::ft_controller as ft;
+use put_options::data::MakerAllocation;
+use insurance_funds::data::InsuranceFundDrawReason;
+use fee_tiers::data::FeeTier;
 
 
 mod put_options;
 mod call_options;
+mod roll_vaults;
+mod binary_options;
+mod asset_pairs;
+mod rfqs;
+mod insurance_funds;
+mod frontends;
+mod fee_vaults;
+mod fee_tiers;
+mod native_sol;
+mod transfer_fees;
+mod emergency_mode;
+mod remaining_accounts;
 
 declare_id!("FoUvjSVZMDccmb2fCppM24N8yzVpPMKYn1h2CZDV7FFa");
 
@@ -55,8 +86,29 @@ pub const ORACLE_ADDRESS: Pubkey = pubkey!("Fr9SMCeLe7GoLUQq6URuvZUgaCWtEkmD7d18
 // The corresponding private key should be on .env as DEVNET_PROTOCOL_FEES_KEY
 pub const PROTOCOL_FEES_ADDRESS: Pubkey = pubkey!("Dku3bu5hqZVBXR39s6UW65nTPQ9rjevhhrfKhfpqgi8D");
 
-pub const PROTOCOL_TOTAL_FEES:f64 = 0.01;
-pub const FRONTEND_SHARE:f64 = 0.5;
+// Asset pairs are managed by the protocol admin, with the same key as protocol fees
+pub const ADMIN_ADDRESS: Pubkey = PROTOCOL_FEES_ADDRESS;
+
+// Total fees on premiums are configured per asset pair (AssetPairConfig), but can never be above 10%
+pub const MAX_PROTOCOL_FEES_BPS: u16 = 1000;
+
+// Maturity grids of standardized asset pairs: daily or weekly expiries
+pub const DAILY_MATURITY_GRID_SECONDS: u64 = 24*60*60;
+pub const WEEKLY_MATURITY_GRID_SECONDS: u64 = 7*24*60*60;
+
+// Maximum number of makers (and of resting bids) that can be passed in remaining accounts
+pub const MAX_REMAINING_MAKERS: usize = 16;
+pub const MAX_REMAINING_BIDS: usize = 8;
+
+// Maximum number of makers of put option vaults allocating lots with a maker queue,
+// takers must pass all the makers with lots available when buying.
+// Passing that many makers (or bids) exceeds the accounts of a legacy transaction,
+// clients must send versioned transactions with address lookup tables
+pub const MAX_QUEUED_MAKERS: u16 = MAX_REMAINING_MAKERS as u16;
+
+// Fee tiers of takers are based on the premiums they paid over the last 30 days
+pub const MAX_FEE_TIERS: usize = 4;
+pub const FEE_TIER_VOLUME_WINDOW_DAYS: usize = 30;
 
 #[program]
 pub mod anchor_solhedge {
@@ -92,10 +144,75 @@ pub mod anchor_solhedge {
         PartiallyExercised
     }
 
+    #[derive(AnchorSerialize, AnchorDeserialize)]
+    pub struct TakerBuyPutSpreadReturn {
+        pub num_lots: u64,
+        pub long_premium: u64,      // paid for the bought leg, fees included
+        pub short_premium: u64      // received for the sold leg, net of fees
+    }
+
+    #[derive(AnchorSerialize, AnchorDeserialize)]
+    pub struct PutOptionEarlyExerciseReturn {
+        pub base_asset_exercised: u64,
+        pub quote_asset_transfer: u64
+    }
+
+    #[derive(AnchorSerialize, AnchorDeserialize)]
+    pub struct CallOptionSettleReturn {
+        pub settle_result: CallOptionSettleResult,
+        pub base_asset_transfer: u64,
+        pub quote_asset_transfer: u64
+    }
+
+    #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+    pub enum CallOptionSettleResult {
+        NotExercised,
+        FullyExercised,
+        PartiallyExercised
+    }
+
+    #[derive(AnchorSerialize, AnchorDeserialize)]
+    pub struct RollVaultWithdrawReturn {
+        pub base_asset_transfer: u64,
+        pub quote_asset_transfer: u64
+    }
+
+    #[derive(AnchorSerialize, AnchorDeserialize)]
+    pub struct RollVaultClaimReturn {
+        pub shares_minted: u64,
+        pub quote_asset_transfer: u64
+    }
+
+    #[derive(AnchorSerialize, AnchorDeserialize)]
+    pub struct TakerBuyLotsBinaryOptionReturn {
+        pub num_lots_bought: u64,
+        pub price: u64
+    }
+
+    #[derive(AnchorSerialize, AnchorDeserialize)]
+    pub struct BinaryOptionSettleReturn {
+        pub in_the_money: bool,
+        pub quote_asset_transfer: u64
+    }
+
     pub fn initialize(_ctx: Context<Initialize>) -> Result<()> {
         Ok(())
     }
 
+    //----------- START ASSET PAIRS FAÇADE ------------------------------/
+    pub fn admin_create_asset_pair_config(ctx: Context<AdminCreateAssetPairConfig>,
+        params: AssetPairConfigParams
+    ) -> Result<()> {
+        ap::admin_create_asset_pair_config(ctx, params)
+    }
+
+    pub fn admin_update_asset_pair_config(ctx: Context<AdminUpdateAssetPairConfig>,
+        params: AssetPairConfigParams
+    ) -> Result<()> {
+        ap::admin_update_asset_pair_config(ctx, params)
+    }
+    //----------- END ASSET PAIRS FAÇADE ------------------------------/
+
     //----------- START PUT OPTIONS FAÇADE ------------------------------/
     pub fn oracle_update_put_option_settle_price(
         ctx: Context<OracleUpdatePutOptionSettlePrice>,
@@ -111,6 +228,14 @@ pub mod anchor_solhedge {
         po::oracle_update_put_option_price(ctx, new_fair_price)
     }
 
+    pub fn oracle_update_put_option_price_with_spot(
+        ctx: Context<OracleUpdatePutOptionFairPrice>,
+        new_fair_price: u64,
+        spot_price: u64
+    ) -> Result<()> {
+        po::oracle_update_put_option_price_with_spot(ctx, new_fair_price, spot_price)
+    }
+
     pub fn gen_settle_put_option_price_ticket(ctx: Context<GenSettlePutOptionPriceTicket>) -> Result<()> {
         po::gen_settle_put_option_price_ticket(ctx)
     }
@@ -156,6 +281,13 @@ pub mod anchor_solhedge {
         po::taker_adjust_funding_put_option_vault(ctx, new_funding)
     }
 
+    //remember, oracle should have written last spot price at most MAX_SECONDS_FROM_LAST_FAIR_PRICE_UPDATE before
+    pub fn taker_exercise_put_option_early<'info>(ctx: Context<'_, '_, '_, 'info, TakerExercisePutOptionEarly<'info>>,
+        max_base_asset_to_exercise: u64
+    ) -> Result<PutOptionEarlyExerciseReturn> {
+        po::taker_exercise_put_option_early(ctx, max_base_asset_to_exercise)
+    }
+
     //remember, oracle should have written last fair price at most MAX_SECONDS_FROM_LAST_FAIR_PRICE_UPDATE before
     pub fn taker_buy_lots_put_option_vault<'info>(ctx: Context<'_, '_, '_, 'info, TakerBuyLotsPutOptionVault<'info>>,
         max_fair_price: u64,
@@ -165,7 +297,34 @@ pub mod anchor_solhedge {
         po::taker_buy_lots_put_option_vault(ctx, max_fair_price, num_lots_to_buy, initial_funding)
     }
 
-    pub fn maker_adjust_position_put_option_vault(ctx: Context<MakerAdjustPositionPutOptionVault>,     
+    //remember, oracle should have written last fair prices of both legs at most MAX_SECONDS_FROM_LAST_FAIR_PRICE_UPDATE before
+    pub fn taker_buy_put_spread<'info>(ctx: Context<'_, '_, '_, 'info, TakerBuyPutSpread<'info>>,
+        max_net_premium: u64,
+        num_lots: u64,
+        num_bids: u8
+    ) -> Result<TakerBuyPutSpreadReturn> {
+        po::taker_buy_put_spread(ctx, max_net_premium, num_lots, num_bids)
+    }
+
+    pub fn taker_place_put_option_bid(ctx: Context<TakerPlacePutOptionBid>,
+        num_lots: u64,
+        max_fair_price: u64,
+        expiry: u64
+    ) -> Result<()> {
+        po::taker_place_put_option_bid(ctx, num_lots, max_fair_price, expiry)
+    }
+
+    pub fn taker_cancel_put_option_bid(ctx: Context<TakerCancelPutOptionBid>) -> Result<u64> {
+        po::taker_cancel_put_option_bid(ctx)
+    }
+
+    pub fn crank_match_put_option_taker_bids<'info>(ctx: Context<'_, '_, '_, 'info, CrankMatchPutOptionTakerBids<'info>>,
+        num_bids: u8
+    ) -> Result<u64> {
+        po::crank_match_put_option_taker_bids(ctx, num_bids)
+    }
+
+    pub fn maker_adjust_position_put_option_vault<'info>(ctx: Context<'_, '_, '_, 'info, MakerAdjustPositionPutOptionVault<'info>>,     
         num_lots_to_sell: u64,
         premium_limit: u64
     ) -> Result<()> {
@@ -173,12 +332,32 @@ pub mod anchor_solhedge {
         po::maker_adjust_position_put_option_vault(ctx, num_lots_to_sell, premium_limit)
     }
 
-    pub fn maker_enter_put_option_vault(ctx: Context<MakerEnterPutOptionVault>,     
+    pub fn maker_exit_put_option_vault(ctx: Context<MakerExitPutOptionVault>) -> Result<u64> {
+        po::maker_exit_put_option_vault(ctx)
+    }
+
+    pub fn maker_enter_put_option_vault<'info>(ctx: Context<'_, '_, '_, 'info, MakerEnterPutOptionVault<'info>>,     
         num_lots_to_sell: u64,
         premium_limit: u64
     ) -> Result<()> {
 
-        po::maker_enter_put_option_vault(ctx, num_lots_to_sell, premium_limit)        
+        po::maker_enter_put_option_vault(ctx, num_lots_to_sell, premium_limit)
+    }
+
+    pub fn maker_add_put_option_margin(ctx: Context<MakerAddPutOptionMargin>, amount: u64) -> Result<()> {
+        po::maker_add_put_option_margin(ctx, amount)
+    }
+
+    pub fn fund_put_option_vault_safety_fund(ctx: Context<FundPutOptionSafetyFund>, amount: u64) -> Result<()> {
+        po::fund_put_option_vault_safety_fund(ctx, amount)
+    }
+
+    pub fn crank_return_put_option_safety_fund(ctx: Context<CrankReturnPutOptionSafetyFund>) -> Result<u64> {
+        po::crank_return_put_option_safety_fund(ctx)
+    }
+
+    pub fn liquidate_put_option_maker(ctx: Context<LiquidatePutOptionMaker>) -> Result<u64> {
+        po::liquidate_put_option_maker(ctx)
     }
 
     pub fn maker_create_put_option_vault(ctx: Context<MakerCreatePutOptionVault>,
@@ -186,6 +365,12 @@ pub mod anchor_solhedge {
     ) -> Result<()> {
         po::maker_create_put_option_vault(ctx, params, vault_id)
     }
+
+    pub fn maker_open_put_option_vault(ctx: Context<MakerOpenPutOptionVault>,
+        params: MakerCreatePutOptionParams
+    ) -> Result<u64> {
+        po::maker_open_put_option_vault(ctx, params)
+    }
     //----------- END PUT OPTIONS FAÇADE ------------------------------/
 
     //----------- START CALL OPTIONS FAÇADE ------------------------------/
@@ -201,6 +386,12 @@ pub mod anchor_solhedge {
         co::maker_create_call_option_vault(ctx, params, vault_id)
     }
 
+    pub fn maker_open_call_option_vault(ctx: Context<MakerOpenCallOptionVault>,
+        params: MakerCreateCallOptionParams
+    ) -> Result<u64> {
+        co::maker_open_call_option_vault(ctx, params)
+    }
+
     pub fn maker_enter_call_option_vault(ctx: Context<MakerEnterCallOptionVault>,     
         num_lots_to_sell: u64,
         premium_limit: u64
@@ -215,6 +406,10 @@ pub mod anchor_solhedge {
         co::maker_adjust_position_call_option_vault(ctx, num_lots_to_sell, premium_limit)
     }
 
+    pub fn maker_exit_call_option_vault(ctx: Context<MakerExitCallOptionVault>) -> Result<u64> {
+        co::maker_exit_call_option_vault(ctx)
+    }
+
     pub fn gen_update_call_option_fair_price_ticket(ctx: Context<GenUpdateCallOptionFairPriceTicket>) -> Result<()> {
         co::gen_update_call_option_fair_price_ticket(ctx)
     }
@@ -226,6 +421,14 @@ pub mod anchor_solhedge {
         co::oracle_update_call_option_price(ctx, new_fair_price)
     }
 
+    pub fn oracle_update_call_option_price_with_spot(
+        ctx: Context<OracleUpdateCallOptionFairPrice>,
+        new_fair_price: u64,
+        spot_price: u64
+    ) -> Result<()> {
+        co::oracle_update_call_option_price_with_spot(ctx, new_fair_price, spot_price)
+    }
+
     pub fn taker_buy_lots_call_option_vault<'info>(ctx: Context<'_, '_, '_, 'info, TakerBuyLotsCallOptionVault<'info>>,
     max_fair_price: u64,
     num_lots_to_buy: u64,
@@ -233,8 +436,286 @@ pub mod anchor_solhedge {
     ) -> Result<TakerBuyLotsCallOptionReturn> {
         co::taker_buy_lots_call_option_vault(ctx, max_fair_price, num_lots_to_buy, initial_funding)
     }
+
+    pub fn gen_settle_call_option_price_ticket(ctx: Context<GenSettleCallOptionPriceTicket>) -> Result<()> {
+        co::gen_settle_call_option_price_ticket(ctx)
+    }
+
+    pub fn oracle_update_call_option_settle_price(
+        ctx: Context<OracleUpdateCallOptionSettlePrice>,
+        settle_price: u64
+    ) -> Result<()> {
+        co::oracle_update_call_option_settle_price(ctx, settle_price)
+    }
+
+    pub fn maker_settle_call_option(ctx: Context<MakerSettleCallOption>) -> Result<CallOptionSettleReturn> {
+        co::maker_settle_call_option(ctx)
+    }
+
+    pub fn taker_settle_call_option(ctx: Context<TakerSettleCallOption>) -> Result<CallOptionSettleReturn> {
+        co::taker_settle_call_option(ctx)
+    }
+
+    pub fn maker_activate_call_option_emergency_mode(ctx: Context<MakerActivateCallOptionEmergencyMode>) -> Result<()> {
+        co::maker_activate_call_option_emergency_mode(ctx)
+    }
+
+    pub fn taker_activate_call_option_emergency_mode(ctx: Context<TakerActivateCallOptionEmergencyMode>) -> Result<()> {
+        co::taker_activate_call_option_emergency_mode(ctx)
+    }
+
+    pub fn maker_call_option_emergency_exit(ctx: Context<MakerCallOptionEmergencyExit>) -> Result<()> {
+        co::maker_call_option_emergency_exit(ctx)
+    }
+
+    pub fn taker_call_option_emergency_exit(ctx: Context<TakerCallOptionEmergencyExit>) -> Result<()> {
+        co::taker_call_option_emergency_exit(ctx)
+    }
     //----------- END CALL OPTIONS FAÇADE ------------------------------/
 
+    //----------- START BINARY OPTIONS FAÇADE ------------------------------/
+    pub fn maker_next_binary_option_vault_id(ctx: Context<MakerNextBinaryOptionVaultId>,
+        params: MakerCreateBinaryOptionParams
+    ) -> Result<u64> {
+        bo::maker_next_binary_option_vault_id(ctx, params)
+    }
+
+    pub fn maker_create_binary_option_vault(ctx: Context<MakerCreateBinaryOptionVault>,
+        params: MakerCreateBinaryOptionParams, vault_id: u64
+    ) -> Result<()> {
+        bo::maker_create_binary_option_vault(ctx, params, vault_id)
+    }
+
+    pub fn maker_enter_binary_option_vault(ctx: Context<MakerEnterBinaryOptionVault>,     
+        num_lots_to_sell: u64,
+        premium_limit: u64
+    ) -> Result<()> {
+        bo::maker_enter_binary_option_vault(ctx, num_lots_to_sell, premium_limit)
+    }
+
+    pub fn maker_adjust_position_binary_option_vault(ctx: Context<MakerAdjustPositionBinaryOptionVault>,     
+        num_lots_to_sell: u64,
+        premium_limit: u64
+    ) -> Result<()> {
+        bo::maker_adjust_position_binary_option_vault(ctx, num_lots_to_sell, premium_limit)
+    }
+
+    pub fn maker_exit_binary_option_vault(ctx: Context<MakerExitBinaryOptionVault>) -> Result<u64> {
+        bo::maker_exit_binary_option_vault(ctx)
+    }
+
+    pub fn gen_update_binary_option_fair_price_ticket(ctx: Context<GenUpdateBinaryOptionFairPriceTicket>) -> Result<()> {
+        bo::gen_update_binary_option_fair_price_ticket(ctx)
+    }
+
+    pub fn oracle_update_binary_option_price(
+        ctx: Context<OracleUpdateBinaryOptionFairPrice>,
+        new_fair_price: u64
+    ) -> Result<()> {
+        bo::oracle_update_binary_option_price(ctx, new_fair_price)
+    }
+
+    //remember, oracle should have written last fair price at most MAX_SECONDS_FROM_LAST_FAIR_PRICE_UPDATE before
+    pub fn taker_buy_lots_binary_option_vault<'info>(ctx: Context<'_, '_, '_, 'info, TakerBuyLotsBinaryOptionVault<'info>>,
+        max_fair_price: u64,
+        num_lots_to_buy: u64
+    ) -> Result<TakerBuyLotsBinaryOptionReturn> {
+        bo::taker_buy_lots_binary_option_vault(ctx, max_fair_price, num_lots_to_buy)
+    }
+
+    pub fn gen_settle_binary_option_price_ticket(ctx: Context<GenSettleBinaryOptionPriceTicket>) -> Result<()> {
+        bo::gen_settle_binary_option_price_ticket(ctx)
+    }
+
+    pub fn oracle_update_binary_option_settle_price(
+        ctx: Context<OracleUpdateBinaryOptionSettlePrice>,
+        settle_price: u64
+    ) -> Result<()> {
+        bo::oracle_update_binary_option_settle_price(ctx, settle_price)
+    }
+
+    pub fn maker_settle_binary_option(ctx: Context<MakerSettleBinaryOption>) -> Result<BinaryOptionSettleReturn> {
+        bo::maker_settle_binary_option(ctx)
+    }
+
+    pub fn taker_settle_binary_option(ctx: Context<TakerSettleBinaryOption>) -> Result<BinaryOptionSettleReturn> {
+        bo::taker_settle_binary_option(ctx)
+    }
+
+    pub fn maker_activate_binary_option_emergency_mode(ctx: Context<MakerActivateBinaryOptionEmergencyMode>) -> Result<()> {
+        bo::maker_activate_binary_option_emergency_mode(ctx)
+    }
+
+    pub fn taker_activate_binary_option_emergency_mode(ctx: Context<TakerActivateBinaryOptionEmergencyMode>) -> Result<()> {
+        bo::taker_activate_binary_option_emergency_mode(ctx)
+    }
+
+    pub fn maker_binary_option_emergency_exit(ctx: Context<MakerBinaryOptionEmergencyExit>) -> Result<()> {
+        bo::maker_binary_option_emergency_exit(ctx)
+    }
+    //----------- END BINARY OPTIONS FAÇADE ------------------------------/
+
+    //----------- START ROLL VAULTS FAÇADE ------------------------------/
+    pub fn create_covered_call_roll_vault(ctx: Context<CreateCoveredCallRollVault>,
+        params: CreateCoveredCallRollVaultParams
+    ) -> Result<()> {
+        rv::create_covered_call_roll_vault(ctx, params)
+    }
+
+    pub fn deposit_covered_call_roll_vault(ctx: Context<DepositCoveredCallRollVault>,
+        base_asset_amount: u64
+    ) -> Result<u64> {
+        rv::deposit_covered_call_roll_vault(ctx, base_asset_amount)
+    }
+
+    pub fn withdraw_covered_call_roll_vault(ctx: Context<WithdrawCoveredCallRollVault>,
+        shares: u64
+    ) -> Result<RollVaultWithdrawReturn> {
+        rv::withdraw_covered_call_roll_vault(ctx, shares)
+    }
+
+    pub fn crank_roll_covered_call_vault(ctx: Context<CrankRollCoveredCallVault>,
+        maturity: u64,
+        vault_id: u64
+    ) -> Result<u64> {
+        rv::crank_roll_covered_call_vault(ctx, maturity, vault_id)
+    }
+
+    pub fn crank_settle_covered_call_roll_vault(ctx: Context<CrankSettleCoveredCallRollVault>) -> Result<CallOptionSettleReturn> {
+        rv::crank_settle_covered_call_roll_vault(ctx)
+    }
+
+    pub fn crank_covered_call_roll_vault_emergency_exit(ctx: Context<CrankCoveredCallRollVaultEmergencyExit>) -> Result<u64> {
+        rv::crank_covered_call_roll_vault_emergency_exit(ctx)
+    }
+
+    pub fn create_cash_secured_put_roll_vault(ctx: Context<CreateCashSecuredPutRollVault>,
+        params: CreateCashSecuredPutRollVaultParams
+    ) -> Result<()> {
+        rv::create_cash_secured_put_roll_vault(ctx, params)
+    }
+
+    pub fn oracle_update_cash_secured_put_roll_spot_price(ctx: Context<OracleUpdateCashSecuredPutRollSpotPrice>,
+        spot_price: u64
+    ) -> Result<()> {
+        rv::oracle_update_cash_secured_put_roll_spot_price(ctx, spot_price)
+    }
+
+    pub fn request_deposit_cash_secured_put_roll_vault(ctx: Context<RequestDepositCashSecuredPutRollVault>,
+        quote_asset_amount: u64
+    ) -> Result<()> {
+        rv::request_deposit_cash_secured_put_roll_vault(ctx, quote_asset_amount)
+    }
+
+    pub fn request_withdraw_cash_secured_put_roll_vault(ctx: Context<RequestWithdrawCashSecuredPutRollVault>,
+        shares: u64
+    ) -> Result<()> {
+        rv::request_withdraw_cash_secured_put_roll_vault(ctx, shares)
+    }
+
+    pub fn crank_process_cash_secured_put_roll_queues(ctx: Context<CrankProcessCashSecuredPutRollQueues>) -> Result<()> {
+        rv::crank_process_cash_secured_put_roll_queues(ctx)
+    }
+
+    pub fn claim_cash_secured_put_roll_ticket(ctx: Context<ClaimCashSecuredPutRollTicket>) -> Result<RollVaultClaimReturn> {
+        rv::claim_cash_secured_put_roll_ticket(ctx)
+    }
+
+    pub fn crank_roll_cash_secured_put_vault(ctx: Context<CrankRollCashSecuredPutVault>,
+        maturity: u64,
+        strike: u64,
+        vault_id: u64
+    ) -> Result<u64> {
+        rv::crank_roll_cash_secured_put_vault(ctx, maturity, strike, vault_id)
+    }
+
+    pub fn crank_settle_cash_secured_put_roll_vault(ctx: Context<CrankSettleCashSecuredPutRollVault>) -> Result<u64> {
+        rv::crank_settle_cash_secured_put_roll_vault(ctx)
+    }
+    //----------- END ROLL VAULTS FAÇADE ------------------------------/
+
+    //----------- START RFQS FAÇADE ------------------------------/
+    pub fn taker_post_put_option_rfq(ctx: Context<TakerPostPutOptionRfq>,
+        num_lots: u64,
+        lot_size: i8,
+        expiry: u64
+    ) -> Result<()> {
+        rfq::taker_post_put_option_rfq(ctx, num_lots, lot_size, expiry)
+    }
+
+    pub fn taker_cancel_put_option_rfq(ctx: Context<TakerCancelPutOptionRfq>) -> Result<()> {
+        rfq::taker_cancel_put_option_rfq(ctx)
+    }
+
+    pub fn maker_quote_put_option_rfq(ctx: Context<MakerQuotePutOptionRfq>,
+        premium: u64
+    ) -> Result<()> {
+        rfq::maker_quote_put_option_rfq(ctx, premium)
+    }
+
+    pub fn maker_withdraw_put_option_rfq_quote(ctx: Context<MakerWithdrawPutOptionRfqQuote>) -> Result<u64> {
+        rfq::maker_withdraw_put_option_rfq_quote(ctx)
+    }
+
+    pub fn taker_accept_put_option_rfq_quote(ctx: Context<TakerAcceptPutOptionRfqQuote>,
+        expected_premium: u64
+    ) -> Result<u64> {
+        rfq::taker_accept_put_option_rfq_quote(ctx, expected_premium)
+    }
+    //----------- END RFQS FAÇADE ------------------------------/
+
+    //----------- START INSURANCE FUNDS FAÇADE ------------------------------/
+    pub fn admin_update_insurance_fund(ctx: Context<AdminUpdateInsuranceFund>,
+        fee_share_bps: u16
+    ) -> Result<()> {
+        insurance::admin_update_insurance_fund(ctx, fee_share_bps)
+    }
+
+    pub fn admin_draw_insurance_fund_to_put_option_vault(ctx: Context<AdminDrawInsuranceFundToPutOptionVault>,
+        amount: u64,
+        reason: InsuranceFundDrawReason
+    ) -> Result<()> {
+        insurance::admin_draw_insurance_fund_to_put_option_vault(ctx, amount, reason)
+    }
+    //----------- END INSURANCE FUNDS FAÇADE ------------------------------/
+
+    //----------- START FRONTENDS FAÇADE ------------------------------/
+    pub fn admin_register_frontend(ctx: Context<AdminRegisterFrontend>,
+        fee_share_bps: u16
+    ) -> Result<()> {
+        fe::admin_register_frontend(ctx, fee_share_bps)
+    }
+
+    pub fn admin_update_frontend(ctx: Context<AdminUpdateFrontend>,
+        fee_share_bps: u16,
+        enabled: bool
+    ) -> Result<()> {
+        fe::admin_update_frontend(ctx, fee_share_bps, enabled)
+    }
+
+    pub fn frontend_update_payout_account(ctx: Context<FrontendUpdatePayoutAccount>) -> Result<()> {
+        fe::frontend_update_payout_account(ctx)
+    }
+    //----------- END FRONTENDS FAÇADE ------------------------------/
+
+    //----------- START FEE VAULTS FAÇADE ------------------------------/
+    pub fn claim_protocol_fees(ctx: Context<ClaimProtocolFees>) -> Result<u64> {
+        fv::claim_protocol_fees(ctx)
+    }
+
+    pub fn claim_frontend_fees(ctx: Context<ClaimFrontendFees>) -> Result<u64> {
+        fv::claim_frontend_fees(ctx)
+    }
+    //----------- END FEE VAULTS FAÇADE ------------------------------/
+
+    //----------- START FEE TIERS FAÇADE ------------------------------/
+    pub fn admin_update_fee_tiers(ctx: Context<AdminUpdateFeeTiers>,
+        params: FeeTiersParams
+    ) -> Result<()> {
+        ft::admin_update_fee_tiers(ctx, params)
+    }
+    //----------- END FEE TIERS FAÇADE ------------------------------/
+
 }
 
 
@@ -246,7 +727,19 @@ pub struct MakerCreatePutOptionParams {
     pub max_takers: u16,
     pub lot_size: i8, //10^lot_size, for instance 0 means 1; -1 means 0.1; 2 means 100
     pub num_lots_to_sell: u64,
-    pub premium_limit: u64
+    pub premium_limit: u64,
+    pub cash_settled: bool, // if true, takers do not deposit anything and get max(strike - settled_price, 0) (puts) or max(settled_price - strike, 0) (calls) at settlement
+    pub american: bool,     // if true, funded takers may exercise before FREEZE_SECONDS to maturity at the oracle spot price
+    pub barrier: u64,       // 0 for vanilla options, otherwise the barrier level observed on oracle spot prices until maturity
+    pub barrier_up: bool,   // if true, the barrier is crossed when spot_price >= barrier, otherwise when spot_price <= barrier
+    pub knock_in: bool,     // if true, options are only exercised if the barrier has been crossed, otherwise (knock-out) only if it has not
+    pub auction_seconds: u64,               // 0 for no auction, otherwise length of the Dutch auction opening the new vault
+    pub auction_start_multiple_bps: u16,    // premium multiple of the fair price at the start of the auction, in basis points (15000 is 1.5x)
+    pub auction_floor_multiple_bps: u16,    // premium multiple of the fair price at the end of the auction, in basis points
+    pub maker_allocation: MakerAllocation,  // how lots bought by takers are allocated among the makers of the vault
+    pub initial_margin_bps: u16,            // 0 for fully collateralized makers, otherwise (cash-settled only) the part of the notional they deposit
+    pub maintenance_margin_bps: u16,        // margin makers must keep on the notional they have sold, on top of what those options are worth
+    pub liquidation_bonus_bps: u16          // paid to liquidators on top of what the options of a liquidated maker are worth
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
@@ -257,5 +750,66 @@ pub struct MakerCreateCallOptionParams {
     pub max_takers: u16,
     pub lot_size: i8, //10^lot_size, for instance 0 means 1; -1 means 0.1; 2 means 100
     pub num_lots_to_sell: u64,
+    pub premium_limit: u64,
+    pub cash_settled: bool, // if true, takers do not deposit anything and get max(strike - settled_price, 0) (puts) or max(settled_price - strike, 0) (calls) at settlement
+    pub barrier: u64,       // 0 for vanilla options, otherwise the barrier level observed on oracle spot prices until maturity
+    pub barrier_up: bool,   // if true, the barrier is crossed when spot_price >= barrier, otherwise when spot_price <= barrier
+    pub knock_in: bool,     // if true, options are only exercised if the barrier has been crossed, otherwise (knock-out) only if it has not
+    pub auction_seconds: u64,               // 0 for no auction, otherwise length of the Dutch auction opening the new vault
+    pub auction_start_multiple_bps: u16,    // premium multiple of the fair price at the start of the auction, in basis points (15000 is 1.5x)
+    pub auction_floor_multiple_bps: u16     // premium multiple of the fair price at the end of the auction, in basis points
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct MakerCreateBinaryOptionParams {
+    pub maturity: u64, 
+    pub strike: u64,
+    pub payout_per_lot: u64,    // fixed amount in quote asset lamports paid for each lot in the money, makers deposit it as collateral
+    pub is_put: bool,           // if true, pays when settled_price < strike (binary put), otherwise when settled_price > strike (binary call)
+    pub max_makers: u16,
+    pub max_takers: u16,
+    pub num_lots_to_sell: u64,
     pub premium_limit: u64
-}
\ No newline at end of file
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct CreateCoveredCallRollVaultParams {
+    pub strike_offset_bps: u16,     // strike of each new series is the previous settled price plus this offset
+    pub tenor_seconds: u64,         // maturity of each new series is the previous maturity plus this tenor
+    pub lot_size: i8,               //10^lot_size, for instance 0 means 1; -1 means 0.1; 2 means 100
+    pub max_takers: u16,
+    pub first_maturity: u64,
+    pub first_strike: u64
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct CreateCashSecuredPutRollVaultParams {
+    pub strike_offset_bps: u16,     // strike of each new series is the oracle spot price minus this offset
+    pub tenor_seconds: u64,         // maturity of each new series is the previous maturity plus this tenor
+    pub lot_size: i8,               //10^lot_size, for instance 0 means 1; -1 means 0.1; 2 means 100
+    pub max_takers: u16,
+    pub first_maturity: u64
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct AssetPairConfigParams {
+    pub enabled: bool,
+    pub min_lot_size: i8,           //10^lot_size, for instance 0 means 1; -1 means 0.1; 2 means 100
+    pub max_lot_size: i8,
+    pub min_strike: u64,
+    pub max_strike: u64,
+    pub max_maturity_seconds: u64,  // at most MAX_MATURITY_FUTURE_SECONDS
+    pub price_source: Pubkey,
+    pub protocol_fees_bps: u16,     // at most MAX_PROTOCOL_FEES_BPS
+    pub standardized: bool,         // if true, the listing rules below apply to new vault factories
+    pub strike_tick: u64,
+    pub maturity_grid_seconds: u64, // DAILY_MATURITY_GRID_SECONDS or WEEKLY_MATURITY_GRID_SECONDS
+    pub maturity_grid_offset_seconds: u64 // less than maturity_grid_seconds, for instance 8 hours for 08:00 UTC daily expiries
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct FeeTiersParams {
+    pub tiers: [FeeTier; 4],            // MAX_FEE_TIERS, with increasing min_volume (tiers with discount_bps = 0 are unused)
+    pub maker_rebate_bps: u16,          // share of the protocol share of fees paid back to makers, 0 for no rebate
+    pub maker_rebate_min_seconds: u64   // how long the collateral of a maker must have been resting to get the rebate
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token_interface::{self, CloseAccount, SyncNative, TokenAccount};
+
+pub fn is_native_mint(mint: &Pubkey) -> bool {
+    *mint == native_mint::ID
+}
+
+// When the mint is wSOL, tops up the owner's token account with native lamports
+// so that it holds at least `amount` before it is debited by a token transfer.
+// The owner only needs an (even empty) wSOL account, no manual wrapping.
+pub fn wrap_native_sol_if_needed<'info>(
+    mint: &Pubkey,
+    owner: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+    amount: u64,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>
+) -> Result<()> {
+    if !is_native_mint(mint) {
+        return Ok(());
+    }
+    // read the balance from account data, the deserialized copy
+    // may be stale after previous transfers in the same instruction
+    let balance = token_interface::accessor::amount(token_account)?;
+    if balance >= amount {
+        return Ok(());
+    }
+    let missing_lamports = amount - balance;
+    msg!("Wrapping {} lamports into wSOL account {}", missing_lamports, token_account.key());
+    let lamports_transfer_context = CpiContext::new(
+        system_program.clone(),
+        system_program::Transfer {
+            from: owner.clone(),
+            to: token_account.clone()
+        }
+    );
+    system_program::transfer(lamports_transfer_context, missing_lamports)?;
+
+    let sync_native_context = CpiContext::new(
+        token_program.clone(),
+        SyncNative {
+            account: token_account.clone()
+        }
+    );
+    token_interface::sync_native(sync_native_context)
+}
+
+// When the mint is wSOL, closes the owner's token account after a payout,
+// so that all of its balance (and rent) is returned as native lamports.
+// Unwrapping is opt-in: the account is only closed if its close authority has been
+// explicitly set to the owner (e.g. a wSOL account created just for the transaction).
+// Accounts without a close authority, like the usual associated token accounts, are
+// left untouched and keep the wSOL.
+// Must be the last use of the token account in the instruction.
+pub fn unwrap_native_sol_if_needed<'info>(
+    mint: &Pubkey,
+    owner: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>
+) -> Result<()> {
+    if !is_native_mint(mint) || !has_opted_in_to_unwrap(owner, token_account)? {
+        return Ok(());
+    }
+    msg!("Unwrapping wSOL account {} to {}", token_account.key(), owner.key());
+    let close_context = CpiContext::new(
+        token_program.clone(),
+        CloseAccount {
+            account: token_account.clone(),
+            destination: owner.clone(),
+            authority: owner.clone()
+        }
+    );
+    token_interface::close_account(close_context)
+}
+
+fn has_opted_in_to_unwrap(owner: &AccountInfo, token_account: &AccountInfo) -> Result<bool> {
+    let data = token_account.try_borrow_data()?;
+    let account = TokenAccount::try_deserialize(&mut &data[..])?;
+    Ok(account.close_authority == COption::Some(owner.key()))
+}
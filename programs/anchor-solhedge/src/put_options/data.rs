@@ -14,7 +14,46 @@ pub struct PutOptionVaultFactoryInfo {
     pub last_fair_price: u64,
     pub ts_last_fair_price: u64,
     pub settled_price: u64,
-    pub emergency_mode: bool
+    pub emergency_mode: bool,
+    pub cash_settled: bool,         // if true, options in this factory are settled in cash: takers never deposit anything
+    pub american: bool,             // if true, funded takers may exercise their options before the factory is frozen
+    pub protocol_fees_bps: u16,     // fees on premiums, copied from the AssetPairConfig when the factory is created
+
+    pub last_spot_price: u64,       // spot price of base asset in quote asset lamports, written by the oracle (used for early exercise)
+    pub ts_last_spot_price: u64,
+
+    pub barrier: u64,               // 0 for vanilla options, otherwise the barrier level in quote asset lamports
+    pub barrier_up: bool,           // if true, knocked when the spot price goes up to the barrier, otherwise when it goes down to it
+    pub knock_in: bool,             // if true, options are only exercised if knocked (knock-in), otherwise only if never knocked (knock-out)
+    pub knocked: bool               // permanently set by the first oracle spot price observation that crosses the barrier before maturity
+}
+
+impl PutOptionVaultFactoryInfo {
+    // Records an oracle spot price observation, the knocked flag can never be unset
+    pub fn observe_barrier(&mut self, spot_price: u64) {
+        if self.barrier > 0 && !self.knocked {
+            let crossed = if self.barrier_up { spot_price >= self.barrier } else { spot_price <= self.barrier };
+            if crossed {
+                self.knocked = true;
+                msg!("Spot price {} crossed barrier {}, options are now knocked", spot_price, self.barrier);
+            }
+        }
+    }
+
+    // Knocked-out options, and knock-in options that have never been knocked, are never exercised
+    pub fn is_barrier_void(&self) -> bool {
+        self.barrier > 0 && self.knock_in != self.knocked
+    }
+
+    // Unlike is_barrier_void, this can never be undone by a later spot price observation
+    pub fn is_knocked_out(&self) -> bool {
+        self.barrier > 0 && !self.knock_in && self.knocked
+    }
+
+    // Id the next vault created in this factory gets, vault ids of a new factory start at 1
+    pub fn upcoming_vault_id(&self) -> u64 {
+        if self.is_initialized { self.next_vault_id } else { 1 }
+    }
 }
 
 #[account]
@@ -27,6 +66,7 @@ pub struct PutOptionVaultInfo {
     pub lot_size: i8, //10^lot_size, for instance 0 means 1; -1 means 0.1; 2 means 100
 
     pub makers_num: u16,
+    pub next_maker_ord: u16,                // ord of the last maker that joined, makers_num goes down when makers exit
     pub makers_total_pending_sell: u64,     // the amount of quote asset lamports that makers have deposited, but not yet sold
     pub makers_total_pending_settle: u64,   // the amount of quote asset lamports that has been sold in options and has not been settled
     pub is_makers_full: bool,
@@ -34,8 +74,107 @@ pub struct PutOptionVaultInfo {
     pub takers_num: u16,
     pub takers_total_deposited: u64,        // the amount that takers have funded
     pub is_takers_full: bool,
-    pub bonus_not_exercised: u64            // the amount of bonus that has been given to early settlers (makers) when the option was exercised
+    pub bonus_not_exercised: u64,           // the amount of bonus that has been given to early settlers (makers) when the option was exercised
                                         // but the takers have not fully funded what they had bought.
+
+    // Optional Dutch auction when the vault opens: takers pay the fair price times a multiple that decays
+    // linearly from auction_start_multiple_bps to auction_floor_multiple_bps until auction_end
+    pub auction_start: u64,
+    pub auction_end: u64,                   // 0 if the vault has no auction
+    pub auction_start_multiple_bps: u16,
+    pub auction_floor_multiple_bps: u16,
+
+    pub maker_allocation: MakerAllocation,
+    pub next_maker_queue_seq: u64,          // queue_seq given to the next maker joining the queue
+
+    // Margined (cash-settled) vaults: makers only post initial_margin_bps of the notional they offer,
+    // and can be liquidated when their margin falls below what their sold options are worth plus
+    // maintenance_margin_bps of the notional sold
+    pub initial_margin_bps: u16,            // 0 if makers post the whole notional, as usual
+    pub maintenance_margin_bps: u16,
+    pub liquidation_bonus_bps: u16,         // on top of what sold options are worth, paid to liquidators
+    pub safety_fund: u64,                   // quote asset lamports in the treasury covering margin shortfalls
+
+    // Settlement of margined vaults: takers settle once every maker has, and get paid pro-rata
+    // when makers owed more than their margin and the safety fund could cover
+    pub makers_settled: u16,
+    pub makers_total_payout: u64,           // quote asset lamports settled makers owed to takers
+    pub margin_shortfall: u64,              // the part of makers_total_payout that is not covered yet
+    pub takers_settled: u16
+}
+
+impl PutOptionVaultInfo {
+    // Premium per unit of base asset takers pay at current_time, the fair price itself once the auction is over
+    pub fn premium_price(&self, fair_price: u64, current_time: u64) -> u64 {
+        if current_time >= self.auction_end {
+            return fair_price;
+        }
+        let duration = self.auction_end.saturating_sub(self.auction_start) as u128;
+        let elapsed = current_time.saturating_sub(self.auction_start) as u128;
+        let decay_bps = (self.auction_start_multiple_bps.saturating_sub(self.auction_floor_multiple_bps) as u128)*elapsed/duration;
+        let multiple_bps = (self.auction_start_multiple_bps as u128).saturating_sub(decay_bps);
+        ((fair_price as u128)*multiple_bps/10000) as u64
+    }
+
+    pub fn is_margined(&self) -> bool {
+        self.initial_margin_bps > 0
+    }
+
+    // Quote asset lamports a maker deposits to offer notional quote asset lamports of options
+    pub fn margin_for(&self, notional: u64) -> u64 {
+        if !self.is_margined() {
+            return notional;
+        }
+        (((notional as u128)*(self.initial_margin_bps as u128) + 9999)/10000) as u64
+    }
+
+    // Covers what it can of the margin shortfall with the safety fund, returning the amount covered.
+    // Called whenever either of them grows, so that takers are paid from what the vault holds
+    pub fn cover_margin_shortfall(&mut self) -> u64 {
+        let covered = std::cmp::min(self.margin_shortfall, self.safety_fund);
+        self.safety_fund = self.safety_fund.checked_sub(covered).unwrap();
+        self.margin_shortfall = self.margin_shortfall.checked_sub(covered).unwrap();
+        covered
+    }
+
+    // Once every maker and taker of a margined vault has settled, nobody is owed what is left
+    // of the safety fund
+    pub fn is_fully_settled(&self) -> bool {
+        self.makers_settled >= self.makers_num && self.takers_settled >= self.takers_num
+    }
+
+    // What a taker owed payoff in a margined vault actually gets, once every maker has settled
+    pub fn margined_taker_payoff(&self, payoff: u64) -> u64 {
+        if self.margin_shortfall == 0 || self.makers_total_payout == 0 {
+            return payoff;
+        }
+        let covered = self.makers_total_payout.saturating_sub(self.margin_shortfall) as u128;
+        ((payoff as u128)*covered/(self.makers_total_payout as u128)) as u64
+    }
+
+    // Puts a maker at the back of the maker queue
+    pub fn take_maker_queue_seq(&mut self) -> u64 {
+        let seq = self.next_maker_queue_seq;
+        self.next_maker_queue_seq = self.next_maker_queue_seq.checked_add(1).unwrap();
+        seq
+    }
+}
+
+// How the lots bought by a taker are allocated among the makers of a vault
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MakerAllocation {
+    #[default]
+    TakerChoice,    // in the order the taker passes the makers
+    Fifo,           // first to the makers that have been longer in the maker queue
+    ProRata         // in proportion to the lots each maker has available
+}
+
+impl PutOptionVaultInfo {
+    // Ord of a maker joining the vault, never reused when makers exit
+    pub fn take_maker_ord(&mut self) -> u16 {
+        self.next_maker_ord = self.next_maker_ord.checked_add(1).unwrap();
+        self.next_maker_ord
+    }
 }
 
 #[account]
@@ -47,13 +186,11 @@ pub struct PutOptionMakerInfo {
     pub is_settled: bool,           // if the maker has already got his tokens after maturity
     pub premium_limit: u64,         // minimum price for option premium he is willing to get, can be zero if he is ok of selling at whatever the fair price
     pub owner: Pubkey,
-    pub put_option_vault: Pubkey
-}
-
-impl PutOptionMakerInfo {
-    pub fn from<'info>(info: &AccountInfo<'info>) -> Account<'info, Self> {
-        Account::try_from(info).unwrap()
-    }
+    pub put_option_vault: Pubkey,
+    pub early_exercised_base: u64,  // base asset lamports she has bought at strike from early exercises, she gets them at settlement
+    pub queue_seq: u64,             // position in the maker queue of the vault, she goes to the back whenever she adds lots
+    pub margin_posted: u64,         // margined vaults only: quote asset lamports she has actually deposited (quote_asset_qty is the notional)
+    pub resting_since: u64          // when she last entered or added lots, for maker rebates on fees
 }
 
 #[account]
@@ -65,7 +202,8 @@ pub struct PutOptionTakerInfo {
     pub qty_deposited: u64,         // how much she has funded (qty_deposited <= max_base_asset)
     pub is_settled: bool,           // if the taker has already got her tokens after maturity
     pub owner: Pubkey,
-    pub put_option_vault: Pubkey
+    pub put_option_vault: Pubkey,
+    pub early_exercised_base: u64   // how much she has already sold at strike by exercising early
 }
 
 #[account]
@@ -79,3 +217,19 @@ pub struct PutOptionSettlePriceTicketInfo {
     pub is_used: bool,
     pub factory_vault: Pubkey
 }
+
+// Resting bid of a taker for lots of a vault, filled at the fair price when makers sell.
+// The premium is escrowed in an associated token account of this PDA
+#[account]
+pub struct PutOptionTakerBidInfo {
+    pub is_initialized: bool,
+
+    pub owner: Pubkey,
+    pub put_option_vault: Pubkey,
+    pub lots_remaining: u64,        // lots the taker still wants to buy
+    pub lots_filled: u64,
+    pub max_fair_price: u64,        // the bid is only filled while last_fair_price is not above this
+    pub expiry: u64,                // the bid is not filled anymore from this time on
+    pub escrowed_premium: u64,      // quote asset lamports left in escrow, enough for lots_remaining at max_fair_price
+    pub bump: u8
+}
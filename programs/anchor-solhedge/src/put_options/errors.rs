@@ -60,5 +60,89 @@ pub enum PutOptionError {
     OptionPremiumTooLow,
 
     #[msg("Insufficient time passed since maturity to activate emergency mode, please wait more")]
-    EmergencyModeTooEarly
-}    
+    EmergencyModeTooEarly,
+
+    #[msg("Vault factory already exists with a different settlement mode (cash or physical)")]
+    SettlementModeMismatch,
+
+    #[msg("Cash-settled options are not funded by takers")]
+    CashSettledNoFunding,
+
+    #[msg("Vault factory already exists with a different exercise style (American or European)")]
+    ExerciseStyleMismatch,
+
+    #[msg("Early exercise is only allowed in physically settled American style vault factories")]
+    EarlyExerciseNotAllowed,
+
+    #[msg("Last spot price update is too old. Please ask the oracle to make a new update")]
+    LastSpotPriceUpdateTooOld,
+
+    #[msg("Spot price is not below strike, early exercise is not favorable")]
+    EarlyExerciseNotFavorable,
+
+    #[msg("Nothing to exercise")]
+    NothingToExercise,
+
+    #[msg("American vaults cannot have more than MAX_REMAINING_MAKERS makers")]
+    TooManyAmericanMakers,
+
+    #[msg("Spread legs must have the same pair, maturity, settlement mode, exercise style and lot size, with a lower strike for the sold leg")]
+    SpreadLegsMismatch,
+
+    #[msg("Not enough lots available or bid to fill both legs of the spread")]
+    SpreadNotFilled,
+
+    #[msg("Net premium of the spread is above max_net_premium")]
+    MaxNetPremiumTooLow,
+
+    #[msg("Vault factory already exists with a different barrier")]
+    BarrierMismatch,
+
+    #[msg("Options have been knocked out, or have not been knocked in")]
+    BarrierVoid,
+
+    #[msg("Number of lots to buy is zero")]
+    LotsToBuyZero,
+
+    #[msg("Bid expiry must be in the future and before the vault factory freezes")]
+    InvalidBidExpiry,
+
+    #[msg("Auction floor must be at least the fair price (10000 bps) and not above the starting multiple")]
+    InvalidAuctionParams,
+
+    #[msg("Makers passed are not all the makers with lots available, in maker queue order")]
+    MakerQueueMismatch,
+
+    #[msg("Vaults with a maker queue cannot have more than MAX_QUEUED_MAKERS makers")]
+    TooManyQueuedMakers,
+
+    #[msg("Too many accounts in remaining accounts")]
+    TooManyRemainingAccounts,
+
+    #[msg("Margined vaults must be cash-settled, with a maintenance margin below the initial margin")]
+    InvalidMarginParams,
+
+    #[msg("Vault is not margined")]
+    NotMargined,
+
+    #[msg("Maker has enough margin, she cannot be liquidated")]
+    MakerNotLiquidatable,
+
+    #[msg("Makers cannot liquidate themselves")]
+    SelfLiquidation,
+
+    #[msg("Amount cannot be zero")]
+    AmountZero,
+
+    #[msg("Resting bids are only allowed in vaults where takers choose the makers")]
+    BidsNeedTakerChoice,
+
+    #[msg("Takers of margined vaults settle after every maker has settled")]
+    MakersNotSettled,
+
+    #[msg("Takers of the vault have started settling, its safety fund cannot grow anymore")]
+    TakersSettling,
+
+    #[msg("Safety fund is only returned once every maker and taker of the vault has settled")]
+    VaultNotFullySettled
+}
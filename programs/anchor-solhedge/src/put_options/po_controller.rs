@@ -6,15 +6,35 @@ use crate::{
     LAMPORTS_FOR_UPDATE_SETTLEPRICE_TICKET, 
     LAMPORTS_FOR_UPDATE_FAIRPRICE_TICKET,
     MAX_MATURITY_FUTURE_SECONDS,
-    EMERGENCY_MODE_GRACE_PERIOD,
     MAX_SECONDS_FROM_LAST_FAIR_PRICE_UPDATE,
-    PROTOCOL_TOTAL_FEES,
-    FRONTEND_SHARE
+    MAX_QUEUED_MAKERS,
+    MAX_REMAINING_MAKERS,
+    MAX_REMAINING_BIDS
 };
 use crate::MakerCreatePutOptionParams;
-use anchor_spl::token::{self, Transfer, TokenAccount};
+use anchor_spl::token_interface::{self, TransferChecked, CloseAccount, TokenAccount, Mint};
+use crate::transfer_fees::{amount_with_transfer_fee, amount_after_transfer_fee};
 use crate::anchor_solhedge::*;
-use crate::put_options::data::PutOptionMakerInfo;
+use crate::asset_pairs::ap_controller as ap;
+use crate::asset_pairs::data::{AssetPairConfig, SeriesIndex, SeriesKind};
+use crate::frontends::fe_controller::{frontend_fee_share_bps, record_frontend_trade};
+use crate::fee_tiers::ft_controller::init_taker_stats_if_needed;
+use crate::fee_tiers::data::FeeSchedule;
+use crate::native_sol::{wrap_native_sol_if_needed, unwrap_native_sol_if_needed};
+use crate::emergency_mode::is_emergency_grace_period_over;
+use crate::remaining_accounts::{
+    has_duplicate_accounts,
+    load_writable_program_account,
+    load_writable_token_account,
+    is_program_address
+};
+use crate::put_options::data::{
+    PutOptionVaultFactoryInfo,
+    PutOptionVaultInfo, PutOptionMakerInfo, PutOptionTakerInfo,
+    PutOptionTakerBidInfo, MakerAllocation
+};
+use crate::insurance_funds::data::InsuranceFundInfo;
+use crate::fee_vaults::data::FeeVaultInfo;
 
 pub fn oracle_update_put_option_settle_price(
     ctx: Context<OracleUpdatePutOptionSettlePrice>,
@@ -33,6 +53,7 @@ pub fn oracle_update_put_option_settle_price(
     if !ctx.accounts.vault_factory_info.matured {
         ctx.accounts.vault_factory_info.settled_price = settle_price;
         ctx.accounts.vault_factory_info.matured = true;
+        ap::remove_series_from_index(&mut ctx.accounts.series_index, ctx.accounts.vault_factory_info.key());
     }
 
     ctx.accounts.update_ticket.is_used = true;
@@ -59,6 +80,28 @@ pub fn oracle_update_put_option_price(
     Ok(())
 }
 
+pub fn oracle_update_put_option_price_with_spot(
+    ctx: Context<OracleUpdatePutOptionFairPrice>,
+    new_fair_price: u64,
+    spot_price: u64
+) -> Result<()> {
+    require!(
+        spot_price > 0,
+        PutOptionError::PriceZero
+    );
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    if ctx.accounts.vault_factory_info.maturity > current_time.checked_add(FREEZE_SECONDS).unwrap() {
+        ctx.accounts.vault_factory_info.last_spot_price = spot_price;
+        ctx.accounts.vault_factory_info.ts_last_spot_price = current_time;
+    }
+    // Barrier is monitored on every spot price observation up to maturity
+    if ctx.accounts.vault_factory_info.maturity > current_time {
+        ctx.accounts.vault_factory_info.observe_barrier(spot_price);
+    }
+    oracle_update_put_option_price(ctx, new_fair_price)
+}
+
 pub fn gen_settle_put_option_price_ticket(ctx: Context<GenSettlePutOptionPriceTicket>) -> Result<()> {
     require!(
         ctx.accounts.put_option_settle_price_ticket.is_used == false,
@@ -115,47 +158,22 @@ pub fn gen_update_put_option_fair_price_ticket(ctx: Context<GenUpdatePutOptionFa
 pub fn maker_next_put_option_vault_id(ctx: Context<MakerNextPutOptionVaultId>,
     params: MakerCreatePutOptionParams
 ) -> Result<u64> {
-
-    require!(
-        params.strike > 0,
-        PutOptionError::StrikeZero
-    );
-
-    let current_time = Clock::get().unwrap().unix_timestamp as u64;
-    require!(
-        params.maturity > current_time.checked_add(FREEZE_SECONDS).unwrap(),
-        PutOptionError::MaturityTooEarly
-    );
-
-    require!(
-        params.maturity <= current_time.checked_add(MAX_MATURITY_FUTURE_SECONDS).unwrap(),
-        PutOptionError::MaturityTooLate
-    );
-
-
-    // Initializing factory vault (PutOptionVaultFactoryInfo) if it has been just created
-    if !ctx.accounts.vault_factory_info.is_initialized {
-        ctx.accounts.vault_factory_info.next_vault_id = 1;
-        ctx.accounts.vault_factory_info.maturity = params.maturity;
-        ctx.accounts.vault_factory_info.matured = false;
-        ctx.accounts.vault_factory_info.strike = params.strike;
-        ctx.accounts.vault_factory_info.base_asset = ctx.accounts.base_asset_mint.key();
-        ctx.accounts.vault_factory_info.quote_asset = ctx.accounts.quote_asset_mint.key();
-        ctx.accounts.vault_factory_info.emergency_mode = false;
-
-        ctx.accounts.vault_factory_info.is_initialized = true;
-        msg!("PutOptionVaultFactoryInfo initialized");
-    }
-    let result = ctx.accounts.vault_factory_info.next_vault_id;
-    ctx.accounts.vault_factory_info.next_vault_id = ctx.accounts.vault_factory_info.next_vault_id.checked_add(1).unwrap();
-
-    Ok(result)
+    let base_asset = ctx.accounts.base_asset_mint.key();
+    let quote_asset = ctx.accounts.quote_asset_mint.key();
+    allocate_put_option_vault_id(
+        &mut ctx.accounts.vault_factory_info,
+        &ctx.accounts.asset_pair_config,
+        &mut ctx.accounts.series_index,
+        base_asset,
+        quote_asset,
+        &params
+    )
 }
 
 pub fn maker_activate_put_option_emergency_mode(ctx: Context<MakerActivatePutOptionEmergencyMode>) -> Result<()> {
     let current_time = Clock::get().unwrap().unix_timestamp as u64;
     require!(
-        current_time.checked_sub(ctx.accounts.vault_factory_info.maturity).unwrap() > EMERGENCY_MODE_GRACE_PERIOD,
+        is_emergency_grace_period_over(ctx.accounts.vault_factory_info.maturity, current_time),
         PutOptionError::EmergencyModeTooEarly
     );
     
@@ -167,8 +185,9 @@ pub fn maker_activate_put_option_emergency_mode(ctx: Context<MakerActivatePutOpt
 pub fn taker_put_option_emergency_exit(ctx: Context<TakerPutOptionEmergencyExit>) -> Result<()> {
     if ctx.accounts.put_option_taker_info.qty_deposited > 0 {
         let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_accounts = Transfer {
+        let cpi_accounts = TransferChecked {
             from: ctx.accounts.vault_base_asset_treasury.to_account_info(),
+            mint: ctx.accounts.base_asset_mint.to_account_info(),
             to: ctx.accounts.taker_base_asset_account.to_account_info(),
             authority: ctx.accounts.vault_info.to_account_info(),
         };
@@ -186,8 +205,14 @@ pub fn taker_put_option_emergency_exit(ctx: Context<TakerPutOptionEmergencyExit>
 
         let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
 
-        token::transfer(token_transfer_context, ctx.accounts.put_option_taker_info.qty_deposited)?;
+        token_interface::transfer_checked(token_transfer_context, ctx.accounts.put_option_taker_info.qty_deposited, ctx.accounts.base_asset_mint.decimals)?;
     }
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.base_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.taker_base_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
     ctx.accounts.put_option_taker_info.qty_deposited = 0;
     ctx.accounts.put_option_taker_info.is_settled = true;
 
@@ -196,8 +221,9 @@ pub fn taker_put_option_emergency_exit(ctx: Context<TakerPutOptionEmergencyExit>
 
 pub fn maker_put_option_emergency_exit(ctx: Context<MakerPutOptionEmergencyExit>) -> Result<()> {
     let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_accounts = Transfer {
+    let cpi_accounts = TransferChecked {
         from: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+        mint: ctx.accounts.quote_asset_mint.to_account_info(),
         to: ctx.accounts.maker_quote_asset_account.to_account_info(),
         authority: ctx.accounts.vault_info.to_account_info(),
     };
@@ -215,10 +241,42 @@ pub fn maker_put_option_emergency_exit(ctx: Context<MakerPutOptionEmergencyExit>
 
     let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
 
-    token::transfer(token_transfer_context, ctx.accounts.put_option_maker_info.quote_asset_qty)?;
+    // In margined vaults she gets back the margin she has posted
+    let collateral = if ctx.accounts.vault_info.is_margined() {
+        ctx.accounts.put_option_maker_info.margin_posted
+    } else {
+        ctx.accounts.put_option_maker_info.quote_asset_qty
+    };
+    token_interface::transfer_checked(token_transfer_context, collateral, ctx.accounts.quote_asset_mint.decimals)?;
+
+    if ctx.accounts.put_option_maker_info.early_exercised_base > 0 {
+        // base assets she has already bought from early exercises are hers
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_base_asset_treasury.to_account_info(),
+            mint: ctx.accounts.base_asset_mint.to_account_info(),
+            to: ctx.accounts.maker_base_asset_account.to_account_info(),
+            authority: ctx.accounts.vault_info.to_account_info(),
+        };
+        let token_transfer_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::transfer_checked(token_transfer_context, ctx.accounts.put_option_maker_info.early_exercised_base, ctx.accounts.base_asset_mint.decimals)?;
+    }
 
     ctx.accounts.put_option_maker_info.quote_asset_qty = 0;
     ctx.accounts.put_option_maker_info.volume_sold = 0;
+    ctx.accounts.put_option_maker_info.early_exercised_base = 0;
+    ctx.accounts.put_option_maker_info.margin_posted = 0;
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.maker_quote_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.base_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.maker_base_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
     ctx.accounts.put_option_maker_info.is_settled = true;
 
 
@@ -228,7 +286,7 @@ pub fn maker_put_option_emergency_exit(ctx: Context<MakerPutOptionEmergencyExit>
 pub fn taker_activate_put_option_emergency_mode(ctx: Context<TakerActivatePutOptionEmergencyMode>) -> Result<()> {
     let current_time = Clock::get().unwrap().unix_timestamp as u64;
     require!(
-        current_time.checked_sub(ctx.accounts.vault_factory_info.maturity).unwrap() > EMERGENCY_MODE_GRACE_PERIOD,
+        is_emergency_grace_period_over(ctx.accounts.vault_factory_info.maturity, current_time),
         PutOptionError::EmergencyModeTooEarly
     );
     
@@ -244,6 +302,9 @@ pub fn maker_settle_put_option(ctx: Context<MakerSettlePutOption>) -> Result<Put
         PutOptionError::IllegalState  // should not have passed maturity test, must never happen
     );
 
+    if ctx.accounts.vault_factory_info.cash_settled {
+        return maker_settle_cash_settled_put_option(ctx);
+    }
 
     let mut result = PutOptionSettleReturn {
         base_asset_transfer: 0,
@@ -251,14 +312,15 @@ pub fn maker_settle_put_option(ctx: Context<MakerSettlePutOption>) -> Result<Put
         settle_result: PutOptionSettleResult::NotExercised
     };
 
-    if ctx.accounts.vault_factory_info.settled_price > ctx.accounts.vault_factory_info.strike {
+    if ctx.accounts.vault_factory_info.settled_price > ctx.accounts.vault_factory_info.strike || ctx.accounts.vault_factory_info.is_barrier_void() {
         msg!("Put option is not favorable to taker, will NOT be exercised");
         // i.e. maker gets her deposited quote assets back
         result.settle_result = PutOptionSettleResult::NotExercised;
         // Proceed to transfer 
         let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_accounts = Transfer {
+        let cpi_accounts = TransferChecked {
             from: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
             to: ctx.accounts.maker_quote_asset_account.to_account_info(),
             authority: ctx.accounts.vault_info.to_account_info(),
         };
@@ -276,7 +338,7 @@ pub fn maker_settle_put_option(ctx: Context<MakerSettlePutOption>) -> Result<Put
 
         let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
 
-        token::transfer(token_transfer_context, ctx.accounts.put_option_maker_info.quote_asset_qty)?;
+        token_interface::transfer_checked(token_transfer_context, ctx.accounts.put_option_maker_info.quote_asset_qty, ctx.accounts.quote_asset_mint.decimals)?;
 
         result.quote_asset_transfer = ctx.accounts.put_option_maker_info.quote_asset_qty;
         result.base_asset_transfer = 0;
@@ -307,8 +369,9 @@ pub fn maker_settle_put_option(ctx: Context<MakerSettlePutOption>) -> Result<Put
             result.settle_result = PutOptionSettleResult::PartiallyExercised;
             // Proceed to transfer 
             let cpi_program = ctx.accounts.token_program.to_account_info();
-            let cpi_accounts = Transfer {
+            let cpi_accounts = TransferChecked {
                 from: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+                mint: ctx.accounts.quote_asset_mint.to_account_info(),
                 to: ctx.accounts.maker_quote_asset_account.to_account_info(),
                 authority: ctx.accounts.vault_info.to_account_info(),
             };
@@ -326,7 +389,7 @@ pub fn maker_settle_put_option(ctx: Context<MakerSettlePutOption>) -> Result<Put
 
             let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
 
-            token::transfer(token_transfer_context, transfer_quote_asset)?;
+            token_interface::transfer_checked(token_transfer_context, transfer_quote_asset, ctx.accounts.quote_asset_mint.decimals)?;
             result.quote_asset_transfer = transfer_quote_asset;
         } else {
             msg!("Maker will be fully exercised!");
@@ -341,8 +404,9 @@ pub fn maker_settle_put_option(ctx: Context<MakerSettlePutOption>) -> Result<Put
             );
             let base_lamports = base_lamports_f64.floor() as u64;
             let cpi_program = ctx.accounts.token_program.to_account_info();
-            let cpi_accounts = Transfer {
+            let cpi_accounts = TransferChecked {
                 from: ctx.accounts.vault_base_asset_treasury.to_account_info(),
+                mint: ctx.accounts.base_asset_mint.to_account_info(),
                 to: ctx.accounts.maker_base_asset_account.to_account_info(),
                 authority: ctx.accounts.vault_info.to_account_info(),
             };
@@ -360,14 +424,54 @@ pub fn maker_settle_put_option(ctx: Context<MakerSettlePutOption>) -> Result<Put
 
             let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
 
-            token::transfer(token_transfer_context, base_lamports)?;
+            token_interface::transfer_checked(token_transfer_context, base_lamports, ctx.accounts.base_asset_mint.decimals)?;
             result.base_asset_transfer = base_lamports;
                 
         }            
     }
 
+    if ctx.accounts.put_option_maker_info.early_exercised_base > 0 {
+        msg!("Maker gets the base assets she bought from early exercises");
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_base_asset_treasury.to_account_info(),
+            mint: ctx.accounts.base_asset_mint.to_account_info(),
+            to: ctx.accounts.maker_base_asset_account.to_account_info(),
+            authority: ctx.accounts.vault_info.to_account_info(),
+        };
+
+        // Preparing PDA signer
+        let auth_bump = *ctx.bumps.get("vault_info").unwrap();
+        let seeds = &[
+            "PutOptionVaultInfo".as_bytes().as_ref(), 
+            &ctx.accounts.vault_factory_info.key().to_bytes(),
+            &ctx.accounts.vault_info.ord.to_le_bytes(),
+            &[auth_bump],
+        ];
+        let signer = &[&seeds[..]];
+
+
+        let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+        token_interface::transfer_checked(token_transfer_context, ctx.accounts.put_option_maker_info.early_exercised_base, ctx.accounts.base_asset_mint.decimals)?;
+        result.base_asset_transfer = result.base_asset_transfer.checked_add(ctx.accounts.put_option_maker_info.early_exercised_base).unwrap();
+    }
+
     ctx.accounts.put_option_maker_info.quote_asset_qty = 0;
     ctx.accounts.put_option_maker_info.volume_sold = 0;
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.maker_quote_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.base_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.maker_base_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    ctx.accounts.put_option_maker_info.early_exercised_base = 0;
     ctx.accounts.put_option_maker_info.is_settled = true;
 
     Ok(result)
@@ -380,6 +484,10 @@ pub fn taker_settle_put_option(ctx: Context<TakerSettlePutOption>) -> Result<Put
         PutOptionError::IllegalState  // should not have passed maturity test, must never happen
     );
 
+    if ctx.accounts.vault_factory_info.cash_settled {
+        return taker_settle_cash_settled_put_option(ctx);
+    }
+
     let mut result = PutOptionSettleReturn {
         base_asset_transfer: 0,
         quote_asset_transfer: 0,
@@ -387,14 +495,15 @@ pub fn taker_settle_put_option(ctx: Context<TakerSettlePutOption>) -> Result<Put
     };
 
 
-    if ctx.accounts.vault_factory_info.settled_price > ctx.accounts.vault_factory_info.strike {
+    if ctx.accounts.vault_factory_info.settled_price > ctx.accounts.vault_factory_info.strike || ctx.accounts.vault_factory_info.is_barrier_void() {
         msg!("Put option is not favorable to taker, will NOT be exercised");
         // i.e. taker gets her deposited base assets back
         result.settle_result = PutOptionSettleResult::NotExercised;
         if ctx.accounts.put_option_taker_info.qty_deposited > 0 {
             let cpi_program = ctx.accounts.token_program.to_account_info();
-            let cpi_accounts = Transfer {
+            let cpi_accounts = TransferChecked {
                 from: ctx.accounts.vault_base_asset_treasury.to_account_info(),
+                mint: ctx.accounts.base_asset_mint.to_account_info(),
                 to: ctx.accounts.taker_base_asset_account.to_account_info(),
                 authority: ctx.accounts.vault_info.to_account_info(),
             };
@@ -412,7 +521,7 @@ pub fn taker_settle_put_option(ctx: Context<TakerSettlePutOption>) -> Result<Put
 
             let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
 
-            token::transfer(token_transfer_context, ctx.accounts.put_option_taker_info.qty_deposited)?;
+            token_interface::transfer_checked(token_transfer_context, ctx.accounts.put_option_taker_info.qty_deposited, ctx.accounts.base_asset_mint.decimals)?;
             result.base_asset_transfer = ctx.accounts.put_option_taker_info.qty_deposited;
             result.quote_asset_transfer = 0;
         }
@@ -431,8 +540,9 @@ pub fn taker_settle_put_option(ctx: Context<TakerSettlePutOption>) -> Result<Put
             );
             let qty_deposited_quote_lamports_value = qty_deposited_quote_lamports_value_f64.floor() as u64;
             let cpi_program = ctx.accounts.token_program.to_account_info();
-            let cpi_accounts = Transfer {
+            let cpi_accounts = TransferChecked {
                 from: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+                mint: ctx.accounts.quote_asset_mint.to_account_info(),
                 to: ctx.accounts.taker_quote_asset_account.to_account_info(),
                 authority: ctx.accounts.vault_info.to_account_info(),
             };
@@ -450,21 +560,182 @@ pub fn taker_settle_put_option(ctx: Context<TakerSettlePutOption>) -> Result<Put
 
             let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
 
-            token::transfer(token_transfer_context, qty_deposited_quote_lamports_value)?;
+            token_interface::transfer_checked(token_transfer_context, qty_deposited_quote_lamports_value, ctx.accounts.quote_asset_mint.decimals)?;
             result.base_asset_transfer = 0;
             result.quote_asset_transfer = qty_deposited_quote_lamports_value;
         }
     }
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.base_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.taker_base_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.taker_quote_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
     ctx.accounts.put_option_taker_info.qty_deposited = 0;
     ctx.accounts.put_option_taker_info.is_settled = true;
 
     Ok(result)
 }
 
+// In a cash-settled put the maker pays, from her deposited quote assets, max(strike - settled_price, 0)
+// for each base asset unit she has sold options for, and gets the rest back
+fn maker_settle_cash_settled_put_option(ctx: Context<MakerSettlePutOption>) -> Result<PutOptionSettleReturn> {
+    let mut result = PutOptionSettleReturn {
+        base_asset_transfer: 0,
+        quote_asset_transfer: 0,
+        settle_result: PutOptionSettleResult::NotExercised
+    };
+
+    let payout = compute_put_option_maker_cash_payout(&ctx.accounts.vault_factory_info, &ctx.accounts.put_option_maker_info)?;
+    if payout > 0 {
+        result.settle_result = PutOptionSettleResult::FullyExercised;
+    }
+
+    let transfer_quote_asset = if ctx.accounts.vault_info.is_margined() {
+        // Whatever her margin does not cover is taken from the safety fund of the vault
+        // and what neither covers is recorded, so takers are paid pro-rata what the vault actually holds
+        let margin_posted = ctx.accounts.put_option_maker_info.margin_posted;
+        let shortfall = payout.saturating_sub(margin_posted);
+        if shortfall > 0 {
+            ctx.accounts.vault_info.margin_shortfall = ctx.accounts.vault_info.margin_shortfall.checked_add(shortfall).unwrap();
+            let covered = ctx.accounts.vault_info.cover_margin_shortfall();
+            msg!("Margin shortfall of {} USDC lamports, {} covered by the safety fund", shortfall, covered);
+        }
+        ctx.accounts.vault_info.makers_total_payout = ctx.accounts.vault_info.makers_total_payout.checked_add(payout).unwrap();
+        ctx.accounts.vault_info.makers_settled = ctx.accounts.vault_info.makers_settled.checked_add(1).unwrap();
+        ctx.accounts.put_option_maker_info.margin_posted = 0;
+        margin_posted.saturating_sub(payout)
+    } else {
+        ctx.accounts.put_option_maker_info.quote_asset_qty.checked_sub(payout).unwrap()
+    };
+    if transfer_quote_asset > 0 {
+        // Proceed to transfer 
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.maker_quote_asset_account.to_account_info(),
+            authority: ctx.accounts.vault_info.to_account_info(),
+        };
+
+        // Preparing PDA signer
+        let auth_bump = *ctx.bumps.get("vault_info").unwrap();
+        let seeds = &[
+            "PutOptionVaultInfo".as_bytes().as_ref(), 
+            &ctx.accounts.vault_factory_info.key().to_bytes(),
+            &ctx.accounts.vault_info.ord.to_le_bytes(),
+            &[auth_bump],
+        ];
+        let signer = &[&seeds[..]];
+
+
+        let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+        token_interface::transfer_checked(token_transfer_context, transfer_quote_asset, ctx.accounts.quote_asset_mint.decimals)?;
+    }
+    result.quote_asset_transfer = transfer_quote_asset;
+
+    ctx.accounts.put_option_maker_info.quote_asset_qty = 0;
+    // Makers of margined vaults may be settled by anyone, only the maker herself can unwrap her wSOL
+    if ctx.accounts.put_option_maker_info.owner == ctx.accounts.initializer.key() {
+        unwrap_native_sol_if_needed(
+            &ctx.accounts.quote_asset_mint.key(),
+            &ctx.accounts.initializer.to_account_info(),
+            &ctx.accounts.maker_quote_asset_account.to_account_info(),
+            &ctx.accounts.token_program.to_account_info()
+        )?;
+    }
+    ctx.accounts.put_option_maker_info.volume_sold = 0;
+    ctx.accounts.put_option_maker_info.is_settled = true;
+
+    Ok(result)
+}
+
+// In a cash-settled put the taker gets max(strike - settled_price, 0) in quote asset
+// for each base asset unit she has bought options for (max_base_asset)
+fn taker_settle_cash_settled_put_option(ctx: Context<TakerSettlePutOption>) -> Result<PutOptionSettleReturn> {
+    let mut result = PutOptionSettleReturn {
+        base_asset_transfer: 0,
+        quote_asset_transfer: 0,
+        settle_result: PutOptionSettleResult::NotExercised
+    };
+
+    if ctx.accounts.vault_factory_info.settled_price < ctx.accounts.vault_factory_info.strike && !ctx.accounts.vault_factory_info.is_barrier_void() {
+        msg!("Cash-settled put option is favorable to taker, WILL be exercised");
+        let price_difference = ctx.accounts.vault_factory_info.strike.checked_sub(ctx.accounts.vault_factory_info.settled_price).unwrap();
+        let payoff_f64 = (ctx.accounts.put_option_taker_info.max_base_asset as f64) / 10.0f64.powf(ctx.accounts.base_asset_mint.decimals as f64) * (price_difference as f64);
+        require!(
+            payoff_f64.is_finite() && payoff_f64 >= 0.0,
+            PutOptionError::Overflow
+        );
+        let mut payoff = payoff_f64.floor() as u64;
+        if ctx.accounts.vault_info.is_margined() {
+            // Takers of margined vaults are paid after every maker has, pro-rata if margins fell short
+            require!(
+                ctx.accounts.vault_info.makers_settled >= ctx.accounts.vault_info.makers_num,
+                PutOptionError::MakersNotSettled
+            );
+            payoff = ctx.accounts.vault_info.margined_taker_payoff(payoff);
+        }
+        if payoff > 0 {
+            result.settle_result = PutOptionSettleResult::FullyExercised;
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+                mint: ctx.accounts.quote_asset_mint.to_account_info(),
+                to: ctx.accounts.taker_quote_asset_account.to_account_info(),
+                authority: ctx.accounts.vault_info.to_account_info(),
+            };
+
+            // Preparing PDA signer
+            let auth_bump = *ctx.bumps.get("vault_info").unwrap();
+            let seeds = &[
+                "PutOptionVaultInfo".as_bytes().as_ref(), 
+                &ctx.accounts.vault_factory_info.key().to_bytes(),
+                &ctx.accounts.vault_info.ord.to_le_bytes(),
+                &[auth_bump],
+            ];
+            let signer = &[&seeds[..]];
+    
+
+            let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+            token_interface::transfer_checked(token_transfer_context, payoff, ctx.accounts.quote_asset_mint.decimals)?;
+            result.quote_asset_transfer = payoff;
+        }
+    } else {
+        msg!("Cash-settled put option is not favorable to taker, will NOT be exercised");
+    }
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.taker_quote_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+
+    if ctx.accounts.vault_info.is_margined() {
+        ctx.accounts.vault_info.takers_settled = ctx.accounts.vault_info.takers_settled.checked_add(1).unwrap();
+    }
+    ctx.accounts.put_option_taker_info.is_settled = true;
+
+    Ok(result)
+}
+
 pub fn taker_adjust_funding_put_option_vault(ctx: Context<TakerAdjustFundingPutOptionVault>,
     new_funding: u64
 ) -> Result<u64> {
 
+    require!(
+        !ctx.accounts.vault_factory_info.cash_settled,
+        PutOptionError::CashSettledNoFunding
+    );
+
     let current_time = Clock::get().unwrap().unix_timestamp as u64;
     // Period to adjust funding is already closed
     require!(
@@ -479,16 +750,25 @@ pub fn taker_adjust_funding_put_option_vault(ctx: Context<TakerAdjustFundingPutO
         let max_increase_amount = ctx.accounts.put_option_taker_info.max_base_asset.checked_sub(ctx.accounts.put_option_taker_info.qty_deposited).unwrap();
         let increase_amount = std::cmp::min(wanted_increase_amount, max_increase_amount);
         if increase_amount > 0 {
+            wrap_native_sol_if_needed(
+                &ctx.accounts.base_asset_mint.key(),
+                &ctx.accounts.initializer.to_account_info(),
+                &ctx.accounts.taker_base_asset_account.to_account_info(),
+                increase_amount,
+                &ctx.accounts.system_program.to_account_info(),
+                &ctx.accounts.token_program.to_account_info()
+            )?;
             {
                 let cpi_program = ctx.accounts.token_program.to_account_info();
                 msg!("Started transferring base assets to increase funding for option");
-                let cpi_accounts = Transfer {
+                let cpi_accounts = TransferChecked {
                     from: ctx.accounts.taker_base_asset_account.to_account_info(),
+                    mint: ctx.accounts.base_asset_mint.to_account_info(),
                     to: ctx.accounts.vault_base_asset_treasury.to_account_info(),
                     authority: ctx.accounts.initializer.to_account_info(),
                 };
                 let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
-                token::transfer(token_transfer_context, increase_amount)?;
+                token_interface::transfer_checked(token_transfer_context, amount_with_transfer_fee(&ctx.accounts.base_asset_mint.to_account_info(), increase_amount)?, ctx.accounts.base_asset_mint.decimals)?;
                 msg!("Finished transferring base assets to increase funding for option");
             }            
             final_funding = final_funding.checked_add(increase_amount).unwrap();
@@ -500,8 +780,9 @@ pub fn taker_adjust_funding_put_option_vault(ctx: Context<TakerAdjustFundingPutO
         let decrease_amount = ctx.accounts.put_option_taker_info.qty_deposited.checked_sub(new_funding).unwrap();
         // Proceed to transfer 
         let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_accounts = Transfer {
+        let cpi_accounts = TransferChecked {
             from: ctx.accounts.vault_base_asset_treasury.to_account_info(),
+            mint: ctx.accounts.base_asset_mint.to_account_info(),
             to: ctx.accounts.taker_base_asset_account.to_account_info(),
             authority: ctx.accounts.vault_info.to_account_info(),
         };
@@ -519,7 +800,13 @@ pub fn taker_adjust_funding_put_option_vault(ctx: Context<TakerAdjustFundingPutO
 
         let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
 
-        token::transfer(token_transfer_context, decrease_amount)?;
+        token_interface::transfer_checked(token_transfer_context, decrease_amount, ctx.accounts.base_asset_mint.decimals)?;
+        unwrap_native_sol_if_needed(
+            &ctx.accounts.base_asset_mint.key(),
+            &ctx.accounts.initializer.to_account_info(),
+            &ctx.accounts.taker_base_asset_account.to_account_info(),
+            &ctx.accounts.token_program.to_account_info()
+        )?;
         final_funding = new_funding;
         ctx.accounts.put_option_taker_info.qty_deposited = new_funding;
         ctx.accounts.vault_info.takers_total_deposited = ctx.accounts.vault_info.takers_total_deposited.checked_sub(decrease_amount).unwrap();
@@ -533,203 +820,265 @@ pub fn taker_adjust_funding_put_option_vault(ctx: Context<TakerAdjustFundingPutO
     Ok(final_funding)
 }
 
-//remember, oracle should have written last fair price at most MAX_SECONDS_FROM_LAST_FAIR_PRICE_UPDATE before
-pub fn taker_buy_lots_put_option_vault<'info>(ctx: Context<'_, '_, '_, 'info, TakerBuyLotsPutOptionVault<'info>>,
-    max_fair_price: u64,
-    num_lots_to_buy: u64,
-    initial_funding: u64
-) -> Result<TakerBuyLotsPutOptionReturn> {
+//remember, oracle should have written last spot price at most MAX_SECONDS_FROM_LAST_FAIR_PRICE_UPDATE before
+pub fn taker_exercise_put_option_early<'info>(ctx: Context<'_, '_, '_, 'info, TakerExercisePutOptionEarly<'info>>,
+    max_base_asset_to_exercise: u64
+) -> Result<PutOptionEarlyExerciseReturn> {
 
-    // Must pass PutOptionMakerInfo and corresponding quote asset ATAs (to receive premium) of potential sellers
-    // in remaining accounts
     require!(
-        ctx.remaining_accounts.len() > 0,
-        PutOptionError::EmptyRemainingAccounts
+        ctx.accounts.vault_factory_info.american && !ctx.accounts.vault_factory_info.cash_settled,
+        PutOptionError::EarlyExerciseNotAllowed
     );
 
-
-    // Always in pairs, first the PutOptionMakerInfo, followed by the seller ATA
+    // Must pass the PutOptionMakerInfo of all the makers of the vault not settled yet, who buy
+    // the exercised base assets, in remaining accounts
     require!(
-        ctx.remaining_accounts.len() % 2 == 0,
-        PutOptionError::RemainingAccountsNumIsOdd
-    );      
-
+        ctx.remaining_accounts.len() > 0,
+        PutOptionError::EmptyRemainingAccounts
+    );
 
     let current_time = Clock::get().unwrap().unix_timestamp as u64;
-    // Period to take options is already closed
+    // Period to exercise early is already closed, taker must wait for settlement
     require!(
         ctx.accounts.vault_factory_info.maturity > current_time.checked_add(FREEZE_SECONDS).unwrap(),
         PutOptionError::MaturityTooEarly
     );
 
-    // If taker is entering the vault, we initialize her PutOptionTakerInfo
-    // If she already has a PutOptionTakerInfo, she is buying more put options
-    if !ctx.accounts.put_option_taker_info.is_initialized {
-        require!(
-            !ctx.accounts.vault_info.is_takers_full,
-            PutOptionError::TakersFull
-        );
-
-        ctx.accounts.vault_info.takers_num = ctx.accounts.vault_info.takers_num.checked_add(1).unwrap();
-        if ctx.accounts.vault_info.takers_num >= ctx.accounts.vault_info.max_takers {
-            ctx.accounts.vault_info.is_takers_full = true;
-        }
-
-        ctx.accounts.put_option_taker_info.ord = ctx.accounts.vault_info.takers_num;
-        ctx.accounts.put_option_taker_info.max_base_asset = 0;
-        ctx.accounts.put_option_taker_info.qty_deposited = 0;
-        ctx.accounts.put_option_taker_info.is_settled = false;
-        ctx.accounts.put_option_taker_info.owner = ctx.accounts.initializer.key();
-        ctx.accounts.put_option_taker_info.put_option_vault = ctx.accounts.vault_info.key();
-
-        ctx.accounts.put_option_taker_info.is_initialized = true;
-    }
-
-    // We cannot have a timestamp for the last fair price in the future
+    // We cannot have a timestamp for the last spot price in the future
     require!(
-        ctx.accounts.vault_factory_info.ts_last_fair_price <= current_time,
+        ctx.accounts.vault_factory_info.ts_last_spot_price <= current_time,
         PutOptionError::IllegalState
     );
 
-    // We only sell if the option price has been updated recently
-    let seconds_from_update = current_time.checked_sub(ctx.accounts.vault_factory_info.ts_last_fair_price).unwrap();
+    // We only exercise if the spot price has been updated recently
+    let seconds_from_update = current_time.checked_sub(ctx.accounts.vault_factory_info.ts_last_spot_price).unwrap();
     require!(
         seconds_from_update <= MAX_SECONDS_FROM_LAST_FAIR_PRICE_UPDATE,
-        PutOptionError::LastFairPriceUpdateTooOld
+        PutOptionError::LastSpotPriceUpdateTooOld
     );
 
-    // We won't sell if the taker is not willing to pay the current fair price
     require!(
-        max_fair_price >= ctx.accounts.vault_factory_info.last_fair_price,
-        PutOptionError::MaxFairPriceTooLow
+        ctx.accounts.vault_factory_info.last_spot_price < ctx.accounts.vault_factory_info.strike,
+        PutOptionError::EarlyExerciseNotFavorable
     );
 
-    let lot_multiplier:f64 = 10.0f64.powf(ctx.accounts.vault_info.lot_size as f64);
     require!(
-        lot_multiplier.is_finite(),
-        PutOptionError::Overflow
+        !ctx.accounts.vault_factory_info.is_barrier_void(),
+        PutOptionError::BarrierVoid
     );
 
-    // How much does one lot costs in quote asset lamports
-    let lot_price_in_quote_lamports_f64 = lot_multiplier*(ctx.accounts.vault_factory_info.strike as f64);
-    require!(
-        lot_price_in_quote_lamports_f64.is_finite(),
-        PutOptionError::Overflow
-    );
+    let wanted_base_asset = std::cmp::min(max_base_asset_to_exercise, ctx.accounts.put_option_taker_info.qty_deposited);
     require!(
-        lot_price_in_quote_lamports_f64 > 0.0,
-        PutOptionError::IllegalState
+        wanted_base_asset > 0,
+        PutOptionError::NothingToExercise
     );
 
-    // Always use integer prices
-    let lot_price_in_quote_lamports = lot_price_in_quote_lamports_f64.ceil() as u64;
+    let base_unit = 10.0f64.powf(ctx.accounts.base_asset_mint.decimals as f64);
+    let strike = ctx.accounts.vault_factory_info.strike as f64;
 
-    let mut total_lots_bought:u64 = 0;
-    for i in 0..(ctx.remaining_accounts.len()/2) {
-        let mut maker_info: Account<PutOptionMakerInfo> = PutOptionMakerInfo::from(&ctx.remaining_accounts[2*i]);
-        let maker_ata:Account<TokenAccount> = Account::try_from(&ctx.remaining_accounts[2*i + 1])?;
+    require!(
+        ctx.remaining_accounts.len() <= MAX_REMAINING_MAKERS,
+        PutOptionError::TooManyRemainingAccounts
+    );
+    require!(
+        !has_duplicate_accounts(ctx.remaining_accounts),
+        PutOptionError::AccountValidationError
+    );
 
+    // Exercises are allocated pro-rata to what each maker has sold, so the taker must pass all the
+    // makers not settled yet: what they have deposited must be all that is pending settlement
+    let mut makers: Vec<Account<PutOptionMakerInfo>> = Vec::with_capacity(ctx.remaining_accounts.len());
+    let mut capacities: Vec<u64> = Vec::with_capacity(ctx.remaining_accounts.len());
+    let mut total_quote_asset_qty:u64 = 0;
+    for maker_account in ctx.remaining_accounts.iter() {
+        let maker_info = load_put_option_maker_info(maker_account, &ctx.accounts.vault_info)?;
         require!(
-            maker_info.owner == maker_ata.owner,
+            !maker_info.is_settled,
             PutOptionError::AccountValidationError
         );
 
+        // How much base asset this maker may still buy at strike with what she has sold in options
+        let maker_capacity_f64 = (maker_info.volume_sold as f64) / strike * base_unit;
         require!(
-            maker_info.put_option_vault == ctx.accounts.vault_info.key(),
-            PutOptionError::AccountValidationError
+            maker_capacity_f64.is_finite(),
+            PutOptionError::Overflow
         );
+        total_quote_asset_qty = total_quote_asset_qty.checked_add(maker_info.quote_asset_qty).unwrap();
+        capacities.push(maker_capacity_f64.floor() as u64);
+        makers.push(maker_info);
+    }
+    require!(
+        total_quote_asset_qty == ctx.accounts.vault_info.makers_total_pending_settle,
+        PutOptionError::MakerQueueMismatch
+    );
 
-        require!(
-            maker_ata.mint == ctx.accounts.quote_asset_mint.key(),
-            PutOptionError::AccountValidationError
-        );
-        
-        let maker_avbl_quote_asset = maker_info.quote_asset_qty.checked_sub(maker_info.volume_sold).unwrap();
-        let max_lots_from_this_maker = maker_avbl_quote_asset.checked_div(lot_price_in_quote_lamports).unwrap();
-        let lots_from_this_maker = std::cmp::min(max_lots_from_this_maker, num_lots_to_buy.checked_sub(total_lots_bought).unwrap());
-        if lots_from_this_maker > 0 {
-            let reserve_amount = lots_from_this_maker.checked_mul(lot_price_in_quote_lamports).unwrap();
-            maker_info.volume_sold = maker_info.volume_sold.checked_add(reserve_amount).unwrap();
-            ctx.accounts.vault_info.makers_total_pending_sell = ctx.accounts.vault_info.makers_total_pending_sell.checked_sub(reserve_amount).unwrap();
-            let new_avbl_quote_asset = maker_info.quote_asset_qty.checked_sub(maker_info.volume_sold).unwrap();
-            let new_avbl_lots = new_avbl_quote_asset.checked_div(lot_price_in_quote_lamports).unwrap();
-            if new_avbl_lots < 1 {
-                maker_info.is_all_sold = true;
-            }
-            // Now transfer the premium to the maker and protocol
-            let premium_to_maker_f64 = (ctx.accounts.vault_factory_info.last_fair_price as f64)*lot_multiplier*(lots_from_this_maker as f64);
-            require!(
-                premium_to_maker_f64.is_finite() && premium_to_maker_f64 > 0.0,
-                PutOptionError::IllegalState
-            );
-            let mut premium_to_maker = premium_to_maker_f64.round() as u64;
-            let total_fees = premium_to_maker_f64*PROTOCOL_TOTAL_FEES;
-            let backend_share = (total_fees*(1.0 - FRONTEND_SHARE)).ceil() as u64;
-            let frontend_share = (total_fees*(FRONTEND_SHARE)).ceil() as u64;
+    let base_per_maker = allocate_put_option_lots_to_makers(MakerAllocation::ProRata, &capacities, wanted_base_asset);
+
+    let mut total_base_exercised:u64 = 0;
+    let mut total_quote_transfer:u64 = 0;
+    for (i, mut maker_info) in makers.into_iter().enumerate() {
+        let base_from_this_maker = base_per_maker[i];
+        if base_from_this_maker > 0 {
+            let quote_to_taker_f64 = (base_from_this_maker as f64) / base_unit * strike;
             require!(
-                premium_to_maker > backend_share + frontend_share,
-                PutOptionError::OptionPremiumTooLow
+                quote_to_taker_f64.is_finite(),
+                PutOptionError::Overflow
             );
-            premium_to_maker = premium_to_maker.checked_sub(backend_share).unwrap();
-            premium_to_maker = premium_to_maker.checked_sub(frontend_share).unwrap();
-
-
-
-            {
-                let cpi_program = ctx.accounts.token_program.to_account_info();
-                msg!("Started transferring premium lamports in quote asset from taker to maker");                
-                let cpi_accounts = Transfer {
-                    from: ctx.accounts.taker_quote_asset_account.to_account_info(),
-                    to: maker_ata.to_account_info(),
-                    authority: ctx.accounts.initializer.to_account_info(),
-                };
-                let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
-                token::transfer(token_transfer_context, premium_to_maker)?;
-                msg!("Finished transferring premium quote asset lamports to maker");
-            }
-
-            {
-                let cpi_program = ctx.accounts.token_program.to_account_info();
-                msg!("Started transferring backend fee lamports to protocol");
-                let cpi_accounts = Transfer {
-                    from: ctx.accounts.taker_quote_asset_account.to_account_info(),
-                    to: ctx.accounts.protocol_quote_asset_treasury.to_account_info(),
-                    authority: ctx.accounts.initializer.to_account_info(),
-                };
-                let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
-                token::transfer(token_transfer_context, backend_share)?;
-                msg!("Finished transferring backend fee lamports to protocol");
-            }
+            let quote_to_taker = std::cmp::min(quote_to_taker_f64.floor() as u64, maker_info.volume_sold);
 
-            {
-                let cpi_program = ctx.accounts.token_program.to_account_info();
-                msg!("Started transferring frontend fee lamports to protocol");
-                let cpi_accounts = Transfer {
-                    from: ctx.accounts.taker_quote_asset_account.to_account_info(),
-                    to: ctx.accounts.frontend_quote_asset_treasury.to_account_info(),
-                    authority: ctx.accounts.initializer.to_account_info(),
-                };
-                let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
-                token::transfer(token_transfer_context, frontend_share)?;
-                msg!("Finished transferring frontend fee lamports to protocol");
+            // Part of what she has sold is now exercised: she pays it now and
+            // will get the corresponding base assets at settlement
+            maker_info.volume_sold = maker_info.volume_sold.checked_sub(quote_to_taker).unwrap();
+            maker_info.quote_asset_qty = maker_info.quote_asset_qty.checked_sub(quote_to_taker).unwrap();
+            maker_info.early_exercised_base = maker_info.early_exercised_base.checked_add(base_from_this_maker).unwrap();
 
-            }
-        
-            total_lots_bought = total_lots_bought.checked_add(lots_from_this_maker).unwrap();
+            total_base_exercised = total_base_exercised.checked_add(base_from_this_maker).unwrap();
+            total_quote_transfer = total_quote_transfer.checked_add(quote_to_taker).unwrap();
             { // Serializing maker info
-                let mut data = ctx.remaining_accounts[2*i].try_borrow_mut_data()?;
+                let mut data = ctx.remaining_accounts[i].try_borrow_mut_data()?;
                 maker_info.try_serialize(&mut data.as_mut())?;    
             }
-            if total_lots_bought >= num_lots_to_buy {
-                break;
-            }
         }
     }
     require!(
-        total_lots_bought <= num_lots_to_buy,
+        total_base_exercised > 0,
+        PutOptionError::NothingToExercise
+    );
+    require!(
+        total_base_exercised <= wanted_base_asset,
         PutOptionError::IllegalState
     );
 
+    if total_quote_transfer > 0 {
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        msg!("Started transferring strike value in quote asset to taker");
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.taker_quote_asset_account.to_account_info(),
+            authority: ctx.accounts.vault_info.to_account_info(),
+        };
+
+        // Preparing PDA signer
+        let auth_bump = *ctx.bumps.get("vault_info").unwrap();
+        let seeds = &[
+            "PutOptionVaultInfo".as_bytes().as_ref(), 
+            &ctx.accounts.vault_factory_info.key().to_bytes(),
+            &ctx.accounts.vault_info.ord.to_le_bytes(),
+            &[auth_bump],
+        ];
+        let signer = &[&seeds[..]];
+
+
+        let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+        token_interface::transfer_checked(token_transfer_context, total_quote_transfer, ctx.accounts.quote_asset_mint.decimals)?;
+        msg!("Finished transferring strike value in quote asset to taker");
+        unwrap_native_sol_if_needed(
+            &ctx.accounts.quote_asset_mint.key(),
+            &ctx.accounts.initializer.to_account_info(),
+            &ctx.accounts.taker_quote_asset_account.to_account_info(),
+            &ctx.accounts.token_program.to_account_info()
+        )?;
+    }
+
+    // The exercised base assets stay in the vault, now owned by the makers, and
+    // this part of the taker's right has been consumed
+    ctx.accounts.put_option_taker_info.qty_deposited = ctx.accounts.put_option_taker_info.qty_deposited.checked_sub(total_base_exercised).unwrap();
+    ctx.accounts.put_option_taker_info.max_base_asset = ctx.accounts.put_option_taker_info.max_base_asset.checked_sub(total_base_exercised).unwrap();
+    ctx.accounts.put_option_taker_info.early_exercised_base = ctx.accounts.put_option_taker_info.early_exercised_base.checked_add(total_base_exercised).unwrap();
+    ctx.accounts.vault_info.takers_total_deposited = ctx.accounts.vault_info.takers_total_deposited.checked_sub(total_base_exercised).unwrap();
+    ctx.accounts.vault_info.makers_total_pending_settle = ctx.accounts.vault_info.makers_total_pending_settle.checked_sub(total_quote_transfer).unwrap();
+
+    let result = PutOptionEarlyExerciseReturn {
+        base_asset_exercised: total_base_exercised,
+        quote_asset_transfer: total_quote_transfer
+    };
+    Ok(result)
+}
+
+//remember, oracle should have written last fair price at most MAX_SECONDS_FROM_LAST_FAIR_PRICE_UPDATE before
+pub fn taker_buy_lots_put_option_vault<'info>(ctx: Context<'_, '_, '_, 'info, TakerBuyLotsPutOptionVault<'info>>,
+    max_fair_price: u64,
+    num_lots_to_buy: u64,
+    initial_funding: u64
+) -> Result<TakerBuyLotsPutOptionReturn> {
+
+    // Must pass PutOptionMakerInfo and corresponding quote asset ATAs (to receive premium) of potential sellers
+    // in remaining accounts
+    require!(
+        ctx.remaining_accounts.len() > 0,
+        PutOptionError::EmptyRemainingAccounts
+    );
+
+
+    // Always in pairs, first the PutOptionMakerInfo, followed by the seller ATA
+    require!(
+        ctx.remaining_accounts.len() % 2 == 0,
+        PutOptionError::RemainingAccountsNumIsOdd
+    );      
+
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    // Period to take options is already closed
+    require!(
+        ctx.accounts.vault_factory_info.maturity > current_time.checked_add(FREEZE_SECONDS).unwrap(),
+        PutOptionError::MaturityTooEarly
+    );
+
+    // If taker is entering the vault, we initialize her PutOptionTakerInfo
+    // If she already has a PutOptionTakerInfo, she is buying more put options
+    if !ctx.accounts.put_option_taker_info.is_initialized {
+        init_put_option_taker_info(&mut ctx.accounts.vault_info, &mut ctx.accounts.put_option_taker_info, ctx.accounts.initializer.key())?;
+    }
+
+    check_put_option_fair_price_is_fresh(&ctx.accounts.vault_factory_info, current_time)?;
+
+    // We won't sell if the taker is not willing to pay the current fair price (or auction price)
+    let premium_price = ctx.accounts.vault_info.premium_price(ctx.accounts.vault_factory_info.last_fair_price, current_time);
+    require!(
+        max_fair_price >= premium_price,
+        PutOptionError::MaxFairPriceTooLow
+    );
+
+    // Takers never fund cash-settled options
+    require!(
+        !ctx.accounts.vault_factory_info.cash_settled || initial_funding == 0,
+        PutOptionError::CashSettledNoFunding
+    );
+
+    let lot_multiplier:f64 = 10.0f64.powf(ctx.accounts.vault_info.lot_size as f64);
+    require!(
+        lot_multiplier.is_finite(),
+        PutOptionError::Overflow
+    );
+
+    let payer = PutOptionQuotePayer {
+        quote_asset_account: &ctx.accounts.taker_quote_asset_account,
+        quote_asset_mint: &ctx.accounts.quote_asset_mint,
+        initializer: ctx.accounts.initializer.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info()
+    };
+    // Fee tier of the taker based on her rolling volume before this purchase
+    init_taker_stats_if_needed(&mut ctx.accounts.taker_stats_info, ctx.accounts.initializer.key(), ctx.accounts.quote_asset_mint.key());
+    let fees = PutOptionFees {
+        fee_vault_quote_asset_treasury: ctx.accounts.fee_vault_quote_asset_treasury.to_account_info(),
+        frontend_fee_share_bps: frontend_fee_share_bps(&ctx.accounts.frontend_info),
+        schedule: ctx.accounts.fee_tiers_info.schedule_for(ctx.accounts.taker_stats_info.rolling_volume(current_time))
+    };
+    let (total_lots_bought, premiums_paid, backend_fees, frontend_fees) = buy_put_option_lots_from_makers(
+        &ctx.accounts.vault_factory_info, 
+        &mut ctx.accounts.vault_info, 
+        ctx.remaining_accounts, 
+        &payer, 
+        &fees,
+        num_lots_to_buy
+    )?;
+    ctx.accounts.fee_vault_info.accrue_backend_fees(backend_fees, &ctx.accounts.insurance_fund_info);
+    record_frontend_trade(&mut ctx.accounts.frontend_info, &mut ctx.accounts.fee_vault_info, premiums_paid, frontend_fees);
+    ctx.accounts.taker_stats_info.record_volume(current_time, premiums_paid);
+
     let mut base_asset_transfer_qty:u64 = 0;
     if total_lots_bought > 0 {
         let max_initial_funding_base_lamports_f64 = (total_lots_bought as f64)*lot_multiplier*(10.0f64.powf(ctx.accounts.base_asset_mint.decimals as f64));
@@ -743,16 +1092,25 @@ pub fn taker_buy_lots_put_option_vault<'info>(ctx: Context<'_, '_, '_, 'info, Ta
             let missing_funding = ctx.accounts.put_option_taker_info.max_base_asset.checked_sub(ctx.accounts.put_option_taker_info.qty_deposited).unwrap();
             base_asset_transfer_qty = std::cmp::min(initial_funding, missing_funding);
             
+            wrap_native_sol_if_needed(
+                &ctx.accounts.base_asset_mint.key(),
+                &ctx.accounts.initializer.to_account_info(),
+                &ctx.accounts.taker_base_asset_account.to_account_info(),
+                base_asset_transfer_qty,
+                &ctx.accounts.system_program.to_account_info(),
+                &ctx.accounts.token_program.to_account_info()
+            )?;
             {
                 let cpi_program = ctx.accounts.token_program.to_account_info();
                 msg!("Started transferring base assets to fund option");
-                let cpi_accounts = Transfer {
+                let cpi_accounts = TransferChecked {
                     from: ctx.accounts.taker_base_asset_account.to_account_info(),
+                    mint: ctx.accounts.base_asset_mint.to_account_info(),
                     to: ctx.accounts.vault_base_asset_treasury.to_account_info(),
                     authority: ctx.accounts.initializer.to_account_info(),
                 };
                 let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
-                token::transfer(token_transfer_context, base_asset_transfer_qty)?;
+                token_interface::transfer_checked(token_transfer_context, amount_with_transfer_fee(&ctx.accounts.base_asset_mint.to_account_info(), base_asset_transfer_qty)?, ctx.accounts.base_asset_mint.decimals)?;
                 msg!("Finished transferring base assets to fund option")
             }            
             ctx.accounts.vault_info.takers_total_deposited = ctx.accounts.vault_info.takers_total_deposited.checked_add(base_asset_transfer_qty).unwrap();
@@ -768,13 +1126,361 @@ pub fn taker_buy_lots_put_option_vault<'info>(ctx: Context<'_, '_, '_, 'info, Ta
 
     let result = TakerBuyLotsPutOptionReturn {
         num_lots_bought: total_lots_bought,
-        price: ctx.accounts.vault_factory_info.last_fair_price,
+        price: premium_price,
         funding_added: base_asset_transfer_qty
     };
     Ok(result)
 }
 
-pub fn maker_adjust_position_put_option_vault(ctx: Context<MakerAdjustPositionPutOptionVault>,     
+// Places a resting bid for num_lots lots of the vault, escrowing the premium for all of them at
+// max_fair_price. The bid is filled by makers entering or adjusting their position and by
+// crank_match_put_option_taker_bids, at the fair price of the moment
+pub fn taker_place_put_option_bid(ctx: Context<TakerPlacePutOptionBid>,
+    num_lots: u64,
+    max_fair_price: u64,
+    expiry: u64
+) -> Result<()> {
+    require!(
+        num_lots > 0,
+        PutOptionError::LotsToBuyZero
+    );
+
+    require!(
+        max_fair_price > 0,
+        PutOptionError::PriceZero
+    );
+
+    // Bids are filled by whichever maker enters or is passed to the crank, which would bypass a maker queue
+    require!(
+        ctx.accounts.vault_info.maker_allocation == MakerAllocation::TakerChoice,
+        PutOptionError::BidsNeedTakerChoice
+    );
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    let freeze_time = ctx.accounts.vault_factory_info.maturity.checked_sub(FREEZE_SECONDS).unwrap();
+    require!(
+        current_time < freeze_time,
+        PutOptionError::MaturityTooEarly
+    );
+    require!(
+        expiry > current_time && expiry <= freeze_time,
+        PutOptionError::InvalidBidExpiry
+    );
+
+    // If taker is entering the vault, we initialize her PutOptionTakerInfo
+    if !ctx.accounts.put_option_taker_info.is_initialized {
+        init_put_option_taker_info(&mut ctx.accounts.vault_info, &mut ctx.accounts.put_option_taker_info, ctx.accounts.initializer.key())?;
+    }
+
+    // Escrowing whole lamports per lot, so that any partial fill at a fair price up to
+    // max_fair_price is always covered
+    let lot_multiplier:f64 = 10.0f64.powf(ctx.accounts.vault_info.lot_size as f64);
+    let max_premium_per_lot_f64 = (max_fair_price as f64)*lot_multiplier;
+    require!(
+        max_premium_per_lot_f64.is_finite(),
+        PutOptionError::Overflow
+    );
+    let max_premium_per_lot = max_premium_per_lot_f64.ceil() as u64;
+    let escrow_amount = max_premium_per_lot.checked_mul(num_lots).ok_or(PutOptionError::Overflow)?;
+
+    wrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.taker_quote_asset_account.to_account_info(),
+        escrow_amount,
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    ctx.accounts.taker_quote_asset_account.reload()?;
+
+    require!(
+        ctx.accounts.taker_quote_asset_account.amount >= escrow_amount,
+        PutOptionError::InsufficientFunds
+    );
+
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.taker_quote_asset_account.to_account_info(),
+        mint: ctx.accounts.quote_asset_mint.to_account_info(),
+        to: ctx.accounts.bid_quote_asset_escrow.to_account_info(),
+        authority: ctx.accounts.initializer.to_account_info(),
+    };
+    let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
+    token_interface::transfer_checked(token_transfer_context, amount_with_transfer_fee(&ctx.accounts.quote_asset_mint.to_account_info(), escrow_amount)?, ctx.accounts.quote_asset_mint.decimals)?;
+    msg!("Escrowed {} USDC lamports for a bid of {} lots", escrow_amount, num_lots);
+
+    ctx.accounts.taker_bid_info.owner = ctx.accounts.initializer.key();
+    ctx.accounts.taker_bid_info.put_option_vault = ctx.accounts.vault_info.key();
+    ctx.accounts.taker_bid_info.lots_remaining = num_lots;
+    ctx.accounts.taker_bid_info.lots_filled = 0;
+    ctx.accounts.taker_bid_info.max_fair_price = max_fair_price;
+    ctx.accounts.taker_bid_info.expiry = expiry;
+    ctx.accounts.taker_bid_info.escrowed_premium = escrow_amount;
+    ctx.accounts.taker_bid_info.bump = *ctx.bumps.get("taker_bid_info").unwrap();
+
+    ctx.accounts.taker_bid_info.is_initialized = true;
+    msg!("PutOptionTakerBidInfo initialized");
+
+    Ok(())
+}
+
+// Closes a bid, filled or not, refunding the premium left in escrow. Lots already bought stay
+// in the PutOptionTakerInfo of the taker
+pub fn taker_cancel_put_option_bid(ctx: Context<TakerCancelPutOptionBid>) -> Result<u64> {
+    let refund_amount = ctx.accounts.bid_quote_asset_escrow.amount;
+
+    // Preparing PDA signer
+    let bid_owner = ctx.accounts.taker_bid_info.owner;
+    let seeds = &[
+        "PutOptionTakerBidInfo".as_bytes().as_ref(),
+        ctx.accounts.taker_bid_info.put_option_vault.as_ref(),
+        bid_owner.as_ref(),
+        &[ctx.accounts.taker_bid_info.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    if refund_amount > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.bid_quote_asset_escrow.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.taker_quote_asset_account.to_account_info(),
+            authority: ctx.accounts.taker_bid_info.to_account_info(),
+        };
+        let token_transfer_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::transfer_checked(token_transfer_context, refund_amount, ctx.accounts.quote_asset_mint.decimals)?;
+        msg!("Refunded {} USDC lamports of escrowed premium", refund_amount);
+    }
+
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.bid_quote_asset_escrow.to_account_info(),
+        destination: ctx.accounts.initializer.to_account_info(),
+        authority: ctx.accounts.taker_bid_info.to_account_info(),
+    };
+    let close_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+    token_interface::close_account(close_context)?;
+
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.taker_quote_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    msg!("Bid closed after {} lots filled", ctx.accounts.taker_bid_info.lots_filled);
+
+    Ok(refund_amount)
+}
+
+// Matches resting taker bids with makers of the vault at the current fair price. Remaining accounts
+// are num_bids triples of PutOptionTakerBidInfo, its escrow and the PutOptionTakerInfo of the bidder,
+// followed by pairs of PutOptionMakerInfo and the maker quote asset ATA (to receive premium).
+// Returns the number of lots sold
+pub fn crank_match_put_option_taker_bids<'info>(ctx: Context<'_, '_, '_, 'info, CrankMatchPutOptionTakerBids<'info>>,
+    num_bids: u8
+) -> Result<u64> {
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        ctx.accounts.vault_factory_info.maturity > current_time.checked_add(FREEZE_SECONDS).unwrap(),
+        PutOptionError::MaturityTooEarly
+    );
+    check_put_option_fair_price_is_fresh(&ctx.accounts.vault_factory_info, current_time)?;
+
+    let num_bid_accounts = 3*(num_bids as usize);
+    require!(
+        ctx.remaining_accounts.len() > num_bid_accounts,
+        PutOptionError::EmptyRemainingAccounts
+    );
+    require!(
+        (ctx.remaining_accounts.len() - num_bid_accounts) % 2 == 0,
+        PutOptionError::RemainingAccountsNumIsOdd
+    );
+    require!(
+        num_bids as usize <= MAX_REMAINING_BIDS &&
+        (ctx.remaining_accounts.len() - num_bid_accounts)/2 <= MAX_REMAINING_MAKERS,
+        PutOptionError::TooManyRemainingAccounts
+    );
+    require!(
+        !has_duplicate_accounts(ctx.remaining_accounts),
+        PutOptionError::AccountValidationError
+    );
+    let (bid_accounts, maker_accounts) = ctx.remaining_accounts.split_at(num_bid_accounts);
+
+    let fill = PutOptionBidFill {
+        quote_asset_mint: &ctx.accounts.quote_asset_mint,
+        base_asset_decimals: ctx.accounts.base_asset_mint.decimals,
+        fee_vault_quote_asset_treasury: ctx.accounts.fee_vault_quote_asset_treasury.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info()
+    };
+
+    let mut total_lots_sold:u64 = 0;
+    let mut backend_fees:u64 = 0;
+    for i in 0..(maker_accounts.len()/2) {
+        let mut maker_info = load_put_option_maker_info(&maker_accounts[2*i], &ctx.accounts.vault_info)?;
+        load_put_option_maker_quote_asset_account(&maker_accounts[2*i + 1], maker_info.owner, ctx.accounts.quote_asset_mint.key())?;
+        if maker_info.is_settled {
+            continue;
+        }
+
+        let (lots_sold, _, maker_backend_fees) = fill_put_option_taker_bids(
+            &ctx.accounts.vault_factory_info,
+            &mut ctx.accounts.vault_info,
+            &mut maker_info,
+            &maker_accounts[2*i + 1],
+            bid_accounts,
+            &fill
+        )?;
+        if lots_sold > 0 {
+            // Serializing maker info
+            let mut data = maker_accounts[2*i].try_borrow_mut_data()?;
+            maker_info.try_serialize(&mut data.as_mut())?;
+            total_lots_sold = total_lots_sold.checked_add(lots_sold).unwrap();
+        }
+        backend_fees = backend_fees.checked_add(maker_backend_fees).unwrap();
+    }
+    ctx.accounts.fee_vault_info.accrue_backend_fees(backend_fees, &ctx.accounts.insurance_fund_info);
+    msg!("Matched {} lots of resting taker bids", total_lots_sold);
+
+    Ok(total_lots_sold)
+}
+
+//remember, oracle should have written last fair prices of both legs at most MAX_SECONDS_FROM_LAST_FAIR_PRICE_UPDATE before
+// Buys num_lots lots of the higher strike put from the makers passed and sells as many lots of the lower strike
+// put to resting taker bids of its vault, entering it as a maker. Remaining accounts are num_bids triples of
+// PutOptionTakerBidInfo, its escrow and the PutOptionTakerInfo of the bidder, in the lower strike vault, followed by
+// pairs of PutOptionMakerInfo and the maker quote asset ATA (to receive premium) in the higher strike vault.
+// Both legs are filled in full or nothing happens, and the premium paid for the bought leg (fees included)
+// minus the premium received for the sold leg (net of fees) cannot be above max_net_premium
+pub fn taker_buy_put_spread<'info>(ctx: Context<'_, '_, '_, 'info, TakerBuyPutSpread<'info>>,
+    max_net_premium: u64,
+    num_lots: u64,
+    num_bids: u8
+) -> Result<TakerBuyPutSpreadReturn> {
+
+    require!(
+        num_lots > 0,
+        PutOptionError::LotsToSellZero
+    );
+
+    let num_bid_accounts = 3*(num_bids as usize);
+    require!(
+        num_bids > 0 && ctx.remaining_accounts.len() > num_bid_accounts,
+        PutOptionError::EmptyRemainingAccounts
+    );
+
+    // Makers of the higher strike leg always in pairs, first the PutOptionMakerInfo, followed by the seller ATA
+    require!(
+        (ctx.remaining_accounts.len() - num_bid_accounts) % 2 == 0,
+        PutOptionError::RemainingAccountsNumIsOdd
+    );
+    require!(
+        num_bids as usize <= MAX_REMAINING_BIDS,
+        PutOptionError::TooManyRemainingAccounts
+    );
+    require!(
+        !has_duplicate_accounts(ctx.remaining_accounts),
+        PutOptionError::AccountValidationError
+    );
+    let (bid_accounts, maker_accounts) = ctx.remaining_accounts.split_at(num_bid_accounts);
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    // Period to take options is already closed (both legs have the same maturity)
+    require!(
+        ctx.accounts.long_vault_factory_info.maturity > current_time.checked_add(FREEZE_SECONDS).unwrap(),
+        PutOptionError::MaturityTooEarly
+    );
+
+    check_put_option_fair_price_is_fresh(&ctx.accounts.long_vault_factory_info, current_time)?;
+    check_put_option_fair_price_is_fresh(&ctx.accounts.short_vault_factory_info, current_time)?;
+
+    if !ctx.accounts.put_option_taker_info.is_initialized {
+        init_put_option_taker_info(&mut ctx.accounts.long_vault_info, &mut ctx.accounts.put_option_taker_info, ctx.accounts.initializer.key())?;
+    }
+
+    let payer = PutOptionQuotePayer {
+        quote_asset_account: &ctx.accounts.taker_quote_asset_account,
+        quote_asset_mint: &ctx.accounts.quote_asset_mint,
+        initializer: ctx.accounts.initializer.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info()
+    };
+
+    // Long leg: buy the higher strike puts, charged as in taker_buy_lots_put_option_vault
+    init_taker_stats_if_needed(&mut ctx.accounts.taker_stats_info, ctx.accounts.initializer.key(), ctx.accounts.quote_asset_mint.key());
+    let fees = PutOptionFees {
+        fee_vault_quote_asset_treasury: ctx.accounts.fee_vault_quote_asset_treasury.to_account_info(),
+        frontend_fee_share_bps: frontend_fee_share_bps(&ctx.accounts.frontend_info),
+        schedule: ctx.accounts.fee_tiers_info.schedule_for(ctx.accounts.taker_stats_info.rolling_volume(current_time))
+    };
+    let (lots_bought, premiums_paid, backend_fees, frontend_fees) = buy_put_option_lots_from_makers(
+        &ctx.accounts.long_vault_factory_info, 
+        &mut ctx.accounts.long_vault_info, 
+        maker_accounts, 
+        &payer, 
+        &fees,
+        num_lots
+    )?;
+    require!(
+        lots_bought == num_lots,
+        PutOptionError::SpreadNotFilled
+    );
+    ctx.accounts.fee_vault_info.accrue_backend_fees(backend_fees, &ctx.accounts.insurance_fund_info);
+    record_frontend_trade(&mut ctx.accounts.frontend_info, &mut ctx.accounts.fee_vault_info, premiums_paid, frontend_fees);
+    ctx.accounts.taker_stats_info.record_volume(current_time, premiums_paid);
+
+    let lot_multiplier:f64 = 10.0f64.powf(ctx.accounts.long_vault_info.lot_size as f64);
+    let max_base_asset_f64 = (lots_bought as f64)*lot_multiplier*(10.0f64.powf(ctx.accounts.base_asset_mint.decimals as f64));
+    require!(
+        max_base_asset_f64.is_finite(),
+        PutOptionError::Overflow
+    );  
+    ctx.accounts.put_option_taker_info.max_base_asset = ctx.accounts.put_option_taker_info.max_base_asset.checked_add(max_base_asset_f64.ceil() as u64).unwrap();
+
+    // Short leg: she enters the lower strike vault as a maker with the same number of lots,
+    // all of them sold right away to the resting bids passed
+    enter_put_option_vault_as_maker(
+        &ctx.accounts.short_vault_factory_info,
+        &mut ctx.accounts.short_vault_info,
+        &mut ctx.accounts.put_option_maker_info,
+        ctx.accounts.short_vault_quote_asset_treasury.to_account_info(),
+        &payer,
+        num_lots,
+        0
+    )?;
+    let fill = PutOptionBidFill {
+        quote_asset_mint: &ctx.accounts.quote_asset_mint,
+        base_asset_decimals: ctx.accounts.base_asset_mint.decimals,
+        fee_vault_quote_asset_treasury: ctx.accounts.fee_vault_quote_asset_treasury.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info()
+    };
+    let (lots_sold, premiums_received, short_backend_fees) = fill_put_option_taker_bids(
+        &ctx.accounts.short_vault_factory_info,
+        &mut ctx.accounts.short_vault_info,
+        &mut ctx.accounts.put_option_maker_info,
+        &ctx.accounts.taker_quote_asset_account.to_account_info(),
+        bid_accounts,
+        &fill
+    )?;
+    require!(
+        lots_sold == num_lots,
+        PutOptionError::SpreadNotFilled
+    );
+    ctx.accounts.fee_vault_info.accrue_backend_fees(short_backend_fees, &ctx.accounts.insurance_fund_info);
+
+    let net_premium = premiums_paid.saturating_sub(premiums_received);
+    require!(
+        net_premium <= max_net_premium,
+        PutOptionError::MaxNetPremiumTooLow
+    );
+    msg!("Bought a put spread of {} lots for a net premium of {} USDC lamports", num_lots, net_premium);
+
+    let result = TakerBuyPutSpreadReturn {
+        num_lots,
+        long_premium: premiums_paid,
+        short_premium: premiums_received
+    };
+    Ok(result)
+}
+
+pub fn maker_adjust_position_put_option_vault<'info>(ctx: Context<'_, '_, '_, 'info, MakerAdjustPositionPutOptionVault<'info>>,     
     num_lots_to_sell: u64,
     premium_limit: u64
 ) -> Result<()> {
@@ -811,23 +1517,39 @@ pub fn maker_adjust_position_put_option_vault(ctx: Context<MakerAdjustPositionPu
         // Maker wants to increase her position in the vault
 
         let increase_amount = wanted_amount.checked_sub(ctx.accounts.put_option_maker_info.quote_asset_qty).unwrap();
+        let deposit_amount = ctx.accounts.vault_info.margin_for(increase_amount);
+        wrap_native_sol_if_needed(
+            &ctx.accounts.quote_asset_mint.key(),
+            &ctx.accounts.initializer.to_account_info(),
+            &ctx.accounts.maker_quote_asset_account.to_account_info(),
+            deposit_amount,
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.token_program.to_account_info()
+        )?;
         // Proceed to transfer 
         let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_accounts = Transfer {
+        let cpi_accounts = TransferChecked {
             from: ctx.accounts.maker_quote_asset_account.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
             to: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
             authority: ctx.accounts.initializer.to_account_info(),
         };
         let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
 
-        token::transfer(token_transfer_context, increase_amount)?;
-        msg!("Transferred {} USDC lamports to quote asset treasury", increase_amount);
+        token_interface::transfer_checked(token_transfer_context, amount_with_transfer_fee(&ctx.accounts.quote_asset_mint.to_account_info(), deposit_amount)?, ctx.accounts.quote_asset_mint.decimals)?;
+        msg!("Transferred {} USDC lamports to quote asset treasury", deposit_amount);
+        if ctx.accounts.vault_info.is_margined() {
+            ctx.accounts.put_option_maker_info.margin_posted = ctx.accounts.put_option_maker_info.margin_posted.checked_add(deposit_amount).unwrap();
+        }
         ctx.accounts.put_option_maker_info.quote_asset_qty = ctx.accounts.put_option_maker_info.quote_asset_qty.checked_add(increase_amount).unwrap();
         require!(
             ctx.accounts.put_option_maker_info.quote_asset_qty.checked_sub(ctx.accounts.put_option_maker_info.volume_sold).unwrap() >= rounded_lot_value,
             PutOptionError::IllegalState
         );
         ctx.accounts.put_option_maker_info.is_all_sold = false;
+        // Lots added go to the back of the maker queue, together with those she had left
+        ctx.accounts.put_option_maker_info.queue_seq = ctx.accounts.vault_info.take_maker_queue_seq();
+        ctx.accounts.put_option_maker_info.resting_since = current_time;
         ctx.accounts.vault_info.makers_total_pending_sell = ctx.accounts.vault_info.makers_total_pending_sell.checked_add(increase_amount).unwrap();
         ctx.accounts.vault_info.makers_total_pending_settle = ctx.accounts.vault_info.makers_total_pending_settle.checked_add(increase_amount).unwrap();
 
@@ -839,10 +1561,17 @@ pub fn maker_adjust_position_put_option_vault(ctx: Context<MakerAdjustPositionPu
             decrease_amount <= max_decrease,
             PutOptionError::OversizedDecrease
         );
+        // In margined vaults she gets back what she has posted beyond the margin her new position requires
+        let withdraw_amount = if ctx.accounts.vault_info.is_margined() {
+            compute_put_option_maker_margin_release(&ctx.accounts.vault_factory_info, &ctx.accounts.vault_info, &ctx.accounts.put_option_maker_info, wanted_amount)?
+        } else {
+            decrease_amount
+        };
         // Proceed to transfer 
         let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_accounts = Transfer {
+        let cpi_accounts = TransferChecked {
             from: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
             to: ctx.accounts.maker_quote_asset_account.to_account_info(),
             authority: ctx.accounts.vault_info.to_account_info(),
         };
@@ -860,8 +1589,17 @@ pub fn maker_adjust_position_put_option_vault(ctx: Context<MakerAdjustPositionPu
 
         let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
 
-        token::transfer(token_transfer_context, decrease_amount)?;
-        msg!("Transferred {} USDC lamports from quote asset treasury to user", decrease_amount);
+        token_interface::transfer_checked(token_transfer_context, withdraw_amount, ctx.accounts.quote_asset_mint.decimals)?;
+        msg!("Transferred {} USDC lamports from quote asset treasury to user", withdraw_amount);
+        if ctx.accounts.vault_info.is_margined() {
+            ctx.accounts.put_option_maker_info.margin_posted = ctx.accounts.put_option_maker_info.margin_posted.checked_sub(withdraw_amount).unwrap();
+        }
+        unwrap_native_sol_if_needed(
+            &ctx.accounts.quote_asset_mint.key(),
+            &ctx.accounts.initializer.to_account_info(),
+            &ctx.accounts.maker_quote_asset_account.to_account_info(),
+            &ctx.accounts.token_program.to_account_info()
+        )?;
         ctx.accounts.put_option_maker_info.quote_asset_qty = ctx.accounts.put_option_maker_info.quote_asset_qty.checked_sub(decrease_amount).unwrap();
         ctx.accounts.put_option_maker_info.is_all_sold = ctx.accounts.put_option_maker_info.quote_asset_qty.checked_sub(ctx.accounts.put_option_maker_info.volume_sold).unwrap() < rounded_lot_value;
         ctx.accounts.vault_info.makers_total_pending_sell = ctx.accounts.vault_info.makers_total_pending_sell.checked_sub(decrease_amount).unwrap();
@@ -876,12 +1614,111 @@ pub fn maker_adjust_position_put_option_vault(ctx: Context<MakerAdjustPositionPu
         PutOptionError::IllegalState
     );
 
+    // Optionally filling resting taker bids with the lots just offered
+    if !ctx.remaining_accounts.is_empty() {
+        let (mut fee_vault_info, insurance_fund_info, bid_accounts) = split_put_option_bid_fill_accounts(ctx.remaining_accounts, ctx.accounts.quote_asset_mint.key())?;
+        check_put_option_fair_price_is_fresh(&ctx.accounts.vault_factory_info, current_time)?;
+        let fill = PutOptionBidFill {
+            quote_asset_mint: &ctx.accounts.quote_asset_mint,
+            base_asset_decimals: ctx.accounts.base_asset_mint.decimals,
+            fee_vault_quote_asset_treasury: ctx.remaining_accounts[1].clone(),
+            token_program: ctx.accounts.token_program.to_account_info()
+        };
+        let (lots_sold, _, backend_fees) = fill_put_option_taker_bids(
+            &ctx.accounts.vault_factory_info,
+            &mut ctx.accounts.vault_info,
+            &mut ctx.accounts.put_option_maker_info,
+            &ctx.accounts.maker_quote_asset_account.to_account_info(),
+            bid_accounts,
+            &fill
+        )?;
+        accrue_put_option_bid_fill_fees(&mut fee_vault_info, &ctx.remaining_accounts[0], &insurance_fund_info, backend_fees)?;
+        msg!("Sold {} lots to resting taker bids", lots_sold);
+    }
+
 
     Ok(())
 
 }
 
-pub fn maker_enter_put_option_vault(ctx: Context<MakerEnterPutOptionVault>,     
+pub fn maker_exit_put_option_vault(ctx: Context<MakerExitPutOptionVault>) -> Result<u64> {
+
+    msg!("Entered maker_exit_put_option_vault");
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        ctx.accounts.vault_factory_info.maturity > current_time.checked_add(FREEZE_SECONDS).unwrap(),
+        PutOptionError::MaturityTooEarly
+    );
+
+    require!(
+        ctx.accounts.put_option_maker_info.quote_asset_qty >= ctx.accounts.put_option_maker_info.volume_sold,
+        PutOptionError::IllegalState
+    );
+
+    // Maker gets back everything that has not been sold yet
+    let withdraw_amount = ctx.accounts.put_option_maker_info.quote_asset_qty.checked_sub(ctx.accounts.put_option_maker_info.volume_sold).unwrap();
+    // In margined vaults she keeps posted the margin of what she has sold
+    let transfer_amount = if ctx.accounts.vault_info.is_margined() {
+        compute_put_option_maker_margin_release(&ctx.accounts.vault_factory_info, &ctx.accounts.vault_info, &ctx.accounts.put_option_maker_info, ctx.accounts.put_option_maker_info.volume_sold)?
+    } else {
+        withdraw_amount
+    };
+    if transfer_amount > 0 {
+        // Proceed to transfer 
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.maker_quote_asset_account.to_account_info(),
+            authority: ctx.accounts.vault_info.to_account_info(),
+        };
+
+        // Preparing PDA signer
+        let auth_bump = *ctx.bumps.get("vault_info").unwrap();
+        let seeds = &[
+            "PutOptionVaultInfo".as_bytes().as_ref(), 
+            &ctx.accounts.vault_factory_info.key().to_bytes(),
+            &ctx.accounts.vault_info.ord.to_le_bytes(),
+            &[auth_bump],
+        ];
+        let signer = &[&seeds[..]];
+
+
+        let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+        token_interface::transfer_checked(token_transfer_context, transfer_amount, ctx.accounts.quote_asset_mint.decimals)?;
+        msg!("Transferred {} USDC lamports from quote asset treasury to user", transfer_amount);
+        unwrap_native_sol_if_needed(
+            &ctx.accounts.quote_asset_mint.key(),
+            &ctx.accounts.initializer.to_account_info(),
+            &ctx.accounts.maker_quote_asset_account.to_account_info(),
+            &ctx.accounts.token_program.to_account_info()
+        )?;
+        if ctx.accounts.vault_info.is_margined() {
+            ctx.accounts.put_option_maker_info.margin_posted = ctx.accounts.put_option_maker_info.margin_posted.checked_sub(transfer_amount).unwrap();
+        }
+    }
+    // Her unsold notional leaves the vault even when her margin must stay posted
+    ctx.accounts.put_option_maker_info.quote_asset_qty = ctx.accounts.put_option_maker_info.quote_asset_qty.checked_sub(withdraw_amount).unwrap();
+    ctx.accounts.vault_info.makers_total_pending_sell = ctx.accounts.vault_info.makers_total_pending_sell.checked_sub(withdraw_amount).unwrap();
+    ctx.accounts.vault_info.makers_total_pending_settle = ctx.accounts.vault_info.makers_total_pending_settle.checked_sub(withdraw_amount).unwrap();
+    ctx.accounts.put_option_maker_info.is_all_sold = true;
+
+    if ctx.accounts.put_option_maker_info.volume_sold == 0 && ctx.accounts.put_option_maker_info.early_exercised_base == 0 {
+        // Nothing has been sold, so the maker has no commitments in this vault:
+        // we free her slot and give her back the rent of her PutOptionMakerInfo
+        ctx.accounts.vault_info.makers_num = ctx.accounts.vault_info.makers_num.checked_sub(1).unwrap();
+        ctx.accounts.vault_info.is_makers_full = ctx.accounts.vault_info.makers_num >= ctx.accounts.vault_info.max_makers;
+        ctx.accounts.put_option_maker_info.close(ctx.accounts.initializer.to_account_info())?;
+        msg!("Maker has left the vault, PutOptionMakerInfo closed");
+    } else {
+        msg!("Maker still has {} USDC lamports sold in options, she will stay until settlement", ctx.accounts.put_option_maker_info.volume_sold);
+    }
+
+    Ok(withdraw_amount)
+}
+
+pub fn maker_enter_put_option_vault<'info>(ctx: Context<'_, '_, '_, 'info, MakerEnterPutOptionVault<'info>>,     
     num_lots_to_sell: u64,
     premium_limit: u64
 ) -> Result<()> {
@@ -897,76 +1734,398 @@ pub fn maker_enter_put_option_vault(ctx: Context<MakerEnterPutOptionVault>,
         PutOptionError::MaturityTooEarly
     );
 
-    ctx.accounts.vault_info.makers_num = ctx.accounts.vault_info.makers_num.checked_add(1).unwrap();
-
-    if ctx.accounts.vault_info.makers_num >= ctx.accounts.vault_info.max_makers {
-        ctx.accounts.vault_info.is_makers_full = true;
+    let payer = PutOptionQuotePayer {
+        quote_asset_account: &ctx.accounts.maker_quote_asset_account,
+        quote_asset_mint: &ctx.accounts.quote_asset_mint,
+        initializer: ctx.accounts.initializer.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info()
+    };
+    enter_put_option_vault_as_maker(
+        &ctx.accounts.vault_factory_info,
+        &mut ctx.accounts.vault_info,
+        &mut ctx.accounts.put_option_maker_info,
+        ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+        &payer,
+        num_lots_to_sell,
+        premium_limit
+    )?;
+
+    // Optionally filling resting taker bids with the lots just offered
+    if !ctx.remaining_accounts.is_empty() {
+        let (mut fee_vault_info, insurance_fund_info, bid_accounts) = split_put_option_bid_fill_accounts(ctx.remaining_accounts, ctx.accounts.quote_asset_mint.key())?;
+        check_put_option_fair_price_is_fresh(&ctx.accounts.vault_factory_info, current_time)?;
+        let fill = PutOptionBidFill {
+            quote_asset_mint: &ctx.accounts.quote_asset_mint,
+            base_asset_decimals: ctx.accounts.base_asset_mint.decimals,
+            fee_vault_quote_asset_treasury: ctx.remaining_accounts[1].clone(),
+            token_program: ctx.accounts.token_program.to_account_info()
+        };
+        let (lots_sold, _, backend_fees) = fill_put_option_taker_bids(
+            &ctx.accounts.vault_factory_info,
+            &mut ctx.accounts.vault_info,
+            &mut ctx.accounts.put_option_maker_info,
+            &ctx.accounts.maker_quote_asset_account.to_account_info(),
+            bid_accounts,
+            &fill
+        )?;
+        accrue_put_option_bid_fill_fees(&mut fee_vault_info, &ctx.remaining_accounts[0], &insurance_fund_info, backend_fees)?;
+        msg!("Sold {} lots to resting taker bids", lots_sold);
     }
 
-    // Proceed to transfer 
+    Ok(())
+}
+
+pub fn maker_add_put_option_margin(ctx: Context<MakerAddPutOptionMargin>, amount: u64) -> Result<()> {
+
+    msg!("Entered maker_add_put_option_margin");
+    require!(
+        ctx.accounts.vault_info.is_margined(),
+        PutOptionError::NotMargined
+    );
+    require!(
+        amount > 0,
+        PutOptionError::AmountZero
+    );
+
+    wrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.maker_quote_asset_account.to_account_info(),
+        amount,
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    // Proceed to transfer 
     let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_accounts = Transfer {
+    let cpi_accounts = TransferChecked {
         from: ctx.accounts.maker_quote_asset_account.to_account_info(),
+        mint: ctx.accounts.quote_asset_mint.to_account_info(),
         to: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
         authority: ctx.accounts.initializer.to_account_info(),
     };
     let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
 
-    let lot_multiplier:f64 = 10.0f64.powf(ctx.accounts.vault_info.lot_size as f64);
-    msg!("num_lots_to_sell: {}", num_lots_to_sell);
-    msg!("lot_multiplier: {}", lot_multiplier);
-    msg!("strike: {}", ctx.accounts.vault_factory_info.strike);
-    let lot_value = lot_multiplier*(ctx.accounts.vault_factory_info.strike as f64);
-    let rounded_lot_value = lot_value.ceil() as u64;
+    token_interface::transfer_checked(token_transfer_context, amount_with_transfer_fee(&ctx.accounts.quote_asset_mint.to_account_info(), amount)?, ctx.accounts.quote_asset_mint.decimals)?;
+    ctx.accounts.put_option_maker_info.margin_posted = ctx.accounts.put_option_maker_info.margin_posted.checked_add(amount).unwrap();
+    msg!("Maker added {} USDC lamports of margin, {} posted", amount, ctx.accounts.put_option_maker_info.margin_posted);
 
-    let transfer_amount_f64 = (num_lots_to_sell as f64)*lot_value;
+    Ok(())
+}
+
+pub fn fund_put_option_vault_safety_fund(ctx: Context<FundPutOptionSafetyFund>, amount: u64) -> Result<()> {
+
+    msg!("Entered fund_put_option_vault_safety_fund");
     require!(
-        transfer_amount_f64.is_finite(),
-        PutOptionError::Overflow
+        ctx.accounts.vault_info.is_margined(),
+        PutOptionError::NotMargined
     );
+    // Takers already paid could not get their share of it
     require!(
-        transfer_amount_f64 >= 0.0,
+        ctx.accounts.vault_info.takers_settled == 0,
+        PutOptionError::TakersSettling
+    );
+    require!(
+        amount > 0,
+        PutOptionError::AmountZero
+    );
+
+    wrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.funder_quote_asset_account.to_account_info(),
+        amount,
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    // Proceed to transfer 
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.funder_quote_asset_account.to_account_info(),
+        mint: ctx.accounts.quote_asset_mint.to_account_info(),
+        to: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+        authority: ctx.accounts.initializer.to_account_info(),
+    };
+    let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
+
+    token_interface::transfer_checked(token_transfer_context, amount_with_transfer_fee(&ctx.accounts.quote_asset_mint.to_account_info(), amount)?, ctx.accounts.quote_asset_mint.decimals)?;
+    ctx.accounts.vault_info.safety_fund = ctx.accounts.vault_info.safety_fund.checked_add(amount).unwrap();
+    // Makers that have already settled may have left a shortfall this covers
+    let covered = ctx.accounts.vault_info.cover_margin_shortfall();
+    msg!("Safety fund of the vault now holds {} USDC lamports, {} went to the margin shortfall", ctx.accounts.vault_info.safety_fund, covered);
+
+    Ok(())
+}
+
+// Once every maker and taker of a margined vault has settled, sends what is left of its safety fund
+// to the insurance fund of the quote asset, which is where draws and most of it come from.
+// Returns the amount returned
+pub fn crank_return_put_option_safety_fund(ctx: Context<CrankReturnPutOptionSafetyFund>) -> Result<u64> {
+    require!(
+        ctx.accounts.vault_info.is_margined(),
+        PutOptionError::NotMargined
+    );
+    require!(
+        ctx.accounts.vault_info.is_fully_settled(),
+        PutOptionError::VaultNotFullySettled
+    );
+
+    let amount = ctx.accounts.vault_info.safety_fund;
+    if amount > 0 {
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.insurance_fund_quote_asset_treasury.to_account_info(),
+            authority: ctx.accounts.vault_info.to_account_info(),
+        };
+
+        // Preparing PDA signer
+        let auth_bump = *ctx.bumps.get("vault_info").unwrap();
+        let seeds = &[
+            "PutOptionVaultInfo".as_bytes().as_ref(), 
+            &ctx.accounts.vault_factory_info.key().to_bytes(),
+            &ctx.accounts.vault_info.ord.to_le_bytes(),
+            &[auth_bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token_interface::transfer_checked(token_transfer_context, amount, ctx.accounts.quote_asset_mint.decimals)?;
+        ctx.accounts.vault_info.safety_fund = 0;
+    }
+    msg!("Returned {} USDC lamports of safety fund to the insurance fund", amount);
+
+    Ok(amount)
+}
+
+// Liquidator takes over, fully collateralized, the options sold by an undermargined maker.
+// She is paid the liability of the position plus the liquidation bonus, first out of the
+// margin of the maker and then out of the safety fund of the vault. Returns what she was paid.
+pub fn liquidate_put_option_maker(ctx: Context<LiquidatePutOptionMaker>) -> Result<u64> {
+
+    msg!("Entered liquidate_put_option_maker");
+    require!(
+        ctx.accounts.vault_info.is_margined(),
+        PutOptionError::NotMargined
+    );
+    require!(
+        ctx.accounts.put_option_maker_info.owner != ctx.accounts.initializer.key(),
+        PutOptionError::SelfLiquidation
+    );
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        ctx.accounts.vault_factory_info.maturity > current_time,
+        PutOptionError::MaturityTooEarly
+    );
+    check_put_option_fair_price_is_fresh(&ctx.accounts.vault_factory_info, current_time)?;
+
+    // We cannot have a timestamp for the last spot price in the future
+    require!(
+        ctx.accounts.vault_factory_info.ts_last_spot_price <= current_time,
         PutOptionError::IllegalState
     );
-    
-    let transfer_amount = transfer_amount_f64.ceil() as u64;
+    // We only liquidate if the spot price has been updated recently
+    let seconds_from_update = current_time.checked_sub(ctx.accounts.vault_factory_info.ts_last_spot_price).unwrap();
+    require!(
+        seconds_from_update <= MAX_SECONDS_FROM_LAST_FAIR_PRICE_UPDATE,
+        PutOptionError::LastSpotPriceUpdateTooOld
+    );
 
+    let volume_sold = ctx.accounts.put_option_maker_info.volume_sold;
+    let margin_posted = ctx.accounts.put_option_maker_info.margin_posted;
+    let (liability, requirement) = compute_put_option_maker_margin_requirement(
+        &ctx.accounts.vault_factory_info,
+        &ctx.accounts.vault_info,
+        &ctx.accounts.put_option_maker_info
+    )?;
+    msg!("Maker has {} USDC lamports of margin posted, {} required", margin_posted, requirement);
     require!(
-        ctx.accounts.maker_quote_asset_account.amount >= transfer_amount,
-        PutOptionError::InsufficientFunds
+        volume_sold > 0 && margin_posted < requirement,
+        PutOptionError::MakerNotLiquidatable
     );
 
+    let compensation_f64 = (liability as f64)*(10000.0 + ctx.accounts.vault_info.liquidation_bonus_bps as f64)/10000.0;
+    require!(
+        compensation_f64.is_finite(),
+        PutOptionError::Overflow
+    );
+    let compensation = compensation_f64.ceil() as u64;
+    let from_margin = compensation.min(margin_posted);
+    let from_safety_fund = compensation.checked_sub(from_margin).unwrap().min(ctx.accounts.vault_info.safety_fund);
+    let paid = from_margin.checked_add(from_safety_fund).unwrap();
+    if paid < compensation {
+        msg!("Safety fund could only cover {} of the {} USDC lamports owed to the liquidator", from_safety_fund, compensation.checked_sub(from_margin).unwrap());
+    }
 
-    token::transfer(token_transfer_context, transfer_amount)?;
-    msg!("Transferred {} USDC lamports to quote asset treasury", transfer_amount);
+    // Liquidator must post the whole volume sold as collateral, and receives what she is paid:
+    // only the net amount moves
+    if volume_sold > paid {
+        let deposit_amount = volume_sold.checked_sub(paid).unwrap();
+        wrap_native_sol_if_needed(
+            &ctx.accounts.quote_asset_mint.key(),
+            &ctx.accounts.initializer.to_account_info(),
+            &ctx.accounts.liquidator_quote_asset_account.to_account_info(),
+            deposit_amount,
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.token_program.to_account_info()
+        )?;
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.liquidator_quote_asset_account.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+            authority: ctx.accounts.initializer.to_account_info(),
+        };
+        let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
 
-    // Updating vault_info ...
-    ctx.accounts.vault_info.makers_total_pending_sell = ctx.accounts.vault_info.makers_total_pending_sell.checked_add(transfer_amount).unwrap();
-    ctx.accounts.vault_info.makers_total_pending_settle = ctx.accounts.vault_info.makers_total_pending_settle.checked_add(transfer_amount).unwrap();
-    msg!("Finished initialization of PutOptionVaultInfo, now initializing PutOptionMakerInfo");
+        token_interface::transfer_checked(token_transfer_context, amount_with_transfer_fee(&ctx.accounts.quote_asset_mint.to_account_info(), deposit_amount)?, ctx.accounts.quote_asset_mint.decimals)?;
+        msg!("Transferred {} USDC lamports from liquidator to quote asset treasury", deposit_amount);
+    } else if paid > volume_sold {
+        let withdraw_amount = paid.checked_sub(volume_sold).unwrap();
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.liquidator_quote_asset_account.to_account_info(),
+            authority: ctx.accounts.vault_info.to_account_info(),
+        };
 
-    // Now initializing info about this maker in the vault (PutOptionMakerInfo)
-    ctx.accounts.put_option_maker_info.ord = ctx.accounts.vault_info.makers_num;
-    ctx.accounts.put_option_maker_info.quote_asset_qty = transfer_amount;
+        // Preparing PDA signer
+        let auth_bump = *ctx.bumps.get("vault_info").unwrap();
+        let seeds = &[
+            "PutOptionVaultInfo".as_bytes().as_ref(), 
+            &ctx.accounts.vault_factory_info.key().to_bytes(),
+            &ctx.accounts.vault_info.ord.to_le_bytes(),
+            &[auth_bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+        token_interface::transfer_checked(token_transfer_context, withdraw_amount, ctx.accounts.quote_asset_mint.decimals)?;
+        msg!("Transferred {} USDC lamports from quote asset treasury to liquidator", withdraw_amount);
+        unwrap_native_sol_if_needed(
+            &ctx.accounts.quote_asset_mint.key(),
+            &ctx.accounts.initializer.to_account_info(),
+            &ctx.accounts.liquidator_quote_asset_account.to_account_info(),
+            &ctx.accounts.token_program.to_account_info()
+        )?;
+    }
+
+    // Whatever the liquidated maker had not sold yet is no longer offered
+    let unsold_amount = ctx.accounts.put_option_maker_info.quote_asset_qty.checked_sub(volume_sold).unwrap();
+    ctx.accounts.vault_info.makers_total_pending_sell = ctx.accounts.vault_info.makers_total_pending_sell.checked_sub(unsold_amount).unwrap();
+    ctx.accounts.vault_info.makers_total_pending_settle = ctx.accounts.vault_info.makers_total_pending_settle.checked_sub(unsold_amount).unwrap();
+    ctx.accounts.vault_info.safety_fund = ctx.accounts.vault_info.safety_fund.checked_sub(from_safety_fund).unwrap();
+
+    // Remaining margin, if any, can be taken back by the liquidated maker on exit or settlement
+    ctx.accounts.put_option_maker_info.quote_asset_qty = 0;
     ctx.accounts.put_option_maker_info.volume_sold = 0;
-    ctx.accounts.put_option_maker_info.is_all_sold = false;
+    ctx.accounts.put_option_maker_info.is_all_sold = true;
+    ctx.accounts.put_option_maker_info.margin_posted = margin_posted.checked_sub(from_margin).unwrap();
+
+    if ctx.accounts.liquidator_maker_info.put_option_vault != ctx.accounts.vault_info.key() {
+        // Liquidator was not a maker in this vault yet
+        ctx.accounts.vault_info.makers_num = ctx.accounts.vault_info.makers_num.checked_add(1).unwrap();
+        ctx.accounts.vault_info.is_makers_full = ctx.accounts.vault_info.makers_num >= ctx.accounts.vault_info.max_makers;
+        ctx.accounts.liquidator_maker_info.ord = ctx.accounts.vault_info.take_maker_ord();
+        ctx.accounts.liquidator_maker_info.quote_asset_qty = 0;
+        ctx.accounts.liquidator_maker_info.volume_sold = 0;
+        ctx.accounts.liquidator_maker_info.is_settled = false;
+        ctx.accounts.liquidator_maker_info.is_all_sold = true;
+        ctx.accounts.liquidator_maker_info.premium_limit = 0;
+        ctx.accounts.liquidator_maker_info.owner = ctx.accounts.initializer.key();
+        ctx.accounts.liquidator_maker_info.put_option_vault = ctx.accounts.vault_info.key();
+        ctx.accounts.liquidator_maker_info.early_exercised_base = 0;
+        ctx.accounts.liquidator_maker_info.queue_seq = ctx.accounts.vault_info.take_maker_queue_seq();
+        ctx.accounts.liquidator_maker_info.resting_since = current_time;
+        ctx.accounts.liquidator_maker_info.margin_posted = 0;
+    }
     require!(
-        ctx.accounts.put_option_maker_info.quote_asset_qty >= rounded_lot_value,
+        ctx.accounts.liquidator_maker_info.is_settled == false,
         PutOptionError::IllegalState
     );
-    ctx.accounts.put_option_maker_info.is_settled = false;
-    ctx.accounts.put_option_maker_info.premium_limit = premium_limit;
-    ctx.accounts.put_option_maker_info.owner = ctx.accounts.maker_quote_asset_account.owner;
-    ctx.accounts.put_option_maker_info.put_option_vault = ctx.accounts.vault_info.key();
-    msg!("Vault initialization finished");
-
+    ctx.accounts.liquidator_maker_info.quote_asset_qty = ctx.accounts.liquidator_maker_info.quote_asset_qty.checked_add(volume_sold).unwrap();
+    ctx.accounts.liquidator_maker_info.volume_sold = ctx.accounts.liquidator_maker_info.volume_sold.checked_add(volume_sold).unwrap();
+    ctx.accounts.liquidator_maker_info.margin_posted = ctx.accounts.liquidator_maker_info.margin_posted.checked_add(volume_sold).unwrap();
+    msg!("Liquidator took over {} USDC lamports of sold options and was paid {}", volume_sold, paid);
 
-    Ok(())
+    Ok(paid)
 }
 
 pub fn maker_create_put_option_vault(ctx: Context<MakerCreatePutOptionVault>,
     params: MakerCreatePutOptionParams, vault_id: u64
 ) -> Result<()> {
+    let payer = PutOptionQuotePayer {
+        quote_asset_account: &ctx.accounts.maker_quote_asset_account,
+        quote_asset_mint: &ctx.accounts.quote_asset_mint,
+        initializer: ctx.accounts.initializer.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info()
+    };
+    fund_new_put_option_vault(
+        ctx.accounts.vault_factory_info.key(),
+        &mut ctx.accounts.vault_info,
+        &mut ctx.accounts.put_option_maker_info,
+        ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+        &payer,
+        &params,
+        vault_id
+    )
+}
+
+// Same as maker_next_put_option_vault_id followed by maker_create_put_option_vault, without
+// a separate transaction reserving the vault id. Returns the id of the new vault
+pub fn maker_open_put_option_vault(ctx: Context<MakerOpenPutOptionVault>,
+    params: MakerCreatePutOptionParams
+) -> Result<u64> {
+    let base_asset = ctx.accounts.base_asset_mint.key();
+    let quote_asset = ctx.accounts.quote_asset_mint.key();
+    let vault_id = allocate_put_option_vault_id(
+        &mut ctx.accounts.vault_factory_info,
+        &ctx.accounts.asset_pair_config,
+        &mut ctx.accounts.series_index,
+        base_asset,
+        quote_asset,
+        &params
+    )?;
+
+    let payer = PutOptionQuotePayer {
+        quote_asset_account: &ctx.accounts.maker_quote_asset_account,
+        quote_asset_mint: &ctx.accounts.quote_asset_mint,
+        initializer: ctx.accounts.initializer.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info()
+    };
+    fund_new_put_option_vault(
+        ctx.accounts.vault_factory_info.key(),
+        &mut ctx.accounts.vault_info,
+        &mut ctx.accounts.put_option_maker_info,
+        ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+        &payer,
+        &params,
+        vault_id
+    )?;
+
+    Ok(vault_id)
+}
+
+// Checks the series against the asset pair configuration, initializes its vault factory
+// if it has just been created and reserves the id of a new vault in it
+fn allocate_put_option_vault_id(vault_factory_info: &mut Account<PutOptionVaultFactoryInfo>,
+    asset_pair_config: &Account<AssetPairConfig>,
+    series_index: &mut Account<SeriesIndex>,
+    base_asset: Pubkey,
+    quote_asset: Pubkey,
+    params: &MakerCreatePutOptionParams
+) -> Result<u64> {
+
+    require!(
+        params.strike > 0,
+        PutOptionError::StrikeZero
+    );
 
     let current_time = Clock::get().unwrap().unix_timestamp as u64;
     require!(
@@ -974,22 +2133,106 @@ pub fn maker_create_put_option_vault(ctx: Context<MakerCreatePutOptionVault>,
         PutOptionError::MaturityTooEarly
     );
 
+    require!(
+        params.maturity <= current_time.checked_add(MAX_MATURITY_FUTURE_SECONDS).unwrap(),
+        PutOptionError::MaturityTooLate
+    );
+
+
+    // Only asset pairs registered by the admin are allowed
+    ap::check_asset_pair_allows_factory(asset_pair_config, Some(params.lot_size), params.strike, params.maturity, current_time)?;
+
+    // Initializing factory vault (PutOptionVaultFactoryInfo) if it has been just created
+    if !vault_factory_info.is_initialized {
+        init_put_option_vault_factory_info(
+            vault_factory_info, 
+            base_asset, 
+            quote_asset, 
+            params,
+            asset_pair_config.protocol_fees_bps
+        );
+        ap::add_series_to_index(
+            series_index,
+            SeriesKind::Put,
+            vault_factory_info.key(),
+            params.strike,
+            params.maturity,
+            current_time
+        );
+    }
+    require!(
+        vault_factory_info.cash_settled == params.cash_settled,
+        PutOptionError::SettlementModeMismatch
+    );
+    require!(
+        vault_factory_info.american == params.american,
+        PutOptionError::ExerciseStyleMismatch
+    );
+    require!(
+        vault_factory_info.barrier == params.barrier && vault_factory_info.barrier_up == params.barrier_up && vault_factory_info.knock_in == params.knock_in,
+        PutOptionError::BarrierMismatch
+    );
+    let result = vault_factory_info.next_vault_id;
+    vault_factory_info.next_vault_id = vault_factory_info.next_vault_id.checked_add(1).unwrap();
+
+    Ok(result)
+}
+
+// Deposits the collateral of the first maker (the payer) of a new vault, for params.num_lots_to_sell lots,
+// and initializes the vault
+fn fund_new_put_option_vault<'info>(vault_factory_key: Pubkey,
+    vault_info: &mut Account<PutOptionVaultInfo>,
+    put_option_maker_info: &mut Account<PutOptionMakerInfo>,
+    vault_quote_asset_treasury: AccountInfo<'info>,
+    payer: &PutOptionQuotePayer<'_, 'info>,
+    params: &MakerCreatePutOptionParams,
+    vault_id: u64
+) -> Result<()> {
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        params.maturity > current_time.checked_add(FREEZE_SECONDS).unwrap(),
+        PutOptionError::MaturityTooEarly
+    );
+
+    // The auction price never goes below the fair price, which is what takers pay once it is over
+    require!(
+        params.auction_seconds == 0 || (params.auction_floor_multiple_bps >= 10000 && params.auction_floor_multiple_bps <= params.auction_start_multiple_bps),
+        PutOptionError::InvalidAuctionParams
+    );
+
+    require!(
+        params.maker_allocation == MakerAllocation::TakerChoice || params.max_makers <= MAX_QUEUED_MAKERS,
+        PutOptionError::TooManyQueuedMakers
+    );
+
+    // Early exercises are allocated among all the makers of the vault, passed in remaining accounts
+    require!(
+        !params.american || params.max_makers as usize <= MAX_REMAINING_MAKERS,
+        PutOptionError::TooManyAmericanMakers
+    );
+
+    require!(
+        params.initial_margin_bps == 0 || (
+            params.cash_settled &&
+            params.initial_margin_bps < 10000 &&
+            params.maintenance_margin_bps < params.initial_margin_bps
+        ),
+        PutOptionError::InvalidMarginParams
+    );
+
 
     // Initializing this new vault (PutOptionVaultInfo)
     // and updating number of vaults in factory
     msg!("Started initialization of PutOptionVaultInfo");
-    ctx.accounts.vault_info.factory_vault = ctx.accounts.vault_factory_info.key();
-    ctx.accounts.vault_info.ord = vault_id;
-    ctx.accounts.vault_info.max_makers = params.max_makers;
-    ctx.accounts.vault_info.max_takers = params.max_takers;
-    ctx.accounts.vault_info.lot_size = params.lot_size;
 
     // Proceed to transfer (still initializing vault)
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.maker_quote_asset_account.to_account_info(),
-        to: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
-        authority: ctx.accounts.initializer.to_account_info(),
+    let cpi_program = payer.token_program.clone();
+    let cpi_accounts = TransferChecked {
+        from: payer.quote_asset_account.to_account_info(),
+        mint: payer.quote_asset_mint.to_account_info(),
+        to: vault_quote_asset_treasury,
+        authority: payer.initializer.clone(),
     };
     let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
 
@@ -999,12 +2242,607 @@ pub fn maker_create_put_option_vault(ctx: Context<MakerCreatePutOptionVault>,
 
 
     let transfer_amount_f64 = (params.num_lots_to_sell as f64)*lot_value;
-    msg!("params.lot_size: {}", params.lot_size);
-    msg!("num_lots_to_sell: {}", params.num_lots_to_sell);
+    require!(
+        transfer_amount_f64.is_finite(),
+        PutOptionError::Overflow
+    );
+    require!(
+        transfer_amount_f64 >= 0.0,
+        PutOptionError::IllegalState
+    );
+
+    let transfer_amount = transfer_amount_f64.ceil() as u64;
+
+    init_put_option_vault_with_first_maker(
+        vault_factory_key,
+        vault_info,
+        put_option_maker_info,
+        payer.quote_asset_account.owner,
+        params,
+        vault_id,
+        transfer_amount
+    );
+
+    // In margined vaults she only deposits the initial margin of what she offers
+    let deposit_amount = vault_info.margin_for(transfer_amount);
+    if vault_info.is_margined() {
+        put_option_maker_info.margin_posted = deposit_amount;
+    }
+
+    wrap_native_sol_if_needed(
+        &payer.quote_asset_account.mint,
+        &payer.initializer,
+        &payer.quote_asset_account.to_account_info(),
+        deposit_amount,
+        &payer.system_program,
+        &payer.token_program
+    )?;
+    // balance may have changed in this instruction (SOL wrapped)
+    require!(
+        token_interface::accessor::amount(&payer.quote_asset_account.to_account_info())? >= deposit_amount,
+        PutOptionError::InsufficientFunds
+    );
+
+    token_interface::transfer_checked(token_transfer_context, amount_with_transfer_fee(&payer.quote_asset_mint.to_account_info(), deposit_amount)?, payer.quote_asset_mint.decimals)?;
+    msg!("Transferred {} USDC lamports to quote asset treasury", deposit_amount);
+
+    require!(
+        put_option_maker_info.quote_asset_qty >= rounded_lot_value,
+        PutOptionError::IllegalState
+    );
+    msg!("Vault initialization finished");
+    
+    Ok(())
+}
+
+// Where fees on premiums paid by a taker go: the quote asset treasury of the fee vault, with the
+// share of fees of the frontend (zero unless registered) and the fee tier of the taker
+struct PutOptionFees<'info> {
+    fee_vault_quote_asset_treasury: AccountInfo<'info>,
+    frontend_fee_share_bps: u16,
+    schedule: FeeSchedule
+}
+
+// Accounts of the signer paying quote assets (premium or collateral) in the helpers below
+struct PutOptionQuotePayer<'a, 'info> {
+    quote_asset_account: &'a InterfaceAccount<'info, TokenAccount>,
+    quote_asset_mint: &'a InterfaceAccount<'info, Mint>,
+    initializer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    token_program: AccountInfo<'info>
+}
+
+pub fn init_put_option_taker_info(vault_info: &mut Account<PutOptionVaultInfo>,
+    put_option_taker_info: &mut Account<PutOptionTakerInfo>,
+    owner: Pubkey
+) -> Result<()> {
+    require!(
+        !vault_info.is_takers_full,
+        PutOptionError::TakersFull
+    );
+
+    vault_info.takers_num = vault_info.takers_num.checked_add(1).unwrap();
+    if vault_info.takers_num >= vault_info.max_takers {
+        vault_info.is_takers_full = true;
+    }
+
+    put_option_taker_info.ord = vault_info.takers_num;
+    put_option_taker_info.max_base_asset = 0;
+    put_option_taker_info.qty_deposited = 0;
+    put_option_taker_info.is_settled = false;
+    put_option_taker_info.owner = owner;
+    put_option_taker_info.put_option_vault = vault_info.key();
+    put_option_taker_info.early_exercised_base = 0;
+
+    put_option_taker_info.is_initialized = true;
+
+    Ok(())
+}
+
+fn check_put_option_fair_price_is_fresh(vault_factory_info: &Account<PutOptionVaultFactoryInfo>, current_time: u64) -> Result<()> {
+    // We cannot have a timestamp for the last fair price in the future
+    require!(
+        vault_factory_info.ts_last_fair_price <= current_time,
+        PutOptionError::IllegalState
+    );
+
+    // We only sell if the option price has been updated recently
+    let seconds_from_update = current_time.checked_sub(vault_factory_info.ts_last_fair_price).unwrap();
+    require!(
+        seconds_from_update <= MAX_SECONDS_FROM_LAST_FAIR_PRICE_UPDATE,
+        PutOptionError::LastFairPriceUpdateTooOld
+    );
+
+    Ok(())
+}
+
+// Buys up to num_lots_to_buy lots at the last fair price from the makers passed in maker_accounts
+// (pairs of PutOptionMakerInfo and maker quote asset ATA), paying premiums to each maker and the fees
+// of all fills to the fee vault at once, after wrapping what the whole batch costs if the quote
+// asset is wSOL. Returns the lots bought, the premiums paid (fees included)
+// and the backend and frontend fees, for the caller to accrue in the fee vault
+fn buy_put_option_lots_from_makers<'info>(vault_factory_info: &Account<'info, PutOptionVaultFactoryInfo>,
+    vault_info: &mut Account<'info, PutOptionVaultInfo>,
+    maker_accounts: &[AccountInfo<'info>],
+    payer: &PutOptionQuotePayer<'_, 'info>,
+    fees: &PutOptionFees<'info>,
+    num_lots_to_buy: u64
+) -> Result<(u64, u64, u64, u64)> {
+    let frontend_fee_share = (fees.frontend_fee_share_bps as f64)/10000.0;
+    let mut premiums_paid:u64 = 0;
+    let mut backend_fees:u64 = 0;
+    let mut frontend_fees:u64 = 0;
+
+    let lot_multiplier:f64 = 10.0f64.powf(vault_info.lot_size as f64);
+    require!(
+        lot_multiplier.is_finite(),
+        PutOptionError::Overflow
+    );
+
+    // How much does one lot costs in quote asset lamports
+    let lot_price_in_quote_lamports_f64 = lot_multiplier*(vault_factory_info.strike as f64);
+    require!(
+        lot_price_in_quote_lamports_f64.is_finite(),
+        PutOptionError::Overflow
+    );
+    require!(
+        lot_price_in_quote_lamports_f64 > 0.0,
+        PutOptionError::IllegalState
+    );
+
+    // Always use integer prices
+    let lot_price_in_quote_lamports = lot_price_in_quote_lamports_f64.ceil() as u64;
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    let premium_price = vault_info.premium_price(vault_factory_info.last_fair_price, current_time);
+
+    require!(
+        maker_accounts.len()/2 <= MAX_REMAINING_MAKERS,
+        PutOptionError::TooManyRemainingAccounts
+    );
+    require!(
+        !has_duplicate_accounts(maker_accounts),
+        PutOptionError::AccountValidationError
+    );
+
+    // All makers are checked and lots allocated before paying any premium
+    let mut makers: Vec<(Account<PutOptionMakerInfo>, InterfaceAccount<TokenAccount>)> = Vec::with_capacity(maker_accounts.len()/2);
+    let mut avbl_lots: Vec<u64> = Vec::with_capacity(maker_accounts.len()/2);
+    let mut total_avbl_quote_asset:u64 = 0;
+    for i in 0..(maker_accounts.len()/2) {
+        let maker_info = load_put_option_maker_info(&maker_accounts[2*i], vault_info)?;
+        let maker_ata = load_put_option_maker_quote_asset_account(&maker_accounts[2*i + 1], maker_info.owner, payer.quote_asset_account.mint)?;
+
+        if vault_info.maker_allocation != MakerAllocation::TakerChoice {
+            if let Some((previous_maker_info, _)) = makers.last() {
+                require!(
+                    maker_info.queue_seq > previous_maker_info.queue_seq,
+                    PutOptionError::MakerQueueMismatch
+                );
+            }
+        }
+
+        let maker_avbl_quote_asset = maker_info.quote_asset_qty.checked_sub(maker_info.volume_sold).unwrap();
+        total_avbl_quote_asset = total_avbl_quote_asset.checked_add(maker_avbl_quote_asset).unwrap();
+        avbl_lots.push(maker_avbl_quote_asset.checked_div(lot_price_in_quote_lamports).unwrap());
+        makers.push((maker_info, maker_ata));
+    }
+
+    // With a maker queue no maker can be skipped: what is available from the makers passed must be
+    // all that is pending sale in the vault
+    if vault_info.maker_allocation != MakerAllocation::TakerChoice {
+        require!(
+            total_avbl_quote_asset == vault_info.makers_total_pending_sell,
+            PutOptionError::MakerQueueMismatch
+        );
+    }
+
+    let lots_per_maker = allocate_put_option_lots_to_makers(vault_info.maker_allocation, &avbl_lots, num_lots_to_buy);
+
+    let mut total_lots_bought:u64 = 0;
+    let mut total_transfer_amount:u64 = 0;
+    let mut fills: Vec<(usize, Account<PutOptionMakerInfo>, InterfaceAccount<TokenAccount>, u64)> = Vec::with_capacity(lots_per_maker.len());
+    for (i, (mut maker_info, maker_ata)) in makers.into_iter().enumerate() {
+        let lots_from_this_maker = lots_per_maker[i];
+        if lots_from_this_maker > 0 {
+            let reserve_amount = lots_from_this_maker.checked_mul(lot_price_in_quote_lamports).unwrap();
+            maker_info.volume_sold = maker_info.volume_sold.checked_add(reserve_amount).unwrap();
+            vault_info.makers_total_pending_sell = vault_info.makers_total_pending_sell.checked_sub(reserve_amount).unwrap();
+            let new_avbl_quote_asset = maker_info.quote_asset_qty.checked_sub(maker_info.volume_sold).unwrap();
+            let new_avbl_lots = new_avbl_quote_asset.checked_div(lot_price_in_quote_lamports).unwrap();
+            if new_avbl_lots < 1 {
+                maker_info.is_all_sold = true;
+            }
+            // Now transfer the premium to the maker and protocol
+            let premium_to_maker_f64 = (premium_price as f64)*lot_multiplier*(lots_from_this_maker as f64);
+            require!(
+                premium_to_maker_f64.is_finite() && premium_to_maker_f64 > 0.0,
+                PutOptionError::IllegalState
+            );
+            let mut premium_to_maker = premium_to_maker_f64.round() as u64;
+            let total_fees = fees.schedule.total_fees(premium_to_maker_f64, vault_factory_info.protocol_fees_bps);
+            let backend_share = (total_fees*(1.0 - frontend_fee_share)).ceil() as u64;
+            let frontend_share = (total_fees*frontend_fee_share).ceil() as u64;
+            require!(
+                premium_to_maker > backend_share + frontend_share,
+                PutOptionError::OptionPremiumTooLow
+            );
+            premium_to_maker = premium_to_maker.checked_sub(backend_share).unwrap();
+            premium_to_maker = premium_to_maker.checked_sub(frontend_share).unwrap();
+            // Makers whose collateral has been resting for long get part of the protocol share back
+            let maker_rebate = fees.schedule.maker_rebate(backend_share, maker_info.resting_since, current_time);
+            premium_to_maker = premium_to_maker.checked_add(maker_rebate).unwrap();
+            let backend_share = backend_share - maker_rebate;
+
+            // Like the fees, premiums are grossed up for transfer fees: the taker pays them and
+            // makers receive the whole premium
+            let premium_transfer_amount = amount_with_transfer_fee(&payer.quote_asset_mint.to_account_info(), premium_to_maker)?;
+            total_transfer_amount = total_transfer_amount.checked_add(premium_transfer_amount).unwrap();
+
+            backend_fees = backend_fees.checked_add(backend_share).unwrap();
+            frontend_fees = frontend_fees.checked_add(frontend_share).unwrap();
+            premiums_paid = premiums_paid.checked_add(premium_to_maker + backend_share + frontend_share).unwrap();
+            total_lots_bought = total_lots_bought.checked_add(lots_from_this_maker).unwrap();
+            fills.push((i, maker_info, maker_ata, premium_transfer_amount));
+        }
+    }
+    require!(
+        total_lots_bought <= num_lots_to_buy,
+        PutOptionError::IllegalState
+    );
+
+    let fees_transfer_amount = amount_with_transfer_fee(&payer.quote_asset_mint.to_account_info(), backend_fees + frontend_fees)?;
+    total_transfer_amount = total_transfer_amount.checked_add(fees_transfer_amount).unwrap();
+
+    // Wrapping what the whole batch costs at once, instead of once per maker
+    wrap_native_sol_if_needed(
+        &payer.quote_asset_account.mint,
+        &payer.initializer,
+        &payer.quote_asset_account.to_account_info(),
+        total_transfer_amount,
+        &payer.system_program,
+        &payer.token_program
+    )?;
+
+    for (i, maker_info, maker_ata, premium_transfer_amount) in fills {
+        {
+            let cpi_program = payer.token_program.clone();
+            msg!("Started transferring premium lamports in quote asset from taker to maker");                
+            let cpi_accounts = TransferChecked {
+                from: payer.quote_asset_account.to_account_info(),
+                mint: payer.quote_asset_mint.to_account_info(),
+                to: maker_ata.to_account_info(),
+                authority: payer.initializer.clone(),
+            };
+            let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
+            token_interface::transfer_checked(token_transfer_context, premium_transfer_amount, payer.quote_asset_mint.decimals)?;
+            msg!("Finished transferring premium quote asset lamports to maker");
+        }
+        { // Serializing maker info
+            let mut data = maker_accounts[2*i].try_borrow_mut_data()?;
+            maker_info.try_serialize(&mut data.as_mut())?;    
+        }
+    }
+
+    if backend_fees + frontend_fees > 0 {
+        let cpi_program = payer.token_program.clone();
+        msg!("Started transferring fee lamports to fee vault");
+        let cpi_accounts = TransferChecked {
+            from: payer.quote_asset_account.to_account_info(),
+            mint: payer.quote_asset_mint.to_account_info(),
+            to: fees.fee_vault_quote_asset_treasury.clone(),
+            authority: payer.initializer.clone(),
+        };
+        let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
+        token_interface::transfer_checked(token_transfer_context, fees_transfer_amount, payer.quote_asset_mint.decimals)?;
+        msg!("Finished transferring fee lamports to fee vault");
+    }
+
+    Ok((total_lots_bought, premiums_paid, backend_fees, frontend_fees))
+}
+
+// PutOptionMakerInfo of a maker of vault_info passed in remaining accounts: writable, owned by
+// this program and at the address derived from the vault and its owner
+fn load_put_option_maker_info<'info>(info: &AccountInfo<'info>,
+    vault_info: &Account<PutOptionVaultInfo>
+) -> Result<Account<'info, PutOptionMakerInfo>> {
+    let maker_info: Account<PutOptionMakerInfo> = load_writable_program_account(info).ok_or(PutOptionError::AccountValidationError)?;
+    require!(
+        maker_info.put_option_vault == vault_info.key() &&
+        is_program_address(info.key, &[
+            "PutOptionMakerInfo".as_bytes(),
+            vault_info.factory_vault.as_ref(),
+            &vault_info.ord.to_le_bytes(),
+            maker_info.owner.as_ref()
+        ]),
+        PutOptionError::AccountValidationError
+    );
+
+    Ok(maker_info)
+}
+
+// Quote asset account of a maker passed in remaining accounts to receive premiums
+fn load_put_option_maker_quote_asset_account<'info>(info: &AccountInfo<'info>,
+    maker: Pubkey,
+    quote_asset_mint: Pubkey
+) -> Result<InterfaceAccount<'info, TokenAccount>> {
+    let maker_ata = load_writable_token_account(info).ok_or(PutOptionError::AccountValidationError)?;
+    require!(
+        maker_ata.owner == maker && maker_ata.mint == quote_asset_mint,
+        PutOptionError::AccountValidationError
+    );
+
+    Ok(maker_ata)
+}
+
+// Lots sold by each maker out of num_lots_to_buy, given the lots available from each of them in the
+// order they have been passed (maker queue order unless the vault lets the taker choose)
+fn allocate_put_option_lots_to_makers(maker_allocation: MakerAllocation,
+    avbl_lots: &[u64],
+    num_lots_to_buy: u64
+) -> Vec<u64> {
+    let total_avbl_lots:u64 = avbl_lots.iter().sum();
+    let lots_to_allocate = std::cmp::min(num_lots_to_buy, total_avbl_lots);
+    let mut lots_per_maker = vec![0u64; avbl_lots.len()];
+    if maker_allocation == MakerAllocation::ProRata && lots_to_allocate < total_avbl_lots {
+        for (i, maker_avbl_lots) in avbl_lots.iter().enumerate() {
+            lots_per_maker[i] = ((lots_to_allocate as u128)*(*maker_avbl_lots as u128)/(total_avbl_lots as u128)) as u64;
+        }
+    }
+
+    // Whatever is left (everything but rounding leftovers for pro-rata) goes first to the first makers
+    let mut remaining_lots = lots_to_allocate - lots_per_maker.iter().sum::<u64>();
+    for (i, maker_avbl_lots) in avbl_lots.iter().enumerate() {
+        if remaining_lots == 0 {
+            break;
+        }
+        let extra_lots = std::cmp::min(maker_avbl_lots - lots_per_maker[i], remaining_lots);
+        lots_per_maker[i] += extra_lots;
+        remaining_lots -= extra_lots;
+    }
+
+    lots_per_maker
+}
+
+// Accounts used by all the fills of resting taker bids in an instruction
+struct PutOptionBidFill<'a, 'info> {
+    quote_asset_mint: &'a InterfaceAccount<'info, Mint>,
+    base_asset_decimals: u8,
+    fee_vault_quote_asset_treasury: AccountInfo<'info>,
+    token_program: AccountInfo<'info>
+}
+
+// Makers entering or adjusting their position may fill resting taker bids right away by passing in
+// remaining accounts the FeeVaultInfo of the quote asset and its treasury, the InsuranceFundInfo of
+// the quote asset, followed by triples of PutOptionTakerBidInfo, its escrow and the PutOptionTakerInfo of
+// the bidder. Bids are not tied to a frontend, so there is no frontend share of fees when filling them
+fn split_put_option_bid_fill_accounts<'a, 'info>(remaining_accounts: &'a [AccountInfo<'info>],
+    quote_asset_mint: Pubkey
+) -> Result<(Account<'info, FeeVaultInfo>, Account<'info, InsuranceFundInfo>, &'a [AccountInfo<'info>])> {
+    require!(
+        remaining_accounts.len() > 3 && (remaining_accounts.len() - 3) % 3 == 0,
+        PutOptionError::AccountValidationError
+    );
+    require!(
+        (remaining_accounts.len() - 3)/3 <= MAX_REMAINING_BIDS,
+        PutOptionError::TooManyRemainingAccounts
+    );
+    require!(
+        !has_duplicate_accounts(remaining_accounts),
+        PutOptionError::AccountValidationError
+    );
+    let fee_vault_info: Account<FeeVaultInfo> = load_writable_program_account(&remaining_accounts[0]).ok_or(PutOptionError::AccountValidationError)?;
+    let fee_vault_quote_asset_treasury = load_writable_token_account(&remaining_accounts[1]).ok_or(PutOptionError::AccountValidationError)?;
+    let insurance_fund_info = Account::<InsuranceFundInfo>::try_from(&remaining_accounts[2]).map_err(|_| PutOptionError::AccountValidationError)?;
+    require!(
+        is_program_address(&fee_vault_info.key(), &["FeeVaultInfo".as_bytes(), quote_asset_mint.as_ref()]) &&
+        fee_vault_info.is_initialized &&
+        fee_vault_quote_asset_treasury.owner == fee_vault_info.key() &&
+        fee_vault_quote_asset_treasury.mint == quote_asset_mint &&
+        is_program_address(&insurance_fund_info.key(), &["InsuranceFundInfo".as_bytes(), quote_asset_mint.as_ref()]) &&
+        insurance_fund_info.is_initialized,
+        PutOptionError::AccountValidationError
+    );
+
+    Ok((fee_vault_info, insurance_fund_info, &remaining_accounts[3..]))
+}
+
+// Accrues the backend fees of bid fills in the FeeVaultInfo passed in remaining accounts and writes it back
+fn accrue_put_option_bid_fill_fees(fee_vault_info: &mut Account<FeeVaultInfo>,
+    fee_vault_info_account: &AccountInfo,
+    insurance_fund_info: &InsuranceFundInfo,
+    backend_fees: u64
+) -> Result<()> {
+    fee_vault_info.accrue_backend_fees(backend_fees, insurance_fund_info);
+    let mut data = fee_vault_info_account.try_borrow_mut_data()?;
+    fee_vault_info.try_serialize(&mut data.as_mut())?;
+
+    Ok(())
+}
+
+// Sells lots of a maker to the resting taker bids in bid_accounts (triples of PutOptionTakerBidInfo,
+// its escrow and the PutOptionTakerInfo of the bidder) at the last fair price, which must be fresh.
+// Premiums, net of fees, are paid from the escrows to maker_quote_asset_account and fees to the fee vault.
+// Returns the number of lots sold, the premiums paid to the maker and the backend fees received by the fee vault,
+// for the caller to accrue
+fn fill_put_option_taker_bids<'info>(vault_factory_info: &Account<'info, PutOptionVaultFactoryInfo>,
+    vault_info: &mut Account<'info, PutOptionVaultInfo>,
+    maker_info: &mut PutOptionMakerInfo,
+    maker_quote_asset_account: &AccountInfo<'info>,
+    bid_accounts: &[AccountInfo<'info>],
+    fill: &PutOptionBidFill<'_, 'info>
+) -> Result<(u64, u64, u64)> {
+    require!(
+        vault_info.maker_allocation == MakerAllocation::TakerChoice,
+        PutOptionError::BidsNeedTakerChoice
+    );
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+
+    let lot_multiplier:f64 = 10.0f64.powf(vault_info.lot_size as f64);
+    let lot_price_in_quote_lamports_f64 = lot_multiplier*(vault_factory_info.strike as f64);
+    require!(
+        lot_price_in_quote_lamports_f64.is_finite() && lot_price_in_quote_lamports_f64 > 0.0,
+        PutOptionError::Overflow
+    );
+    // Always use integer prices
+    let lot_price_in_quote_lamports = lot_price_in_quote_lamports_f64.ceil() as u64;
+    let premium_price = vault_info.premium_price(vault_factory_info.last_fair_price, current_time);
+
+    let mut total_lots_sold:u64 = 0;
+    let mut premiums_to_maker:u64 = 0;
+    let mut backend_fees:u64 = 0;
+    for i in 0..(bid_accounts.len()/3) {
+        let maker_avbl_lots = maker_info.quote_asset_qty.checked_sub(maker_info.volume_sold).unwrap().checked_div(lot_price_in_quote_lamports).unwrap();
+        if maker_avbl_lots == 0 {
+            break;
+        }
+
+        let mut bid: Account<PutOptionTakerBidInfo> = load_writable_program_account(&bid_accounts[3*i]).ok_or(PutOptionError::AccountValidationError)?;
+        let bid_escrow = load_writable_token_account(&bid_accounts[3*i + 1]).ok_or(PutOptionError::AccountValidationError)?;
+        let mut taker_info: Account<PutOptionTakerInfo> = load_writable_program_account(&bid_accounts[3*i + 2]).ok_or(PutOptionError::AccountValidationError)?;
+        require!(
+            bid.put_option_vault == vault_info.key() &&
+            is_program_address(&bid.key(), &[
+                "PutOptionTakerBidInfo".as_bytes(),
+                vault_info.key().as_ref(),
+                bid.owner.as_ref()
+            ]) &&
+            bid_escrow.owner == bid.key() &&
+            bid_escrow.mint == fill.quote_asset_mint.key() &&
+            taker_info.owner == bid.owner &&
+            taker_info.put_option_vault == vault_info.key() &&
+            is_program_address(&taker_info.key(), &[
+                "PutOptionTakerInfo".as_bytes(),
+                vault_info.factory_vault.as_ref(),
+                &vault_info.ord.to_le_bytes(),
+                taker_info.owner.as_ref()
+            ]),
+            PutOptionError::AccountValidationError
+        );
+
+        // Makers never fill their own bids
+        if taker_info.is_settled || bid.owner == maker_info.owner || bid.expiry <= current_time || bid.max_fair_price < premium_price {
+            continue;
+        }
+        let lots_to_bid = std::cmp::min(maker_avbl_lots, bid.lots_remaining);
+        if lots_to_bid == 0 {
+            continue;
+        }
+
+        let reserve_amount = lots_to_bid.checked_mul(lot_price_in_quote_lamports).unwrap();
+        maker_info.volume_sold = maker_info.volume_sold.checked_add(reserve_amount).unwrap();
+        vault_info.makers_total_pending_sell = vault_info.makers_total_pending_sell.checked_sub(reserve_amount).unwrap();
+        let new_avbl_lots = maker_info.quote_asset_qty.checked_sub(maker_info.volume_sold).unwrap().checked_div(lot_price_in_quote_lamports).unwrap();
+        if new_avbl_lots < 1 {
+            maker_info.is_all_sold = true;
+        }
+
+        // Premium paid from the escrow, split between maker and fees as in taker_buy_lots_put_option_vault
+        let premium_f64 = (premium_price as f64)*lot_multiplier*(lots_to_bid as f64);
+        require!(
+            premium_f64.is_finite() && premium_f64 > 0.0,
+            PutOptionError::IllegalState
+        );
+        let premium = premium_f64.round() as u64;
+        // All fees go to the backend, bids are not tied to a frontend
+        let backend_share = (premium_f64*(vault_factory_info.protocol_fees_bps as f64)/10000.0).ceil() as u64;
+        require!(
+            premium > backend_share,
+            PutOptionError::OptionPremiumTooLow
+        );
+        let premium_to_maker = premium - backend_share;
+        require!(
+            backend_share + premium_to_maker <= bid.escrowed_premium,
+            PutOptionError::IllegalState
+        );
+
+        // Preparing PDA signer
+        let seeds = &[
+            "PutOptionTakerBidInfo".as_bytes().as_ref(),
+            bid.put_option_vault.as_ref(),
+            bid.owner.as_ref(),
+            &[bid.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let payments = [
+            (maker_quote_asset_account, premium_to_maker),
+            (&fill.fee_vault_quote_asset_treasury, backend_share)
+        ];
+        for (destination, amount) in payments {
+            let cpi_accounts = TransferChecked {
+                from: bid_accounts[3*i + 1].clone(),
+                mint: fill.quote_asset_mint.to_account_info(),
+                to: destination.clone(),
+                authority: bid_accounts[3*i].clone(),
+            };
+            let token_transfer_context = CpiContext::new_with_signer(fill.token_program.clone(), cpi_accounts, signer);
+            token_interface::transfer_checked(token_transfer_context, amount, fill.quote_asset_mint.decimals)?;
+        }
+        msg!("Filled {} lots of bid {} paying {} USDC lamports of premium", lots_to_bid, bid.key(), premium);
+        // Escrows hold the exact premium, so the fee vault is credited what it received
+        backend_fees = backend_fees.checked_add(amount_after_transfer_fee(&fill.quote_asset_mint.to_account_info(), backend_share)?).unwrap();
+
+        let max_base_asset_f64 = (lots_to_bid as f64)*lot_multiplier*(10.0f64.powf(fill.base_asset_decimals as f64));
+        require!(
+            max_base_asset_f64.is_finite(),
+            PutOptionError::Overflow
+        );
+        taker_info.max_base_asset = taker_info.max_base_asset.checked_add(max_base_asset_f64.ceil() as u64).unwrap();
+
+        bid.lots_remaining -= lots_to_bid;
+        bid.lots_filled = bid.lots_filled.checked_add(lots_to_bid).unwrap();
+        bid.escrowed_premium -= premium;
+
+        { // Serializing bid and taker info
+            let mut data = bid_accounts[3*i].try_borrow_mut_data()?;
+            bid.try_serialize(&mut data.as_mut())?;
+        }
+        {
+            let mut data = bid_accounts[3*i + 2].try_borrow_mut_data()?;
+            taker_info.try_serialize(&mut data.as_mut())?;
+        }
+        total_lots_sold = total_lots_sold.checked_add(lots_to_bid).unwrap();
+        premiums_to_maker = premiums_to_maker.checked_add(premium_to_maker).unwrap();
+    }
+
+    Ok((total_lots_sold, premiums_to_maker, backend_fees))
+}
+
+// Deposits the collateral for num_lots_to_sell lots in the vault and initializes the maker
+// entering it (the payer) 
+fn enter_put_option_vault_as_maker<'info>(vault_factory_info: &Account<PutOptionVaultFactoryInfo>,
+    vault_info: &mut Account<PutOptionVaultInfo>,
+    put_option_maker_info: &mut Account<PutOptionMakerInfo>,
+    vault_quote_asset_treasury: AccountInfo<'info>,
+    payer: &PutOptionQuotePayer<'_, 'info>,
+    num_lots_to_sell: u64,
+    premium_limit: u64
+) -> Result<()> {
+    vault_info.makers_num = vault_info.makers_num.checked_add(1).unwrap();
+
+    if vault_info.makers_num >= vault_info.max_makers {
+        vault_info.is_makers_full = true;
+    }
+
+    // Proceed to transfer 
+    let cpi_program = payer.token_program.clone();
+    let cpi_accounts = TransferChecked {
+        from: payer.quote_asset_account.to_account_info(),
+        mint: payer.quote_asset_mint.to_account_info(),
+        to: vault_quote_asset_treasury,
+        authority: payer.initializer.clone(),
+    };
+    let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
+
+    let lot_multiplier:f64 = 10.0f64.powf(vault_info.lot_size as f64);
+    msg!("num_lots_to_sell: {}", num_lots_to_sell);
     msg!("lot_multiplier: {}", lot_multiplier);
-    msg!("strike: {}", params.strike);
+    msg!("strike: {}", vault_factory_info.strike);
+    let lot_value = lot_multiplier*(vault_factory_info.strike as f64);
+    let rounded_lot_value = lot_value.ceil() as u64;
 
-    msg!("Transfer amount is {}", transfer_amount_f64);
+    let transfer_amount_f64 = (num_lots_to_sell as f64)*lot_value;
     require!(
         transfer_amount_f64.is_finite(),
         PutOptionError::Overflow
@@ -1015,40 +2853,190 @@ pub fn maker_create_put_option_vault(ctx: Context<MakerCreatePutOptionVault>,
     );
     
     let transfer_amount = transfer_amount_f64.ceil() as u64;
-
+    // In margined vaults she only deposits the initial margin of what she offers
+    let deposit_amount = vault_info.margin_for(transfer_amount);
+
+    wrap_native_sol_if_needed(
+        &payer.quote_asset_account.mint,
+        &payer.initializer,
+        &payer.quote_asset_account.to_account_info(),
+        deposit_amount,
+        &payer.system_program,
+        &payer.token_program
+    )?;
+    // balance may have changed in this instruction (premiums paid, SOL wrapped)
     require!(
-        ctx.accounts.maker_quote_asset_account.amount >= transfer_amount,
+        token_interface::accessor::amount(&payer.quote_asset_account.to_account_info())? >= deposit_amount,
         PutOptionError::InsufficientFunds
     );
 
-    token::transfer(token_transfer_context, transfer_amount)?;
-    msg!("Transferred {} USDC lamports to quote asset treasury", transfer_amount);
 
-    // Continuing to initialize vault...
-    ctx.accounts.vault_info.makers_num = 1;
-    ctx.accounts.vault_info.makers_total_pending_sell = transfer_amount;
-    ctx.accounts.vault_info.makers_total_pending_settle = transfer_amount;
-    ctx.accounts.vault_info.is_makers_full = ctx.accounts.vault_info.makers_num >= ctx.accounts.vault_info.max_makers; 
-    ctx.accounts.vault_info.takers_num = 0;
-    ctx.accounts.vault_info.takers_total_deposited = 0;
-    ctx.accounts.vault_info.is_takers_full = ctx.accounts.vault_info.takers_num >= ctx.accounts.vault_info.max_takers;
-    ctx.accounts.vault_info.bonus_not_exercised = 0;
+    token_interface::transfer_checked(token_transfer_context, amount_with_transfer_fee(&payer.quote_asset_mint.to_account_info(), deposit_amount)?, payer.quote_asset_mint.decimals)?;
+    msg!("Transferred {} USDC lamports to quote asset treasury", deposit_amount);
+
+    // Updating vault_info ...
+    vault_info.makers_total_pending_sell = vault_info.makers_total_pending_sell.checked_add(transfer_amount).unwrap();
+    vault_info.makers_total_pending_settle = vault_info.makers_total_pending_settle.checked_add(transfer_amount).unwrap();
     msg!("Finished initialization of PutOptionVaultInfo, now initializing PutOptionMakerInfo");
 
     // Now initializing info about this maker in the vault (PutOptionMakerInfo)
-    ctx.accounts.put_option_maker_info.ord = ctx.accounts.vault_info.makers_num;
-    ctx.accounts.put_option_maker_info.quote_asset_qty = transfer_amount;
-    ctx.accounts.put_option_maker_info.volume_sold = 0;
-    ctx.accounts.put_option_maker_info.is_settled = false;
-    ctx.accounts.put_option_maker_info.is_all_sold = false;
+    put_option_maker_info.ord = vault_info.take_maker_ord();
+    put_option_maker_info.quote_asset_qty = transfer_amount;
+    put_option_maker_info.volume_sold = 0;
+    put_option_maker_info.is_all_sold = false;
     require!(
-        ctx.accounts.put_option_maker_info.quote_asset_qty >= rounded_lot_value,
+        put_option_maker_info.quote_asset_qty >= rounded_lot_value,
         PutOptionError::IllegalState
     );
-    ctx.accounts.put_option_maker_info.premium_limit = params.premium_limit;
-    ctx.accounts.put_option_maker_info.owner = ctx.accounts.maker_quote_asset_account.owner;
-    ctx.accounts.put_option_maker_info.put_option_vault = ctx.accounts.vault_info.key();
+    put_option_maker_info.is_settled = false;
+    put_option_maker_info.premium_limit = premium_limit;
+    put_option_maker_info.owner = payer.quote_asset_account.owner;
+    put_option_maker_info.put_option_vault = vault_info.key();
+    put_option_maker_info.resting_since = Clock::get().unwrap().unix_timestamp as u64;
+    put_option_maker_info.early_exercised_base = 0;
+    put_option_maker_info.queue_seq = vault_info.take_maker_queue_seq();
+    put_option_maker_info.margin_posted = if vault_info.is_margined() { deposit_amount } else { 0 };
     msg!("Vault initialization finished");
-    
+
     Ok(())
 }
+
+pub fn init_put_option_vault_factory_info(vault_factory_info: &mut Account<PutOptionVaultFactoryInfo>,
+    base_asset: Pubkey,
+    quote_asset: Pubkey,
+    params: &MakerCreatePutOptionParams,
+    protocol_fees_bps: u16
+) {
+    vault_factory_info.next_vault_id = 1;
+    vault_factory_info.maturity = params.maturity;
+    vault_factory_info.matured = false;
+    vault_factory_info.strike = params.strike;
+    vault_factory_info.base_asset = base_asset;
+    vault_factory_info.quote_asset = quote_asset;
+    vault_factory_info.emergency_mode = false;
+    vault_factory_info.cash_settled = params.cash_settled;
+    vault_factory_info.american = params.american;
+    vault_factory_info.protocol_fees_bps = protocol_fees_bps;
+    vault_factory_info.last_spot_price = 0;
+    vault_factory_info.ts_last_spot_price = 0;
+    vault_factory_info.barrier = params.barrier;
+    vault_factory_info.barrier_up = params.barrier_up;
+    vault_factory_info.knock_in = params.knock_in;
+    vault_factory_info.knocked = false;
+
+    vault_factory_info.is_initialized = true;
+    msg!("PutOptionVaultFactoryInfo initialized");
+}
+
+// Initializes a new vault (PutOptionVaultInfo) whose first maker (PutOptionMakerInfo) has
+// already deposited quote_asset_qty in the vault quote asset treasury
+pub fn init_put_option_vault_with_first_maker(vault_factory_key: Pubkey,
+    vault_info: &mut Account<PutOptionVaultInfo>,
+    put_option_maker_info: &mut Account<PutOptionMakerInfo>,
+    maker: Pubkey,
+    params: &MakerCreatePutOptionParams,
+    vault_id: u64,
+    quote_asset_qty: u64
+) {
+    vault_info.factory_vault = vault_factory_key;
+    vault_info.ord = vault_id;
+    vault_info.max_makers = params.max_makers;
+    vault_info.max_takers = params.max_takers;
+    vault_info.lot_size = params.lot_size;
+    vault_info.makers_num = 1;
+    vault_info.next_maker_ord = 0;
+    vault_info.makers_total_pending_sell = quote_asset_qty;
+    vault_info.makers_total_pending_settle = quote_asset_qty;
+    vault_info.is_makers_full = vault_info.makers_num >= vault_info.max_makers; 
+    vault_info.takers_num = 0;
+    vault_info.takers_total_deposited = 0;
+    vault_info.is_takers_full = vault_info.takers_num >= vault_info.max_takers;
+    vault_info.bonus_not_exercised = 0;
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    vault_info.auction_start = current_time;
+    vault_info.auction_end = if params.auction_seconds > 0 { current_time.checked_add(params.auction_seconds).unwrap() } else { 0 };
+    vault_info.auction_start_multiple_bps = params.auction_start_multiple_bps;
+    vault_info.auction_floor_multiple_bps = params.auction_floor_multiple_bps;
+    vault_info.maker_allocation = params.maker_allocation;
+    vault_info.next_maker_queue_seq = 0;
+    vault_info.initial_margin_bps = params.initial_margin_bps;
+    vault_info.maintenance_margin_bps = params.maintenance_margin_bps;
+    vault_info.liquidation_bonus_bps = params.liquidation_bonus_bps;
+    vault_info.safety_fund = 0;
+    vault_info.makers_settled = 0;
+    vault_info.makers_total_payout = 0;
+    vault_info.margin_shortfall = 0;
+    vault_info.takers_settled = 0;
+    msg!("Finished initialization of PutOptionVaultInfo, now initializing PutOptionMakerInfo");
+
+    // Now initializing info about this maker in the vault (PutOptionMakerInfo)
+    put_option_maker_info.ord = vault_info.take_maker_ord();
+    put_option_maker_info.quote_asset_qty = quote_asset_qty;
+    put_option_maker_info.volume_sold = 0;
+    put_option_maker_info.is_settled = false;
+    put_option_maker_info.is_all_sold = false;
+    put_option_maker_info.premium_limit = params.premium_limit;
+    put_option_maker_info.owner = maker;
+    put_option_maker_info.put_option_vault = vault_info.key();
+    put_option_maker_info.resting_since = current_time;
+    put_option_maker_info.early_exercised_base = 0;
+    put_option_maker_info.queue_seq = vault_info.take_maker_queue_seq();
+    put_option_maker_info.margin_posted = 0;
+}
+
+// Liability of what a maker of a margined vault has sold (the greater of its fair value and its
+// intrinsic value at the last spot price), and the margin she must keep posted to cover it
+pub fn compute_put_option_maker_margin_requirement(vault_factory_info: &Account<PutOptionVaultFactoryInfo>,
+    vault_info: &Account<PutOptionVaultInfo>,
+    put_option_maker_info: &Account<PutOptionMakerInfo>
+) -> Result<(u64, u64)> {
+    let intrinsic_value = vault_factory_info.strike.saturating_sub(vault_factory_info.last_spot_price);
+    let unit_liability = intrinsic_value.max(vault_factory_info.last_fair_price);
+    // volume_sold is strike*quantity, so volume_sold/strike*unit_liability is what she would owe now
+    let liability_f64 = (put_option_maker_info.volume_sold as f64)/(vault_factory_info.strike as f64)*(unit_liability as f64);
+    let maintenance_f64 = (put_option_maker_info.volume_sold as f64)*(vault_info.maintenance_margin_bps as f64)/10000.0;
+    require!(
+        liability_f64.is_finite() && maintenance_f64.is_finite(),
+        PutOptionError::Overflow
+    );
+    let liability = liability_f64.ceil() as u64;
+    let requirement = liability.checked_add(maintenance_f64.ceil() as u64).unwrap();
+
+    Ok((liability, requirement))
+}
+
+// Margin a maker of a margined vault can withdraw while keeping notional quote asset lamports offered:
+// she always keeps posted the initial margin of that notional, and never less than her maintenance requirement
+pub fn compute_put_option_maker_margin_release(vault_factory_info: &Account<PutOptionVaultFactoryInfo>,
+    vault_info: &Account<PutOptionVaultInfo>,
+    put_option_maker_info: &Account<PutOptionMakerInfo>,
+    notional: u64
+) -> Result<u64> {
+    let (_, requirement) = compute_put_option_maker_margin_requirement(vault_factory_info, vault_info, put_option_maker_info)?;
+    let kept = vault_info.margin_for(notional).max(requirement);
+
+    Ok(put_option_maker_info.margin_posted.saturating_sub(kept))
+}
+
+// What a maker owes to the takers of a cash-settled put option vault, out of her quote_asset_qty
+pub fn compute_put_option_maker_cash_payout(vault_factory_info: &Account<PutOptionVaultFactoryInfo>,
+    put_option_maker_info: &Account<PutOptionMakerInfo>
+) -> Result<u64> {
+    let mut payout:u64 = 0;
+    if vault_factory_info.settled_price < vault_factory_info.strike && !vault_factory_info.is_barrier_void() {
+        msg!("Cash-settled put option is favorable to taker, maker will pay the difference to strike");
+        let price_difference = vault_factory_info.strike.checked_sub(vault_factory_info.settled_price).unwrap();
+        // volume_sold is strike*quantity, so volume_sold/strike*(strike - settled_price) is what she owes
+        let payout_f64 = (put_option_maker_info.volume_sold as f64) / (vault_factory_info.strike as f64) * (price_difference as f64);
+        require!(
+            payout_f64.is_finite() && payout_f64 >= 0.0,
+            PutOptionError::Overflow
+        );
+        // rounding in favor of takers, so the vault always has enough to pay them
+        payout = std::cmp::min(payout_f64.ceil() as u64, put_option_maker_info.volume_sold);
+    } else {
+        msg!("Cash-settled put option is not favorable to taker, will NOT be exercised");
+    }
+
+    Ok(payout)
+}
@@ -1,14 +1,21 @@
 use anchor_lang::prelude::*;
+use crate::insurance_funds::data::InsuranceFundInfo;
+use crate::fee_vaults::data::FeeVaultInfo;
+use crate::fee_tiers::data::{FeeTiersInfo, TakerStatsInfo};
+use crate::frontends::data::FrontendInfo;
 use crate::put_options::data::{
     PutOptionVaultFactoryInfo, 
     PutOptionVaultInfo, PutOptionMakerInfo, PutOptionTakerInfo,
     PutOptionUpdateFairPriceTicketInfo,
-    PutOptionSettlePriceTicketInfo
+    PutOptionSettlePriceTicketInfo,
+    PutOptionTakerBidInfo
 };
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::asset_pairs::data::{AssetPairConfig, SeriesIndex};
+use anchor_spl::token_interface::{Mint, TokenInterface, TokenAccount};
 use anchor_spl::associated_token::AssociatedToken;
-use crate::{PROTOCOL_FEES_ADDRESS, ORACLE_ADDRESS};
+use crate::ORACLE_ADDRESS;
 use crate::MakerCreatePutOptionParams;
+use crate::put_options::errors::PutOptionError;
 
 #[derive(Accounts)]
 #[instruction(
@@ -40,17 +47,17 @@ pub struct MakerAdjustPositionPutOptionVault<'info> {
     pub vault_info: Account<'info, PutOptionVaultInfo>,
 
     // mint for the base_asset
-    pub base_asset_mint: Account<'info, Mint>,
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
 
     // mint for the quote asset
-    pub quote_asset_mint: Account<'info, Mint>,
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
         constraint = vault_quote_asset_treasury.owner == vault_info.key() // Authority set to vault PDA
     )]
-    pub vault_quote_asset_treasury: Box<Account<'info, TokenAccount>>,
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         mut,
@@ -71,7 +78,7 @@ pub struct MakerAdjustPositionPutOptionVault<'info> {
         constraint = maker_quote_asset_account.owner.key() == initializer.key(),
         constraint = maker_quote_asset_account.mint == quote_asset_mint.key()
     )]
-    pub maker_quote_asset_account: Box<Account<'info, TokenAccount>>,
+    pub maker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
 
     // Check if initializer is signer, mut is required to reduce lamports (fees)
@@ -81,7 +88,82 @@ pub struct MakerAdjustPositionPutOptionVault<'info> {
     // System Program requred for deduction of lamports (fees)
     pub system_program: Program<'info, System>,
     // Token Program required to call transfer instruction
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+}
+
+#[derive(Accounts)]
+pub struct MakerExitPutOptionVault<'info> {
+    #[account(        
+        seeds=["PutOptionVaultFactoryInfo".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref(), vault_factory_info.maturity.to_le_bytes().as_ref(), vault_factory_info.strike.to_le_bytes().as_ref()], 
+        bump, 
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.base_asset == base_asset_mint.key(),
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, PutOptionVaultFactoryInfo>,
+
+    #[account(
+        mut,
+        seeds=[
+            "PutOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Account<'info, PutOptionVaultInfo>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = vault_quote_asset_treasury.owner == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // closed (rent goes back to initializer) if nothing has been sold
+    #[account(
+        mut,
+        seeds=[
+            "PutOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        constraint = put_option_maker_info.put_option_vault == vault_info.key(),
+        constraint = put_option_maker_info.owner == initializer.key(),
+        constraint = !put_option_maker_info.is_settled
+    )]
+    pub put_option_maker_info: Account<'info, PutOptionMakerInfo>,
+
+    // unsold quote assets will be sent back to this account
+    #[account(
+        mut,
+        constraint = maker_quote_asset_account.owner.key() == initializer.key(),
+        constraint = maker_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub maker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 
 }
@@ -115,17 +197,17 @@ pub struct MakerEnterPutOptionVault<'info> {
     pub vault_info: Account<'info, PutOptionVaultInfo>,
 
     // mint for the base_asset
-    pub base_asset_mint: Account<'info, Mint>,
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
 
     // mint for the quote asset
-    pub quote_asset_mint: Account<'info, Mint>,
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
         constraint = vault_quote_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
     )]
-    pub vault_quote_asset_treasury: Box<Account<'info, TokenAccount>>,
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init,
@@ -146,7 +228,7 @@ pub struct MakerEnterPutOptionVault<'info> {
         constraint = maker_quote_asset_account.owner.key() == initializer.key(),
         constraint = maker_quote_asset_account.mint == quote_asset_mint.key()
     )]
-    pub maker_quote_asset_account: Box<Account<'info, TokenAccount>>,
+    pub maker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
 
     // Check if initializer is signer, mut is required to reduce lamports (fees)
@@ -156,7 +238,7 @@ pub struct MakerEnterPutOptionVault<'info> {
     // System Program requred for deduction of lamports (fees)
     pub system_program: Program<'info, System>,
     // Token Program required to call transfer instruction
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 
 }
@@ -197,21 +279,21 @@ pub struct TakerPutOptionEmergencyExit<'info> {
     pub put_option_taker_info: Account<'info, PutOptionTakerInfo>,
 
     // mint for the base_asset
-    pub base_asset_mint: Account<'info, Mint>,
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         constraint = vault_base_asset_treasury.mint == base_asset_mint.key(), // Base asset mint
         constraint = vault_base_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
     )]
-    pub vault_base_asset_treasury: Box<Account<'info, TokenAccount>>,
+    pub vault_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         mut,
         constraint = taker_base_asset_account.owner.key() == initializer.key(),
         constraint = taker_base_asset_account.mint == base_asset_mint.key()
     )]
-    pub taker_base_asset_account: Box<Account<'info, TokenAccount>>,
+    pub taker_base_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     // Check if initializer is signer, mut is required to reduce lamports (fees)
     #[account(mut)]
@@ -220,7 +302,7 @@ pub struct TakerPutOptionEmergencyExit<'info> {
     // System Program requred for deduction of lamports (fees)
     pub system_program: Program<'info, System>,
     // Token Program required to call transfer instruction
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>
 
 }
@@ -231,6 +313,7 @@ pub struct MakerPutOptionEmergencyExit<'info> {
         constraint = vault_factory_info.strike > 0,
         constraint = vault_factory_info.is_initialized == true,
         constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.base_asset == base_asset_mint.key(),
         constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
         constraint = vault_factory_info.emergency_mode == true
     )]
@@ -259,22 +342,40 @@ pub struct MakerPutOptionEmergencyExit<'info> {
     )]
     pub put_option_maker_info: Account<'info, PutOptionMakerInfo>,
 
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
     // mint for the quote asset
-    pub quote_asset_mint: Account<'info, Mint>,
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // quote asset mint
         constraint = vault_quote_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
     )]
-    pub vault_quote_asset_treasury: Box<Account<'info, TokenAccount>>,
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         mut,
         constraint = maker_quote_asset_account.owner.key() == initializer.key(),
         constraint = maker_quote_asset_account.mint == quote_asset_mint.key()
     )]
-    pub maker_quote_asset_account: Box<Account<'info, TokenAccount>>,
+    pub maker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = vault_base_asset_treasury.mint == base_asset_mint.key(), // Base asset mint
+        constraint = vault_base_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // maker will get here the base assets she has bought from early exercises, if any
+    #[account(
+        mut,
+        constraint = maker_base_asset_account.owner.key() == initializer.key(),
+        constraint = maker_base_asset_account.mint == base_asset_mint.key()
+    )]
+    pub maker_base_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     // Check if initializer is signer, mut is required to reduce lamports (fees)
     #[account(mut)]
@@ -283,7 +384,7 @@ pub struct MakerPutOptionEmergencyExit<'info> {
     // System Program requred for deduction of lamports (fees)
     pub system_program: Program<'info, System>,
     // Token Program required to call transfer instruction
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>
 }
 
@@ -302,6 +403,11 @@ pub struct MakerCreatePutOptionVault<'info> {
         constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
         constraint = vault_factory_info.maturity == params.maturity,
         constraint = vault_factory_info.strike == params.strike,
+        constraint = vault_factory_info.cash_settled == params.cash_settled,
+        constraint = vault_factory_info.american == params.american,
+        constraint = vault_factory_info.barrier == params.barrier,
+        constraint = vault_factory_info.barrier_up == params.barrier_up,
+        constraint = vault_factory_info.knock_in == params.knock_in,
         constraint = vault_factory_info.emergency_mode == false
     )]
     pub vault_factory_info: Account<'info, PutOptionVaultFactoryInfo>,
@@ -321,10 +427,10 @@ pub struct MakerCreatePutOptionVault<'info> {
     pub vault_info: Account<'info, PutOptionVaultInfo>,
 
     // mint for the base_asset
-    pub base_asset_mint: Account<'info, Mint>,
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
 
     // mint for the quote asset
-    pub quote_asset_mint: Account<'info, Mint>,
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init,
@@ -332,7 +438,7 @@ pub struct MakerCreatePutOptionVault<'info> {
         associated_token::mint = base_asset_mint, 
         associated_token::authority = vault_info // Authority set to PDA
     )]
-    pub vault_base_asset_treasury: Box<Account<'info, TokenAccount>>,
+    pub vault_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init,
@@ -340,7 +446,7 @@ pub struct MakerCreatePutOptionVault<'info> {
         associated_token::mint = quote_asset_mint, // Quote asset mint
         associated_token::authority = vault_info // Authority set to vault PDA
     )]
-    pub vault_quote_asset_treasury: Box<Account<'info, TokenAccount>>,
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init,
@@ -361,7 +467,110 @@ pub struct MakerCreatePutOptionVault<'info> {
         constraint = maker_quote_asset_account.owner.key() == initializer.key(),
         constraint = maker_quote_asset_account.mint == quote_asset_mint.key()
     )]
-    pub maker_quote_asset_account: Box<Account<'info, TokenAccount>>,
+    pub maker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+// Creates the vault factory if needed and a new vault in it in a single instruction,
+// the vault gets the next id of the factory
+#[derive(Accounts)]
+#[instruction(
+    params: MakerCreatePutOptionParams
+)]
+pub struct MakerOpenPutOptionVault<'info> {
+    #[account(
+        init_if_needed, 
+        seeds=["PutOptionVaultFactoryInfo".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref(), &params.maturity.to_le_bytes().as_ref(), &params.strike.to_le_bytes().as_ref()], 
+        bump, 
+        payer = initializer, 
+        space= std::mem::size_of::<PutOptionVaultFactoryInfo>() + 8,        
+        constraint = params.strike > 0,
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Box<Account<'info, PutOptionVaultFactoryInfo>>,
+
+    #[account(
+        init,
+        seeds=[
+            "PutOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            &vault_factory_info.upcoming_vault_id().to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = initializer, 
+        space= std::mem::size_of::<PutOptionVaultInfo>() + 8
+    )]
+    pub vault_info: Box<Account<'info, PutOptionVaultInfo>>,
+
+    #[account(
+        seeds=["AssetPairConfig".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = asset_pair_config.is_initialized == true
+    )]
+    pub asset_pair_config: Box<Account<'info, AssetPairConfig>>,
+
+    #[account(
+        mut,
+        seeds=["SeriesIndex".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        realloc = SeriesIndex::space(series_index.entries.len() + 1),
+        realloc::payer = initializer,
+        realloc::zero = false,
+        constraint = series_index.is_initialized == true
+    )]
+    pub series_index: Box<Account<'info, SeriesIndex>>,
+
+    // mint for the base_asset
+    pub base_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = initializer, // Payer will be initializer
+        associated_token::mint = base_asset_mint, 
+        associated_token::authority = vault_info // Authority set to PDA
+    )]
+    pub vault_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = initializer, // Payer will be initializer
+        associated_token::mint = quote_asset_mint, // Quote asset mint
+        associated_token::authority = vault_info // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        seeds=[
+            "PutOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            &vault_factory_info.upcoming_vault_id().to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<PutOptionMakerInfo>() + 8
+    )]
+    pub put_option_maker_info: Box<Account<'info, PutOptionMakerInfo>>,
+
+    #[account(
+        mut,
+        constraint = maker_quote_asset_account.owner.key() == initializer.key(),
+        constraint = maker_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub maker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     // Check if initializer is signer, mut is required to reduce lamports (fees)
     #[account(mut)]
@@ -370,7 +579,7 @@ pub struct MakerCreatePutOptionVault<'info> {
     // System Program requred for deduction of lamports (fees)
     pub system_program: Program<'info, System>,
     // Token Program required to call transfer instruction
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
@@ -496,11 +705,29 @@ pub struct MakerNextPutOptionVaultId<'info> {
     )]
     pub vault_factory_info: Account<'info, PutOptionVaultFactoryInfo>,
 
+    #[account(
+        seeds=["AssetPairConfig".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = asset_pair_config.is_initialized == true
+    )]
+    pub asset_pair_config: Account<'info, AssetPairConfig>,
+
+    #[account(
+        mut,
+        seeds=["SeriesIndex".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        realloc = SeriesIndex::space(series_index.entries.len() + 1),
+        realloc::payer = initializer,
+        realloc::zero = false,
+        constraint = series_index.is_initialized == true
+    )]
+    pub series_index: Box<Account<'info, SeriesIndex>>,
+
     // mint for the base_asset
-    pub base_asset_mint: Account<'info, Mint>,
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
 
     // mint for the quote asset
-    pub quote_asset_mint: Account<'info, Mint>,
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
 
     // Check if initializer is signer, mut is required to reduce lamports (fees)
     #[account(mut)]
@@ -523,6 +750,14 @@ pub struct OracleUpdatePutOptionSettlePrice<'info> {
     )]
     pub vault_factory_info: Account<'info, PutOptionVaultFactoryInfo>,
 
+    #[account(
+        mut,
+        seeds=["SeriesIndex".as_bytes().as_ref(), vault_factory_info.base_asset.as_ref(), vault_factory_info.quote_asset.as_ref()],
+        bump,
+        constraint = series_index.is_initialized == true
+    )]
+    pub series_index: Box<Account<'info, SeriesIndex>>,
+
     #[account(
         mut,
         seeds=["PutOptionSettlePriceTicketInfo".as_bytes().as_ref(), vault_factory_info.key().as_ref(), ticket_owner.key().as_ref()],
@@ -588,24 +823,24 @@ pub struct TakerSettlePutOption<'info> {
     pub put_option_taker_info: Account<'info, PutOptionTakerInfo>,
 
     // mint for the base_asset
-    pub base_asset_mint: Account<'info, Mint>,
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
 
     // mint for the quote asset
-    pub quote_asset_mint: Account<'info, Mint>,
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         constraint = vault_base_asset_treasury.mint == base_asset_mint.key(), // Base asset mint
         constraint = vault_base_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
     )]
-    pub vault_base_asset_treasury: Box<Account<'info, TokenAccount>>,
+    pub vault_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         mut,
         constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // quote asset mint
         constraint = vault_quote_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
     )]
-    pub vault_quote_asset_treasury: Box<Account<'info, TokenAccount>>,
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
 
 
     // if put option is not exercised, taker will get her base tokens back at this account
@@ -614,7 +849,7 @@ pub struct TakerSettlePutOption<'info> {
         constraint = taker_base_asset_account.owner.key() == initializer.key(),
         constraint = taker_base_asset_account.mint == base_asset_mint.key()
     )]
-    pub taker_base_asset_account: Box<Account<'info, TokenAccount>>,
+    pub taker_base_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     // if put option is exercised, taker will get her quote tokens at this account
     #[account(
@@ -622,7 +857,7 @@ pub struct TakerSettlePutOption<'info> {
         constraint = taker_quote_asset_account.owner.key() == initializer.key(),
         constraint = taker_quote_asset_account.mint == quote_asset_mint.key()
     )]
-    pub taker_quote_asset_account: Box<Account<'info, TokenAccount>>,
+    pub taker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
 
     // Check if initializer is signer, mut is required to reduce lamports (fees)
@@ -632,7 +867,7 @@ pub struct TakerSettlePutOption<'info> {
     // System Program requred for deduction of lamports (fees)
     pub system_program: Program<'info, System>,
     // Token Program required to call transfer instruction
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>
 }
 
@@ -676,17 +911,17 @@ pub struct TakerAdjustFundingPutOptionVault<'info> {
     pub put_option_taker_info: Account<'info, PutOptionTakerInfo>,
 
     // mint for the base_asset
-    pub base_asset_mint: Account<'info, Mint>,
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
 
     // mint for the quote asset
-    pub quote_asset_mint: Account<'info, Mint>,
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         constraint = vault_base_asset_treasury.mint == base_asset_mint.key(), // Base asset mint
         constraint = vault_base_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
     )]
-    pub vault_base_asset_treasury: Box<Account<'info, TokenAccount>>,
+    pub vault_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
 
     // deposit of funding will come/go from/to this account
     #[account(
@@ -694,7 +929,7 @@ pub struct TakerAdjustFundingPutOptionVault<'info> {
         constraint = taker_base_asset_account.owner.key() == initializer.key(),
         constraint = taker_base_asset_account.mint == base_asset_mint.key()
     )]
-    pub taker_base_asset_account: Box<Account<'info, TokenAccount>>,
+    pub taker_base_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     // Check if initializer is signer, mut is required to reduce lamports (fees)
     #[account(mut)]
@@ -703,17 +938,15 @@ pub struct TakerAdjustFundingPutOptionVault<'info> {
     // System Program requred for deduction of lamports (fees)
     pub system_program: Program<'info, System>,
     // Token Program required to call transfer instruction
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>
 }
 
 #[derive(Accounts)]
 #[instruction(
-    max_fair_price: u64,
-    num_lots_to_buy: u64,
-    initial_funding: u64
+    max_base_asset_to_exercise: u64
 )]
-pub struct TakerBuyLotsPutOptionVault<'info> {
+pub struct TakerExercisePutOptionEarly<'info> {
     #[account(
         constraint = vault_factory_info.strike > 0,
         constraint = vault_factory_info.is_initialized == true,
@@ -736,7 +969,7 @@ pub struct TakerBuyLotsPutOptionVault<'info> {
     pub vault_info: Account<'info, PutOptionVaultInfo>,
 
     #[account(
-        init_if_needed,
+        mut,
         seeds=[
             "PutOptionTakerInfo".as_bytes().as_ref(),
             vault_factory_info.key().as_ref(),
@@ -744,56 +977,31 @@ pub struct TakerBuyLotsPutOptionVault<'info> {
             initializer.key().as_ref()
         ],
         bump,
-        payer = initializer,
-        space = std::mem::size_of::<PutOptionTakerInfo>() + 8,
+        constraint = put_option_taker_info.put_option_vault == vault_info.key(),
         constraint = !put_option_taker_info.is_settled
     )]
     pub put_option_taker_info: Account<'info, PutOptionTakerInfo>,
 
-
     // mint for the base_asset
-    pub base_asset_mint: Account<'info, Mint>,
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
 
     // mint for the quote asset
-    pub quote_asset_mint: Account<'info, Mint>,
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
-        constraint = vault_base_asset_treasury.mint == base_asset_mint.key(), // Base asset mint
-        constraint = vault_base_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
+        constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // quote asset mint
+        constraint = vault_quote_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
     )]
-    pub vault_base_asset_treasury: Box<Account<'info, TokenAccount>>,
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    // to pay the option premium (fair price)
+    // taker will get here the strike value of the base assets she exercised
     #[account(
         mut,
         constraint = taker_quote_asset_account.owner.key() == initializer.key(),
         constraint = taker_quote_asset_account.mint == quote_asset_mint.key()
     )]
-    pub taker_quote_asset_account: Box<Account<'info, TokenAccount>>,
-
-    // deposit of initial funding will come from here
-    #[account(
-        mut,
-        constraint = taker_base_asset_account.owner.key() == initializer.key(),
-        constraint = taker_base_asset_account.mint == base_asset_mint.key()
-    )]
-    pub taker_base_asset_account: Box<Account<'info, TokenAccount>>,
-
-    // protocol fees will be paid here
-    #[account(
-        mut,
-        constraint = protocol_quote_asset_treasury.owner.key() == PROTOCOL_FEES_ADDRESS,
-        constraint = protocol_quote_asset_treasury.mint == quote_asset_mint.key()
-    )]
-    pub protocol_quote_asset_treasury: Box<Account<'info, TokenAccount>>,
-
-    // frontend fees will be paid here
-    #[account(
-        mut,
-        constraint = frontend_quote_asset_treasury.mint == quote_asset_mint.key()
-    )]
-    pub frontend_quote_asset_treasury: Box<Account<'info, TokenAccount>>,
+    pub taker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     // Check if initializer is signer, mut is required to reduce lamports (fees)
     #[account(mut)]
@@ -802,20 +1010,21 @@ pub struct TakerBuyLotsPutOptionVault<'info> {
     // System Program requred for deduction of lamports (fees)
     pub system_program: Program<'info, System>,
     // Token Program required to call transfer instruction
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>
 }
 
-
-
 #[derive(Accounts)]
-pub struct MakerSettlePutOption<'info> {
+#[instruction(
+    max_fair_price: u64,
+    num_lots_to_buy: u64,
+    initial_funding: u64
+)]
+pub struct TakerBuyLotsPutOptionVault<'info> {
     #[account(
         constraint = vault_factory_info.strike > 0,
         constraint = vault_factory_info.is_initialized == true,
-        constraint = vault_factory_info.matured == true,
-        constraint = vault_factory_info.settled_price > 0,
+        constraint = vault_factory_info.matured == false,
         constraint = vault_factory_info.base_asset == base_asset_mint.key(),
         constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
         constraint = vault_factory_info.emergency_mode == false
@@ -834,64 +1043,362 @@ pub struct MakerSettlePutOption<'info> {
     pub vault_info: Account<'info, PutOptionVaultInfo>,
 
     #[account(
-        mut,
+        init_if_needed,
         seeds=[
-            "PutOptionMakerInfo".as_bytes().as_ref(),
+            "PutOptionTakerInfo".as_bytes().as_ref(),
             vault_factory_info.key().as_ref(),
             vault_info.ord.to_le_bytes().as_ref(), 
             initializer.key().as_ref()
         ],
         bump,
-        constraint = !put_option_maker_info.is_settled
+        payer = initializer,
+        space = std::mem::size_of::<PutOptionTakerInfo>() + 8,
+        constraint = !put_option_taker_info.is_settled
     )]
-    pub put_option_maker_info: Account<'info, PutOptionMakerInfo>,
+    pub put_option_taker_info: Account<'info, PutOptionTakerInfo>,
+
 
     // mint for the base_asset
-    pub base_asset_mint: Account<'info, Mint>,
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
 
     // mint for the quote asset
-    pub quote_asset_mint: Account<'info, Mint>,
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         constraint = vault_base_asset_treasury.mint == base_asset_mint.key(), // Base asset mint
         constraint = vault_base_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
     )]
-    pub vault_base_asset_treasury: Box<Account<'info, TokenAccount>>,
+    pub vault_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    // to pay the option premium (fair price)
     #[account(
         mut,
-        constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // quote asset mint
-        constraint = vault_quote_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
+        constraint = taker_quote_asset_account.owner.key() == initializer.key(),
+        constraint = taker_quote_asset_account.mint == quote_asset_mint.key()
     )]
-    pub vault_quote_asset_treasury: Box<Account<'info, TokenAccount>>,
+    pub taker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    // deposit of initial funding will come from here
+    #[account(
+        mut,
+        constraint = taker_base_asset_account.owner.key() == initializer.key(),
+        constraint = taker_base_asset_account.mint == base_asset_mint.key()
+    )]
+    pub taker_base_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    // if put option is exercised, maker will get the base tokens she bought at strike price at this account
+    // fees accrue in the fee vault of the quote asset, part of the backend
+    // share for the insurance fund of the quote asset
     #[account(
         mut,
-        constraint = maker_base_asset_account.owner.key() == initializer.key(),
-        constraint = maker_base_asset_account.mint == base_asset_mint.key()
+        seeds=["FeeVaultInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = fee_vault_info.is_initialized == true
     )]
-    pub maker_base_asset_account: Box<Account<'info, TokenAccount>>,
+    pub fee_vault_info: Box<Account<'info, FeeVaultInfo>>,
 
-    // if put option is not exercised, maker will get her quote tokens back at this account
     #[account(
         mut,
-        constraint = maker_quote_asset_account.owner.key() == initializer.key(),
-        constraint = maker_quote_asset_account.mint == quote_asset_mint.key()
+        constraint = fee_vault_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = fee_vault_quote_asset_treasury.owner == fee_vault_info.key() // Authority set to fee vault PDA
     )]
-    pub maker_quote_asset_account: Box<Account<'info, TokenAccount>>,
+    pub fee_vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    #[account(
+        seeds=["InsuranceFundInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = insurance_fund_info.is_initialized == true
+    )]
+    pub insurance_fund_info: Box<Account<'info, InsuranceFundInfo>>,
 
-    // Check if initializer is signer, mut is required to reduce lamports (fees)
-    #[account(mut)]
-    pub initializer: Signer<'info>,
-    
-    // System Program requred for deduction of lamports (fees)
+    // registered frontend of the taker, if any, which earns its share of fees
+    #[account(
+        mut,
+        constraint = frontend_info.quote_asset == quote_asset_mint.key()
+    )]
+    pub frontend_info: Option<Box<Account<'info, FrontendInfo>>>,
+
+    // fee tiers of takers of the quote asset and rebates of makers
+    #[account(
+        seeds=["FeeTiersInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = fee_tiers_info.is_initialized == true
+    )]
+    pub fee_tiers_info: Box<Account<'info, FeeTiersInfo>>,
+
+    // rolling volume of the taker in the quote asset, for her fee tier
+    #[account(
+        init_if_needed,
+        seeds=["TakerStatsInfo".as_bytes().as_ref(), initializer.key().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<TakerStatsInfo>() + 8
+    )]
+    pub taker_stats_info: Box<Account<'info, TakerStatsInfo>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+}
+
+
+
+#[derive(Accounts)]
+#[instruction(max_net_premium: u64, num_lots: u64, num_bids: u8)]
+pub struct TakerBuyPutSpread<'info> {
+    // higher strike leg, that is bought
+    #[account(
+        constraint = long_vault_factory_info.strike > 0,
+        constraint = long_vault_factory_info.is_initialized == true,
+        constraint = long_vault_factory_info.matured == false,
+        constraint = long_vault_factory_info.base_asset == base_asset_mint.key(),
+        constraint = long_vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = long_vault_factory_info.emergency_mode == false
+    )]
+    pub long_vault_factory_info: Box<Account<'info, PutOptionVaultFactoryInfo>>,
+
+    #[account(
+        mut,
+        seeds=[
+            "PutOptionVaultInfo".as_bytes().as_ref(), 
+            long_vault_factory_info.key().as_ref(),
+            long_vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = long_vault_info.factory_vault == long_vault_factory_info.key(),
+    )]
+    pub long_vault_info: Box<Account<'info, PutOptionVaultInfo>>,
+
+    #[account(
+        init_if_needed,
+        seeds=[
+            "PutOptionTakerInfo".as_bytes().as_ref(),
+            long_vault_factory_info.key().as_ref(),
+            long_vault_info.ord.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<PutOptionTakerInfo>() + 8,
+        constraint = !put_option_taker_info.is_settled
+    )]
+    pub put_option_taker_info: Box<Account<'info, PutOptionTakerInfo>>,
+
+    // lower strike leg, that is sold
+    #[account(
+        constraint = short_vault_factory_info.strike > 0,
+        constraint = short_vault_factory_info.is_initialized == true,
+        constraint = short_vault_factory_info.matured == false,
+        constraint = short_vault_factory_info.base_asset == base_asset_mint.key(),
+        constraint = short_vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = short_vault_factory_info.emergency_mode == false,
+        constraint = short_vault_factory_info.maturity == long_vault_factory_info.maturity @ PutOptionError::SpreadLegsMismatch,
+        constraint = short_vault_factory_info.strike < long_vault_factory_info.strike @ PutOptionError::SpreadLegsMismatch,
+        constraint = short_vault_factory_info.cash_settled == long_vault_factory_info.cash_settled @ PutOptionError::SpreadLegsMismatch,
+        constraint = short_vault_factory_info.american == long_vault_factory_info.american @ PutOptionError::SpreadLegsMismatch
+    )]
+    pub short_vault_factory_info: Box<Account<'info, PutOptionVaultFactoryInfo>>,
+
+    #[account(
+        mut,
+        seeds=[
+            "PutOptionVaultInfo".as_bytes().as_ref(), 
+            short_vault_factory_info.key().as_ref(),
+            short_vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = short_vault_info.is_makers_full == false,
+        constraint = short_vault_info.factory_vault == short_vault_factory_info.key(),
+        constraint = short_vault_info.lot_size == long_vault_info.lot_size @ PutOptionError::SpreadLegsMismatch
+    )]
+    pub short_vault_info: Box<Account<'info, PutOptionVaultInfo>>,
+
+    #[account(
+        mut,
+        constraint = short_vault_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = short_vault_quote_asset_treasury.owner.key() == short_vault_info.key() // Authority set to vault PDA
+    )]
+    pub short_vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        seeds=[
+            "PutOptionMakerInfo".as_bytes().as_ref(),
+            short_vault_factory_info.key().as_ref(),
+            short_vault_info.ord.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<PutOptionMakerInfo>() + 8
+    )]
+    pub put_option_maker_info: Box<Account<'info, PutOptionMakerInfo>>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // to pay the option premium of the bought leg and the collateral of the sold leg, and to receive its premium
+    #[account(
+        mut,
+        constraint = taker_quote_asset_account.owner.key() == initializer.key(),
+        constraint = taker_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub taker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // fees accrue in the fee vault of the quote asset, part of the backend
+    // share for the insurance fund of the quote asset
+    #[account(
+        mut,
+        seeds=["FeeVaultInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = fee_vault_info.is_initialized == true
+    )]
+    pub fee_vault_info: Box<Account<'info, FeeVaultInfo>>,
+
+    #[account(
+        mut,
+        constraint = fee_vault_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = fee_vault_quote_asset_treasury.owner == fee_vault_info.key() // Authority set to fee vault PDA
+    )]
+    pub fee_vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds=["InsuranceFundInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = insurance_fund_info.is_initialized == true
+    )]
+    pub insurance_fund_info: Box<Account<'info, InsuranceFundInfo>>,
+
+    // registered frontend of the taker, if any, which earns its share of fees
+    #[account(
+        mut,
+        constraint = frontend_info.quote_asset == quote_asset_mint.key()
+    )]
+    pub frontend_info: Option<Box<Account<'info, FrontendInfo>>>,
+
+    // fee tiers of takers of the quote asset and rebates of makers
+    #[account(
+        seeds=["FeeTiersInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = fee_tiers_info.is_initialized == true
+    )]
+    pub fee_tiers_info: Box<Account<'info, FeeTiersInfo>>,
+
+    // rolling volume of the taker in the quote asset, for her fee tier
+    #[account(
+        init_if_needed,
+        seeds=["TakerStatsInfo".as_bytes().as_ref(), initializer.key().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<TakerStatsInfo>() + 8
+    )]
+    pub taker_stats_info: Box<Account<'info, TakerStatsInfo>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+}
+
+#[derive(Accounts)]
+pub struct MakerSettlePutOption<'info> {
+    #[account(
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == true,
+        constraint = vault_factory_info.settled_price > 0,
+        constraint = vault_factory_info.base_asset == base_asset_mint.key(),
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, PutOptionVaultFactoryInfo>,
+
+    #[account(
+        mut,
+        seeds=[
+            "PutOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Account<'info, PutOptionVaultInfo>,
+
+    #[account(
+        mut,
+        seeds=[
+            "PutOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            put_option_maker_info.owner.as_ref()
+        ],
+        bump,
+        constraint = !put_option_maker_info.is_settled,
+        // anyone can settle makers of margined vaults, so their takers can always get paid
+        constraint = put_option_maker_info.owner == initializer.key() || vault_info.is_margined()
+    )]
+    pub put_option_maker_info: Account<'info, PutOptionMakerInfo>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault_base_asset_treasury.mint == base_asset_mint.key(), // Base asset mint
+        constraint = vault_base_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // quote asset mint
+        constraint = vault_quote_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+
+    // if put option is exercised, maker will get the base tokens she bought at strike price at this account
+    #[account(
+        mut,
+        constraint = maker_base_asset_account.owner.key() == put_option_maker_info.owner,
+        constraint = maker_base_asset_account.mint == base_asset_mint.key()
+    )]
+    pub maker_base_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // if put option is not exercised, maker will get her quote tokens back at this account
+    #[account(
+        mut,
+        constraint = maker_quote_asset_account.owner.key() == put_option_maker_info.owner,
+        constraint = maker_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub maker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
     pub system_program: Program<'info, System>,
     // Token Program required to call transfer instruction
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>
 }
 
@@ -984,3 +1491,440 @@ pub struct TakerActivatePutOptionEmergencyMode<'info> {
     pub system_program: Program<'info, System>,
 
 }
+
+#[derive(Accounts)]
+#[instruction(
+    num_lots: u64,
+    max_fair_price: u64,
+    expiry: u64
+)]
+pub struct TakerPlacePutOptionBid<'info> {
+    #[account(
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.base_asset == base_asset_mint.key(),
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Box<Account<'info, PutOptionVaultFactoryInfo>>,
+
+    #[account(
+        mut,
+        seeds=[
+            "PutOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Box<Account<'info, PutOptionVaultInfo>>,
+
+    #[account(
+        init,
+        seeds=[
+            "PutOptionTakerBidInfo".as_bytes().as_ref(),
+            vault_info.key().as_ref(),
+            initializer.key().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<PutOptionTakerBidInfo>() + 8
+    )]
+    pub taker_bid_info: Box<Account<'info, PutOptionTakerBidInfo>>,
+
+    #[account(
+        init,
+        payer = initializer, // Payer will be initializer
+        associated_token::mint = quote_asset_mint, // Quote asset mint
+        associated_token::authority = taker_bid_info // Authority set to bid PDA
+    )]
+    pub bid_quote_asset_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // lots bought by the bid are added to the PutOptionTakerInfo of the bidder
+    #[account(
+        init_if_needed,
+        seeds=[
+            "PutOptionTakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<PutOptionTakerInfo>() + 8,
+        constraint = !put_option_taker_info.is_settled
+    )]
+    pub put_option_taker_info: Box<Account<'info, PutOptionTakerInfo>>,
+
+    // mint for the base_asset
+    pub base_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // to escrow the option premium
+    #[account(
+        mut,
+        constraint = taker_quote_asset_account.owner.key() == initializer.key(),
+        constraint = taker_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub taker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct TakerCancelPutOptionBid<'info> {
+    #[account(
+        mut,
+        seeds=[
+            "PutOptionTakerBidInfo".as_bytes().as_ref(),
+            taker_bid_info.put_option_vault.as_ref(),
+            initializer.key().as_ref()
+        ],
+        bump = taker_bid_info.bump,
+        close = initializer,
+        constraint = taker_bid_info.owner == initializer.key()
+    )]
+    pub taker_bid_info: Box<Account<'info, PutOptionTakerBidInfo>>,
+
+    #[account(
+        mut,
+        constraint = bid_quote_asset_escrow.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = bid_quote_asset_escrow.owner == taker_bid_info.key() // Authority set to bid PDA
+    )]
+    pub bid_quote_asset_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // escrowed premium left is refunded here
+    #[account(
+        mut,
+        constraint = taker_quote_asset_account.owner.key() == initializer.key(),
+        constraint = taker_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub taker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Check if initializer is signer, mut is required to receive lamports (rent)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+// Anyone can match resting taker bids with makers of the vault, typically right after a fair price update.
+// Bids and makers are passed in remaining accounts
+#[derive(Accounts)]
+pub struct CrankMatchPutOptionTakerBids<'info> {
+    #[account(
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.base_asset == base_asset_mint.key(),
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Box<Account<'info, PutOptionVaultFactoryInfo>>,
+
+    #[account(
+        mut,
+        seeds=[
+            "PutOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Box<Account<'info, PutOptionVaultInfo>>,
+
+    // mint for the base_asset
+    pub base_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // fees accrue in the fee vault of the quote asset, part of the backend
+    // share for the insurance fund of the quote asset
+    #[account(
+        mut,
+        seeds=["FeeVaultInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = fee_vault_info.is_initialized == true
+    )]
+    pub fee_vault_info: Box<Account<'info, FeeVaultInfo>>,
+
+    #[account(
+        mut,
+        constraint = fee_vault_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = fee_vault_quote_asset_treasury.owner == fee_vault_info.key() // Authority set to fee vault PDA
+    )]
+    pub fee_vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds=["InsuranceFundInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = insurance_fund_info.is_initialized == true
+    )]
+    pub insurance_fund_info: Box<Account<'info, InsuranceFundInfo>>,
+
+    pub initializer: Signer<'info>,
+
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    amount: u64
+)]
+pub struct MakerAddPutOptionMargin<'info> {
+    #[account(
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, PutOptionVaultFactoryInfo>,
+
+    #[account(
+        seeds=[
+            "PutOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Account<'info, PutOptionVaultInfo>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = vault_quote_asset_treasury.owner == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds=[
+            "PutOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        constraint = put_option_maker_info.put_option_vault == vault_info.key(),
+        constraint = put_option_maker_info.is_settled == false
+    )]
+    pub put_option_maker_info: Account<'info, PutOptionMakerInfo>,
+
+    #[account(
+        mut,
+        constraint = maker_quote_asset_account.owner.key() == initializer.key(),
+        constraint = maker_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub maker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    amount: u64
+)]
+pub struct FundPutOptionSafetyFund<'info> {
+    #[account(
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Account<'info, PutOptionVaultFactoryInfo>,
+
+    #[account(
+        mut,
+        seeds=[
+            "PutOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Account<'info, PutOptionVaultInfo>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = vault_quote_asset_treasury.owner == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = funder_quote_asset_account.owner.key() == initializer.key(),
+        constraint = funder_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub funder_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CrankReturnPutOptionSafetyFund<'info> {
+    #[account(
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == true,
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key()
+    )]
+    pub vault_factory_info: Box<Account<'info, PutOptionVaultFactoryInfo>>,
+
+    #[account(
+        mut,
+        seeds=[
+            "PutOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Box<Account<'info, PutOptionVaultInfo>>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = vault_quote_asset_treasury.owner == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds=["InsuranceFundInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = insurance_fund_info.is_initialized == true
+    )]
+    pub insurance_fund_info: Box<Account<'info, InsuranceFundInfo>>,
+
+    #[account(
+        mut,
+        constraint = insurance_fund_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = insurance_fund_quote_asset_treasury.owner == insurance_fund_info.key() // Authority set to insurance fund PDA
+    )]
+    pub insurance_fund_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Anyone can crank it, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct LiquidatePutOptionMaker<'info> {
+    #[account(
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Box<Account<'info, PutOptionVaultFactoryInfo>>,
+
+    #[account(
+        mut,
+        seeds=[
+            "PutOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Box<Account<'info, PutOptionVaultInfo>>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = vault_quote_asset_treasury.owner == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // maker being liquidated
+    #[account(
+        mut,
+        seeds=[
+            "PutOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            put_option_maker_info.owner.as_ref()
+        ],
+        bump,
+        constraint = put_option_maker_info.put_option_vault == vault_info.key(),
+        constraint = put_option_maker_info.is_settled == false
+    )]
+    pub put_option_maker_info: Box<Account<'info, PutOptionMakerInfo>>,
+
+    // liquidator takes over the options sold by the maker, fully collateralized
+    #[account(
+        init_if_needed,
+        seeds=[
+            "PutOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<PutOptionMakerInfo>() + 8
+    )]
+    pub liquidator_maker_info: Box<Account<'info, PutOptionMakerInfo>>,
+
+    #[account(
+        mut,
+        constraint = liquidator_quote_asset_account.owner.key() == initializer.key(),
+        constraint = liquidator_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub liquidator_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+// Anchor checks nothing about remaining accounts. These helpers return None (or false) instead
+// of panicking, so that each controller can fail with its own AccountValidationError.
+
+// Same account passed twice would be loaded twice and the last serialization would win
+pub fn has_duplicate_accounts(accounts: &[AccountInfo]) -> bool {
+    accounts.iter().enumerate().any(|(i, account)| accounts[..i].iter().any(|other| other.key == account.key))
+}
+
+// Account of this program (owner and discriminator are checked by Account::try_from) that
+// is going to be modified, so it must be writable
+pub fn load_writable_program_account<'info, T>(info: &AccountInfo<'info>) -> Option<Account<'info, T>>
+where
+    T: AccountSerialize + AccountDeserialize + Owner + Clone
+{
+    if !info.is_writable || *info.owner != crate::ID {
+        return None;
+    }
+    Account::try_from(info).ok()
+}
+
+// Token account (SPL Token or Token-2022) receiving or sending tokens, so it must be writable
+pub fn load_writable_token_account<'info>(info: &AccountInfo<'info>) -> Option<InterfaceAccount<'info, TokenAccount>> {
+    if !info.is_writable {
+        return None;
+    }
+    InterfaceAccount::try_from(info).ok()
+}
+
+// Whether key is the PDA of this program for seeds, searching for the canonical bump
+pub fn is_program_address(key: &Pubkey, seeds: &[&[u8]]) -> bool {
+    Pubkey::find_program_address(seeds, &crate::ID).0 == *key
+}
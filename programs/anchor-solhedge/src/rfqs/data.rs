@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+// Request for quote of a put option taker, for num_lots lots of a vault factory
+#[account]
+pub struct PutOptionRfqInfo {
+    pub is_initialized: bool,
+
+    pub owner: Pubkey,              // taker asking for quotes
+    pub vault_factory: Pubkey,      // PutOptionVaultFactoryInfo
+    pub lot_size: i8,               // 10^lot_size, for instance 0 means 1; -1 means 0.1; 2 means 100
+    pub num_lots: u64,
+    pub expiry: u64                 // quotes cannot be accepted from this time on
+}
+
+// Answer of a maker to an RFQ. The collateral for all lots is escrowed in an associated
+// token account of this PDA until the quote is accepted or withdrawn
+#[account]
+pub struct PutOptionRfqQuoteInfo {
+    pub is_initialized: bool,
+
+    pub rfq: Pubkey,
+    pub maker: Pubkey,
+    pub premium: u64,               // total premium asked for all lots, in quote asset lamports
+    pub collateral: u64,            // quote asset lamports escrowed, num_lots lots at strike
+    pub bump: u8
+}
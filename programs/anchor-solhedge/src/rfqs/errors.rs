@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum RfqError {
+    #[msg("Number of lots is zero")]
+    LotsZero,
+
+    #[msg("Premium cannot be zero")]
+    PremiumZero,
+
+    #[msg("lot_size is outside the range allowed for this asset pair")]
+    LotSizeOutOfRange,
+
+    #[msg("RFQ expiry must be in the future and before the vault factory freezes")]
+    InvalidRfqExpiry,
+
+    #[msg("RFQ has expired")]
+    RfqExpired,
+
+    #[msg("maturity is too early")]
+    MaturityTooEarly,
+
+    #[msg("Quote premium is not the expected one")]
+    QuoteChanged,
+
+    #[msg("RFQs do not support mints with transfer fees")]
+    TransferFeeMint,
+
+    #[msg("Premium does not cover protocol fees")]
+    OptionPremiumTooLow,
+
+    #[msg("Overflow")]
+    Overflow,
+
+    #[msg("Insufficient funds")]
+    InsufficientFunds,
+
+    #[msg("Makers cannot quote their own RFQs")]
+    SelfQuote,
+
+    #[msg("Options of this vault factory have been knocked out")]
+    KnockedOut
+}
@@ -0,0 +1,339 @@
+use anchor_lang::prelude::*;
+use crate::rfqs::validators::*;
+use crate::rfqs::errors::RfqError;
+use crate::FREEZE_SECONDS;
+use crate::MakerCreatePutOptionParams;
+use anchor_spl::token_interface::{self, TransferChecked, CloseAccount};
+use crate::transfer_fees::has_transfer_fee;
+use crate::frontends::fe_controller::{frontend_fee_share_bps, record_frontend_trade};
+use crate::native_sol::{wrap_native_sol_if_needed, unwrap_native_sol_if_needed};
+use crate::put_options::po_controller as po;
+use crate::put_options::data::MakerAllocation;
+
+// A taker asks makers for a private quote on num_lots lots of a put option vault factory.
+// Quotes can be accepted until expiry, which must come before the factory freezes
+pub fn taker_post_put_option_rfq(ctx: Context<TakerPostPutOptionRfq>,
+    num_lots: u64,
+    lot_size: i8,
+    expiry: u64
+) -> Result<()> {
+    require!(
+        num_lots > 0,
+        RfqError::LotsZero
+    );
+
+    require!(
+        lot_size >= ctx.accounts.asset_pair_config.min_lot_size && lot_size <= ctx.accounts.asset_pair_config.max_lot_size,
+        RfqError::LotSizeOutOfRange
+    );
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    let freeze_time = ctx.accounts.vault_factory_info.maturity.checked_sub(FREEZE_SECONDS).unwrap();
+    require!(
+        current_time < freeze_time,
+        RfqError::MaturityTooEarly
+    );
+    require!(
+        expiry > current_time && expiry <= freeze_time,
+        RfqError::InvalidRfqExpiry
+    );
+
+    ctx.accounts.rfq_info.owner = ctx.accounts.initializer.key();
+    ctx.accounts.rfq_info.vault_factory = ctx.accounts.vault_factory_info.key();
+    ctx.accounts.rfq_info.lot_size = lot_size;
+    ctx.accounts.rfq_info.num_lots = num_lots;
+    ctx.accounts.rfq_info.expiry = expiry;
+
+    ctx.accounts.rfq_info.is_initialized = true;
+    msg!("PutOptionRfqInfo initialized for {} lots", num_lots);
+
+    Ok(())
+}
+
+// Closes the RFQ, quotes already posted for it can still be withdrawn by their makers
+pub fn taker_cancel_put_option_rfq(_ctx: Context<TakerCancelPutOptionRfq>) -> Result<()> {
+    msg!("PutOptionRfqInfo closed");
+    Ok(())
+}
+
+// A maker answers an RFQ with the total premium she asks, escrowing the collateral
+// for all the lots at strike until the quote is accepted or withdrawn
+pub fn maker_quote_put_option_rfq(ctx: Context<MakerQuotePutOptionRfq>,
+    premium: u64
+) -> Result<()> {
+    require!(
+        premium > 0,
+        RfqError::PremiumZero
+    );
+
+    require!(
+        ctx.accounts.rfq_info.owner != ctx.accounts.initializer.key(),
+        RfqError::SelfQuote
+    );
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        current_time < ctx.accounts.rfq_info.expiry,
+        RfqError::RfqExpired
+    );
+    require!(
+        !ctx.accounts.vault_factory_info.is_knocked_out(),
+        RfqError::KnockedOut
+    );
+
+    // The escrow is moved to the vault as is once accepted, there is no way to gross up for fees
+    require!(
+        !has_transfer_fee(&ctx.accounts.quote_asset_mint.to_account_info())?,
+        RfqError::TransferFeeMint
+    );
+
+    let collateral = compute_put_option_rfq_collateral(
+        ctx.accounts.rfq_info.num_lots,
+        ctx.accounts.rfq_info.lot_size,
+        ctx.accounts.vault_factory_info.strike
+    )?;
+
+    wrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.maker_quote_asset_account.to_account_info(),
+        collateral,
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    ctx.accounts.maker_quote_asset_account.reload()?;
+
+    require!(
+        ctx.accounts.maker_quote_asset_account.amount >= collateral,
+        RfqError::InsufficientFunds
+    );
+
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.maker_quote_asset_account.to_account_info(),
+        mint: ctx.accounts.quote_asset_mint.to_account_info(),
+        to: ctx.accounts.quote_collateral_escrow.to_account_info(),
+        authority: ctx.accounts.initializer.to_account_info(),
+    };
+    let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
+    token_interface::transfer_checked(token_transfer_context, collateral, ctx.accounts.quote_asset_mint.decimals)?;
+    msg!("Escrowed {} USDC lamports of collateral for a quote of {} USDC lamports", collateral, premium);
+
+    ctx.accounts.quote_info.rfq = ctx.accounts.rfq_info.key();
+    ctx.accounts.quote_info.maker = ctx.accounts.initializer.key();
+    ctx.accounts.quote_info.premium = premium;
+    ctx.accounts.quote_info.collateral = collateral;
+    ctx.accounts.quote_info.bump = *ctx.bumps.get("quote_info").unwrap();
+
+    ctx.accounts.quote_info.is_initialized = true;
+    msg!("PutOptionRfqQuoteInfo initialized");
+
+    Ok(())
+}
+
+// Closes a quote that has not been accepted, returning the escrowed collateral to the maker.
+// Returns the amount refunded
+pub fn maker_withdraw_put_option_rfq_quote(ctx: Context<MakerWithdrawPutOptionRfqQuote>) -> Result<u64> {
+    let refund_amount = ctx.accounts.quote_collateral_escrow.amount;
+
+    // Preparing PDA signer
+    let maker = ctx.accounts.quote_info.maker;
+    let seeds = &[
+        "PutOptionRfqQuoteInfo".as_bytes().as_ref(),
+        ctx.accounts.quote_info.rfq.as_ref(),
+        maker.as_ref(),
+        &[ctx.accounts.quote_info.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    if refund_amount > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.quote_collateral_escrow.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.maker_quote_asset_account.to_account_info(),
+            authority: ctx.accounts.quote_info.to_account_info(),
+        };
+        let token_transfer_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::transfer_checked(token_transfer_context, refund_amount, ctx.accounts.quote_asset_mint.decimals)?;
+        msg!("Refunded {} USDC lamports of escrowed collateral", refund_amount);
+    }
+
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.quote_collateral_escrow.to_account_info(),
+        destination: ctx.accounts.initializer.to_account_info(),
+        authority: ctx.accounts.quote_info.to_account_info(),
+    };
+    let close_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+    token_interface::close_account(close_context)?;
+
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.maker_quote_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+
+    Ok(refund_amount)
+}
+
+// The taker accepts a quote: the escrowed collateral becomes a new vault of the factory with the
+// quoting maker as its only maker and the taker as its only taker, all lots sold at the quoted premium.
+// From then on the vault is settled like any other. Returns the id of the new vault
+pub fn taker_accept_put_option_rfq_quote(ctx: Context<TakerAcceptPutOptionRfqQuote>,
+    expected_premium: u64
+) -> Result<u64> {
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        ctx.accounts.vault_factory_info.maturity > current_time.checked_add(FREEZE_SECONDS).unwrap(),
+        RfqError::MaturityTooEarly
+    );
+    require!(
+        current_time < ctx.accounts.rfq_info.expiry,
+        RfqError::RfqExpired
+    );
+    // Guards the taker against a quote replaced between reading it and accepting it
+    require!(
+        ctx.accounts.quote_info.premium == expected_premium,
+        RfqError::QuoteChanged
+    );
+    // The quote may have been posted before the barrier was crossed, knocked-out options would never pay
+    require!(
+        !ctx.accounts.vault_factory_info.is_knocked_out(),
+        RfqError::KnockedOut
+    );
+
+    let premium = ctx.accounts.quote_info.premium;
+    let total_fees = (premium as f64)*(ctx.accounts.vault_factory_info.protocol_fees_bps as f64)/10000.0;
+    // Fees are only shared with registered frontends
+    let frontend_fee_share = (frontend_fee_share_bps(&ctx.accounts.frontend_info) as f64)/10000.0;
+    let backend_share = (total_fees*(1.0 - frontend_fee_share)).ceil() as u64;
+    let frontend_share = (total_fees*frontend_fee_share).ceil() as u64;
+    require!(
+        premium > backend_share + frontend_share,
+        RfqError::OptionPremiumTooLow
+    );
+    let premium_to_maker = premium.checked_sub(backend_share).unwrap().checked_sub(frontend_share).unwrap();
+
+    let vault_id = ctx.accounts.vault_factory_info.next_vault_id;
+    ctx.accounts.vault_factory_info.next_vault_id = ctx.accounts.vault_factory_info.next_vault_id.checked_add(1).unwrap();
+
+    // Moving the escrowed collateral to the new vault
+    let collateral = ctx.accounts.quote_info.collateral;
+    {
+        let maker = ctx.accounts.quote_info.maker;
+        let seeds = &[
+            "PutOptionRfqQuoteInfo".as_bytes().as_ref(),
+            ctx.accounts.quote_info.rfq.as_ref(),
+            maker.as_ref(),
+            &[ctx.accounts.quote_info.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.quote_collateral_escrow.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+            authority: ctx.accounts.quote_info.to_account_info(),
+        };
+        let token_transfer_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::transfer_checked(token_transfer_context, collateral, ctx.accounts.quote_asset_mint.decimals)?;
+        msg!("Transferred {} USDC lamports of escrowed collateral to quote asset treasury", collateral);
+
+        let cpi_accounts = CloseAccount {
+            account: ctx.accounts.quote_collateral_escrow.to_account_info(),
+            destination: ctx.accounts.maker.to_account_info(),
+            authority: ctx.accounts.quote_info.to_account_info(),
+        };
+        let close_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::close_account(close_context)?;
+    }
+
+    let params = MakerCreatePutOptionParams {
+        maturity: ctx.accounts.vault_factory_info.maturity,
+        strike: ctx.accounts.vault_factory_info.strike,
+        max_makers: 1,
+        max_takers: 1,
+        lot_size: ctx.accounts.rfq_info.lot_size,
+        num_lots_to_sell: ctx.accounts.rfq_info.num_lots,
+        premium_limit: 0,
+        cash_settled: ctx.accounts.vault_factory_info.cash_settled,
+        american: ctx.accounts.vault_factory_info.american,
+        barrier: ctx.accounts.vault_factory_info.barrier,
+        barrier_up: ctx.accounts.vault_factory_info.barrier_up,
+        knock_in: ctx.accounts.vault_factory_info.knock_in,
+        auction_seconds: 0,
+        auction_start_multiple_bps: 0,
+        auction_floor_multiple_bps: 0,
+        maker_allocation: MakerAllocation::TakerChoice,
+        initial_margin_bps: 0,
+        maintenance_margin_bps: 0,
+        liquidation_bonus_bps: 0
+    };
+    po::init_put_option_vault_with_first_maker(
+        ctx.accounts.vault_factory_info.key(),
+        &mut ctx.accounts.vault_info,
+        &mut ctx.accounts.put_option_maker_info,
+        ctx.accounts.maker.key(),
+        &params,
+        vault_id,
+        collateral
+    );
+
+    // All the lots of the maker are sold to the taker
+    ctx.accounts.put_option_maker_info.volume_sold = collateral;
+    ctx.accounts.put_option_maker_info.is_all_sold = true;
+    ctx.accounts.vault_info.makers_total_pending_sell = ctx.accounts.vault_info.makers_total_pending_sell.checked_sub(collateral).unwrap();
+
+    po::init_put_option_taker_info(&mut ctx.accounts.vault_info, &mut ctx.accounts.put_option_taker_info, ctx.accounts.initializer.key())?;
+    let max_base_asset_f64 = (ctx.accounts.rfq_info.num_lots as f64)*10.0f64.powf(ctx.accounts.rfq_info.lot_size as f64)*(10.0f64.powf(ctx.accounts.base_asset_mint.decimals as f64));
+    require!(
+        max_base_asset_f64.is_finite(),
+        RfqError::Overflow
+    );
+    ctx.accounts.put_option_taker_info.max_base_asset = max_base_asset_f64.ceil() as u64;
+
+    // Taker pays the premium to the maker and fees to the fee vault of the quote asset
+    wrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.taker_quote_asset_account.to_account_info(),
+        premium,
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    require!(
+        token_interface::accessor::amount(&ctx.accounts.taker_quote_asset_account.to_account_info())? >= premium,
+        RfqError::InsufficientFunds
+    );
+    let payments = [
+        (ctx.accounts.maker_quote_asset_account.to_account_info(), premium_to_maker),
+        (ctx.accounts.fee_vault_quote_asset_treasury.to_account_info(), backend_share + frontend_share)
+    ];
+    for (destination, amount) in payments {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.taker_quote_asset_account.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: destination,
+            authority: ctx.accounts.initializer.to_account_info(),
+        };
+        let token_transfer_context = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token_interface::transfer_checked(token_transfer_context, amount, ctx.accounts.quote_asset_mint.decimals)?;
+    }
+    msg!("Paid {} USDC lamports of premium, {} to maker", premium, premium_to_maker);
+    ctx.accounts.fee_vault_info.accrue_backend_fees(backend_share, &ctx.accounts.insurance_fund_info);
+    record_frontend_trade(&mut ctx.accounts.frontend_info, &mut ctx.accounts.fee_vault_info, premium, frontend_share);
+
+    Ok(vault_id)
+}
+
+// Quote asset lamports backing num_lots lots at strike, rounded up per lot like when takers buy lots
+fn compute_put_option_rfq_collateral(num_lots: u64, lot_size: i8, strike: u64) -> Result<u64> {
+    let lot_value_f64 = 10.0f64.powf(lot_size as f64)*(strike as f64);
+    require!(
+        lot_value_f64.is_finite() && lot_value_f64 > 0.0,
+        RfqError::Overflow
+    );
+    let lot_value = lot_value_f64.ceil() as u64;
+
+    Ok(lot_value.checked_mul(num_lots).ok_or(RfqError::Overflow)?)
+}
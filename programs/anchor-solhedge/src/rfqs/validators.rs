@@ -0,0 +1,352 @@
+use anchor_lang::prelude::*;
+use crate::insurance_funds::data::InsuranceFundInfo;
+use crate::fee_vaults::data::FeeVaultInfo;
+use crate::frontends::data::FrontendInfo;
+use crate::rfqs::data::{PutOptionRfqInfo, PutOptionRfqQuoteInfo};
+use crate::put_options::data::{
+    PutOptionVaultFactoryInfo,
+    PutOptionVaultInfo, PutOptionMakerInfo, PutOptionTakerInfo
+};
+use crate::asset_pairs::data::AssetPairConfig;
+use anchor_spl::token_interface::{Mint, TokenInterface, TokenAccount};
+use anchor_spl::associated_token::AssociatedToken;
+
+#[derive(Accounts)]
+#[instruction(
+    num_lots: u64,
+    lot_size: i8,
+    expiry: u64
+)]
+pub struct TakerPostPutOptionRfq<'info> {
+    #[account(
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Box<Account<'info, PutOptionVaultFactoryInfo>>,
+
+    #[account(
+        seeds=["AssetPairConfig".as_bytes().as_ref(), vault_factory_info.base_asset.as_ref(), vault_factory_info.quote_asset.as_ref()],
+        bump,
+        constraint = asset_pair_config.is_initialized == true
+    )]
+    pub asset_pair_config: Box<Account<'info, AssetPairConfig>>,
+
+    #[account(
+        init,
+        seeds=[
+            "PutOptionRfqInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            initializer.key().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<PutOptionRfqInfo>() + 8
+    )]
+    pub rfq_info: Box<Account<'info, PutOptionRfqInfo>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct TakerCancelPutOptionRfq<'info> {
+    #[account(
+        mut,
+        seeds=[
+            "PutOptionRfqInfo".as_bytes().as_ref(),
+            rfq_info.vault_factory.as_ref(),
+            initializer.key().as_ref()
+        ],
+        bump,
+        close = initializer,
+        constraint = rfq_info.owner == initializer.key()
+    )]
+    pub rfq_info: Box<Account<'info, PutOptionRfqInfo>>,
+
+    // Check if initializer is signer, mut is required to receive lamports (rent)
+    #[account(mut)]
+    pub initializer: Signer<'info>
+}
+
+#[derive(Accounts)]
+#[instruction(
+    premium: u64
+)]
+pub struct MakerQuotePutOptionRfq<'info> {
+    #[account(
+        constraint = rfq_info.is_initialized == true
+    )]
+    pub rfq_info: Box<Account<'info, PutOptionRfqInfo>>,
+
+    #[account(
+        constraint = vault_factory_info.key() == rfq_info.vault_factory,
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Box<Account<'info, PutOptionVaultFactoryInfo>>,
+
+    #[account(
+        init,
+        seeds=[
+            "PutOptionRfqQuoteInfo".as_bytes().as_ref(),
+            rfq_info.key().as_ref(),
+            initializer.key().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<PutOptionRfqQuoteInfo>() + 8
+    )]
+    pub quote_info: Box<Account<'info, PutOptionRfqQuoteInfo>>,
+
+    #[account(
+        init,
+        payer = initializer, // Payer will be initializer
+        associated_token::mint = quote_asset_mint, // Quote asset mint
+        associated_token::authority = quote_info // Authority set to quote PDA
+    )]
+    pub quote_collateral_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // collateral comes from here
+    #[account(
+        mut,
+        constraint = maker_quote_asset_account.owner.key() == initializer.key(),
+        constraint = maker_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub maker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct MakerWithdrawPutOptionRfqQuote<'info> {
+    #[account(
+        mut,
+        seeds=[
+            "PutOptionRfqQuoteInfo".as_bytes().as_ref(),
+            quote_info.rfq.as_ref(),
+            initializer.key().as_ref()
+        ],
+        bump = quote_info.bump,
+        close = initializer,
+        constraint = quote_info.maker == initializer.key()
+    )]
+    pub quote_info: Box<Account<'info, PutOptionRfqQuoteInfo>>,
+
+    #[account(
+        mut,
+        constraint = quote_collateral_escrow.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = quote_collateral_escrow.owner == quote_info.key() // Authority set to quote PDA
+    )]
+    pub quote_collateral_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // escrowed collateral is returned here
+    #[account(
+        mut,
+        constraint = maker_quote_asset_account.owner.key() == initializer.key(),
+        constraint = maker_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub maker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Check if initializer is signer, mut is required to receive lamports (rent)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    expected_premium: u64
+)]
+pub struct TakerAcceptPutOptionRfqQuote<'info> {
+    #[account(
+        mut,
+        seeds=[
+            "PutOptionRfqInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            initializer.key().as_ref()
+        ],
+        bump,
+        close = initializer,
+        constraint = rfq_info.owner == initializer.key()
+    )]
+    pub rfq_info: Box<Account<'info, PutOptionRfqInfo>>,
+
+    #[account(
+        mut,
+        constraint = vault_factory_info.key() == rfq_info.vault_factory,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.base_asset == base_asset_mint.key(),
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Box<Account<'info, PutOptionVaultFactoryInfo>>,
+
+    #[account(
+        mut,
+        seeds=[
+            "PutOptionRfqQuoteInfo".as_bytes().as_ref(),
+            rfq_info.key().as_ref(),
+            maker.key().as_ref()
+        ],
+        bump = quote_info.bump,
+        close = maker,
+        constraint = quote_info.maker == maker.key()
+    )]
+    pub quote_info: Box<Account<'info, PutOptionRfqQuoteInfo>>,
+
+    #[account(
+        mut,
+        constraint = quote_collateral_escrow.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = quote_collateral_escrow.owner == quote_info.key() // Authority set to quote PDA
+    )]
+    pub quote_collateral_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // private vault of the maker and the taker, it gets the next id of the factory
+    #[account(
+        init,
+        seeds=[
+            "PutOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            &vault_factory_info.upcoming_vault_id().to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = initializer, 
+        space= std::mem::size_of::<PutOptionVaultInfo>() + 8
+    )]
+    pub vault_info: Box<Account<'info, PutOptionVaultInfo>>,
+
+    #[account(
+        init,
+        payer = initializer, // Payer will be initializer
+        associated_token::mint = base_asset_mint, 
+        associated_token::authority = vault_info // Authority set to PDA
+    )]
+    pub vault_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = initializer, // Payer will be initializer
+        associated_token::mint = quote_asset_mint, // Quote asset mint
+        associated_token::authority = vault_info // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        seeds=[
+            "PutOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            &vault_factory_info.upcoming_vault_id().to_le_bytes().as_ref(), 
+            maker.key().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<PutOptionMakerInfo>() + 8
+    )]
+    pub put_option_maker_info: Box<Account<'info, PutOptionMakerInfo>>,
+
+    #[account(
+        init,
+        seeds=[
+            "PutOptionTakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            &vault_factory_info.upcoming_vault_id().to_le_bytes().as_ref(), 
+            initializer.key().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<PutOptionTakerInfo>() + 8
+    )]
+    pub put_option_taker_info: Box<Account<'info, PutOptionTakerInfo>>,
+
+    // mint for the base_asset
+    pub base_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // maker of the accepted quote, gets back the rent of the quote
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+
+    // premium is paid here
+    #[account(
+        mut,
+        constraint = maker_quote_asset_account.owner.key() == maker.key(),
+        constraint = maker_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub maker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // to pay the option premium
+    #[account(
+        mut,
+        constraint = taker_quote_asset_account.owner.key() == initializer.key(),
+        constraint = taker_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub taker_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // fees accrue in the fee vault of the quote asset, part of the backend
+    // share for the insurance fund of the quote asset
+    #[account(
+        mut,
+        seeds=["FeeVaultInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = fee_vault_info.is_initialized == true
+    )]
+    pub fee_vault_info: Box<Account<'info, FeeVaultInfo>>,
+
+    #[account(
+        mut,
+        constraint = fee_vault_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = fee_vault_quote_asset_treasury.owner == fee_vault_info.key() // Authority set to fee vault PDA
+    )]
+    pub fee_vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds=["InsuranceFundInfo".as_bytes().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = insurance_fund_info.is_initialized == true
+    )]
+    pub insurance_fund_info: Box<Account<'info, InsuranceFundInfo>>,
+
+    // registered frontend of the taker, if any, which earns its share of fees
+    #[account(
+        mut,
+        constraint = frontend_info.quote_asset == quote_asset_mint.key()
+    )]
+    pub frontend_info: Option<Box<Account<'info, FrontendInfo>>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct CoveredCallRollVaultInfo {
+    pub is_initialized: bool,
+
+    pub base_asset: Pubkey,
+    pub quote_asset: Pubkey,
+    pub strike_offset_bps: u16,         // strike of the next series is the last settled price plus this offset, in basis points
+    pub tenor_seconds: u64,             // maturity of the next series is the last maturity plus this tenor
+    pub lot_size: i8,                   //10^lot_size, for instance 0 means 1; -1 means 0.1; 2 means 100
+    pub max_takers: u16,
+
+    pub total_shares: u64,
+    pub base_asset_idle: u64,           // base asset lamports in the roll vault treasury, not committed to a series
+    pub quote_asset_accrued: u64,       // premiums and strike payments received, in quote asset lamports
+
+    pub is_in_series: bool,             // if the idle base asset has been committed to a call option vault that has not been settled yet
+    pub base_asset_committed: u64,      // base asset lamports deposited in the current call option vault
+    pub current_call_option_vault: Pubkey,
+    pub next_maturity: u64,
+    pub next_strike: u64
+}
+
+#[account]
+pub struct CoveredCallRollShareInfo {
+    pub is_initialized: bool,
+
+    pub shares: u64,
+    pub owner: Pubkey,
+    pub roll_vault: Pubkey
+}
+
+#[account]
+pub struct CashSecuredPutRollVaultInfo {
+    pub is_initialized: bool,
+
+    pub base_asset: Pubkey,
+    pub quote_asset: Pubkey,
+    pub share_mint: Pubkey,
+    pub strike_offset_bps: u16,             // strike of each series is the oracle spot price minus this offset, in basis points
+    pub tenor_seconds: u64,                 // maturity of the next series is the last maturity plus this tenor
+    pub lot_size: i8,                       //10^lot_size, for instance 0 means 1; -1 means 0.1; 2 means 100
+    pub max_takers: u16,
+
+    pub epoch: u64,                         // deposit and withdraw requests made now will be processed when this epoch is closed
+    pub total_shares: u64,                  // shares issued, including the ones not yet claimed
+    pub quote_asset_idle: u64,              // quote asset lamports owned by the shareholders, not committed to a series
+    pub pending_deposits: u64,              // quote asset lamports queued for deposit in the current epoch
+    pub pending_withdraw_shares: u64,       // shares queued for withdrawal in the current epoch (held in the share escrow)
+    pub quote_reserved_for_withdrawals: u64,// quote asset lamports of processed withdrawals that have not been claimed yet
+
+    pub is_in_series: bool,                 // if the idle quote asset has been committed to a put option vault that has not been settled yet
+    pub quote_asset_committed: u64,         // quote asset lamports deposited in the current put option vault
+    pub current_put_option_vault: Pubkey,
+    pub next_maturity: u64,
+
+    pub last_spot_price: u64,               // written by the oracle, used to choose the strike of the next series
+    pub ts_last_spot_price: u64
+}
+
+#[account]
+pub struct CashSecuredPutRollEpochInfo {
+    pub roll_vault: Pubkey,
+    pub epoch: u64,
+
+    pub deposits: u64,                      // quote asset lamports deposited in this epoch
+    pub shares_minted: u64,                 // shares that these deposits were worth when the epoch was closed
+    pub withdraw_shares: u64,               // shares withdrawn in this epoch
+    pub quote_for_withdrawals: u64          // quote asset lamports that these shares were worth when the epoch was closed
+}
+
+#[account]
+pub struct CashSecuredPutRollTicketInfo {
+    pub is_initialized: bool,
+
+    pub epoch: u64,
+    pub deposit_amount: u64,
+    pub withdraw_shares: u64,
+    pub owner: Pubkey,
+    pub roll_vault: Pubkey
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum RollVaultError {
+    #[msg("strike cannot be zero")]
+    StrikeZero,
+
+    #[msg("maturity is too early")]
+    MaturityTooEarly,
+
+    #[msg("maturity is too late")]
+    MaturityTooLate,
+
+    #[msg("Tenor must be longer than the freeze period and shorter than the max maturity in the future")]
+    InvalidTenor,
+
+    #[msg("Maturity and strike are not the next ones in the roll schedule")]
+    InvalidRollSchedule,
+
+    #[msg("vault_id must be the next vault id of the vault factory")]
+    InvalidVaultId,
+
+    #[msg("Roll vaults only sell physically settled options")]
+    CashSettledSeries,
+
+    #[msg("Amount cannot be zero")]
+    AmountZero,
+
+    #[msg("Not enough idle base asset to sell one lot")]
+    NothingToRoll,
+
+    #[msg("Deposits and withdrawals are only allowed between series")]
+    SeriesInProgress,
+
+    #[msg("Roll vault has no base asset to price new shares")]
+    NoBaseAssetToPriceShares,
+
+    #[msg("Not enough shares")]
+    InsufficientShares,
+
+    #[msg("Not enough funds in source account")]
+    InsufficientFunds,
+
+    #[msg("Overflow error")]
+    Overflow,
+
+    #[msg("Illegal internal state")]
+    IllegalState,
+
+    #[msg("Strike offset must be below 100%")]
+    InvalidStrikeOffset,
+
+    #[msg("Price cannot be zero")]
+    PriceZero,
+
+    #[msg("Last spot price update is too old. Please ask the oracle to make a new update")]
+    LastSpotPriceUpdateTooOld,
+
+    #[msg("Roll vaults only sell cash-settled European put options")]
+    PhysicallySettledSeries,
+
+    #[msg("Please claim your processed requests from a previous epoch first")]
+    UnclaimedTicket,
+
+    #[msg("Requests of this ticket have not been processed yet")]
+    TicketNotProcessed,
+
+    #[msg("Roll vault has no quote asset to price shares")]
+    NoQuoteAssetToPriceShares,
+
+    #[msg("Roll vaults only trade series without barrier")]
+    BarrierSeries,
+
+    #[msg("Roll vaults do not support mints with transfer fees")]
+    TransferFeeMint,
+
+    #[msg("Insufficient time passed since maturity to activate emergency mode, please wait more")]
+    EmergencyModeTooEarly
+}
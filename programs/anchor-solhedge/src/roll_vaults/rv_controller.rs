@@ -0,0 +1,1042 @@
+use anchor_lang::prelude::*;
+use crate::roll_vaults::validators::*;
+use crate::roll_vaults::errors::RollVaultError;
+use crate::call_options::co_controller as co;
+use crate::put_options::po_controller as po;
+use crate::{
+    FREEZE_SECONDS,
+    MAX_MATURITY_FUTURE_SECONDS,
+    MAX_SECONDS_FROM_LAST_FAIR_PRICE_UPDATE
+};
+use crate::{
+    CreateCoveredCallRollVaultParams, MakerCreateCallOptionParams,
+    CreateCashSecuredPutRollVaultParams, MakerCreatePutOptionParams
+};
+use anchor_spl::token_interface::{self, TransferChecked, MintTo, Burn};
+use crate::anchor_solhedge::*;
+use crate::asset_pairs::ap_controller as ap;
+use crate::asset_pairs::data::SeriesKind;
+use crate::transfer_fees::has_transfer_fee;
+use crate::native_sol::{wrap_native_sol_if_needed, unwrap_native_sol_if_needed};
+use crate::emergency_mode::is_emergency_grace_period_over;
+use crate::roll_vaults::data::{CashSecuredPutRollVaultInfo, CashSecuredPutRollTicketInfo};
+use crate::put_options::data::MakerAllocation;
+
+pub fn create_covered_call_roll_vault(ctx: Context<CreateCoveredCallRollVault>,
+    params: CreateCoveredCallRollVaultParams
+) -> Result<()> {
+    require!(
+        params.first_strike > 0,
+        RollVaultError::StrikeZero
+    );
+
+    require!(
+        params.tenor_seconds > FREEZE_SECONDS && params.tenor_seconds <= MAX_MATURITY_FUTURE_SECONDS,
+        RollVaultError::InvalidTenor
+    );
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        params.first_maturity > current_time.checked_add(FREEZE_SECONDS).unwrap(),
+        RollVaultError::MaturityTooEarly
+    );
+    require!(
+        params.first_maturity <= current_time.checked_add(MAX_MATURITY_FUTURE_SECONDS).unwrap(),
+        RollVaultError::MaturityTooLate
+    );
+
+    // Cranks move assets between the roll vault and option vaults, transfer fees
+    // would make the vault hold less than it accounts for
+    require!(
+        !has_transfer_fee(&ctx.accounts.base_asset_mint.to_account_info())? && !has_transfer_fee(&ctx.accounts.quote_asset_mint.to_account_info())?,
+        RollVaultError::TransferFeeMint
+    );
+
+    ctx.accounts.roll_vault_info.base_asset = ctx.accounts.base_asset_mint.key();
+    ctx.accounts.roll_vault_info.quote_asset = ctx.accounts.quote_asset_mint.key();
+    ctx.accounts.roll_vault_info.strike_offset_bps = params.strike_offset_bps;
+    ctx.accounts.roll_vault_info.tenor_seconds = params.tenor_seconds;
+    ctx.accounts.roll_vault_info.lot_size = params.lot_size;
+    ctx.accounts.roll_vault_info.max_takers = params.max_takers;
+    ctx.accounts.roll_vault_info.total_shares = 0;
+    ctx.accounts.roll_vault_info.base_asset_idle = 0;
+    ctx.accounts.roll_vault_info.quote_asset_accrued = 0;
+    ctx.accounts.roll_vault_info.is_in_series = false;
+    ctx.accounts.roll_vault_info.base_asset_committed = 0;
+    ctx.accounts.roll_vault_info.current_call_option_vault = Pubkey::default();
+    ctx.accounts.roll_vault_info.next_maturity = params.first_maturity;
+    ctx.accounts.roll_vault_info.next_strike = params.first_strike;
+
+    ctx.accounts.roll_vault_info.is_initialized = true;
+    msg!("CoveredCallRollVaultInfo initialized");
+
+    Ok(())
+}
+
+pub fn deposit_covered_call_roll_vault(ctx: Context<DepositCoveredCallRollVault>,
+    base_asset_amount: u64
+) -> Result<u64> {
+    require!(
+        base_asset_amount > 0,
+        RollVaultError::AmountZero
+    );
+
+    // While a series is running we do not know what the committed base asset is worth
+    require!(
+        !ctx.accounts.roll_vault_info.is_in_series,
+        RollVaultError::SeriesInProgress
+    );
+
+    // New shares are priced by the idle base asset, and come with their pro-rata part of the
+    // accrued quote asset, that the depositor must pay too
+    let mut new_shares = base_asset_amount;
+    let mut quote_asset_amount:u64 = 0;
+    if ctx.accounts.roll_vault_info.total_shares > 0 {
+        require!(
+            ctx.accounts.roll_vault_info.base_asset_idle > 0,
+            RollVaultError::NoBaseAssetToPriceShares
+        );
+        let total_shares = ctx.accounts.roll_vault_info.total_shares as u128;
+        new_shares = u64::try_from(
+            (base_asset_amount as u128).checked_mul(total_shares).unwrap() / (ctx.accounts.roll_vault_info.base_asset_idle as u128)
+        ).map_err(|_| RollVaultError::Overflow)?;
+        let quote_asset_value = (new_shares as u128).checked_mul(ctx.accounts.roll_vault_info.quote_asset_accrued as u128).unwrap();
+        let mut quote_asset_amount_u128 = quote_asset_value / total_shares;
+        if quote_asset_value % total_shares > 0 {
+            // rounded up, in favour of the current shareholders
+            quote_asset_amount_u128 += 1;
+        }
+        quote_asset_amount = u64::try_from(quote_asset_amount_u128).map_err(|_| RollVaultError::Overflow)?;
+    }
+    require!(
+        new_shares > 0,
+        RollVaultError::AmountZero
+    );
+    wrap_native_sol_if_needed(
+        &ctx.accounts.base_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.depositor_base_asset_account.to_account_info(),
+        base_asset_amount,
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    wrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.depositor_quote_asset_account.to_account_info(),
+        quote_asset_amount,
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    ctx.accounts.depositor_base_asset_account.reload()?;
+    ctx.accounts.depositor_quote_asset_account.reload()?;
+    require!(
+        ctx.accounts.depositor_base_asset_account.amount >= base_asset_amount,
+        RollVaultError::InsufficientFunds
+    );
+    require!(
+        ctx.accounts.depositor_quote_asset_account.amount >= quote_asset_amount,
+        RollVaultError::InsufficientFunds
+    );
+
+    {
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.depositor_base_asset_account.to_account_info(),
+            mint: ctx.accounts.base_asset_mint.to_account_info(),
+            to: ctx.accounts.roll_base_asset_treasury.to_account_info(),
+            authority: ctx.accounts.initializer.to_account_info(),
+        };
+        let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
+        token_interface::transfer_checked(token_transfer_context, base_asset_amount, ctx.accounts.base_asset_mint.decimals)?;
+        msg!("Transferred {} base asset lamports to roll vault", base_asset_amount);
+    }
+
+    if quote_asset_amount > 0 {
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.depositor_quote_asset_account.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.roll_quote_asset_treasury.to_account_info(),
+            authority: ctx.accounts.initializer.to_account_info(),
+        };
+        let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
+        token_interface::transfer_checked(token_transfer_context, quote_asset_amount, ctx.accounts.quote_asset_mint.decimals)?;
+        msg!("Transferred {} quote asset lamports to roll vault", quote_asset_amount);
+    }
+
+    if !ctx.accounts.share_info.is_initialized {
+        ctx.accounts.share_info.shares = 0;
+        ctx.accounts.share_info.owner = ctx.accounts.initializer.key();
+        ctx.accounts.share_info.roll_vault = ctx.accounts.roll_vault_info.key();
+        ctx.accounts.share_info.is_initialized = true;
+    }
+    ctx.accounts.share_info.shares = ctx.accounts.share_info.shares.checked_add(new_shares).unwrap();
+    ctx.accounts.roll_vault_info.total_shares = ctx.accounts.roll_vault_info.total_shares.checked_add(new_shares).unwrap();
+    ctx.accounts.roll_vault_info.base_asset_idle = ctx.accounts.roll_vault_info.base_asset_idle.checked_add(base_asset_amount).unwrap();
+    ctx.accounts.roll_vault_info.quote_asset_accrued = ctx.accounts.roll_vault_info.quote_asset_accrued.checked_add(quote_asset_amount).unwrap();
+
+    Ok(new_shares)
+}
+
+pub fn withdraw_covered_call_roll_vault(ctx: Context<WithdrawCoveredCallRollVault>,
+    shares: u64
+) -> Result<RollVaultWithdrawReturn> {
+    require!(
+        shares > 0,
+        RollVaultError::AmountZero
+    );
+
+    require!(
+        !ctx.accounts.roll_vault_info.is_in_series,
+        RollVaultError::SeriesInProgress
+    );
+
+    require!(
+        ctx.accounts.share_info.shares >= shares,
+        RollVaultError::InsufficientShares
+    );
+
+    let total_shares = ctx.accounts.roll_vault_info.total_shares as u128;
+    require!(
+        total_shares >= shares as u128,
+        RollVaultError::IllegalState
+    );
+    let base_asset_amount = u64::try_from(
+        (shares as u128).checked_mul(ctx.accounts.roll_vault_info.base_asset_idle as u128).unwrap() / total_shares
+    ).map_err(|_| RollVaultError::Overflow)?;
+    let quote_asset_amount = u64::try_from(
+        (shares as u128).checked_mul(ctx.accounts.roll_vault_info.quote_asset_accrued as u128).unwrap() / total_shares
+    ).map_err(|_| RollVaultError::Overflow)?;
+
+    // Preparing PDA signer
+    let auth_bump = *ctx.bumps.get("roll_vault_info").unwrap();
+    let base_asset_key = ctx.accounts.base_asset_mint.key();
+    let quote_asset_key = ctx.accounts.quote_asset_mint.key();
+    let strike_offset_bps = ctx.accounts.roll_vault_info.strike_offset_bps.to_le_bytes();
+    let tenor_seconds = ctx.accounts.roll_vault_info.tenor_seconds.to_le_bytes();
+    let seeds = &[
+        "CoveredCallRollVaultInfo".as_bytes().as_ref(),
+        base_asset_key.as_ref(),
+        quote_asset_key.as_ref(),
+        strike_offset_bps.as_ref(),
+        tenor_seconds.as_ref(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    if base_asset_amount > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.roll_base_asset_treasury.to_account_info(),
+            mint: ctx.accounts.base_asset_mint.to_account_info(),
+            to: ctx.accounts.depositor_base_asset_account.to_account_info(),
+            authority: ctx.accounts.roll_vault_info.to_account_info(),
+        };
+        let token_transfer_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::transfer_checked(token_transfer_context, base_asset_amount, ctx.accounts.base_asset_mint.decimals)?;
+    }
+    if quote_asset_amount > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.roll_quote_asset_treasury.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.depositor_quote_asset_account.to_account_info(),
+            authority: ctx.accounts.roll_vault_info.to_account_info(),
+        };
+        let token_transfer_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::transfer_checked(token_transfer_context, quote_asset_amount, ctx.accounts.quote_asset_mint.decimals)?;
+    }
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.base_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.depositor_base_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    unwrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.depositor_quote_asset_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+
+    ctx.accounts.share_info.shares = ctx.accounts.share_info.shares.checked_sub(shares).unwrap();
+    ctx.accounts.roll_vault_info.total_shares = ctx.accounts.roll_vault_info.total_shares.checked_sub(shares).unwrap();
+    ctx.accounts.roll_vault_info.base_asset_idle = ctx.accounts.roll_vault_info.base_asset_idle.checked_sub(base_asset_amount).unwrap();
+    ctx.accounts.roll_vault_info.quote_asset_accrued = ctx.accounts.roll_vault_info.quote_asset_accrued.checked_sub(quote_asset_amount).unwrap();
+
+    let result = RollVaultWithdrawReturn {
+        base_asset_transfer: base_asset_amount,
+        quote_asset_transfer: quote_asset_amount
+    };
+    Ok(result)
+}
+
+// Commits all the idle base asset (in whole lots) to a new call option vault, with the roll vault as its only maker.
+// maturity must be the first one of the roll schedule that is still open for trading
+pub fn crank_roll_covered_call_vault(ctx: Context<CrankRollCoveredCallVault>,
+    maturity: u64,
+    vault_id: u64
+) -> Result<u64> {
+    require!(
+        ctx.accounts.roll_vault_info.next_strike > 0,
+        RollVaultError::IllegalState
+    );
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    check_roll_maturity(ctx.accounts.roll_vault_info.next_maturity, ctx.accounts.roll_vault_info.tenor_seconds, maturity, current_time)?;
+
+    let params = MakerCreateCallOptionParams {
+        maturity,
+        strike: ctx.accounts.roll_vault_info.next_strike,
+        max_makers: 1,
+        max_takers: ctx.accounts.roll_vault_info.max_takers,
+        lot_size: ctx.accounts.roll_vault_info.lot_size,
+        num_lots_to_sell: 0,
+        premium_limit: 0,
+        cash_settled: false,
+        barrier: 0,
+        barrier_up: false,
+        knock_in: false,
+        auction_seconds: 0,
+        auction_start_multiple_bps: 0,
+        auction_floor_multiple_bps: 0
+    };
+
+    // Roll vault series are restricted by the asset pair configuration like any other vault factory
+    ap::check_asset_pair_allows_factory(&ctx.accounts.asset_pair_config, Some(params.lot_size), params.strike, params.maturity, current_time)?;
+
+    if !ctx.accounts.vault_factory_info.is_initialized {
+        co::init_call_option_vault_factory_info(
+            &mut ctx.accounts.vault_factory_info, 
+            ctx.accounts.base_asset_mint.key(), 
+            ctx.accounts.quote_asset_mint.key(), 
+            &params,
+            ctx.accounts.asset_pair_config.protocol_fees_bps
+        );
+        ap::add_series_to_index(
+            &mut ctx.accounts.series_index,
+            SeriesKind::Call,
+            ctx.accounts.vault_factory_info.key(),
+            params.strike,
+            params.maturity,
+            current_time
+        );
+    }
+    require!(
+        !ctx.accounts.vault_factory_info.cash_settled,
+        RollVaultError::CashSettledSeries
+    );
+    require!(
+        ctx.accounts.vault_factory_info.barrier == 0,
+        RollVaultError::BarrierSeries
+    );
+    require!(
+        vault_id == ctx.accounts.vault_factory_info.next_vault_id,
+        RollVaultError::InvalidVaultId
+    );
+    ctx.accounts.vault_factory_info.next_vault_id = ctx.accounts.vault_factory_info.next_vault_id.checked_add(1).unwrap();
+
+    let lot_multiplier:f64 = 10.0f64.powf(params.lot_size as f64);
+    let lot_lamports_qty = lot_multiplier*10.0f64.powf(ctx.accounts.base_asset_mint.decimals as f64);
+    require!(
+        lot_lamports_qty.is_finite() && lot_lamports_qty > 0.0,
+        RollVaultError::Overflow
+    );
+    let rounded_lamports_qty = lot_lamports_qty.ceil() as u64;
+
+    let num_lots = ctx.accounts.roll_vault_info.base_asset_idle.checked_div(rounded_lamports_qty).unwrap();
+    require!(
+        num_lots > 0,
+        RollVaultError::NothingToRoll
+    );
+    let transfer_amount = num_lots.checked_mul(rounded_lamports_qty).unwrap();
+
+    // Preparing PDA signer
+    let auth_bump = *ctx.bumps.get("roll_vault_info").unwrap();
+    let base_asset_key = ctx.accounts.base_asset_mint.key();
+    let quote_asset_key = ctx.accounts.quote_asset_mint.key();
+    let strike_offset_bps = ctx.accounts.roll_vault_info.strike_offset_bps.to_le_bytes();
+    let tenor_seconds = ctx.accounts.roll_vault_info.tenor_seconds.to_le_bytes();
+    let seeds = &[
+        "CoveredCallRollVaultInfo".as_bytes().as_ref(),
+        base_asset_key.as_ref(),
+        quote_asset_key.as_ref(),
+        strike_offset_bps.as_ref(),
+        tenor_seconds.as_ref(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.roll_base_asset_treasury.to_account_info(),
+        mint: ctx.accounts.base_asset_mint.to_account_info(),
+        to: ctx.accounts.vault_base_asset_treasury.to_account_info(),
+        authority: ctx.accounts.roll_vault_info.to_account_info(),
+    };
+    let token_transfer_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+    token_interface::transfer_checked(token_transfer_context, transfer_amount, ctx.accounts.base_asset_mint.decimals)?;
+    msg!("Transferred {} base asset lamports to base asset treasury", transfer_amount);
+
+    co::init_call_option_vault_with_first_maker(
+        ctx.accounts.vault_factory_info.key(),
+        &mut ctx.accounts.vault_info,
+        &mut ctx.accounts.call_option_maker_info,
+        ctx.accounts.roll_vault_info.key(),
+        &params,
+        vault_id,
+        transfer_amount
+    );
+
+    ctx.accounts.roll_vault_info.base_asset_idle = ctx.accounts.roll_vault_info.base_asset_idle.checked_sub(transfer_amount).unwrap();
+    ctx.accounts.roll_vault_info.base_asset_committed = transfer_amount;
+    ctx.accounts.roll_vault_info.current_call_option_vault = ctx.accounts.vault_info.key();
+    ctx.accounts.roll_vault_info.is_in_series = true;
+    msg!("Roll vault entered series with maturity {} and strike {}", maturity, params.strike);
+
+    Ok(transfer_amount)
+}
+
+pub fn crank_settle_covered_call_roll_vault(ctx: Context<CrankSettleCoveredCallRollVault>) -> Result<CallOptionSettleReturn> {
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        ctx.accounts.vault_factory_info.maturity < current_time,
+        RollVaultError::IllegalState  // should not have passed maturity test, must never happen
+    );
+
+    let result = co::compute_call_option_maker_physical_settlement(
+        &ctx.accounts.vault_factory_info,
+        &mut ctx.accounts.vault_info,
+        &ctx.accounts.call_option_maker_info,
+        ctx.accounts.base_asset_mint.decimals
+    )?;
+
+    // Preparing PDA signer
+    let auth_bump = *ctx.bumps.get("vault_info").unwrap();
+    let vault_factory_key = ctx.accounts.vault_factory_info.key();
+    let vault_ord = ctx.accounts.vault_info.ord.to_le_bytes();
+    let seeds = &[
+        "CallOptionVaultInfo".as_bytes().as_ref(), 
+        vault_factory_key.as_ref(),
+        vault_ord.as_ref(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    if result.base_asset_transfer > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_base_asset_treasury.to_account_info(),
+            mint: ctx.accounts.base_asset_mint.to_account_info(),
+            to: ctx.accounts.roll_base_asset_treasury.to_account_info(),
+            authority: ctx.accounts.vault_info.to_account_info(),
+        };
+        let token_transfer_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::transfer_checked(token_transfer_context, result.base_asset_transfer, ctx.accounts.base_asset_mint.decimals)?;
+    }
+    if result.quote_asset_transfer > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.roll_quote_asset_treasury.to_account_info(),
+            authority: ctx.accounts.vault_info.to_account_info(),
+        };
+        let token_transfer_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::transfer_checked(token_transfer_context, result.quote_asset_transfer, ctx.accounts.quote_asset_mint.decimals)?;
+    }
+
+    ctx.accounts.call_option_maker_info.base_asset_qty = 0;
+    ctx.accounts.call_option_maker_info.volume_sold = 0;
+    ctx.accounts.call_option_maker_info.is_settled = true;
+
+    // Premiums have been paid by takers directly to the roll vault quote asset treasury during the series,
+    // so the treasury balance is the accrued quote asset
+    ctx.accounts.roll_quote_asset_treasury.reload()?;
+    ctx.accounts.roll_vault_info.quote_asset_accrued = ctx.accounts.roll_quote_asset_treasury.amount;
+    ctx.accounts.roll_vault_info.base_asset_idle = ctx.accounts.roll_vault_info.base_asset_idle.checked_add(result.base_asset_transfer).unwrap();
+    ctx.accounts.roll_vault_info.base_asset_committed = 0;
+    ctx.accounts.roll_vault_info.current_call_option_vault = Pubkey::default();
+    ctx.accounts.roll_vault_info.is_in_series = false;
+
+    // Next series: same tenor, strike at the configured offset from the settled price
+    let next_strike_f64 = (ctx.accounts.vault_factory_info.settled_price as f64) * (1.0 + (ctx.accounts.roll_vault_info.strike_offset_bps as f64)/10000.0);
+    require!(
+        next_strike_f64.is_finite(),
+        RollVaultError::Overflow
+    );
+    ctx.accounts.roll_vault_info.next_strike = next_strike_f64.ceil() as u64;
+    ctx.accounts.roll_vault_info.next_maturity = ctx.accounts.vault_factory_info.maturity.checked_add(ctx.accounts.roll_vault_info.tenor_seconds).unwrap();
+    msg!("Next series will have maturity {} and strike {}", ctx.accounts.roll_vault_info.next_maturity, ctx.accounts.roll_vault_info.next_strike);
+
+    Ok(result)
+}
+
+// The series position is taken back as if the options had not been exercised, and the next series
+// keeps the strike of this one, as there is no settled price
+pub fn crank_covered_call_roll_vault_emergency_exit(ctx: Context<CrankCoveredCallRollVaultEmergencyExit>) -> Result<u64> {
+    if !ctx.accounts.vault_factory_info.emergency_mode {
+        let current_time = Clock::get().unwrap().unix_timestamp as u64;
+        require!(
+            is_emergency_grace_period_over(ctx.accounts.vault_factory_info.maturity, current_time),
+            RollVaultError::EmergencyModeTooEarly
+        );
+        ctx.accounts.vault_factory_info.emergency_mode = true;
+    }
+
+    let base_asset_qty = ctx.accounts.call_option_maker_info.base_asset_qty;
+    co::transfer_call_option_emergency_collateral(
+        &ctx.accounts.vault_info,
+        *ctx.bumps.get("vault_info").unwrap(),
+        &ctx.accounts.call_option_maker_info,
+        &ctx.accounts.vault_base_asset_treasury,
+        &ctx.accounts.base_asset_mint,
+        ctx.accounts.roll_base_asset_treasury.to_account_info(),
+        ctx.accounts.token_program.to_account_info()
+    )?;
+
+    ctx.accounts.call_option_maker_info.base_asset_qty = 0;
+    ctx.accounts.call_option_maker_info.volume_sold = 0;
+    ctx.accounts.call_option_maker_info.is_settled = true;
+
+    // Premiums already received during the series stay with the roll vault
+    ctx.accounts.roll_vault_info.quote_asset_accrued = ctx.accounts.roll_quote_asset_treasury.amount;
+    ctx.accounts.roll_vault_info.base_asset_idle = ctx.accounts.roll_vault_info.base_asset_idle.checked_add(base_asset_qty).unwrap();
+    ctx.accounts.roll_vault_info.base_asset_committed = 0;
+    ctx.accounts.roll_vault_info.current_call_option_vault = Pubkey::default();
+    ctx.accounts.roll_vault_info.is_in_series = false;
+    ctx.accounts.roll_vault_info.next_maturity = ctx.accounts.vault_factory_info.maturity.checked_add(ctx.accounts.roll_vault_info.tenor_seconds).unwrap();
+    msg!("Roll vault left series in emergency mode, next series will have maturity {} and strike {}", ctx.accounts.roll_vault_info.next_maturity, ctx.accounts.roll_vault_info.next_strike);
+
+    Ok(base_asset_qty)
+}
+
+pub fn create_cash_secured_put_roll_vault(ctx: Context<CreateCashSecuredPutRollVault>,
+    params: CreateCashSecuredPutRollVaultParams
+) -> Result<()> {
+    require!(
+        params.strike_offset_bps < 10000,
+        RollVaultError::InvalidStrikeOffset
+    );
+
+    require!(
+        params.tenor_seconds > FREEZE_SECONDS && params.tenor_seconds <= MAX_MATURITY_FUTURE_SECONDS,
+        RollVaultError::InvalidTenor
+    );
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        params.first_maturity > current_time.checked_add(FREEZE_SECONDS).unwrap(),
+        RollVaultError::MaturityTooEarly
+    );
+    require!(
+        params.first_maturity <= current_time.checked_add(MAX_MATURITY_FUTURE_SECONDS).unwrap(),
+        RollVaultError::MaturityTooLate
+    );
+
+    // Cranks move assets between the roll vault and option vaults, transfer fees
+    // would make the vault hold less than it accounts for
+    require!(
+        !has_transfer_fee(&ctx.accounts.base_asset_mint.to_account_info())? && !has_transfer_fee(&ctx.accounts.quote_asset_mint.to_account_info())?,
+        RollVaultError::TransferFeeMint
+    );
+
+    ctx.accounts.roll_vault_info.base_asset = ctx.accounts.base_asset_mint.key();
+    ctx.accounts.roll_vault_info.quote_asset = ctx.accounts.quote_asset_mint.key();
+    ctx.accounts.roll_vault_info.share_mint = ctx.accounts.share_mint.key();
+    ctx.accounts.roll_vault_info.strike_offset_bps = params.strike_offset_bps;
+    ctx.accounts.roll_vault_info.tenor_seconds = params.tenor_seconds;
+    ctx.accounts.roll_vault_info.lot_size = params.lot_size;
+    ctx.accounts.roll_vault_info.max_takers = params.max_takers;
+    ctx.accounts.roll_vault_info.epoch = 0;
+    ctx.accounts.roll_vault_info.total_shares = 0;
+    ctx.accounts.roll_vault_info.quote_asset_idle = 0;
+    ctx.accounts.roll_vault_info.pending_deposits = 0;
+    ctx.accounts.roll_vault_info.pending_withdraw_shares = 0;
+    ctx.accounts.roll_vault_info.quote_reserved_for_withdrawals = 0;
+    ctx.accounts.roll_vault_info.is_in_series = false;
+    ctx.accounts.roll_vault_info.quote_asset_committed = 0;
+    ctx.accounts.roll_vault_info.current_put_option_vault = Pubkey::default();
+    ctx.accounts.roll_vault_info.next_maturity = params.first_maturity;
+    ctx.accounts.roll_vault_info.last_spot_price = 0;
+    ctx.accounts.roll_vault_info.ts_last_spot_price = 0;
+
+    ctx.accounts.roll_vault_info.is_initialized = true;
+    msg!("CashSecuredPutRollVaultInfo initialized");
+
+    Ok(())
+}
+
+pub fn oracle_update_cash_secured_put_roll_spot_price(ctx: Context<OracleUpdateCashSecuredPutRollSpotPrice>,
+    spot_price: u64
+) -> Result<()> {
+    require!(
+        spot_price > 0,
+        RollVaultError::PriceZero
+    );
+
+    ctx.accounts.roll_vault_info.last_spot_price = spot_price;
+    ctx.accounts.roll_vault_info.ts_last_spot_price = Clock::get().unwrap().unix_timestamp as u64;
+
+    Ok(())
+}
+
+// Deposits are queued and only get their shares when the current epoch is closed
+pub fn request_deposit_cash_secured_put_roll_vault(ctx: Context<RequestDepositCashSecuredPutRollVault>,
+    quote_asset_amount: u64
+) -> Result<()> {
+    require!(
+        quote_asset_amount > 0,
+        RollVaultError::AmountZero
+    );
+
+    open_cash_secured_put_roll_ticket(&ctx.accounts.roll_vault_info, &mut ctx.accounts.ticket_info, ctx.accounts.initializer.key())?;
+
+    wrap_native_sol_if_needed(
+        &ctx.accounts.quote_asset_mint.key(),
+        &ctx.accounts.initializer.to_account_info(),
+        &ctx.accounts.depositor_quote_asset_account.to_account_info(),
+        quote_asset_amount,
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program.to_account_info()
+    )?;
+    ctx.accounts.depositor_quote_asset_account.reload()?;
+    require!(
+        ctx.accounts.depositor_quote_asset_account.amount >= quote_asset_amount,
+        RollVaultError::InsufficientFunds
+    );
+
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.depositor_quote_asset_account.to_account_info(),
+        mint: ctx.accounts.quote_asset_mint.to_account_info(),
+        to: ctx.accounts.roll_quote_asset_treasury.to_account_info(),
+        authority: ctx.accounts.initializer.to_account_info(),
+    };
+    let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
+    token_interface::transfer_checked(token_transfer_context, quote_asset_amount, ctx.accounts.quote_asset_mint.decimals)?;
+    msg!("Queued deposit of {} quote asset lamports for epoch {}", quote_asset_amount, ctx.accounts.roll_vault_info.epoch);
+
+    ctx.accounts.ticket_info.deposit_amount = ctx.accounts.ticket_info.deposit_amount.checked_add(quote_asset_amount).unwrap();
+    ctx.accounts.roll_vault_info.pending_deposits = ctx.accounts.roll_vault_info.pending_deposits.checked_add(quote_asset_amount).unwrap();
+
+    Ok(())
+}
+
+// Shares to withdraw are kept in escrow until the current epoch is closed
+pub fn request_withdraw_cash_secured_put_roll_vault(ctx: Context<RequestWithdrawCashSecuredPutRollVault>,
+    shares: u64
+) -> Result<()> {
+    require!(
+        shares > 0,
+        RollVaultError::AmountZero
+    );
+
+    open_cash_secured_put_roll_ticket(&ctx.accounts.roll_vault_info, &mut ctx.accounts.ticket_info, ctx.accounts.initializer.key())?;
+
+    require!(
+        ctx.accounts.depositor_share_account.amount >= shares,
+        RollVaultError::InsufficientShares
+    );
+
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.depositor_share_account.to_account_info(),
+        mint: ctx.accounts.share_mint.to_account_info(),
+        to: ctx.accounts.roll_share_escrow.to_account_info(),
+        authority: ctx.accounts.initializer.to_account_info(),
+    };
+    let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
+    token_interface::transfer_checked(token_transfer_context, shares, ctx.accounts.share_mint.decimals)?;
+    msg!("Queued withdrawal of {} shares for epoch {}", shares, ctx.accounts.roll_vault_info.epoch);
+
+    ctx.accounts.ticket_info.withdraw_shares = ctx.accounts.ticket_info.withdraw_shares.checked_add(shares).unwrap();
+    ctx.accounts.roll_vault_info.pending_withdraw_shares = ctx.accounts.roll_vault_info.pending_withdraw_shares.checked_add(shares).unwrap();
+
+    Ok(())
+}
+
+// Closes the current epoch: all queued deposits and withdrawals are priced at the same share value
+pub fn crank_process_cash_secured_put_roll_queues(ctx: Context<CrankProcessCashSecuredPutRollQueues>) -> Result<()> {
+    let deposits = ctx.accounts.roll_vault_info.pending_deposits;
+    let withdraw_shares = ctx.accounts.roll_vault_info.pending_withdraw_shares;
+    let total_shares = ctx.accounts.roll_vault_info.total_shares as u128;
+    let nav = ctx.accounts.roll_vault_info.quote_asset_idle as u128;
+
+    let mut shares_minted = deposits;
+    let mut quote_for_withdrawals:u64 = 0;
+    if total_shares > 0 {
+        require!(
+            nav > 0,
+            RollVaultError::NoQuoteAssetToPriceShares
+        );
+        shares_minted = u64::try_from((deposits as u128).checked_mul(total_shares).unwrap() / nav).map_err(|_| RollVaultError::Overflow)?;
+        quote_for_withdrawals = u64::try_from((withdraw_shares as u128).checked_mul(nav).unwrap() / total_shares).map_err(|_| RollVaultError::Overflow)?;
+    }
+
+    if withdraw_shares > 0 {
+        // Preparing PDA signer
+        let auth_bump = *ctx.bumps.get("roll_vault_info").unwrap();
+        let base_asset_key = ctx.accounts.base_asset_mint.key();
+        let quote_asset_key = ctx.accounts.quote_asset_mint.key();
+        let strike_offset_bps = ctx.accounts.roll_vault_info.strike_offset_bps.to_le_bytes();
+        let tenor_seconds = ctx.accounts.roll_vault_info.tenor_seconds.to_le_bytes();
+        let seeds = &[
+            "CashSecuredPutRollVaultInfo".as_bytes().as_ref(),
+            base_asset_key.as_ref(),
+            quote_asset_key.as_ref(),
+            strike_offset_bps.as_ref(),
+            tenor_seconds.as_ref(),
+            &[auth_bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.share_mint.to_account_info(),
+            from: ctx.accounts.roll_share_escrow.to_account_info(),
+            authority: ctx.accounts.roll_vault_info.to_account_info(),
+        };
+        let token_burn_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::burn(token_burn_context, withdraw_shares)?;
+    }
+
+    ctx.accounts.epoch_info.roll_vault = ctx.accounts.roll_vault_info.key();
+    ctx.accounts.epoch_info.epoch = ctx.accounts.roll_vault_info.epoch;
+    ctx.accounts.epoch_info.deposits = deposits;
+    ctx.accounts.epoch_info.shares_minted = shares_minted;
+    ctx.accounts.epoch_info.withdraw_shares = withdraw_shares;
+    ctx.accounts.epoch_info.quote_for_withdrawals = quote_for_withdrawals;
+
+    ctx.accounts.roll_vault_info.total_shares = ctx.accounts.roll_vault_info.total_shares.checked_add(shares_minted).unwrap().checked_sub(withdraw_shares).unwrap();
+    ctx.accounts.roll_vault_info.quote_asset_idle = ctx.accounts.roll_vault_info.quote_asset_idle.checked_add(deposits).unwrap().checked_sub(quote_for_withdrawals).unwrap();
+    ctx.accounts.roll_vault_info.quote_reserved_for_withdrawals = ctx.accounts.roll_vault_info.quote_reserved_for_withdrawals.checked_add(quote_for_withdrawals).unwrap();
+    ctx.accounts.roll_vault_info.pending_deposits = 0;
+    ctx.accounts.roll_vault_info.pending_withdraw_shares = 0;
+    msg!("Closed epoch {}: {} shares minted, {} quote asset lamports for withdrawals", ctx.accounts.roll_vault_info.epoch, shares_minted, quote_for_withdrawals);
+    ctx.accounts.roll_vault_info.epoch = ctx.accounts.roll_vault_info.epoch.checked_add(1).unwrap();
+
+    Ok(())
+}
+
+pub fn claim_cash_secured_put_roll_ticket(ctx: Context<ClaimCashSecuredPutRollTicket>) -> Result<RollVaultClaimReturn> {
+    require!(
+        ctx.accounts.ticket_info.epoch < ctx.accounts.roll_vault_info.epoch,
+        RollVaultError::TicketNotProcessed
+    );
+
+    let mut shares:u64 = 0;
+    if ctx.accounts.ticket_info.deposit_amount > 0 {
+        require!(
+            ctx.accounts.epoch_info.deposits > 0,
+            RollVaultError::IllegalState
+        );
+        shares = u64::try_from(
+            (ctx.accounts.ticket_info.deposit_amount as u128).checked_mul(ctx.accounts.epoch_info.shares_minted as u128).unwrap() / (ctx.accounts.epoch_info.deposits as u128)
+        ).map_err(|_| RollVaultError::Overflow)?;
+    }
+    let mut quote_asset_amount:u64 = 0;
+    if ctx.accounts.ticket_info.withdraw_shares > 0 {
+        require!(
+            ctx.accounts.epoch_info.withdraw_shares > 0,
+            RollVaultError::IllegalState
+        );
+        quote_asset_amount = u64::try_from(
+            (ctx.accounts.ticket_info.withdraw_shares as u128).checked_mul(ctx.accounts.epoch_info.quote_for_withdrawals as u128).unwrap() / (ctx.accounts.epoch_info.withdraw_shares as u128)
+        ).map_err(|_| RollVaultError::Overflow)?;
+    }
+
+    // Preparing PDA signer
+    let auth_bump = *ctx.bumps.get("roll_vault_info").unwrap();
+    let base_asset_key = ctx.accounts.base_asset_mint.key();
+    let quote_asset_key = ctx.accounts.quote_asset_mint.key();
+    let strike_offset_bps = ctx.accounts.roll_vault_info.strike_offset_bps.to_le_bytes();
+    let tenor_seconds = ctx.accounts.roll_vault_info.tenor_seconds.to_le_bytes();
+    let seeds = &[
+        "CashSecuredPutRollVaultInfo".as_bytes().as_ref(),
+        base_asset_key.as_ref(),
+        quote_asset_key.as_ref(),
+        strike_offset_bps.as_ref(),
+        tenor_seconds.as_ref(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    if shares > 0 {
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.share_mint.to_account_info(),
+            to: ctx.accounts.depositor_share_account.to_account_info(),
+            authority: ctx.accounts.roll_vault_info.to_account_info(),
+        };
+        let token_mint_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::mint_to(token_mint_context, shares)?;
+    }
+    if quote_asset_amount > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.roll_quote_asset_treasury.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.depositor_quote_asset_account.to_account_info(),
+            authority: ctx.accounts.roll_vault_info.to_account_info(),
+        };
+        let token_transfer_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::transfer_checked(token_transfer_context, quote_asset_amount, ctx.accounts.quote_asset_mint.decimals)?;
+        ctx.accounts.roll_vault_info.quote_reserved_for_withdrawals = ctx.accounts.roll_vault_info.quote_reserved_for_withdrawals.checked_sub(quote_asset_amount).unwrap();
+        unwrap_native_sol_if_needed(
+            &ctx.accounts.quote_asset_mint.key(),
+            &ctx.accounts.initializer.to_account_info(),
+            &ctx.accounts.depositor_quote_asset_account.to_account_info(),
+            &ctx.accounts.token_program.to_account_info()
+        )?;
+    }
+
+    ctx.accounts.ticket_info.deposit_amount = 0;
+    ctx.accounts.ticket_info.withdraw_shares = 0;
+
+    let result = RollVaultClaimReturn {
+        shares_minted: shares,
+        quote_asset_transfer: quote_asset_amount
+    };
+    Ok(result)
+}
+
+// Commits all the idle quote asset (in whole lots) to a new cash-settled put option vault, with the roll vault as its only maker.
+// Strike must be the oracle spot price minus the configured offset, and maturity the first one of the roll schedule that is still open
+pub fn crank_roll_cash_secured_put_vault(ctx: Context<CrankRollCashSecuredPutVault>,
+    maturity: u64,
+    strike: u64,
+    vault_id: u64
+) -> Result<u64> {
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    check_roll_maturity(ctx.accounts.roll_vault_info.next_maturity, ctx.accounts.roll_vault_info.tenor_seconds, maturity, current_time)?;
+
+    // We cannot have a timestamp for the last spot price in the future
+    require!(
+        ctx.accounts.roll_vault_info.ts_last_spot_price <= current_time,
+        RollVaultError::IllegalState
+    );
+    let seconds_from_update = current_time.checked_sub(ctx.accounts.roll_vault_info.ts_last_spot_price).unwrap();
+    require!(
+        ctx.accounts.roll_vault_info.last_spot_price > 0 && seconds_from_update <= MAX_SECONDS_FROM_LAST_FAIR_PRICE_UPDATE,
+        RollVaultError::LastSpotPriceUpdateTooOld
+    );
+    let expected_strike = (ctx.accounts.roll_vault_info.last_spot_price as u128)
+        .checked_mul(10000u128 - ctx.accounts.roll_vault_info.strike_offset_bps as u128).unwrap() / 10000u128;
+    require!(
+        strike > 0 && strike as u128 == expected_strike,
+        RollVaultError::InvalidRollSchedule
+    );
+
+    let params = MakerCreatePutOptionParams {
+        maturity,
+        strike,
+        max_makers: 1,
+        max_takers: ctx.accounts.roll_vault_info.max_takers,
+        lot_size: ctx.accounts.roll_vault_info.lot_size,
+        num_lots_to_sell: 0,
+        premium_limit: 0,
+        cash_settled: true,
+        american: false,
+        barrier: 0,
+        barrier_up: false,
+        knock_in: false,
+        auction_seconds: 0,
+        auction_start_multiple_bps: 0,
+        auction_floor_multiple_bps: 0,
+        maker_allocation: MakerAllocation::TakerChoice,
+        initial_margin_bps: 0,
+        maintenance_margin_bps: 0,
+        liquidation_bonus_bps: 0
+    };
+
+    // Roll vault series are restricted by the asset pair configuration like any other vault factory
+    ap::check_asset_pair_allows_factory(&ctx.accounts.asset_pair_config, Some(params.lot_size), params.strike, params.maturity, current_time)?;
+
+    if !ctx.accounts.vault_factory_info.is_initialized {
+        po::init_put_option_vault_factory_info(
+            &mut ctx.accounts.vault_factory_info, 
+            ctx.accounts.base_asset_mint.key(), 
+            ctx.accounts.quote_asset_mint.key(), 
+            &params,
+            ctx.accounts.asset_pair_config.protocol_fees_bps
+        );
+        ap::add_series_to_index(
+            &mut ctx.accounts.series_index,
+            SeriesKind::Put,
+            ctx.accounts.vault_factory_info.key(),
+            params.strike,
+            params.maturity,
+            current_time
+        );
+    }
+    require!(
+        ctx.accounts.vault_factory_info.cash_settled && !ctx.accounts.vault_factory_info.american,
+        RollVaultError::PhysicallySettledSeries
+    );
+    require!(
+        ctx.accounts.vault_factory_info.barrier == 0,
+        RollVaultError::BarrierSeries
+    );
+    require!(
+        vault_id == ctx.accounts.vault_factory_info.next_vault_id,
+        RollVaultError::InvalidVaultId
+    );
+    ctx.accounts.vault_factory_info.next_vault_id = ctx.accounts.vault_factory_info.next_vault_id.checked_add(1).unwrap();
+
+    let lot_multiplier:f64 = 10.0f64.powf(params.lot_size as f64);
+    let lot_value = lot_multiplier*(strike as f64);
+    require!(
+        lot_value.is_finite() && lot_value > 0.0,
+        RollVaultError::Overflow
+    );
+    let rounded_lot_value = lot_value.ceil() as u64;
+
+    let num_lots = ctx.accounts.roll_vault_info.quote_asset_idle.checked_div(rounded_lot_value).unwrap();
+    require!(
+        num_lots > 0,
+        RollVaultError::NothingToRoll
+    );
+    let transfer_amount_f64 = (num_lots as f64)*lot_value;
+    require!(
+        transfer_amount_f64.is_finite(),
+        RollVaultError::Overflow
+    );
+    let transfer_amount = transfer_amount_f64.ceil() as u64;
+    require!(
+        transfer_amount <= ctx.accounts.roll_vault_info.quote_asset_idle,
+        RollVaultError::IllegalState
+    );
+
+    // Preparing PDA signer
+    let auth_bump = *ctx.bumps.get("roll_vault_info").unwrap();
+    let base_asset_key = ctx.accounts.base_asset_mint.key();
+    let quote_asset_key = ctx.accounts.quote_asset_mint.key();
+    let strike_offset_bps = ctx.accounts.roll_vault_info.strike_offset_bps.to_le_bytes();
+    let tenor_seconds = ctx.accounts.roll_vault_info.tenor_seconds.to_le_bytes();
+    let seeds = &[
+        "CashSecuredPutRollVaultInfo".as_bytes().as_ref(),
+        base_asset_key.as_ref(),
+        quote_asset_key.as_ref(),
+        strike_offset_bps.as_ref(),
+        tenor_seconds.as_ref(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.roll_quote_asset_treasury.to_account_info(),
+        mint: ctx.accounts.quote_asset_mint.to_account_info(),
+        to: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+        authority: ctx.accounts.roll_vault_info.to_account_info(),
+    };
+    let token_transfer_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+    token_interface::transfer_checked(token_transfer_context, transfer_amount, ctx.accounts.quote_asset_mint.decimals)?;
+    msg!("Transferred {} USDC lamports to quote asset treasury", transfer_amount);
+
+    po::init_put_option_vault_with_first_maker(
+        ctx.accounts.vault_factory_info.key(),
+        &mut ctx.accounts.vault_info,
+        &mut ctx.accounts.put_option_maker_info,
+        ctx.accounts.roll_vault_info.key(),
+        &params,
+        vault_id,
+        transfer_amount
+    );
+
+    ctx.accounts.roll_vault_info.quote_asset_idle = ctx.accounts.roll_vault_info.quote_asset_idle.checked_sub(transfer_amount).unwrap();
+    ctx.accounts.roll_vault_info.quote_asset_committed = transfer_amount;
+    ctx.accounts.roll_vault_info.current_put_option_vault = ctx.accounts.vault_info.key();
+    ctx.accounts.roll_vault_info.is_in_series = true;
+    msg!("Roll vault entered series with maturity {} and strike {}", maturity, strike);
+
+    Ok(transfer_amount)
+}
+
+pub fn crank_settle_cash_secured_put_roll_vault(ctx: Context<CrankSettleCashSecuredPutRollVault>) -> Result<u64> {
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        ctx.accounts.vault_factory_info.maturity < current_time,
+        RollVaultError::IllegalState  // should not have passed maturity test, must never happen
+    );
+
+    let payout = po::compute_put_option_maker_cash_payout(&ctx.accounts.vault_factory_info, &ctx.accounts.put_option_maker_info)?;
+    let transfer_quote_asset = ctx.accounts.put_option_maker_info.quote_asset_qty.checked_sub(payout).unwrap();
+    if transfer_quote_asset > 0 {
+        // Preparing PDA signer
+        let auth_bump = *ctx.bumps.get("vault_info").unwrap();
+        let seeds = &[
+            "PutOptionVaultInfo".as_bytes().as_ref(), 
+            &ctx.accounts.vault_factory_info.key().to_bytes(),
+            &ctx.accounts.vault_info.ord.to_le_bytes(),
+            &[auth_bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_quote_asset_treasury.to_account_info(),
+            mint: ctx.accounts.quote_asset_mint.to_account_info(),
+            to: ctx.accounts.roll_quote_asset_treasury.to_account_info(),
+            authority: ctx.accounts.vault_info.to_account_info(),
+        };
+        let token_transfer_context = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::transfer_checked(token_transfer_context, transfer_quote_asset, ctx.accounts.quote_asset_mint.decimals)?;
+    }
+
+    ctx.accounts.put_option_maker_info.quote_asset_qty = 0;
+    ctx.accounts.put_option_maker_info.volume_sold = 0;
+    ctx.accounts.put_option_maker_info.is_settled = true;
+
+    // Premiums have been paid by takers directly to the roll vault quote asset treasury during the series,
+    // so what is in the treasury, apart from queued deposits and processed withdrawals, belongs to the shareholders
+    ctx.accounts.roll_quote_asset_treasury.reload()?;
+    ctx.accounts.roll_vault_info.quote_asset_idle = ctx.accounts.roll_quote_asset_treasury.amount
+        .checked_sub(ctx.accounts.roll_vault_info.pending_deposits).unwrap()
+        .checked_sub(ctx.accounts.roll_vault_info.quote_reserved_for_withdrawals).unwrap();
+    ctx.accounts.roll_vault_info.quote_asset_committed = 0;
+    ctx.accounts.roll_vault_info.current_put_option_vault = Pubkey::default();
+    ctx.accounts.roll_vault_info.is_in_series = false;
+    ctx.accounts.roll_vault_info.next_maturity = ctx.accounts.vault_factory_info.maturity.checked_add(ctx.accounts.roll_vault_info.tenor_seconds).unwrap();
+    msg!("Next series will have maturity {}", ctx.accounts.roll_vault_info.next_maturity);
+
+    Ok(transfer_quote_asset)
+}
+
+// A user has a single ticket per roll vault, its requests must be claimed before making requests in a later epoch
+fn open_cash_secured_put_roll_ticket(roll_vault_info: &Account<CashSecuredPutRollVaultInfo>,
+    ticket_info: &mut Account<CashSecuredPutRollTicketInfo>,
+    owner: Pubkey
+) -> Result<()> {
+    if !ticket_info.is_initialized {
+        ticket_info.deposit_amount = 0;
+        ticket_info.withdraw_shares = 0;
+        ticket_info.owner = owner;
+        ticket_info.roll_vault = roll_vault_info.key();
+        ticket_info.is_initialized = true;
+    } else if ticket_info.epoch != roll_vault_info.epoch {
+        require!(
+            ticket_info.deposit_amount == 0 && ticket_info.withdraw_shares == 0,
+            RollVaultError::UnclaimedTicket
+        );
+    }
+    ticket_info.epoch = roll_vault_info.epoch;
+
+    Ok(())
+}
+
+// maturity must follow next_maturity by a whole number of tenors. If rolls have been missed,
+// we skip to the first maturity still open for trading, never further
+fn check_roll_maturity(next_maturity: u64, tenor: u64, maturity: u64, current_time: u64) -> Result<()> {
+    let first_open_maturity = current_time.checked_add(FREEZE_SECONDS).unwrap();
+    require!(
+        maturity >= next_maturity && (maturity - next_maturity) % tenor == 0,
+        RollVaultError::InvalidRollSchedule
+    );
+    require!(
+        maturity > first_open_maturity,
+        RollVaultError::MaturityTooEarly
+    );
+    require!(
+        maturity == next_maturity || maturity <= first_open_maturity.checked_add(tenor).unwrap(),
+        RollVaultError::InvalidRollSchedule
+    );
+    require!(
+        maturity <= current_time.checked_add(MAX_MATURITY_FUTURE_SECONDS).unwrap(),
+        RollVaultError::MaturityTooLate
+    );
+
+    Ok(())
+}
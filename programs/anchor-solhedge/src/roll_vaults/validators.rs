@@ -0,0 +1,1126 @@
+use anchor_lang::prelude::*;
+use crate::roll_vaults::data::{
+    CoveredCallRollVaultInfo,
+    CoveredCallRollShareInfo,
+    CashSecuredPutRollVaultInfo,
+    CashSecuredPutRollEpochInfo,
+    CashSecuredPutRollTicketInfo
+};
+use crate::call_options::data::{
+    CallOptionVaultFactoryInfo,
+    CallOptionVaultInfo,
+    CallOptionMakerInfo
+};
+use crate::asset_pairs::data::{AssetPairConfig, SeriesIndex};
+use anchor_spl::token_interface::{Mint, TokenInterface, TokenAccount};
+use crate::put_options::data::{
+    PutOptionVaultFactoryInfo,
+    PutOptionVaultInfo,
+    PutOptionMakerInfo
+};
+use anchor_spl::associated_token::AssociatedToken;
+use crate::ORACLE_ADDRESS;
+use crate::{CreateCoveredCallRollVaultParams, CreateCashSecuredPutRollVaultParams};
+
+#[derive(Accounts)]
+#[instruction(
+    params: CreateCoveredCallRollVaultParams
+)]
+pub struct CreateCoveredCallRollVault<'info> {
+    #[account(
+        init,
+        seeds=[
+            "CoveredCallRollVaultInfo".as_bytes().as_ref(),
+            base_asset_mint.key().as_ref(),
+            quote_asset_mint.key().as_ref(),
+            &params.strike_offset_bps.to_le_bytes().as_ref(),
+            &params.tenor_seconds.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<CoveredCallRollVaultInfo>() + 8,
+        constraint = params.first_strike > 0
+    )]
+    pub roll_vault_info: Account<'info, CoveredCallRollVaultInfo>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = initializer, // Payer will be initializer
+        associated_token::mint = base_asset_mint, 
+        associated_token::authority = roll_vault_info // Authority set to PDA
+    )]
+    pub roll_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = initializer, // Payer will be initializer
+        associated_token::mint = quote_asset_mint, // Quote asset mint
+        associated_token::authority = roll_vault_info // Authority set to roll vault PDA
+    )]
+    pub roll_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    base_asset_amount: u64
+)]
+pub struct DepositCoveredCallRollVault<'info> {
+    #[account(
+        mut,
+        seeds=[
+            "CoveredCallRollVaultInfo".as_bytes().as_ref(),
+            base_asset_mint.key().as_ref(),
+            quote_asset_mint.key().as_ref(),
+            roll_vault_info.strike_offset_bps.to_le_bytes().as_ref(),
+            roll_vault_info.tenor_seconds.to_le_bytes().as_ref()
+        ],
+        bump,
+        constraint = roll_vault_info.is_initialized == true,
+        constraint = roll_vault_info.base_asset == base_asset_mint.key(),
+        constraint = roll_vault_info.quote_asset == quote_asset_mint.key()
+    )]
+    pub roll_vault_info: Account<'info, CoveredCallRollVaultInfo>,
+
+    #[account(
+        init_if_needed,
+        seeds=[
+            "CoveredCallRollShareInfo".as_bytes().as_ref(),
+            roll_vault_info.key().as_ref(),
+            initializer.key().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<CoveredCallRollShareInfo>() + 8
+    )]
+    pub share_info: Account<'info, CoveredCallRollShareInfo>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = roll_base_asset_treasury.mint == base_asset_mint.key(), // Base asset mint
+        constraint = roll_base_asset_treasury.owner.key() == roll_vault_info.key() // Authority set to roll vault PDA
+    )]
+    pub roll_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = roll_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = roll_quote_asset_treasury.owner.key() == roll_vault_info.key() // Authority set to roll vault PDA
+    )]
+    pub roll_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = depositor_base_asset_account.owner.key() == initializer.key(),
+        constraint = depositor_base_asset_account.mint == base_asset_mint.key()
+    )]
+    pub depositor_base_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // pays the share of accrued premiums that comes with the new shares
+    #[account(
+        mut,
+        constraint = depositor_quote_asset_account.owner.key() == initializer.key(),
+        constraint = depositor_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub depositor_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    shares: u64
+)]
+pub struct WithdrawCoveredCallRollVault<'info> {
+    #[account(
+        mut,
+        seeds=[
+            "CoveredCallRollVaultInfo".as_bytes().as_ref(),
+            base_asset_mint.key().as_ref(),
+            quote_asset_mint.key().as_ref(),
+            roll_vault_info.strike_offset_bps.to_le_bytes().as_ref(),
+            roll_vault_info.tenor_seconds.to_le_bytes().as_ref()
+        ],
+        bump,
+        constraint = roll_vault_info.is_initialized == true,
+        constraint = roll_vault_info.base_asset == base_asset_mint.key(),
+        constraint = roll_vault_info.quote_asset == quote_asset_mint.key()
+    )]
+    pub roll_vault_info: Account<'info, CoveredCallRollVaultInfo>,
+
+    #[account(
+        mut,
+        seeds=[
+            "CoveredCallRollShareInfo".as_bytes().as_ref(),
+            roll_vault_info.key().as_ref(),
+            initializer.key().as_ref()
+        ],
+        bump,
+        constraint = share_info.is_initialized == true,
+        constraint = share_info.roll_vault == roll_vault_info.key()
+    )]
+    pub share_info: Account<'info, CoveredCallRollShareInfo>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = roll_base_asset_treasury.mint == base_asset_mint.key(), // Base asset mint
+        constraint = roll_base_asset_treasury.owner.key() == roll_vault_info.key() // Authority set to roll vault PDA
+    )]
+    pub roll_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = roll_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = roll_quote_asset_treasury.owner.key() == roll_vault_info.key() // Authority set to roll vault PDA
+    )]
+    pub roll_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = depositor_base_asset_account.owner.key() == initializer.key(),
+        constraint = depositor_base_asset_account.mint == base_asset_mint.key()
+    )]
+    pub depositor_base_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = depositor_quote_asset_account.owner.key() == initializer.key(),
+        constraint = depositor_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub depositor_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+// Permissionless, anyone may crank the roll (and pays the rent of the new series accounts)
+#[derive(Accounts)]
+#[instruction(
+    maturity: u64, 
+    vault_id: u64
+)]
+pub struct CrankRollCoveredCallVault<'info> {
+    #[account(
+        mut,
+        seeds=[
+            "CoveredCallRollVaultInfo".as_bytes().as_ref(),
+            base_asset_mint.key().as_ref(),
+            quote_asset_mint.key().as_ref(),
+            roll_vault_info.strike_offset_bps.to_le_bytes().as_ref(),
+            roll_vault_info.tenor_seconds.to_le_bytes().as_ref()
+        ],
+        bump,
+        constraint = roll_vault_info.is_initialized == true,
+        constraint = roll_vault_info.is_in_series == false,
+        constraint = roll_vault_info.base_asset == base_asset_mint.key(),
+        constraint = roll_vault_info.quote_asset == quote_asset_mint.key()
+    )]
+    pub roll_vault_info: Box<Account<'info, CoveredCallRollVaultInfo>>,
+
+    #[account(
+        mut,
+        constraint = roll_base_asset_treasury.mint == base_asset_mint.key(), // Base asset mint
+        constraint = roll_base_asset_treasury.owner.key() == roll_vault_info.key() // Authority set to roll vault PDA
+    )]
+    pub roll_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed, 
+        seeds=[
+            "CallOptionVaultFactoryInfo".as_bytes().as_ref(), 
+            base_asset_mint.key().as_ref(), 
+            quote_asset_mint.key().as_ref(), 
+            &maturity.to_le_bytes().as_ref(), 
+            &roll_vault_info.next_strike.to_le_bytes().as_ref()
+        ], 
+        bump, 
+        payer = initializer, 
+        space= std::mem::size_of::<CallOptionVaultFactoryInfo>() + 8,
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Box<Account<'info, CallOptionVaultFactoryInfo>>,
+
+    #[account(
+        seeds=["AssetPairConfig".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = asset_pair_config.is_initialized == true
+    )]
+    pub asset_pair_config: Box<Account<'info, AssetPairConfig>>,
+
+    #[account(
+        mut,
+        seeds=["SeriesIndex".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        realloc = SeriesIndex::space(series_index.entries.len() + 1),
+        realloc::payer = initializer,
+        realloc::zero = false,
+        constraint = series_index.is_initialized == true
+    )]
+    pub series_index: Box<Account<'info, SeriesIndex>>,
+
+    #[account(
+        init,
+        seeds=[
+            "CallOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            &vault_id.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = initializer, 
+        space= std::mem::size_of::<CallOptionVaultInfo>() + 8
+    )]
+    pub vault_info: Box<Account<'info, CallOptionVaultInfo>>,
+
+    #[account(
+        init,
+        payer = initializer, // Payer will be initializer
+        associated_token::mint = base_asset_mint, 
+        associated_token::authority = vault_info // Authority set to PDA
+    )]
+    pub vault_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = initializer, // Payer will be initializer
+        associated_token::mint = quote_asset_mint, // Quote asset mint
+        associated_token::authority = vault_info // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // the roll vault is the maker
+    #[account(
+        init,
+        seeds=[
+            "CallOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            &vault_id.to_le_bytes().as_ref(), 
+            roll_vault_info.key().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<CallOptionMakerInfo>() + 8
+    )]
+    pub call_option_maker_info: Box<Account<'info, CallOptionMakerInfo>>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+// Permissionless, anyone may crank the settlement of the current series
+#[derive(Accounts)]
+pub struct CrankSettleCoveredCallRollVault<'info> {
+    #[account(
+        mut,
+        seeds=[
+            "CoveredCallRollVaultInfo".as_bytes().as_ref(),
+            base_asset_mint.key().as_ref(),
+            quote_asset_mint.key().as_ref(),
+            roll_vault_info.strike_offset_bps.to_le_bytes().as_ref(),
+            roll_vault_info.tenor_seconds.to_le_bytes().as_ref()
+        ],
+        bump,
+        constraint = roll_vault_info.is_initialized == true,
+        constraint = roll_vault_info.is_in_series == true,
+        constraint = roll_vault_info.current_call_option_vault == vault_info.key(),
+        constraint = roll_vault_info.base_asset == base_asset_mint.key(),
+        constraint = roll_vault_info.quote_asset == quote_asset_mint.key()
+    )]
+    pub roll_vault_info: Box<Account<'info, CoveredCallRollVaultInfo>>,
+
+    #[account(
+        mut,
+        constraint = roll_base_asset_treasury.mint == base_asset_mint.key(), // Base asset mint
+        constraint = roll_base_asset_treasury.owner.key() == roll_vault_info.key() // Authority set to roll vault PDA
+    )]
+    pub roll_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = roll_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = roll_quote_asset_treasury.owner.key() == roll_vault_info.key() // Authority set to roll vault PDA
+    )]
+    pub roll_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == true,
+        constraint = vault_factory_info.settled_price > 0,
+        constraint = vault_factory_info.base_asset == base_asset_mint.key(),
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Box<Account<'info, CallOptionVaultFactoryInfo>>,
+
+    #[account(
+        mut,
+        seeds=[
+            "CallOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Box<Account<'info, CallOptionVaultInfo>>,
+
+    #[account(
+        mut,
+        seeds=[
+            "CallOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            roll_vault_info.key().as_ref()
+        ],
+        bump,
+        constraint = !call_option_maker_info.is_settled
+    )]
+    pub call_option_maker_info: Box<Account<'info, CallOptionMakerInfo>>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault_base_asset_treasury.mint == base_asset_mint.key(), // Base asset mint
+        constraint = vault_base_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // quote asset mint
+        constraint = vault_quote_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub initializer: Signer<'info>,
+
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Permissionless, anyone may crank the emergency exit of the current series once the grace period
+// after its maturity has passed without a settle price
+#[derive(Accounts)]
+pub struct CrankCoveredCallRollVaultEmergencyExit<'info> {
+    #[account(
+        mut,
+        seeds=[
+            "CoveredCallRollVaultInfo".as_bytes().as_ref(),
+            base_asset_mint.key().as_ref(),
+            quote_asset_mint.key().as_ref(),
+            roll_vault_info.strike_offset_bps.to_le_bytes().as_ref(),
+            roll_vault_info.tenor_seconds.to_le_bytes().as_ref()
+        ],
+        bump,
+        constraint = roll_vault_info.is_initialized == true,
+        constraint = roll_vault_info.is_in_series == true,
+        constraint = roll_vault_info.current_call_option_vault == vault_info.key(),
+        constraint = roll_vault_info.base_asset == base_asset_mint.key(),
+        constraint = roll_vault_info.quote_asset == quote_asset_mint.key()
+    )]
+    pub roll_vault_info: Box<Account<'info, CoveredCallRollVaultInfo>>,
+
+    #[account(
+        mut,
+        constraint = roll_base_asset_treasury.mint == base_asset_mint.key(), // Base asset mint
+        constraint = roll_base_asset_treasury.owner.key() == roll_vault_info.key() // Authority set to roll vault PDA
+    )]
+    pub roll_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        constraint = roll_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = roll_quote_asset_treasury.owner.key() == roll_vault_info.key() // Authority set to roll vault PDA
+    )]
+    pub roll_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // emergency mode is activated here if no one has done it yet
+    #[account(
+        mut,
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == false,
+        constraint = vault_factory_info.base_asset == base_asset_mint.key(),
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key()
+    )]
+    pub vault_factory_info: Box<Account<'info, CallOptionVaultFactoryInfo>>,
+
+    #[account(
+        seeds=[
+            "CallOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Box<Account<'info, CallOptionVaultInfo>>,
+
+    #[account(
+        mut,
+        seeds=[
+            "CallOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            roll_vault_info.key().as_ref()
+        ],
+        bump,
+        constraint = !call_option_maker_info.is_settled
+    )]
+    pub call_option_maker_info: Box<Account<'info, CallOptionMakerInfo>>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault_base_asset_treasury.mint == base_asset_mint.key(), // Base asset mint
+        constraint = vault_base_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub initializer: Signer<'info>,
+
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    params: CreateCashSecuredPutRollVaultParams
+)]
+pub struct CreateCashSecuredPutRollVault<'info> {
+    #[account(
+        init,
+        seeds=[
+            "CashSecuredPutRollVaultInfo".as_bytes().as_ref(),
+            base_asset_mint.key().as_ref(),
+            quote_asset_mint.key().as_ref(),
+            &params.strike_offset_bps.to_le_bytes().as_ref(),
+            &params.tenor_seconds.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<CashSecuredPutRollVaultInfo>() + 8
+    )]
+    pub roll_vault_info: Box<Account<'info, CashSecuredPutRollVaultInfo>>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // shares of the roll vault, with the same decimals as the quote asset
+    #[account(
+        init,
+        seeds=["CashSecuredPutRollShareMint".as_bytes().as_ref(), roll_vault_info.key().as_ref()],
+        bump,
+        payer = initializer,
+        mint::decimals = quote_asset_mint.decimals,
+        mint::authority = roll_vault_info
+    )]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = initializer, // Payer will be initializer
+        associated_token::mint = quote_asset_mint, // Quote asset mint
+        associated_token::authority = roll_vault_info // Authority set to roll vault PDA
+    )]
+    pub roll_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // shares queued for withdrawal are kept here until their epoch is closed
+    #[account(
+        init,
+        payer = initializer, // Payer will be initializer
+        associated_token::mint = share_mint, 
+        associated_token::authority = roll_vault_info // Authority set to roll vault PDA
+    )]
+    pub roll_share_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct OracleUpdateCashSecuredPutRollSpotPrice<'info> {
+    #[account(
+        mut,
+        constraint = roll_vault_info.is_initialized == true
+    )]
+    pub roll_vault_info: Account<'info, CashSecuredPutRollVaultInfo>,
+
+    // Check if initializer is signer, should also be the oracle
+    #[account(
+        constraint = initializer.key() == ORACLE_ADDRESS
+    )]
+    pub initializer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    quote_asset_amount: u64
+)]
+pub struct RequestDepositCashSecuredPutRollVault<'info> {
+    #[account(
+        mut,
+        seeds=[
+            "CashSecuredPutRollVaultInfo".as_bytes().as_ref(),
+            base_asset_mint.key().as_ref(),
+            quote_asset_mint.key().as_ref(),
+            roll_vault_info.strike_offset_bps.to_le_bytes().as_ref(),
+            roll_vault_info.tenor_seconds.to_le_bytes().as_ref()
+        ],
+        bump,
+        constraint = roll_vault_info.is_initialized == true,
+        constraint = roll_vault_info.base_asset == base_asset_mint.key(),
+        constraint = roll_vault_info.quote_asset == quote_asset_mint.key()
+    )]
+    pub roll_vault_info: Box<Account<'info, CashSecuredPutRollVaultInfo>>,
+
+    #[account(
+        init_if_needed,
+        seeds=[
+            "CashSecuredPutRollTicketInfo".as_bytes().as_ref(),
+            roll_vault_info.key().as_ref(),
+            initializer.key().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<CashSecuredPutRollTicketInfo>() + 8
+    )]
+    pub ticket_info: Account<'info, CashSecuredPutRollTicketInfo>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = roll_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = roll_quote_asset_treasury.owner.key() == roll_vault_info.key() // Authority set to roll vault PDA
+    )]
+    pub roll_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = depositor_quote_asset_account.owner.key() == initializer.key(),
+        constraint = depositor_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub depositor_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    shares: u64
+)]
+pub struct RequestWithdrawCashSecuredPutRollVault<'info> {
+    #[account(
+        mut,
+        seeds=[
+            "CashSecuredPutRollVaultInfo".as_bytes().as_ref(),
+            base_asset_mint.key().as_ref(),
+            quote_asset_mint.key().as_ref(),
+            roll_vault_info.strike_offset_bps.to_le_bytes().as_ref(),
+            roll_vault_info.tenor_seconds.to_le_bytes().as_ref()
+        ],
+        bump,
+        constraint = roll_vault_info.is_initialized == true,
+        constraint = roll_vault_info.base_asset == base_asset_mint.key(),
+        constraint = roll_vault_info.quote_asset == quote_asset_mint.key()
+    )]
+    pub roll_vault_info: Box<Account<'info, CashSecuredPutRollVaultInfo>>,
+
+    #[account(
+        init_if_needed,
+        seeds=[
+            "CashSecuredPutRollTicketInfo".as_bytes().as_ref(),
+            roll_vault_info.key().as_ref(),
+            initializer.key().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<CashSecuredPutRollTicketInfo>() + 8
+    )]
+    pub ticket_info: Account<'info, CashSecuredPutRollTicketInfo>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds=["CashSecuredPutRollShareMint".as_bytes().as_ref(), roll_vault_info.key().as_ref()],
+        bump,
+        constraint = share_mint.key() == roll_vault_info.share_mint
+    )]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = roll_share_escrow.mint == share_mint.key(),
+        constraint = roll_share_escrow.owner.key() == roll_vault_info.key() // Authority set to roll vault PDA
+    )]
+    pub roll_share_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = depositor_share_account.owner.key() == initializer.key(),
+        constraint = depositor_share_account.mint == share_mint.key()
+    )]
+    pub depositor_share_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+// Permissionless, anyone may close the current epoch between series (and pays the rent of its record)
+#[derive(Accounts)]
+pub struct CrankProcessCashSecuredPutRollQueues<'info> {
+    #[account(
+        mut,
+        seeds=[
+            "CashSecuredPutRollVaultInfo".as_bytes().as_ref(),
+            base_asset_mint.key().as_ref(),
+            quote_asset_mint.key().as_ref(),
+            roll_vault_info.strike_offset_bps.to_le_bytes().as_ref(),
+            roll_vault_info.tenor_seconds.to_le_bytes().as_ref()
+        ],
+        bump,
+        constraint = roll_vault_info.is_initialized == true,
+        constraint = roll_vault_info.base_asset == base_asset_mint.key(),
+        constraint = roll_vault_info.quote_asset == quote_asset_mint.key(),
+        constraint = roll_vault_info.is_in_series == false
+    )]
+    pub roll_vault_info: Box<Account<'info, CashSecuredPutRollVaultInfo>>,
+
+    #[account(
+        init,
+        seeds=[
+            "CashSecuredPutRollEpochInfo".as_bytes().as_ref(),
+            roll_vault_info.key().as_ref(),
+            roll_vault_info.epoch.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<CashSecuredPutRollEpochInfo>() + 8
+    )]
+    pub epoch_info: Box<Account<'info, CashSecuredPutRollEpochInfo>>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds=["CashSecuredPutRollShareMint".as_bytes().as_ref(), roll_vault_info.key().as_ref()],
+        bump,
+        constraint = share_mint.key() == roll_vault_info.share_mint
+    )]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = roll_share_escrow.mint == share_mint.key(),
+        constraint = roll_share_escrow.owner.key() == roll_vault_info.key() // Authority set to roll vault PDA
+    )]
+    pub roll_share_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimCashSecuredPutRollTicket<'info> {
+    #[account(
+        mut,
+        seeds=[
+            "CashSecuredPutRollVaultInfo".as_bytes().as_ref(),
+            base_asset_mint.key().as_ref(),
+            quote_asset_mint.key().as_ref(),
+            roll_vault_info.strike_offset_bps.to_le_bytes().as_ref(),
+            roll_vault_info.tenor_seconds.to_le_bytes().as_ref()
+        ],
+        bump,
+        constraint = roll_vault_info.is_initialized == true,
+        constraint = roll_vault_info.base_asset == base_asset_mint.key(),
+        constraint = roll_vault_info.quote_asset == quote_asset_mint.key()
+    )]
+    pub roll_vault_info: Box<Account<'info, CashSecuredPutRollVaultInfo>>,
+
+    #[account(
+        mut,
+        seeds=[
+            "CashSecuredPutRollTicketInfo".as_bytes().as_ref(),
+            roll_vault_info.key().as_ref(),
+            initializer.key().as_ref()
+        ],
+        bump,
+        constraint = ticket_info.is_initialized == true,
+        constraint = ticket_info.roll_vault == roll_vault_info.key()
+    )]
+    pub ticket_info: Account<'info, CashSecuredPutRollTicketInfo>,
+
+    #[account(
+        seeds=[
+            "CashSecuredPutRollEpochInfo".as_bytes().as_ref(),
+            roll_vault_info.key().as_ref(),
+            ticket_info.epoch.to_le_bytes().as_ref()
+        ],
+        bump,
+        constraint = epoch_info.roll_vault == roll_vault_info.key()
+    )]
+    pub epoch_info: Box<Account<'info, CashSecuredPutRollEpochInfo>>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds=["CashSecuredPutRollShareMint".as_bytes().as_ref(), roll_vault_info.key().as_ref()],
+        bump,
+        constraint = share_mint.key() == roll_vault_info.share_mint
+    )]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = roll_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = roll_quote_asset_treasury.owner.key() == roll_vault_info.key() // Authority set to roll vault PDA
+    )]
+    pub roll_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = depositor_share_account.owner.key() == initializer.key(),
+        constraint = depositor_share_account.mint == share_mint.key()
+    )]
+    pub depositor_share_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = depositor_quote_asset_account.owner.key() == initializer.key(),
+        constraint = depositor_quote_asset_account.mint == quote_asset_mint.key()
+    )]
+    pub depositor_quote_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+// Permissionless, anyone may crank the roll (and pays the rent of the new series accounts)
+#[derive(Accounts)]
+#[instruction(
+    maturity: u64, 
+    strike: u64,
+    vault_id: u64
+)]
+pub struct CrankRollCashSecuredPutVault<'info> {
+    #[account(
+        mut,
+        seeds=[
+            "CashSecuredPutRollVaultInfo".as_bytes().as_ref(),
+            base_asset_mint.key().as_ref(),
+            quote_asset_mint.key().as_ref(),
+            roll_vault_info.strike_offset_bps.to_le_bytes().as_ref(),
+            roll_vault_info.tenor_seconds.to_le_bytes().as_ref()
+        ],
+        bump,
+        constraint = roll_vault_info.is_initialized == true,
+        constraint = roll_vault_info.base_asset == base_asset_mint.key(),
+        constraint = roll_vault_info.quote_asset == quote_asset_mint.key(),
+        constraint = roll_vault_info.is_in_series == false
+    )]
+    pub roll_vault_info: Box<Account<'info, CashSecuredPutRollVaultInfo>>,
+
+    #[account(
+        mut,
+        constraint = roll_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = roll_quote_asset_treasury.owner.key() == roll_vault_info.key() // Authority set to roll vault PDA
+    )]
+    pub roll_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed, 
+        seeds=[
+            "PutOptionVaultFactoryInfo".as_bytes().as_ref(), 
+            base_asset_mint.key().as_ref(), 
+            quote_asset_mint.key().as_ref(), 
+            &maturity.to_le_bytes().as_ref(), 
+            &strike.to_le_bytes().as_ref()
+        ], 
+        bump, 
+        payer = initializer, 
+        space= std::mem::size_of::<PutOptionVaultFactoryInfo>() + 8,
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Box<Account<'info, PutOptionVaultFactoryInfo>>,
+
+    #[account(
+        seeds=["AssetPairConfig".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        constraint = asset_pair_config.is_initialized == true
+    )]
+    pub asset_pair_config: Box<Account<'info, AssetPairConfig>>,
+
+    #[account(
+        mut,
+        seeds=["SeriesIndex".as_bytes().as_ref(), base_asset_mint.key().as_ref(), quote_asset_mint.key().as_ref()],
+        bump,
+        realloc = SeriesIndex::space(series_index.entries.len() + 1),
+        realloc::payer = initializer,
+        realloc::zero = false,
+        constraint = series_index.is_initialized == true
+    )]
+    pub series_index: Box<Account<'info, SeriesIndex>>,
+
+    #[account(
+        init,
+        seeds=[
+            "PutOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            &vault_id.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = initializer, 
+        space= std::mem::size_of::<PutOptionVaultInfo>() + 8
+    )]
+    pub vault_info: Box<Account<'info, PutOptionVaultInfo>>,
+
+    #[account(
+        init,
+        payer = initializer, // Payer will be initializer
+        associated_token::mint = base_asset_mint, 
+        associated_token::authority = vault_info // Authority set to PDA
+    )]
+    pub vault_base_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = initializer, // Payer will be initializer
+        associated_token::mint = quote_asset_mint, // Quote asset mint
+        associated_token::authority = vault_info // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // the roll vault is the maker
+    #[account(
+        init,
+        seeds=[
+            "PutOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            &vault_id.to_le_bytes().as_ref(), 
+            roll_vault_info.key().as_ref()
+        ],
+        bump,
+        payer = initializer,
+        space = std::mem::size_of::<PutOptionMakerInfo>() + 8
+    )]
+    pub put_option_maker_info: Box<Account<'info, PutOptionMakerInfo>>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // Check if initializer is signer, mut is required to reduce lamports (fees)
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    
+    // System Program requred for deduction of lamports (fees)
+    pub system_program: Program<'info, System>,
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+// Permissionless, anyone may crank the settlement of the current series
+#[derive(Accounts)]
+pub struct CrankSettleCashSecuredPutRollVault<'info> {
+    #[account(
+        mut,
+        seeds=[
+            "CashSecuredPutRollVaultInfo".as_bytes().as_ref(),
+            base_asset_mint.key().as_ref(),
+            quote_asset_mint.key().as_ref(),
+            roll_vault_info.strike_offset_bps.to_le_bytes().as_ref(),
+            roll_vault_info.tenor_seconds.to_le_bytes().as_ref()
+        ],
+        bump,
+        constraint = roll_vault_info.is_initialized == true,
+        constraint = roll_vault_info.base_asset == base_asset_mint.key(),
+        constraint = roll_vault_info.quote_asset == quote_asset_mint.key(),
+        constraint = roll_vault_info.is_in_series == true,
+        constraint = roll_vault_info.current_put_option_vault == vault_info.key()
+    )]
+    pub roll_vault_info: Box<Account<'info, CashSecuredPutRollVaultInfo>>,
+
+    #[account(
+        mut,
+        constraint = roll_quote_asset_treasury.mint == quote_asset_mint.key(), // Quote asset mint
+        constraint = roll_quote_asset_treasury.owner.key() == roll_vault_info.key() // Authority set to roll vault PDA
+    )]
+    pub roll_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        constraint = vault_factory_info.strike > 0,
+        constraint = vault_factory_info.is_initialized == true,
+        constraint = vault_factory_info.matured == true,
+        constraint = vault_factory_info.settled_price > 0,
+        constraint = vault_factory_info.base_asset == base_asset_mint.key(),
+        constraint = vault_factory_info.quote_asset == quote_asset_mint.key(),
+        constraint = vault_factory_info.emergency_mode == false
+    )]
+    pub vault_factory_info: Box<Account<'info, PutOptionVaultFactoryInfo>>,
+
+    #[account(
+        mut,
+        seeds=[
+            "PutOptionVaultInfo".as_bytes().as_ref(), 
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref()
+        ], bump,
+        constraint = vault_info.factory_vault == vault_factory_info.key(),
+    )]
+    pub vault_info: Box<Account<'info, PutOptionVaultInfo>>,
+
+    #[account(
+        mut,
+        seeds=[
+            "PutOptionMakerInfo".as_bytes().as_ref(),
+            vault_factory_info.key().as_ref(),
+            vault_info.ord.to_le_bytes().as_ref(), 
+            roll_vault_info.key().as_ref()
+        ],
+        bump,
+        constraint = !put_option_maker_info.is_settled
+    )]
+    pub put_option_maker_info: Box<Account<'info, PutOptionMakerInfo>>,
+
+    // mint for the base_asset
+    pub base_asset_mint: InterfaceAccount<'info, Mint>,
+
+    // mint for the quote asset
+    pub quote_asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault_quote_asset_treasury.mint == quote_asset_mint.key(), // quote asset mint
+        constraint = vault_quote_asset_treasury.owner.key() == vault_info.key() // Authority set to vault PDA
+    )]
+    pub vault_quote_asset_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub initializer: Signer<'info>,
+
+    // Token Program required to call transfer instruction
+    pub token_program: Interface<'info, TokenInterface>,
+}
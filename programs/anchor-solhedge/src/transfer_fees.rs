@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::spl_token_2022::{
+    self,
+    extension::{BaseStateWithExtensions, StateWithExtensions, transfer_fee::TransferFeeConfig}
+};
+
+#[error_code]
+pub enum TransferFeeError {
+    #[msg("Overflow computing transfer fee")]
+    Overflow
+}
+
+// Token-2022 mints may have the transfer fee extension, in which case the destination
+// of a transfer receives less than what the source sends.
+
+pub fn has_transfer_fee(mint: &AccountInfo) -> Result<bool> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(false);
+    }
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    Ok(mint_state.get_extension::<TransferFeeConfig>().is_ok())
+}
+
+// How much must be sent so that the destination receives exactly `amount`. Used for deposits,
+// so that vaults always hold what they account for; the depositor pays the transfer fee.
+pub fn amount_with_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(amount);
+    }
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(transfer_fee_config) => {
+            let epoch = Clock::get()?.epoch;
+            let fee = transfer_fee_config.calculate_inverse_epoch_fee(epoch, amount).ok_or(TransferFeeError::Overflow)?;
+            if fee > 0 {
+                msg!("Adding transfer fee of {} to deposit of {}", fee, amount);
+            }
+            Ok(amount.checked_add(fee).ok_or(TransferFeeError::Overflow)?)
+        },
+        Err(_) => Ok(amount)
+    }
+}
+
+// How much the destination receives when `amount` is sent. Used when fees are paid out of escrows
+// that hold an exact amount, so that the recipient is credited only what arrived.
+pub fn amount_after_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(amount);
+    }
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(transfer_fee_config) => {
+            let epoch = Clock::get()?.epoch;
+            let fee = transfer_fee_config.calculate_epoch_fee(epoch, amount).ok_or(TransferFeeError::Overflow)?;
+            Ok(amount.checked_sub(fee).ok_or(TransferFeeError::Overflow)?)
+        },
+        Err(_) => Ok(amount)
+    }
+}
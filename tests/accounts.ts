@@ -37,6 +37,192 @@ export const getMakerNextPutOptionVaultIdFromTx = async (
       return vaultNumber
 }
 
+export const getAssetPairConfigPdaAddress = (
+  program: anchor.Program<AnchorSolhedge>,
+  baseAssetMint: anchor.web3.PublicKey,
+  quoteAssetMint: anchor.web3.PublicKey
+) => {
+  const [assetPairConfig, _assetPairConfigBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("AssetPairConfig")),
+      baseAssetMint.toBuffer(),
+      quoteAssetMint.toBuffer()
+    ],
+    program.programId
+  )
+
+  return assetPairConfig
+}
+
+// Live option series of a pair, created along with its AssetPairConfig
+export const getSeriesIndexPdaAddress = (
+  program: anchor.Program<AnchorSolhedge>,
+  baseAssetMint: anchor.web3.PublicKey,
+  quoteAssetMint: anchor.web3.PublicKey
+) => {
+  const [seriesIndex, _seriesIndexBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("SeriesIndex")),
+      baseAssetMint.toBuffer(),
+      quoteAssetMint.toBuffer()
+    ],
+    program.programId
+  )
+
+  return seriesIndex
+}
+
+// Vault factories can only be created for pairs registered by the admin (the protocol fees key)
+export const createAssetPairConfigIfNeeded = async (
+  program: anchor.Program<AnchorSolhedge>,
+  admin: anchor.web3.Keypair,
+  baseAssetMint: anchor.web3.PublicKey,
+  quoteAssetMint: anchor.web3.PublicKey,
+  params: AssetPairConfigParams
+) => {
+  const assetPairConfig = getAssetPairConfigPdaAddress(program, baseAssetMint, quoteAssetMint)
+  const existing = await program.account.assetPairConfig.fetchNullable(assetPairConfig)
+  if (existing != null) {
+    return assetPairConfig
+  }
+  // the insurance fund, fee vault and fee tiers of the quote asset are created with its first pair
+  const insuranceFund = getInsuranceFundPdaAddress(program, quoteAssetMint)
+  const feeVault = getFeeVaultPdaAddress(program, quoteAssetMint)
+  await program.methods.adminCreateAssetPairConfig(params).accounts({
+    assetPairConfig: assetPairConfig,
+    insuranceFundInfo: insuranceFund,
+    insuranceFundQuoteAssetTreasury: token.getAssociatedTokenAddressSync(quoteAssetMint, insuranceFund, true),
+    feeVaultInfo: feeVault,
+    feeVaultQuoteAssetTreasury: token.getAssociatedTokenAddressSync(quoteAssetMint, feeVault, true),
+    feeTiersInfo: getFeeTiersPdaAddress(program, quoteAssetMint),
+    baseAssetMint: baseAssetMint,
+    quoteAssetMint: quoteAssetMint,
+    initializer: admin.publicKey,
+    tokenProgram: token.TOKEN_PROGRAM_ID
+  }).signers([admin]).rpc({ commitment: "confirmed" })
+
+  return assetPairConfig
+}
+
+// Insurance fund of a quote asset, fed by part of the backend fees
+export const getInsuranceFundPdaAddress = (
+  program: anchor.Program<AnchorSolhedge>,
+  quoteAssetMint: anchor.web3.PublicKey
+) => {
+  const [insuranceFund, _insuranceFundBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("InsuranceFundInfo")),
+      quoteAssetMint.toBuffer()
+    ],
+    program.programId
+  )
+
+  return insuranceFund
+}
+
+// Insurance funds are created with the first asset pair of their quote asset, with no fee share,
+// so the fund is updated to feeShareBps if needed
+export const updateInsuranceFundIfNeeded = async (
+  program: anchor.Program<AnchorSolhedge>,
+  admin: anchor.web3.Keypair,
+  quoteAssetMint: anchor.web3.PublicKey,
+  feeShareBps: number
+) => {
+  const insuranceFund = getInsuranceFundPdaAddress(program, quoteAssetMint)
+  const existing = await program.account.insuranceFundInfo.fetch(insuranceFund)
+  if (existing.feeShareBps != feeShareBps) {
+    await program.methods.adminUpdateInsuranceFund(feeShareBps).accounts({
+      insuranceFundInfo: insuranceFund,
+      initializer: admin.publicKey
+    }).signers([admin]).rpc({ commitment: "confirmed" })
+  }
+
+  return insuranceFund
+}
+
+// Fees on premiums accrue in the fee vault of the quote asset, created with its first asset pair
+export const getFeeVaultPdaAddress = (
+  program: anchor.Program<AnchorSolhedge>,
+  quoteAssetMint: anchor.web3.PublicKey
+) => {
+  const [feeVault, _feeVaultBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("FeeVaultInfo")),
+      quoteAssetMint.toBuffer()
+    ],
+    program.programId
+  )
+
+  return feeVault
+}
+
+export const getFeeTiersPdaAddress = (
+  program: anchor.Program<AnchorSolhedge>,
+  quoteAssetMint: anchor.web3.PublicKey
+) => {
+  const [feeTiers, _feeTiersBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("FeeTiersInfo")),
+      quoteAssetMint.toBuffer()
+    ],
+    program.programId
+  )
+
+  return feeTiers
+}
+
+export const getTakerStatsPdaAddress = (
+  program: anchor.Program<AnchorSolhedge>,
+  taker: anchor.web3.PublicKey,
+  quoteAssetMint: anchor.web3.PublicKey
+) => {
+  const [takerStats, _takerStatsBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("TakerStatsInfo")),
+      taker.toBuffer(),
+      quoteAssetMint.toBuffer()
+    ],
+    program.programId
+  )
+
+  return takerStats
+}
+
+// Frontend registered by the admin for a quote asset, takers pass it to share fees with it
+export const getFrontendPdaAddress = (
+  program: anchor.Program<AnchorSolhedge>,
+  frontendOwner: anchor.web3.PublicKey,
+  quoteAssetMint: anchor.web3.PublicKey
+) => {
+  const [frontend, _frontendBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("FrontendInfo")),
+      frontendOwner.toBuffer(),
+      quoteAssetMint.toBuffer()
+    ],
+    program.programId
+  )
+
+  return frontend
+}
+
+// Asset pairs create the fee tiers of their quote asset without discounts nor maker rebates,
+// the admin sets them afterwards
+export const updateFeeTiers = async (
+  program: anchor.Program<AnchorSolhedge>,
+  admin: anchor.web3.Keypair,
+  quoteAssetMint: anchor.web3.PublicKey,
+  params: FeeTiersParams
+) => {
+  const feeTiers = getFeeTiersPdaAddress(program, quoteAssetMint)
+  await program.methods.adminUpdateFeeTiers(params).accounts({
+    feeTiersInfo: feeTiers,
+    initializer: admin.publicKey
+  }).signers([admin]).rpc({ commitment: "confirmed" })
+
+  return feeTiers
+}
+
 export const getCallOptionVaultFactoryPdaAddress = async (
   program: anchor.Program<AnchorSolhedge>,
   baseAssetMint: anchor.web3.PublicKey,
@@ -147,6 +333,22 @@ export const getUserSettleTicketAccountAddressForPutVaultFactory = async (
   return ticketAccountAddress
 }
 
+export const getUserSettleTicketAccountAddressForCallVaultFactory = async (
+  program: anchor.Program<AnchorSolhedge>,
+  vaultFactoryInfo: anchor.web3.PublicKey,
+  user: anchor.web3.PublicKey
+) => {
+  const [ticketAccountAddress, _ticketAccountBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("CallOptionSettlePriceTicketInfo")),
+      vaultFactoryInfo.toBuffer(),
+      user.toBuffer()
+    ],
+    program.programId
+  )
+  return ticketAccountAddress
+}
+
 export const getUserTicketAccountAddressForCallVaultFactory = async (
   program: anchor.Program<AnchorSolhedge>,
   vaultFactoryInfo: anchor.web3.PublicKey,
@@ -198,6 +400,314 @@ export const getPutMakerVaultAssociatedAccountAddress = async (
   return userAssociatedAccountAddress
 }
 
+export const getPutTakerVaultAssociatedAccountAddress = async (
+  program: anchor.Program<AnchorSolhedge>,
+  vaultFactoryInfo: anchor.web3.PublicKey,
+  vaultId: anchor.BN,
+  user: anchor.web3.PublicKey
+) => {
+  const [userAssociatedAccountAddress, _UserAssociatedAccountBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("PutOptionTakerInfo")),
+      vaultFactoryInfo.toBuffer(),
+      vaultId.toArrayLike(Buffer, "le", 8),
+      user.toBuffer()
+    ],
+    program.programId
+  )
+  return userAssociatedAccountAddress
+}
+
+// One resting bid per taker and put option vault, it is also the authority of the escrowed premium
+export const getPutOptionTakerBidPdaAddress = (
+  program: anchor.Program<AnchorSolhedge>,
+  vaultInfo: anchor.web3.PublicKey,
+  taker: anchor.web3.PublicKey
+) => {
+  const [takerBidInfo, _takerBidInfoBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("PutOptionTakerBidInfo")),
+      vaultInfo.toBuffer(),
+      taker.toBuffer()
+    ],
+    program.programId
+  )
+
+  return takerBidInfo
+}
+
+// One RFQ per taker and vault factory
+export const getPutOptionRfqPdaAddress = (
+  program: anchor.Program<AnchorSolhedge>,
+  vaultFactoryInfo: anchor.web3.PublicKey,
+  taker: anchor.web3.PublicKey
+) => {
+  const [rfqInfo, _rfqInfoBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("PutOptionRfqInfo")),
+      vaultFactoryInfo.toBuffer(),
+      taker.toBuffer()
+    ],
+    program.programId
+  )
+
+  return rfqInfo
+}
+
+// One quote per maker and RFQ, it is also the authority of the escrowed collateral
+export const getPutOptionRfqQuotePdaAddress = (
+  program: anchor.Program<AnchorSolhedge>,
+  rfqInfo: anchor.web3.PublicKey,
+  maker: anchor.web3.PublicKey
+) => {
+  const [quoteInfo, _quoteInfoBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("PutOptionRfqQuoteInfo")),
+      rfqInfo.toBuffer(),
+      maker.toBuffer()
+    ],
+    program.programId
+  )
+
+  return quoteInfo
+}
+
+export const getCallMakerVaultAssociatedAccountAddress = async (
+  program: anchor.Program<AnchorSolhedge>,
+  vaultFactoryInfo: anchor.web3.PublicKey,
+  vaultId: anchor.BN,
+  user: anchor.web3.PublicKey
+) => {
+  const [userAssociatedAccountAddress, _UserAssociatedAccountBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("CallOptionMakerInfo")),
+      vaultFactoryInfo.toBuffer(),
+      vaultId.toArrayLike(Buffer, "le", 8),
+      user.toBuffer()
+    ],
+    program.programId
+  )
+  return userAssociatedAccountAddress
+}
+
+export const getCallTakerVaultAssociatedAccountAddress = async (
+  program: anchor.Program<AnchorSolhedge>,
+  vaultFactoryInfo: anchor.web3.PublicKey,
+  vaultId: anchor.BN,
+  user: anchor.web3.PublicKey
+) => {
+  const [userAssociatedAccountAddress, _UserAssociatedAccountBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("CallOptionTakerInfo")),
+      vaultFactoryInfo.toBuffer(),
+      vaultId.toArrayLike(Buffer, "le", 8),
+      user.toBuffer()
+    ],
+    program.programId
+  )
+  return userAssociatedAccountAddress
+}
+
+// Binary option vault factories are also keyed by their payout per lot and by being a put or a call
+export const getBinaryOptionVaultFactoryPdaAddress = (
+  program: anchor.Program<AnchorSolhedge>,
+  baseAssetMint: anchor.web3.PublicKey,
+  quoteAssetMint: anchor.web3.PublicKey,
+  maturity: anchor.BN,
+  strike: anchor.BN,
+  payoutPerLot: anchor.BN,
+  isPut: boolean
+) => {
+  const [binaryOptionVaultFactoryInfo, _binaryOptionVaultFactoryInfoBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("BinaryOptionVaultFactoryInfo")),
+      baseAssetMint.toBuffer(),
+      quoteAssetMint.toBuffer(),
+      maturity.toArrayLike(Buffer, "le", 8),
+      strike.toArrayLike(Buffer, "le", 8),
+      payoutPerLot.toArrayLike(Buffer, "le", 8),
+      Buffer.from([isPut ? 1 : 0])
+    ],
+    program.programId
+  )
+
+  return binaryOptionVaultFactoryInfo
+}
+
+export const getBinaryOptionVaultDerivedPdaAddresses = (
+  program: anchor.Program<AnchorSolhedge>,
+  vaultFactoryInfo: anchor.web3.PublicKey,
+  quoteAssetMint: anchor.web3.PublicKey,
+  vaultId: anchor.BN
+) => {
+  const [binaryOptionVaultAddress, _binaryOptionVaultBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("BinaryOptionVaultInfo")),
+      vaultFactoryInfo.toBuffer(),
+      vaultId.toArrayLike(Buffer, "le", 8)
+    ],
+    program.programId
+  )
+  const vaultQuoteAssetTreasury = token.getAssociatedTokenAddressSync(quoteAssetMint, binaryOptionVaultAddress, true)
+
+  return { binaryOptionVaultAddress, vaultQuoteAssetTreasury }
+}
+
+export const getBinaryMakerVaultAssociatedAccountAddress = (
+  program: anchor.Program<AnchorSolhedge>,
+  vaultFactoryInfo: anchor.web3.PublicKey,
+  vaultId: anchor.BN,
+  user: anchor.web3.PublicKey
+) => {
+  const [userAssociatedAccountAddress, _UserAssociatedAccountBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("BinaryOptionMakerInfo")),
+      vaultFactoryInfo.toBuffer(),
+      vaultId.toArrayLike(Buffer, "le", 8),
+      user.toBuffer()
+    ],
+    program.programId
+  )
+  return userAssociatedAccountAddress
+}
+
+export const getBinaryTakerVaultAssociatedAccountAddress = (
+  program: anchor.Program<AnchorSolhedge>,
+  vaultFactoryInfo: anchor.web3.PublicKey,
+  vaultId: anchor.BN,
+  user: anchor.web3.PublicKey
+) => {
+  const [userAssociatedAccountAddress, _UserAssociatedAccountBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("BinaryOptionTakerInfo")),
+      vaultFactoryInfo.toBuffer(),
+      vaultId.toArrayLike(Buffer, "le", 8),
+      user.toBuffer()
+    ],
+    program.programId
+  )
+  return userAssociatedAccountAddress
+}
+
+export const getUserTicketAccountAddressForBinaryVaultFactory = (
+  program: anchor.Program<AnchorSolhedge>,
+  vaultFactoryInfo: anchor.web3.PublicKey,
+  user: anchor.web3.PublicKey
+) => {
+  const [ticketAccountAddress, _ticketAccountBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("BinaryOptionUpdateTicketInfo")),
+      vaultFactoryInfo.toBuffer(),
+      user.toBuffer()
+    ],
+    program.programId
+  )
+  return ticketAccountAddress
+}
+
+// One covered call roll vault per pair, strike offset and tenor
+export const getCoveredCallRollVaultPdaAddress = (
+  program: anchor.Program<AnchorSolhedge>,
+  baseAssetMint: anchor.web3.PublicKey,
+  quoteAssetMint: anchor.web3.PublicKey,
+  strikeOffsetBps: number,
+  tenorSeconds: anchor.BN
+) => {
+  const [rollVaultInfo, _rollVaultInfoBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("CoveredCallRollVaultInfo")),
+      baseAssetMint.toBuffer(),
+      quoteAssetMint.toBuffer(),
+      new anchor.BN(strikeOffsetBps).toArrayLike(Buffer, "le", 2),
+      tenorSeconds.toArrayLike(Buffer, "le", 8)
+    ],
+    program.programId
+  )
+
+  return rollVaultInfo
+}
+
+export const getCoveredCallRollSharePdaAddress = (
+  program: anchor.Program<AnchorSolhedge>,
+  rollVaultInfo: anchor.web3.PublicKey,
+  owner: anchor.web3.PublicKey
+) => {
+  const [shareInfo, _shareInfoBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("CoveredCallRollShareInfo")),
+      rollVaultInfo.toBuffer(),
+      owner.toBuffer()
+    ],
+    program.programId
+  )
+
+  return shareInfo
+}
+
+// One cash-secured put roll vault per pair, strike offset and tenor, with its own share mint
+export const getCashSecuredPutRollVaultPdaAddresses = (
+  program: anchor.Program<AnchorSolhedge>,
+  baseAssetMint: anchor.web3.PublicKey,
+  quoteAssetMint: anchor.web3.PublicKey,
+  strikeOffsetBps: number,
+  tenorSeconds: anchor.BN
+) => {
+  const [rollVaultInfo, _rollVaultInfoBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("CashSecuredPutRollVaultInfo")),
+      baseAssetMint.toBuffer(),
+      quoteAssetMint.toBuffer(),
+      new anchor.BN(strikeOffsetBps).toArrayLike(Buffer, "le", 2),
+      tenorSeconds.toArrayLike(Buffer, "le", 8)
+    ],
+    program.programId
+  )
+  const [shareMint, _shareMintBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("CashSecuredPutRollShareMint")),
+      rollVaultInfo.toBuffer()
+    ],
+    program.programId
+  )
+
+  return { rollVaultInfo, shareMint }
+}
+
+// A user has a single ticket per cash-secured put roll vault
+export const getCashSecuredPutRollTicketPdaAddress = (
+  program: anchor.Program<AnchorSolhedge>,
+  rollVaultInfo: anchor.web3.PublicKey,
+  owner: anchor.web3.PublicKey
+) => {
+  const [ticketInfo, _ticketInfoBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("CashSecuredPutRollTicketInfo")),
+      rollVaultInfo.toBuffer(),
+      owner.toBuffer()
+    ],
+    program.programId
+  )
+
+  return ticketInfo
+}
+
+export const getCashSecuredPutRollEpochPdaAddress = (
+  program: anchor.Program<AnchorSolhedge>,
+  rollVaultInfo: anchor.web3.PublicKey,
+  epoch: anchor.BN
+) => {
+  const [epochInfo, _epochInfoBump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("CashSecuredPutRollEpochInfo")),
+      rollVaultInfo.toBuffer(),
+      epoch.toArrayLike(Buffer, "le", 8)
+    ],
+    program.programId
+  )
+
+  return epochInfo
+}
+
 export const getAllMaybeNotMaturedPutFactories = async (
   program: anchor.Program<AnchorSolhedge>,
 ) => {
@@ -895,7 +1405,19 @@ export class MakerCreatePutOptionParams {
   maxTakers: number //u16,
   lotSize: number //i8,
   numLotsToSell: anchor.BN //u64,
-  premiumLimit: anchor.BN //u64
+  premiumLimit: anchor.BN //u64,
+  cashSettled: boolean //bool
+  american: boolean //bool
+  barrier: anchor.BN //u64
+  barrierUp: boolean //bool
+  knockIn: boolean //bool
+  auctionSeconds: anchor.BN //u64
+  auctionStartMultipleBps: number //u16
+  auctionFloorMultipleBps: number //u16
+  makerAllocation: object //MakerAllocation, { takerChoice: {} }, { fifo: {} } or { proRata: {} }
+  initialMarginBps: number //u16
+  maintenanceMarginBps: number //u16
+  liquidationBonusBps: number //u16
 
   constructor(params: {
     maturity: anchor.BN //u64,
@@ -904,7 +1426,19 @@ export class MakerCreatePutOptionParams {
     maxTakers: number //u16,
     lotSize: number //i8,
     numLotsToSell: anchor.BN //u64,
-    premiumLimit: anchor.BN //u64
+    premiumLimit: anchor.BN //u64,
+    cashSettled: boolean //bool
+    american: boolean //bool
+    barrier: anchor.BN //u64
+    barrierUp: boolean //bool
+    knockIn: boolean //bool
+    auctionSeconds: anchor.BN //u64
+    auctionStartMultipleBps: number //u16
+    auctionFloorMultipleBps: number //u16
+    makerAllocation: object //MakerAllocation
+    initialMarginBps: number //u16
+    maintenanceMarginBps: number //u16
+    liquidationBonusBps: number //u16
   }) {
     this.maturity = params.maturity
     this.strike = params.strike
@@ -913,6 +1447,18 @@ export class MakerCreatePutOptionParams {
     this.lotSize = params.lotSize
     this.numLotsToSell = params.numLotsToSell
     this.premiumLimit = params.premiumLimit
+    this.cashSettled = params.cashSettled
+    this.american = params.american
+    this.barrier = params.barrier
+    this.barrierUp = params.barrierUp
+    this.knockIn = params.knockIn
+    this.auctionSeconds = params.auctionSeconds
+    this.auctionStartMultipleBps = params.auctionStartMultipleBps
+    this.auctionFloorMultipleBps = params.auctionFloorMultipleBps
+    this.makerAllocation = params.makerAllocation
+    this.initialMarginBps = params.initialMarginBps
+    this.maintenanceMarginBps = params.maintenanceMarginBps
+    this.liquidationBonusBps = params.liquidationBonusBps
   }
 }
 
@@ -923,7 +1469,14 @@ export class MakerCreateCallOptionParams {
   maxTakers: number //u16,
   lotSize: number //i8,
   numLotsToSell: anchor.BN //u64,
-  premiumLimit: anchor.BN //u64
+  premiumLimit: anchor.BN //u64,
+  cashSettled: boolean //bool
+  barrier: anchor.BN //u64
+  barrierUp: boolean //bool
+  knockIn: boolean //bool
+  auctionSeconds: anchor.BN //u64
+  auctionStartMultipleBps: number //u16
+  auctionFloorMultipleBps: number //u16
 
   constructor(params: {
     maturity: anchor.BN //u64,
@@ -932,7 +1485,14 @@ export class MakerCreateCallOptionParams {
     maxTakers: number //u16,
     lotSize: number //i8,
     numLotsToSell: anchor.BN //u64,
-    premiumLimit: anchor.BN //u64
+    premiumLimit: anchor.BN //u64,
+    cashSettled: boolean //bool
+    barrier: anchor.BN //u64
+    barrierUp: boolean //bool
+    knockIn: boolean //bool
+    auctionSeconds: anchor.BN //u64
+    auctionStartMultipleBps: number //u16
+    auctionFloorMultipleBps: number //u16
   }) {
     this.maturity = params.maturity
     this.strike = params.strike
@@ -941,5 +1501,71 @@ export class MakerCreateCallOptionParams {
     this.lotSize = params.lotSize
     this.numLotsToSell = params.numLotsToSell
     this.premiumLimit = params.premiumLimit
+    this.cashSettled = params.cashSettled
+    this.barrier = params.barrier
+    this.barrierUp = params.barrierUp
+    this.knockIn = params.knockIn
+    this.auctionSeconds = params.auctionSeconds
+    this.auctionStartMultipleBps = params.auctionStartMultipleBps
+    this.auctionFloorMultipleBps = params.auctionFloorMultipleBps
+  }
+}
+
+export class AssetPairConfigParams {
+  enabled: boolean //bool
+  minLotSize: number //i8
+  maxLotSize: number //i8
+  minStrike: anchor.BN //u64
+  maxStrike: anchor.BN //u64
+  maxMaturitySeconds: anchor.BN //u64
+  priceSource: anchor.web3.PublicKey //Pubkey
+  protocolFeesBps: number //u16
+  standardized: boolean //bool
+  strikeTick: anchor.BN //u64
+  maturityGridSeconds: anchor.BN //u64
+  maturityGridOffsetSeconds: anchor.BN //u64
+
+  constructor(params: {
+    enabled: boolean //bool
+    minLotSize: number //i8
+    maxLotSize: number //i8
+    minStrike: anchor.BN //u64
+    maxStrike: anchor.BN //u64
+    maxMaturitySeconds: anchor.BN //u64
+    priceSource: anchor.web3.PublicKey //Pubkey
+    protocolFeesBps: number //u16
+    standardized: boolean //bool
+    strikeTick: anchor.BN //u64
+    maturityGridSeconds: anchor.BN //u64
+    maturityGridOffsetSeconds: anchor.BN //u64
+  }) {
+    this.enabled = params.enabled
+    this.minLotSize = params.minLotSize
+    this.maxLotSize = params.maxLotSize
+    this.minStrike = params.minStrike
+    this.maxStrike = params.maxStrike
+    this.maxMaturitySeconds = params.maxMaturitySeconds
+    this.priceSource = params.priceSource
+    this.protocolFeesBps = params.protocolFeesBps
+    this.standardized = params.standardized
+    this.strikeTick = params.strikeTick
+    this.maturityGridSeconds = params.maturityGridSeconds
+    this.maturityGridOffsetSeconds = params.maturityGridOffsetSeconds
+  }
+}
+
+export class FeeTiersParams {
+  tiers: { minVolume: anchor.BN, discountBps: number }[] //MAX_FEE_TIERS tiers, unused ones with discountBps 0
+  makerRebateBps: number //u16
+  makerRebateMinSeconds: anchor.BN //u64
+
+  constructor(params: {
+    tiers: { minVolume: anchor.BN, discountBps: number }[]
+    makerRebateBps: number
+    makerRebateMinSeconds: anchor.BN
+  }) {
+    this.tiers = params.tiers
+    this.makerRebateBps = params.makerRebateBps
+    this.makerRebateMinSeconds = params.makerRebateMinSeconds
   }
-}
\ No newline at end of file
+}
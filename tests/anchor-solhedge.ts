@@ -11,6 +11,17 @@ import { assert, expect } from "chai";
 import { 
   MakerCreatePutOptionParams, 
   MakerCreateCallOptionParams,
+  AssetPairConfigParams,
+  createAssetPairConfigIfNeeded,
+  getAssetPairConfigPdaAddress,
+  updateInsuranceFundIfNeeded,
+  getInsuranceFundPdaAddress,
+  getFeeVaultPdaAddress,
+  updateFeeTiers,
+  getFeeTiersPdaAddress,
+  getTakerStatsPdaAddress,
+  getFrontendPdaAddress,
+  FeeTiersParams,
   getPutOptionVaultFactoryPdaAddress,
   getCallOptionVaultFactoryPdaAddress,
   getPutOptionVaultDerivedPdaAddresses, 
@@ -26,9 +37,14 @@ import {
   getUserSettleTicketAccountAddressForPutVaultFactory,
   getPutMakerATAs,
   getMakerNextPutOptionVaultIdFromTx,
+  getSeriesIndexPdaAddress,
   getUserTakerInfoForPutVault,
   getPutSellersAsRemainingAccounts,
   getUserTakerInfoAllPutVaults,
+  getPutTakerVaultAssociatedAccountAddress,
+  getPutOptionTakerBidPdaAddress,
+  getPutOptionRfqPdaAddress,
+  getPutOptionRfqQuotePdaAddress,
   getAllMaybeNotMaturedCallFactories,
   getVaultsForCallFactory,
   getUserMakerInfoAllCallVaults,
@@ -36,13 +52,26 @@ import {
   getUserMakerInfoForCallVault,
   getUserTicketAccountAddressForCallVaultFactory,
   getCallSellersInVault,
-  getCallSellersAsRemainingAccounts
+  getCallSellersAsRemainingAccounts,
+  getCallMakerVaultAssociatedAccountAddress,
+  getCallTakerVaultAssociatedAccountAddress,
+  getUserSettleTicketAccountAddressForCallVaultFactory,
+  getCoveredCallRollVaultPdaAddress,
+  getCoveredCallRollSharePdaAddress,
+  getCashSecuredPutRollVaultPdaAddresses,
+  getCashSecuredPutRollTicketPdaAddress,
+  getCashSecuredPutRollEpochPdaAddress,
+  getBinaryOptionVaultFactoryPdaAddress,
+  getBinaryOptionVaultDerivedPdaAddresses,
+  getBinaryMakerVaultAssociatedAccountAddress,
+  getBinaryTakerVaultAssociatedAccountAddress,
+  getUserTicketAccountAddressForBinaryVaultFactory
 
 } from "./accounts";
 import * as borsh from "borsh";
 import { getOraclePubKey, _testInitializeOracleAccount, updatePutOptionFairPrice, lastKnownPrice, updateCallOptionFairPrice } from "./oracle";
 import { snakeBTCMintAddr, snakeDollarMintAddr, mintSnakeDollarTo, mintSnakeBTCTo } from "./snake-minter-devnet";
-import { oracleAddr, updatePutOptionSettlePrice } from "./oracle";
+import { oracleAddr, updatePutOptionSettlePrice, _testSettlePutOptionPrice, _testUpdatePutOptionFairPrice, _testUpdatePutOptionFairPriceWithSpot, _testUpdateCallOptionFairPrice, _testSettleCallOptionPrice, _testUpdateCashSecuredPutRollSpotPrice, _testUpdateBinaryOptionFairPrice } from "./oracle";
 
 dotenv.config()
 
@@ -69,6 +98,9 @@ const protocolFeesAddr = new anchor.web3.PublicKey(DEVNET_PROTOCOL_FEES_PUBKEY)
 // Should be the same as FREEZE_SECONDS in anchor-solhedge/lib.rs
 const FREEZE_SECONDS = 30*60
 
+// Should be the same as FEE_TIER_VOLUME_WINDOW_DAYS in anchor-solhedge/lib.rs
+const FEE_TIER_VOLUME_WINDOW_DAYS = 30
+
 async function fundPeerIfNeeded(
   payer: anchor.web3.Keypair, 
   peer: anchor.web3.PublicKey,
@@ -216,6 +248,53 @@ describe("anchor-solhedge-devnet", () => {
       console.log("Your transaction signature", tx);
     });
 
+    it("Registering SnakeBTC/SnakeDollar asset pair, if needed", async () => {
+      const protocolFeesKeypair = keyPairFromSecret(TEST_PROTOCOL_FEES_KEY)
+      const assetPairConfig = await createAssetPairConfigIfNeeded(program, protocolFeesKeypair, snakeBTCMintAddr, snakeDollarMintAddr, new AssetPairConfigParams({
+        enabled: true,
+        minLotSize: -8,
+        maxLotSize: 2,
+        minStrike: new anchor.BN(1),
+        maxStrike: new anchor.BN("18446744073709551615"),
+        maxMaturitySeconds: new anchor.BN(30*24*60*60),
+        priceSource: oracleAddr,
+        protocolFeesBps: 100,
+        standardized: false,
+        strikeTick: new anchor.BN(0),
+        maturityGridSeconds: new anchor.BN(0),
+        maturityGridOffsetSeconds: new anchor.BN(0)
+      }))
+      console.log("Asset pair config is", assetPairConfig.toString())
+    });
+
+    it("Setting the fee share of the SnakeDollar insurance fund, if needed", async () => {
+      const protocolFeesKeypair = keyPairFromSecret(TEST_PROTOCOL_FEES_KEY)
+      const insuranceFund = await updateInsuranceFundIfNeeded(program, protocolFeesKeypair, snakeDollarMintAddr, 2000)
+      console.log("Insurance fund is", insuranceFund.toString())
+    });
+
+    it("Checking the SnakeDollar fee vault was created with the asset pair", async () => {
+      const feeVault = getFeeVaultPdaAddress(program, snakeDollarMintAddr)
+      const feeVaultInfo = await program.account.feeVaultInfo.fetch(feeVault)
+      assert.equal(feeVaultInfo.quoteAsset.toString(), snakeDollarMintAddr.toString())
+      console.log("Fee vault is", feeVault.toString())
+    });
+
+    it("Setting the SnakeDollar fee tiers", async () => {
+      const protocolFeesKeypair = keyPairFromSecret(TEST_PROTOCOL_FEES_KEY)
+      const feeTiers = await updateFeeTiers(program, protocolFeesKeypair, snakeDollarMintAddr, new FeeTiersParams({
+        tiers: [
+          { minVolume: new anchor.BN(10000*(10**6)), discountBps: 1000 },
+          { minVolume: new anchor.BN(100000*(10**6)), discountBps: 2500 },
+          { minVolume: new anchor.BN(0), discountBps: 0 },
+          { minVolume: new anchor.BN(0), discountBps: 0 }
+        ],
+        makerRebateBps: 1000,
+        makerRebateMinSeconds: new anchor.BN(24*60*60)
+      }))
+      console.log("Fee tiers are", feeTiers.toString())
+    });
+
     it(`Minting 500 SnakeDollars to ${putMaker1Keypair.publicKey} if his balance is < 500`, async () => {
       let balance = await getTokenBalance(anchor.getProvider().connection, devnetPayerKeypair, snakeDollarMintAddr, putMaker1Keypair.publicKey)
       const mint = await token.getMint(anchor.getProvider().connection, snakeDollarMintAddr)
@@ -286,7 +365,19 @@ describe("anchor-solhedge-devnet", () => {
           maxMakers: 100,
           maxTakers: 100,
           numLotsToSell: new anchor.BN(10),
-          premiumLimit: new anchor.BN(0)  
+          premiumLimit: new anchor.BN(0),
+          cashSettled: false,
+          american: false,
+          barrier: new anchor.BN(0),
+          barrierUp: false,
+          knockIn: false,
+          auctionSeconds: new anchor.BN(0),
+          auctionStartMultipleBps: 0,
+          auctionFloorMultipleBps: 0,
+          makerAllocation: { takerChoice: {} },
+          initialMarginBps: 0,
+          maintenanceMarginBps: 0,
+          liquidationBonusBps: 0
         }
       )
       const putOptionVaultFactoryAddress = await getPutOptionVaultFactoryPdaAddress(program, snakeBTCMintAddr, snakeDollarMintAddr, vaultParams.maturity, vaultParams.strike)
@@ -316,6 +407,7 @@ describe("anchor-solhedge-devnet", () => {
       )
   
       var tx2 = await program.methods.makerCreatePutOptionVault(vaultParams, vaultNumber).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
         initializer: putMaker1Keypair.publicKey,
         vaultFactoryInfo: putOptionVaultFactoryAddress,
         vaultInfo: putOptionVaultAddress,
@@ -388,6 +480,7 @@ describe("anchor-solhedge-devnet", () => {
             if (myBalance >= minEntry) {
               const numLots = Math.floor(myBalance/minEntry)
               let tx3 = await program.methods.makerEnterPutOptionVault(new anchor.BN(numLots), new anchor.BN(0)).accounts({
+                tokenProgram: token.TOKEN_PROGRAM_ID,
                 initializer: putMaker2Keypair.publicKey,
                 vaultFactoryInfo: vaultFactory.publicKey,
                 vaultInfo: vault.publicKey,
@@ -435,6 +528,7 @@ describe("anchor-solhedge-devnet", () => {
     
                 const newQtyLots = maker2InfoForVault[0].account.volumeSold.toNumber()/lotPrice
                 let tx4 = await program.methods.makerAdjustPositionPutOptionVault(new anchor.BN(newQtyLots), new anchor.BN(0)).accounts({        
+                  tokenProgram: token.TOKEN_PROGRAM_ID,
                   initializer: putMaker2Keypair.publicKey,
                   vaultFactoryInfo: vaultFactory.publicKey,
                   vaultInfo: vault.publicKey,
@@ -523,7 +617,6 @@ describe("anchor-solhedge-devnet", () => {
               const lotsToBuy = Math.min(maxLotsToBuy, lotsQuoteAssetCanBuy)
               console.log(`Puttaker ${putTakerKeypair.publicKey} will try to buy ${lotsToBuy} lots`)
               const remainingAccounts = await getPutSellersAsRemainingAccounts(lotsToBuy, program, sellers)
-              const protocolFeesUSDCATA = await createTokenAccount(anchor.getProvider().connection, devnetPayerKeypair, snakeDollarMintAddr, protocolFeesAddr)
               const myMaxPrice = Math.floor(updatedVaultFactory.lastFairPrice.toNumber()*1.05)
               
 
@@ -531,11 +624,16 @@ describe("anchor-solhedge-devnet", () => {
                 new anchor.BN(myMaxPrice), 
                 new anchor.BN(lotsToBuy), 
                 new anchor.BN(btcLamports)).accounts({
+                  tokenProgram: token.TOKEN_PROGRAM_ID,
                   baseAssetMint: snakeBTCMintAddr,
                   quoteAssetMint: snakeDollarMintAddr,
                   initializer: putTakerKeypair.publicKey,
-                  protocolQuoteAssetTreasury: protocolFeesUSDCATA.address,
-                  frontendQuoteAssetTreasury: protocolFeesUSDCATA.address, //also sending frontend share to protocol in this test
+                  feeVaultInfo: getFeeVaultPdaAddress(program, snakeDollarMintAddr),
+                  feeVaultQuoteAssetTreasury: token.getAssociatedTokenAddressSync(snakeDollarMintAddr, getFeeVaultPdaAddress(program, snakeDollarMintAddr), true),
+                  insuranceFundInfo: getInsuranceFundPdaAddress(program, snakeDollarMintAddr),
+                  frontendInfo: null, //unregistered frontend, its share goes to the protocol
+                  feeTiersInfo: getFeeTiersPdaAddress(program, snakeDollarMintAddr),
+                  takerStatsInfo: getTakerStatsPdaAddress(program, putTakerKeypair.publicKey, snakeDollarMintAddr),
                   takerBaseAssetAccount: token.getAssociatedTokenAddressSync(snakeBTCMintAddr, putTakerKeypair.publicKey, false),
                   takerQuoteAssetAccount: token.getAssociatedTokenAddressSync(snakeDollarMintAddr, putTakerKeypair.publicKey, false),
                   vaultFactoryInfo: vaultFactory.publicKey,
@@ -599,6 +697,7 @@ describe("anchor-solhedge-devnet", () => {
           if (vaultFactoryInfo.matured && !makerInfo.account.isSettled) {
             console.log(`Put maker ${putMaker.publicKey} will get of option vault ${vaultAddr}`)
             let tx = await program.methods.makerSettlePutOption().accounts({
+              tokenProgram: token.TOKEN_PROGRAM_ID,
               baseAssetMint: vaultFactoryInfo.baseAsset,
               initializer: putMaker.publicKey,
               makerBaseAssetAccount: baseAssetATAAddr.address,
@@ -629,6 +728,7 @@ describe("anchor-solhedge-devnet", () => {
           if (vaultFactoryInfo.matured && !takerInfo.account.isSettled) {
             console.log(`Put taker ${putTaker.publicKey} will get of option vault ${vaultAddr}`)
             let tx = await program.methods.takerSettlePutOption().accounts({
+              tokenProgram: token.TOKEN_PROGRAM_ID,
               baseAssetMint: vaultFactoryInfo.baseAsset,
               initializer: putTaker.publicKey,
               putOptionTakerInfo: takerInfo.publicKey,
@@ -695,6 +795,32 @@ describe("anchor-solhedge-localnet", () => {
       const buffer = Buffer.from(data, "base64");
       return [key, data, buffer];
     };  
+
+    // Series opened below with this maturity are settled at the end of the suite, after "Waiting for the short maturity".
+    // The margin over FREEZE_SECONDS is the time the tests have to trade in them
+    const shortMaturity = new anchor.BN(Math.floor(Date.now()/1000) + FREEZE_SECONDS + 10*60)
+    const shortStrike = new anchor.BN(24000*(10**6))
+    let rfqVault: { vaultFactory: anchor.web3.PublicKey, vault: anchor.web3.PublicKey, collateral: number } = undefined
+    const marginedStrike = new anchor.BN(20000*(10**6))
+    let marginedVault: { vaultFactory: anchor.web3.PublicKey, vault: anchor.web3.PublicKey, vaultId: anchor.BN } = undefined
+    let coveredCallRoll: { rollVault: anchor.web3.PublicKey, vaultFactory: anchor.web3.PublicKey, vault: anchor.web3.PublicKey, vaultId: anchor.BN, idle: number, committed: number, premium: number } = undefined
+    let putRoll: { rollVault: anchor.web3.PublicKey, shareMint: anchor.web3.PublicKey, vaultFactory: anchor.web3.PublicKey, vault: anchor.web3.PublicKey, vaultId: anchor.BN, idle: number, committed: number, premium: number, pendingDeposit: number } = undefined
+
+    // The oracle sets a fair price (and optionally a spot price) chosen by the test,
+    // instructions needing a fresh one must follow within a minute
+    const setPutFairPrice = async (vaultFactory: anchor.web3.PublicKey, fairPrice: number, spotPrice?: number) => {
+      await program.methods.genUpdatePutOptionFairPriceTicket().accounts({
+        vaultFactoryInfo: vaultFactory,
+        initializer: putTakerKeypair.publicKey,
+        oracleWallet: getOraclePubKey(),
+        putOptionFairPriceTicket: await getUserTicketAccountAddressForPutVaultFactory(program, vaultFactory, putTakerKeypair.publicKey)
+      }).signers([putTakerKeypair]).rpc(confirmOptions)
+      if (spotPrice == undefined) {
+        await _testUpdatePutOptionFairPrice(program, vaultFactory, putTakerKeypair.publicKey, new anchor.BN(fairPrice))
+      } else {
+        await _testUpdatePutOptionFairPriceWithSpot(program, vaultFactory, putTakerKeypair.publicKey, new anchor.BN(fairPrice), new anchor.BN(spotPrice))
+      }
+    }
   
     before(
       "Getting some SOL for minter and put maker, if needed",
@@ -745,6 +871,46 @@ describe("anchor-solhedge-localnet", () => {
       const tx = await program.methods.initialize().rpc();
       console.log("Your transaction signature", tx);
     });
+    it("Registering wBTC/USDC asset pair, if needed", async () => {
+      const assetPairConfig = await createAssetPairConfigIfNeeded(program, protocolFeesKeypair, wormholeBTCToken, usdcToken, new AssetPairConfigParams({
+          enabled: true,
+          minLotSize: -8,
+          maxLotSize: 2,
+          minStrike: new anchor.BN(1),
+          maxStrike: new anchor.BN("18446744073709551615"),
+          maxMaturitySeconds: new anchor.BN(30*24*60*60),
+          priceSource: getOraclePubKey(),
+          protocolFeesBps: 100,
+          standardized: false,
+          strikeTick: new anchor.BN(0),
+          maturityGridSeconds: new anchor.BN(0),
+          maturityGridOffsetSeconds: new anchor.BN(0)
+        }))
+      console.log("Asset pair config is", assetPairConfig.toString())
+    });
+    it("Setting the fee share of the USDC insurance fund, if needed", async () => {
+      const insuranceFund = await updateInsuranceFundIfNeeded(program, protocolFeesKeypair, usdcToken, 2000)
+      console.log("Insurance fund is", insuranceFund.toString())
+    });
+    it("Checking the USDC fee vault was created with the asset pair", async () => {
+      const feeVault = getFeeVaultPdaAddress(program, usdcToken)
+      const feeVaultInfo = await program.account.feeVaultInfo.fetch(feeVault)
+      assert.equal(feeVaultInfo.quoteAsset.toString(), usdcToken.toString())
+      console.log("Fee vault is", feeVault.toString())
+    });
+    it("Setting the USDC fee tiers", async () => {
+      const feeTiers = await updateFeeTiers(program, protocolFeesKeypair, usdcToken, new FeeTiersParams({
+        tiers: [
+          { minVolume: new anchor.BN(10000*(10**6)), discountBps: 1000 },
+          { minVolume: new anchor.BN(100000*(10**6)), discountBps: 2500 },
+          { minVolume: new anchor.BN(0), discountBps: 0 },
+          { minVolume: new anchor.BN(0), discountBps: 0 }
+        ],
+        makerRebateBps: 1000,
+        makerRebateMinSeconds: new anchor.BN(24*60*60)
+      }))
+      console.log("Fee tiers are", feeTiers.toString())
+    });
     it("Creating a call option maker vault", async () => {
       const conn = anchor.getProvider().connection
       const wBTCMintAmountTaker = 0.02
@@ -769,7 +935,14 @@ describe("anchor-solhedge-localnet", () => {
           maxMakers: 100,
           maxTakers: 100,
           numLotsToSell: new anchor.BN(10),
-          premiumLimit: new anchor.BN(0)  
+          premiumLimit: new anchor.BN(0),
+          cashSettled: false,
+          barrier: new anchor.BN(0),
+          barrierUp: false,
+          knockIn: false,
+          auctionSeconds: new anchor.BN(0),
+          auctionStartMultipleBps: 0,
+          auctionFloorMultipleBps: 0
         })
         const callOptionVaultFactoryAddress = await getCallOptionVaultFactoryPdaAddress(program, wormholeBTCToken, usdcToken, vaultParams.maturity, vaultParams.strike)
 
@@ -802,6 +975,7 @@ describe("anchor-solhedge-localnet", () => {
         } = await getCallOptionVaultDerivedPdaAddresses(program, callOptionVaultFactoryAddress, wormholeBTCToken, usdcToken, vaultNumber)
     
         var tx2 = await program.methods.makerCreateCallOptionVault(vaultParams, vaultNumber).accounts({
+          tokenProgram: token.TOKEN_PROGRAM_ID,
           initializer: callMakerKeypair.publicKey,
           vaultFactoryInfo: callOptionVaultFactoryAddress,
           vaultInfo: callOptionVaultAddress,
@@ -856,6 +1030,7 @@ describe("anchor-solhedge-localnet", () => {
 
         try {
           let tx3 = await program.methods.makerEnterCallOptionVault(new anchor.BN(40), new anchor.BN(0)).accounts({
+            tokenProgram: token.TOKEN_PROGRAM_ID,
             initializer: callMaker2Keypair.publicKey,
             vaultFactoryInfo: callOptionVaultFactoryAddress2,
             vaultInfo: vaultInfo.publicKey,
@@ -878,6 +1053,7 @@ describe("anchor-solhedge-localnet", () => {
 
         // testing maker adjust position
         let tx4 = await program.methods.makerAdjustPositionCallOptionVault(new anchor.BN(0), new anchor.BN(0)).accounts({        
+          tokenProgram: token.TOKEN_PROGRAM_ID,
           initializer: callMaker2Keypair.publicKey,
           vaultFactoryInfo: callOptionVaultFactoryAddress2,
           vaultInfo: vaultInfo.publicKey,
@@ -891,6 +1067,7 @@ describe("anchor-solhedge-localnet", () => {
         assert.equal(maker2InfoForVault[0].account.baseAssetQty.toNumber(), 0)
     
         let tx5 = await program.methods.makerAdjustPositionCallOptionVault(new anchor.BN(40), new anchor.BN(0)).accounts({
+          tokenProgram: token.TOKEN_PROGRAM_ID,
           
           initializer: callMaker2Keypair.publicKey,
           vaultFactoryInfo: callOptionVaultFactoryAddress2,
@@ -943,7 +1120,6 @@ describe("anchor-solhedge-localnet", () => {
   
         //Lets suppose put taker slippage tolerance is 5%
         const myMaxPrice = Math.floor(updatedVaultFactory.lastFairPrice.toNumber()*1.05)
-        const protocolFeesUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, protocolFeesKeypair.publicKey)
         const lotPrice = (updatedVaultFactory.strike.toNumber() + updatedVaultFactory.lastFairPrice.toNumber()*1.05)*(10**vaultInfo.account.lotSize)
         const takerLots = Math.floor(usdcMintAmountTaker*(10**mintInfoUSDC.decimals)/lotPrice)
         
@@ -955,11 +1131,16 @@ describe("anchor-solhedge-localnet", () => {
           new anchor.BN(myMaxPrice), 
           new anchor.BN(takerLots), 
           new anchor.BN(quoteAssetInitialFund)).accounts({
+            tokenProgram: token.TOKEN_PROGRAM_ID,
             baseAssetMint: wormholeBTCToken,
             quoteAssetMint: usdcToken,
             initializer: callTakerKeypair.publicKey,
-            protocolQuoteAssetTreasury: protocolFeesUSDCATA.address,
-            frontendQuoteAssetTreasury: protocolFeesUSDCATA.address, //also sending frontend share to protocol in this test
+            feeVaultInfo: getFeeVaultPdaAddress(program, usdcToken),
+            feeVaultQuoteAssetTreasury: token.getAssociatedTokenAddressSync(usdcToken, getFeeVaultPdaAddress(program, usdcToken), true),
+            insuranceFundInfo: getInsuranceFundPdaAddress(program, usdcToken),
+            frontendInfo: null, //unregistered frontend, its share goes to the protocol
+            feeTiersInfo: getFeeTiersPdaAddress(program, usdcToken),
+            takerStatsInfo: getTakerStatsPdaAddress(program, callTakerKeypair.publicKey, usdcToken),
             takerQuoteAssetAccount: callTakerUSDCATA.address,
             vaultFactoryInfo: callOptionVaultFactoryAddress2,
             vaultInfo: vaultInfo.publicKey,
@@ -1048,7 +1229,19 @@ describe("anchor-solhedge-localnet", () => {
           maxMakers: 100,
           maxTakers: 100,
           numLotsToSell: new anchor.BN(1000),
-          premiumLimit: new anchor.BN(Math.floor(lamportPrice/100))  
+          premiumLimit: new anchor.BN(Math.floor(lamportPrice/100)),
+          cashSettled: false,
+          american: false,
+          barrier: new anchor.BN(0),
+          barrierUp: false,
+          knockIn: false,
+          auctionSeconds: new anchor.BN(0),
+          auctionStartMultipleBps: 0,
+          auctionFloorMultipleBps: 0,
+          makerAllocation: { takerChoice: {} },
+          initialMarginBps: 0,
+          maintenanceMarginBps: 0,
+          liquidationBonusBps: 0
         })
   
       const putOptionVaultFactoryAddress = await getPutOptionVaultFactoryPdaAddress(program, wormholeBTCToken, usdcToken, vaultParams.maturity, vaultParams.strike)
@@ -1084,6 +1277,7 @@ describe("anchor-solhedge-localnet", () => {
       //const userAVA = getMakerVaultAssociatedAccountAddress(program, putOptionVaultFactoryAddress, vaultNumber, putMakerKeypair.publicKey)
   
       var tx2 = await program.methods.makerCreatePutOptionVault(vaultParams, vaultNumber).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
         initializer: putMakerKeypair.publicKey,
         vaultFactoryInfo: putOptionVaultFactoryAddress,
         vaultInfo: putOptionVaultAddress,
@@ -1134,6 +1328,7 @@ describe("anchor-solhedge-localnet", () => {
       const vaultQuoteAssetTreasury2 = await token.getAssociatedTokenAddress(usdcToken, vaultInfo.publicKey, true)
   
       let tx3 = await program.methods.makerEnterPutOptionVault(new anchor.BN(500), new anchor.BN(0)).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
         initializer: putMaker2Keypair.publicKey,
         vaultFactoryInfo: putOptionVaultFactoryAddress2,
         vaultInfo: vaultInfo.publicKey,
@@ -1151,6 +1346,7 @@ describe("anchor-solhedge-localnet", () => {
       assert.isTrue(qty500Lots > 0)
   
       let tx4 = await program.methods.makerAdjustPositionPutOptionVault(new anchor.BN(0), new anchor.BN(0)).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
         
         initializer: putMaker2Keypair.publicKey,
         vaultFactoryInfo: putOptionVaultFactoryAddress2,
@@ -1165,6 +1361,7 @@ describe("anchor-solhedge-localnet", () => {
       assert.equal(maker2InfoForVault[0].account.quoteAssetQty.toNumber(), 0)
   
       let tx5 = await program.methods.makerAdjustPositionPutOptionVault(new anchor.BN(500), new anchor.BN(0)).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
         
         initializer: putMaker2Keypair.publicKey,
         vaultFactoryInfo: putOptionVaultFactoryAddress2,
@@ -1264,11 +1461,16 @@ describe("anchor-solhedge-localnet", () => {
           new anchor.BN(myMaxPrice), 
           new anchor.BN(takerLots), 
           new anchor.BN(btcLamports)).accounts({
+            tokenProgram: token.TOKEN_PROGRAM_ID,
             baseAssetMint: wormholeBTCToken,
             quoteAssetMint: usdcToken,
             initializer: putTakerKeypair.publicKey,
-            protocolQuoteAssetTreasury: protocolFeesUSDCATA.address,
-            frontendQuoteAssetTreasury: protocolFeesUSDCATA.address, //also sending frontend share to protocol in this test
+            feeVaultInfo: getFeeVaultPdaAddress(program, usdcToken),
+            feeVaultQuoteAssetTreasury: token.getAssociatedTokenAddressSync(usdcToken, getFeeVaultPdaAddress(program, usdcToken), true),
+            insuranceFundInfo: getInsuranceFundPdaAddress(program, usdcToken),
+            frontendInfo: null, //unregistered frontend, its share goes to the protocol
+            feeTiersInfo: getFeeTiersPdaAddress(program, usdcToken),
+            takerStatsInfo: getTakerStatsPdaAddress(program, putTakerKeypair.publicKey, usdcToken),
             takerBaseAssetAccount: putTakerwBTCATA.address,
             takerQuoteAssetAccount: putTakerUSDCATA.address,
             vaultFactoryInfo: putOptionVaultFactoryAddress2,
@@ -1285,6 +1487,2523 @@ describe("anchor-solhedge-localnet", () => {
   
     });  
 
+    it("Exiting a put option vault partially and entering it again", async () => {
+      const conn = anchor.getProvider().connection
+      const putMakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putMakerKeypair.publicKey)
+      const putMaker2USDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putMaker2Keypair.publicKey)
+      const putTakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putTakerKeypair.publicKey)
+      const putTakerwBTCATA = await createTokenAccount(conn, minterKeypair, wormholeBTCToken, putTakerKeypair.publicKey)
+      await mintTokens(conn, minterKeypair, usdcToken, putTakerUSDCATA.address, minterKeypair, 10)
+      const tokenBalance = async (address: anchor.web3.PublicKey) => Number((await token.getAccount(conn, address)).amount)
+
+      // lots of 25 USDC, the first maker offers 4 of them and the second one 2
+      const strike = new anchor.BN(25000*(10**6))
+      const lotValue = 25*(10**6)
+      const maturity = new anchor.BN(Math.floor(Date.now()/1000) + 7*24*60*60)
+      const vaultParams = new MakerCreatePutOptionParams(
+        {
+          maturity: maturity,
+          strike: strike,
+          lotSize: -3,
+          maxMakers: 2,
+          maxTakers: 100,
+          numLotsToSell: new anchor.BN(4),
+          premiumLimit: new anchor.BN(0),
+          cashSettled: false,
+          american: false,
+          barrier: new anchor.BN(0),
+          barrierUp: false,
+          knockIn: false,
+          auctionSeconds: new anchor.BN(0),
+          auctionStartMultipleBps: 0,
+          auctionFloorMultipleBps: 0,
+          makerAllocation: { takerChoice: {} },
+          initialMarginBps: 0,
+          maintenanceMarginBps: 0,
+          liquidationBonusBps: 0
+        })
+      const vaultFactory = await getPutOptionVaultFactoryPdaAddress(program, wormholeBTCToken, usdcToken, maturity, strike)
+      const vaultId = new anchor.BN(1)
+      const { putOptionVaultAddress, vaultBaseAssetTreasury, vaultQuoteAssetTreasury } = await getPutOptionVaultDerivedPdaAddresses(program, vaultFactory, wormholeBTCToken, usdcToken, vaultId)
+      await program.methods.makerOpenPutOptionVault(vaultParams).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
+        initializer: putMakerKeypair.publicKey,
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: putOptionVaultAddress,
+        vaultBaseAssetTreasury: vaultBaseAssetTreasury,
+        vaultQuoteAssetTreasury: vaultQuoteAssetTreasury,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        makerQuoteAssetAccount: putMakerUSDCATA.address,
+      }).signers([putMakerKeypair]).rpc(confirmOptions)
+      const makerInfo = await getPutMakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, putMakerKeypair.publicKey)
+      const maker2Info = await getPutMakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, putMaker2Keypair.publicKey)
+      const enter2 = (lots: number) => program.methods.makerEnterPutOptionVault(new anchor.BN(lots), new anchor.BN(0)).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
+        initializer: putMaker2Keypair.publicKey,
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: putOptionVaultAddress,
+        vaultQuoteAssetTreasury: vaultQuoteAssetTreasury,
+        putOptionMakerInfo: maker2Info,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        makerQuoteAssetAccount: putMaker2USDCATA.address,
+      }).signers([putMaker2Keypair]).rpc(confirmOptions)
+      const exit = (maker: anchor.web3.Keypair, makerInfoToExit: anchor.web3.PublicKey, makerATA: anchor.web3.PublicKey) => program.methods.makerExitPutOptionVault().accounts({
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: putOptionVaultAddress,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        vaultQuoteAssetTreasury: vaultQuoteAssetTreasury,
+        putOptionMakerInfo: makerInfoToExit,
+        makerQuoteAssetAccount: makerATA,
+        initializer: maker.publicKey,
+        tokenProgram: token.TOKEN_PROGRAM_ID
+      }).signers([maker]).rpc(confirmOptions)
+      await enter2(2)
+      let vault = await program.account.putOptionVaultInfo.fetch(putOptionVaultAddress)
+      assert.equal(vault.makersNum, 2)
+      assert.isTrue(vault.isMakersFull)
+      assert.equal((await program.account.putOptionMakerInfo.fetch(makerInfo)).ord, 1)
+      assert.equal((await program.account.putOptionMakerInfo.fetch(maker2Info)).ord, 2)
+
+      // the taker buys one lot from the first maker
+      const fairPrice = 500*(10**6)
+      await setPutFairPrice(vaultFactory, fairPrice)
+      await program.methods.takerBuyLotsPutOptionVault(new anchor.BN(fairPrice), new anchor.BN(1), new anchor.BN(0)).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        initializer: putTakerKeypair.publicKey,
+        feeVaultInfo: getFeeVaultPdaAddress(program, usdcToken),
+        feeVaultQuoteAssetTreasury: token.getAssociatedTokenAddressSync(usdcToken, getFeeVaultPdaAddress(program, usdcToken), true),
+        insuranceFundInfo: getInsuranceFundPdaAddress(program, usdcToken),
+        frontendInfo: null,
+        feeTiersInfo: getFeeTiersPdaAddress(program, usdcToken),
+        takerStatsInfo: getTakerStatsPdaAddress(program, putTakerKeypair.publicKey, usdcToken),
+        takerBaseAssetAccount: putTakerwBTCATA.address,
+        takerQuoteAssetAccount: putTakerUSDCATA.address,
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: putOptionVaultAddress,
+        vaultBaseAssetTreasury: vaultBaseAssetTreasury,
+        putOptionTakerInfo: await getPutTakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, putTakerKeypair.publicKey)
+      }).remainingAccounts([
+        { pubkey: makerInfo, isSigner: false, isWritable: true },
+        { pubkey: putMakerUSDCATA.address, isSigner: false, isWritable: true }
+      ]).signers([putTakerKeypair]).rpc(confirmOptions)
+
+      // the first maker takes back her 3 unsold lots, and stays in the vault with the one she sold
+      let makerUSDCBefore = await tokenBalance(putMakerUSDCATA.address)
+      await exit(putMakerKeypair, makerInfo, putMakerUSDCATA.address)
+      assert.equal(await tokenBalance(putMakerUSDCATA.address), makerUSDCBefore + 3*lotValue)
+      const maker = await program.account.putOptionMakerInfo.fetch(makerInfo)
+      assert.equal(maker.quoteAssetQty.toNumber(), lotValue)
+      assert.equal(maker.volumeSold.toNumber(), lotValue)
+      assert.isTrue(maker.isAllSold)
+      vault = await program.account.putOptionVaultInfo.fetch(putOptionVaultAddress)
+      assert.equal(vault.makersNum, 2)
+      assert.equal(vault.makersTotalPendingSell.toNumber(), 2*lotValue)
+      assert.equal(vault.makersTotalPendingSettle.toNumber(), 3*lotValue)
+      assert.equal(await tokenBalance(vaultQuoteAssetTreasury), 3*lotValue)
+
+      // the second maker sold nothing, she leaves the vault and frees her slot
+      const maker2USDCBefore = await tokenBalance(putMaker2USDCATA.address)
+      await exit(putMaker2Keypair, maker2Info, putMaker2USDCATA.address)
+      assert.equal(await tokenBalance(putMaker2USDCATA.address), maker2USDCBefore + 2*lotValue)
+      assert.isNull(await program.account.putOptionMakerInfo.fetchNullable(maker2Info))
+      vault = await program.account.putOptionVaultInfo.fetch(putOptionVaultAddress)
+      assert.equal(vault.makersNum, 1)
+      assert.isFalse(vault.isMakersFull)
+      assert.equal(vault.makersTotalPendingSell.toNumber(), 0)
+      assert.equal(vault.makersTotalPendingSettle.toNumber(), lotValue)
+
+      // entering again she takes the free slot with an ord never given to another maker
+      await enter2(1)
+      const maker2 = await program.account.putOptionMakerInfo.fetch(maker2Info)
+      assert.equal(maker2.ord, 3)
+      assert.equal(maker2.quoteAssetQty.toNumber(), lotValue)
+      vault = await program.account.putOptionVaultInfo.fetch(putOptionVaultAddress)
+      assert.equal(vault.makersNum, 2)
+      assert.isTrue(vault.isMakersFull)
+      assert.equal(vault.makersTotalPendingSell.toNumber(), lotValue)
+      assert.equal(vault.makersTotalPendingSettle.toNumber(), 2*lotValue)
+    });
+
+    it("Rejecting invalid makers in remaining accounts of put and call option buys", async () => {
+      const conn = anchor.getProvider().connection
+      const putMakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putMakerKeypair.publicKey)
+      const putTakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putTakerKeypair.publicKey)
+      const putTakerwBTCATA = await createTokenAccount(conn, minterKeypair, wormholeBTCToken, putTakerKeypair.publicKey)
+      const callMakerwBTCATA = await createTokenAccount(conn, minterKeypair, wormholeBTCToken, callMakerKeypair.publicKey)
+      const callMakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, callMakerKeypair.publicKey)
+      const callTakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, callTakerKeypair.publicKey)
+      await mintTokens(conn, minterKeypair, usdcToken, putMakerUSDCATA.address, minterKeypair, 100)
+      await mintTokens(conn, minterKeypair, usdcToken, putTakerUSDCATA.address, minterKeypair, 10)
+      await mintTokens(conn, minterKeypair, wormholeBTCToken, callMakerwBTCATA.address, minterKeypair, 0.01)
+      await mintTokens(conn, minterKeypair, usdcToken, callTakerUSDCATA.address, minterKeypair, 10)
+
+      const strike = new anchor.BN(26000*(10**6))
+      const maturity = new anchor.BN(Math.floor(Date.now()/1000) + 7*24*60*60)
+      const vaultId = new anchor.BN(1)
+      const fairPrice = 200*(10**6)
+
+      // Every invalid set of remaining accounts is rejected before any lot is sold
+      const expectRejected = async (
+        buy: (remainingAccounts: anchor.web3.AccountMeta[]) => Promise<string>,
+        makerInfo: anchor.web3.PublicKey,
+        makerQuoteAssetAccount: anchor.web3.PublicKey,
+        otherVaultMakerInfo: anchor.web3.PublicKey,
+        otherVaultMakerQuoteAssetAccount: anchor.web3.PublicKey,
+        notMakerQuoteAssetAccount: anchor.web3.PublicKey
+      ) => {
+        const invalidRemainingAccounts = {
+          "a premium account that is not the maker's": [
+            { pubkey: makerInfo, isSigner: false, isWritable: true },
+            { pubkey: notMakerQuoteAssetAccount, isSigner: false, isWritable: true }
+          ],
+          "a maker of another vault": [
+            { pubkey: otherVaultMakerInfo, isSigner: false, isWritable: true },
+            { pubkey: otherVaultMakerQuoteAssetAccount, isSigner: false, isWritable: true }
+          ],
+          "a maker account not owned by the program": [
+            { pubkey: makerQuoteAssetAccount, isSigner: false, isWritable: true },
+            { pubkey: makerQuoteAssetAccount, isSigner: false, isWritable: true }
+          ],
+          "the same maker twice": [
+            { pubkey: makerInfo, isSigner: false, isWritable: true },
+            { pubkey: makerQuoteAssetAccount, isSigner: false, isWritable: true },
+            { pubkey: makerInfo, isSigner: false, isWritable: true },
+            { pubkey: makerQuoteAssetAccount, isSigner: false, isWritable: true }
+          ]
+        }
+        for (const [description, remainingAccounts] of Object.entries(invalidRemainingAccounts)) {
+          try {
+            await buy(remainingAccounts)
+            assert.fail(`Lots were bought from ${description}`)
+          } catch (e) {
+            assert.equal(e.error?.errorCode?.code, "AccountValidationError", description)
+          }
+        }
+      }
+
+      // put option vault
+      const putVaultFactory = await getPutOptionVaultFactoryPdaAddress(program, wormholeBTCToken, usdcToken, maturity, strike)
+      const putVault = await getPutOptionVaultDerivedPdaAddresses(program, putVaultFactory, wormholeBTCToken, usdcToken, vaultId)
+      await program.methods.makerOpenPutOptionVault(new MakerCreatePutOptionParams(
+        {
+          maturity: maturity,
+          strike: strike,
+          lotSize: -3,
+          maxMakers: 100,
+          maxTakers: 100,
+          numLotsToSell: new anchor.BN(2),
+          premiumLimit: new anchor.BN(0),
+          cashSettled: false,
+          american: false,
+          barrier: new anchor.BN(0),
+          barrierUp: false,
+          knockIn: false,
+          auctionSeconds: new anchor.BN(0),
+          auctionStartMultipleBps: 0,
+          auctionFloorMultipleBps: 0,
+          makerAllocation: { takerChoice: {} },
+          initialMarginBps: 0,
+          maintenanceMarginBps: 0,
+          liquidationBonusBps: 0
+        })).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
+        initializer: putMakerKeypair.publicKey,
+        vaultFactoryInfo: putVaultFactory,
+        vaultInfo: putVault.putOptionVaultAddress,
+        vaultBaseAssetTreasury: putVault.vaultBaseAssetTreasury,
+        vaultQuoteAssetTreasury: putVault.vaultQuoteAssetTreasury,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        makerQuoteAssetAccount: putMakerUSDCATA.address,
+      }).signers([putMakerKeypair]).rpc(confirmOptions)
+      const putMakerInfo = await getPutMakerVaultAssociatedAccountAddress(program, putVaultFactory, vaultId, putMakerKeypair.publicKey)
+      const otherPutMaker = (await program.account.putOptionMakerInfo.all())
+        .find(m => m.account.putOptionVault.toString() != putVault.putOptionVaultAddress.toString())
+      const otherPutMakerUSDCATA = token.getAssociatedTokenAddressSync(usdcToken, otherPutMaker.account.owner, true)
+      await setPutFairPrice(putVaultFactory, fairPrice)
+      const buyPut = async (remainingAccounts: anchor.web3.AccountMeta[]) => program.methods.takerBuyLotsPutOptionVault(new anchor.BN(fairPrice), new anchor.BN(1), new anchor.BN(0)).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        initializer: putTakerKeypair.publicKey,
+        feeVaultInfo: getFeeVaultPdaAddress(program, usdcToken),
+        feeVaultQuoteAssetTreasury: token.getAssociatedTokenAddressSync(usdcToken, getFeeVaultPdaAddress(program, usdcToken), true),
+        insuranceFundInfo: getInsuranceFundPdaAddress(program, usdcToken),
+        frontendInfo: null,
+        feeTiersInfo: getFeeTiersPdaAddress(program, usdcToken),
+        takerStatsInfo: getTakerStatsPdaAddress(program, putTakerKeypair.publicKey, usdcToken),
+        takerBaseAssetAccount: putTakerwBTCATA.address,
+        takerQuoteAssetAccount: putTakerUSDCATA.address,
+        vaultFactoryInfo: putVaultFactory,
+        vaultInfo: putVault.putOptionVaultAddress,
+        vaultBaseAssetTreasury: putVault.vaultBaseAssetTreasury,
+        putOptionTakerInfo: await getPutTakerVaultAssociatedAccountAddress(program, putVaultFactory, vaultId, putTakerKeypair.publicKey)
+      }).remainingAccounts(remainingAccounts).signers([putTakerKeypair]).rpc(confirmOptions)
+      await expectRejected(buyPut, putMakerInfo, putMakerUSDCATA.address, otherPutMaker.publicKey, otherPutMakerUSDCATA, putTakerUSDCATA.address)
+      await buyPut([
+        { pubkey: putMakerInfo, isSigner: false, isWritable: true },
+        { pubkey: putMakerUSDCATA.address, isSigner: false, isWritable: true }
+      ])
+      assert.equal((await program.account.putOptionMakerInfo.fetch(putMakerInfo)).volumeSold.toNumber(), strike.toNumber()/1000)
+
+      // call option vault
+      const callVaultFactory = await getCallOptionVaultFactoryPdaAddress(program, wormholeBTCToken, usdcToken, maturity, strike)
+      const callVault = await getCallOptionVaultDerivedPdaAddresses(program, callVaultFactory, wormholeBTCToken, usdcToken, vaultId)
+      await program.methods.makerOpenCallOptionVault(new MakerCreateCallOptionParams(
+        {
+          maturity: maturity,
+          strike: strike,
+          lotSize: -3,
+          maxMakers: 100,
+          maxTakers: 100,
+          numLotsToSell: new anchor.BN(2),
+          premiumLimit: new anchor.BN(0),
+          cashSettled: false,
+          barrier: new anchor.BN(0),
+          barrierUp: false,
+          knockIn: false,
+          auctionSeconds: new anchor.BN(0),
+          auctionStartMultipleBps: 0,
+          auctionFloorMultipleBps: 0
+        })).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
+        initializer: callMakerKeypair.publicKey,
+        vaultFactoryInfo: callVaultFactory,
+        vaultInfo: callVault.callOptionVaultAddress,
+        vaultBaseAssetTreasury: callVault.vaultBaseAssetTreasury,
+        vaultQuoteAssetTreasury: callVault.vaultQuoteAssetTreasury,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        makerBaseAssetAccount: callMakerwBTCATA.address,
+      }).signers([callMakerKeypair]).rpc(confirmOptions)
+      const callMakerInfo = await getCallMakerVaultAssociatedAccountAddress(program, callVaultFactory, vaultId, callMakerKeypair.publicKey)
+      const otherCallMaker = (await program.account.callOptionMakerInfo.all())
+        .find(m => m.account.callOptionVault.toString() != callVault.callOptionVaultAddress.toString())
+      const otherCallMakerUSDCATA = token.getAssociatedTokenAddressSync(usdcToken, otherCallMaker.account.owner, true)
+      await program.methods.genUpdateCallOptionFairPriceTicket().accounts({
+        vaultFactoryInfo: callVaultFactory,
+        initializer: callTakerKeypair.publicKey,
+        oracleWallet: getOraclePubKey(),
+        callOptionFairPriceTicket: await getUserTicketAccountAddressForCallVaultFactory(program, callVaultFactory, callTakerKeypair.publicKey)
+      }).signers([callTakerKeypair]).rpc(confirmOptions)
+      await _testUpdateCallOptionFairPrice(program, callVaultFactory, callTakerKeypair.publicKey, new anchor.BN(fairPrice))
+      const buyCall = async (remainingAccounts: anchor.web3.AccountMeta[]) => program.methods.takerBuyLotsCallOptionVault(new anchor.BN(fairPrice), new anchor.BN(1), new anchor.BN(0)).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        initializer: callTakerKeypair.publicKey,
+        feeVaultInfo: getFeeVaultPdaAddress(program, usdcToken),
+        feeVaultQuoteAssetTreasury: token.getAssociatedTokenAddressSync(usdcToken, getFeeVaultPdaAddress(program, usdcToken), true),
+        insuranceFundInfo: getInsuranceFundPdaAddress(program, usdcToken),
+        frontendInfo: null,
+        feeTiersInfo: getFeeTiersPdaAddress(program, usdcToken),
+        takerStatsInfo: getTakerStatsPdaAddress(program, callTakerKeypair.publicKey, usdcToken),
+        takerQuoteAssetAccount: callTakerUSDCATA.address,
+        vaultFactoryInfo: callVaultFactory,
+        vaultInfo: callVault.callOptionVaultAddress,
+        vaultQuoteAssetTreasury: callVault.vaultQuoteAssetTreasury,
+        callOptionTakerInfo: await getCallTakerVaultAssociatedAccountAddress(program, callVaultFactory, vaultId, callTakerKeypair.publicKey)
+      }).remainingAccounts(remainingAccounts).signers([callTakerKeypair]).rpc(confirmOptions)
+      await expectRejected(buyCall, callMakerInfo, callMakerUSDCATA.address, otherCallMaker.publicKey, otherCallMakerUSDCATA, callTakerUSDCATA.address)
+      await buyCall([
+        { pubkey: callMakerInfo, isSigner: false, isWritable: true },
+        { pubkey: callMakerUSDCATA.address, isSigner: false, isWritable: true }
+      ])
+      assert.equal((await program.account.callOptionMakerInfo.fetch(callMakerInfo)).volumeSold.toNumber(), 10**5)
+    });
+
+    it("Opening a put option vault in a new series with a single instruction", async () => {
+      const conn = anchor.getProvider().connection
+      const mintInfoUSDC = await token.getMint(conn, usdcToken)
+      const putMakerUSDCATA = await token.getOrCreateAssociatedTokenAccount(conn, minterKeypair, usdcToken, putMakerKeypair.publicKey)
+
+      let currEpoch = Math.floor(Date.now()/1000)
+      let oneWeek = currEpoch + (7*24*60*60)
+      let lamportPrice = 25000 * (10 ** mintInfoUSDC.decimals)
+
+      const vaultParams = new MakerCreatePutOptionParams(
+        {
+          maturity: new anchor.BN(oneWeek+900),
+          strike: new anchor.BN(lamportPrice),
+          lotSize: -3,
+          maxMakers: 100,
+          maxTakers: 100,
+          numLotsToSell: new anchor.BN(10),
+          premiumLimit: new anchor.BN(0),
+          cashSettled: false,
+          american: false,
+          barrier: new anchor.BN(0),
+          barrierUp: false,
+          knockIn: false,
+          auctionSeconds: new anchor.BN(0),
+          auctionStartMultipleBps: 0,
+          auctionFloorMultipleBps: 0,
+          makerAllocation: { takerChoice: {} },
+          initialMarginBps: 0,
+          maintenanceMarginBps: 0,
+          liquidationBonusBps: 0
+        })
+
+      const putOptionVaultFactoryAddress = await getPutOptionVaultFactoryPdaAddress(program, wormholeBTCToken, usdcToken, vaultParams.maturity, vaultParams.strike)
+      // the factory does not exist yet, its first vault gets id 1
+      const {
+        putOptionVaultAddress, 
+        vaultBaseAssetTreasury, 
+        vaultQuoteAssetTreasury
+      } = await getPutOptionVaultDerivedPdaAddresses(program, putOptionVaultFactoryAddress, wormholeBTCToken, usdcToken, new anchor.BN(1))
+
+      const tx = await program.methods.makerOpenPutOptionVault(vaultParams).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
+        initializer: putMakerKeypair.publicKey,
+        vaultFactoryInfo: putOptionVaultFactoryAddress,
+        vaultInfo: putOptionVaultAddress,
+        vaultBaseAssetTreasury: vaultBaseAssetTreasury,
+        vaultQuoteAssetTreasury: vaultQuoteAssetTreasury,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        makerQuoteAssetAccount: putMakerUSDCATA.address,
+      }).signers([putMakerKeypair]).rpc(confirmOptions)
+
+      const vaultNumber = await getMakerNextPutOptionVaultIdFromTx(program, conn, tx)
+      assert.equal(vaultNumber.toNumber(), 1)
+
+      const factory = await program.account.putOptionVaultFactoryInfo.fetch(putOptionVaultFactoryAddress)
+      assert.equal(factory.nextVaultId.toNumber(), 2)
+      const makerInfoForVault = await getUserMakerInfoForPutVault(program, putOptionVaultAddress, putMakerKeypair.publicKey)
+      assert.equal(makerInfoForVault.length, 1)
+
+      const seriesIndex = await program.account.seriesIndex.fetch(getSeriesIndexPdaAddress(program, wormholeBTCToken, usdcToken))
+      const listed = seriesIndex.entries.map(e => e.factory.toString())
+      assert.include(listed, putOptionVaultFactoryAddress.toString())
+    });
+
+    it("Accepting an RFQ quote as a private put option vault", async () => {
+      const conn = anchor.getProvider().connection
+      const putMakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putMakerKeypair.publicKey)
+      const putTakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putTakerKeypair.publicKey)
+      const putTakerwBTCATA = await createTokenAccount(conn, minterKeypair, wormholeBTCToken, putTakerKeypair.publicKey)
+
+      // RFQs are posted on existing factories, the maker opens the short series with a public vault first
+      const vaultParams = new MakerCreatePutOptionParams(
+        {
+          maturity: shortMaturity,
+          strike: shortStrike,
+          lotSize: -3,
+          maxMakers: 100,
+          maxTakers: 100,
+          numLotsToSell: new anchor.BN(10),
+          premiumLimit: new anchor.BN(0),
+          cashSettled: false,
+          american: false,
+          barrier: new anchor.BN(0),
+          barrierUp: false,
+          knockIn: false,
+          auctionSeconds: new anchor.BN(0),
+          auctionStartMultipleBps: 0,
+          auctionFloorMultipleBps: 0,
+          makerAllocation: { takerChoice: {} },
+          initialMarginBps: 0,
+          maintenanceMarginBps: 0,
+          liquidationBonusBps: 0
+        })
+      const vaultFactory = await getPutOptionVaultFactoryPdaAddress(program, wormholeBTCToken, usdcToken, shortMaturity, shortStrike)
+      const publicVault = await getPutOptionVaultDerivedPdaAddresses(program, vaultFactory, wormholeBTCToken, usdcToken, new anchor.BN(1))
+      await program.methods.makerOpenPutOptionVault(vaultParams).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
+        initializer: putMakerKeypair.publicKey,
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: publicVault.putOptionVaultAddress,
+        vaultBaseAssetTreasury: publicVault.vaultBaseAssetTreasury,
+        vaultQuoteAssetTreasury: publicVault.vaultQuoteAssetTreasury,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        makerQuoteAssetAccount: putMakerUSDCATA.address,
+      }).signers([putMakerKeypair]).rpc(confirmOptions)
+
+      const numLots = 5
+      const rfqInfo = getPutOptionRfqPdaAddress(program, vaultFactory, putTakerKeypair.publicKey)
+      await program.methods.takerPostPutOptionRfq(new anchor.BN(numLots), -3, shortMaturity.subn(FREEZE_SECONDS)).accounts({
+        vaultFactoryInfo: vaultFactory,
+        assetPairConfig: getAssetPairConfigPdaAddress(program, wormholeBTCToken, usdcToken),
+        rfqInfo: rfqInfo,
+        initializer: putTakerKeypair.publicKey
+      }).signers([putTakerKeypair]).rpc(confirmOptions)
+
+      const quoteAccounts = (maker: anchor.web3.PublicKey, makerQuoteAssetAccount: anchor.web3.PublicKey) => {
+        const quoteInfo = getPutOptionRfqQuotePdaAddress(program, rfqInfo, maker)
+        return {
+          rfqInfo: rfqInfo,
+          vaultFactoryInfo: vaultFactory,
+          quoteInfo: quoteInfo,
+          quoteCollateralEscrow: token.getAssociatedTokenAddressSync(usdcToken, quoteInfo, true),
+          quoteAssetMint: usdcToken,
+          makerQuoteAssetAccount: makerQuoteAssetAccount,
+          initializer: maker,
+          tokenProgram: token.TOKEN_PROGRAM_ID
+        }
+      }
+      const premium = 50*(10**6)
+
+      // the taker cannot answer her own RFQ
+      try {
+        await program.methods.makerQuotePutOptionRfq(new anchor.BN(premium))
+          .accounts(quoteAccounts(putTakerKeypair.publicKey, putTakerUSDCATA.address))
+          .signers([putTakerKeypair]).rpc(confirmOptions)
+        assert.fail("Self quote was accepted")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "SelfQuote")
+      }
+
+      const makerUSDCBeforeQuote = Number((await token.getAccount(conn, putMakerUSDCATA.address)).amount)
+      const makerQuote = quoteAccounts(putMakerKeypair.publicKey, putMakerUSDCATA.address)
+      await program.methods.makerQuotePutOptionRfq(new anchor.BN(premium))
+        .accounts(makerQuote)
+        .signers([putMakerKeypair]).rpc(confirmOptions)
+      const quoteInfoAccount = await program.account.putOptionRfqQuoteInfo.fetch(makerQuote.quoteInfo)
+      const collateral = quoteInfoAccount.collateral.toNumber()
+      assert.equal(collateral, Math.ceil((10**-3)*shortStrike.toNumber())*numLots)
+      assert.equal(Number((await token.getAccount(conn, putMakerUSDCATA.address)).amount), makerUSDCBeforeQuote - collateral)
+
+      const factoryBefore = await program.account.putOptionVaultFactoryInfo.fetch(vaultFactory)
+      const vaultId = factoryBefore.nextVaultId
+      assert.equal(vaultId.toNumber(), 2)
+      const privateVault = await getPutOptionVaultDerivedPdaAddresses(program, vaultFactory, wormholeBTCToken, usdcToken, vaultId)
+      const feeVault = getFeeVaultPdaAddress(program, usdcToken)
+      const makerUSDCBeforeAccept = Number((await token.getAccount(conn, putMakerUSDCATA.address)).amount)
+      const takerUSDCBeforeAccept = Number((await token.getAccount(conn, putTakerUSDCATA.address)).amount)
+      await program.methods.takerAcceptPutOptionRfqQuote(new anchor.BN(premium)).accounts({
+        rfqInfo: rfqInfo,
+        vaultFactoryInfo: vaultFactory,
+        quoteInfo: makerQuote.quoteInfo,
+        quoteCollateralEscrow: makerQuote.quoteCollateralEscrow,
+        vaultInfo: privateVault.putOptionVaultAddress,
+        vaultBaseAssetTreasury: privateVault.vaultBaseAssetTreasury,
+        vaultQuoteAssetTreasury: privateVault.vaultQuoteAssetTreasury,
+        putOptionMakerInfo: await getPutMakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, putMakerKeypair.publicKey),
+        putOptionTakerInfo: await getPutTakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, putTakerKeypair.publicKey),
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        maker: putMakerKeypair.publicKey,
+        makerQuoteAssetAccount: putMakerUSDCATA.address,
+        takerQuoteAssetAccount: putTakerUSDCATA.address,
+        feeVaultInfo: feeVault,
+        feeVaultQuoteAssetTreasury: token.getAssociatedTokenAddressSync(usdcToken, feeVault, true),
+        insuranceFundInfo: getInsuranceFundPdaAddress(program, usdcToken),
+        frontendInfo: null,
+        initializer: putTakerKeypair.publicKey,
+        tokenProgram: token.TOKEN_PROGRAM_ID
+      }).signers([putTakerKeypair]).rpc(confirmOptions)
+
+      // protocol fees of the pair are 100 bps, with no frontend all of them go to the backend
+      const fees = Math.ceil(premium*100/10000)
+      assert.equal(Number((await token.getAccount(conn, putMakerUSDCATA.address)).amount), makerUSDCBeforeAccept + premium - fees)
+      assert.equal(Number((await token.getAccount(conn, putTakerUSDCATA.address)).amount), takerUSDCBeforeAccept - premium)
+      assert.equal(Number((await token.getAccount(conn, privateVault.vaultQuoteAssetTreasury)).amount), collateral)
+      assert.isNull(await program.account.putOptionRfqInfo.fetchNullable(rfqInfo))
+      assert.isNull(await program.account.putOptionRfqQuoteInfo.fetchNullable(makerQuote.quoteInfo))
+
+      const takerInfos = await getUserTakerInfoForPutVault(program, privateVault.putOptionVaultAddress, putTakerKeypair.publicKey)
+      assert.equal(takerInfos.length, 1)
+      const mintInfoWBTC = await token.getMint(conn, wormholeBTCToken)
+      const maxBaseAsset = takerInfos[0].account.maxBaseAsset.toNumber()
+      assert.equal(maxBaseAsset, Math.ceil(numLots*(10**-3)*(10**mintInfoWBTC.decimals)))
+      const makerInfos = await getAllPutMakerInfosForVault(program, privateVault.putOptionVaultAddress)
+      assert.equal(makerInfos.length, 1)
+      assert.equal(makerInfos[0].account.volumeSold.toNumber(), collateral)
+
+      // the taker funds all her options so that they can be fully exercised
+      await program.methods.takerAdjustFundingPutOptionVault(new anchor.BN(maxBaseAsset)).accounts({
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: privateVault.putOptionVaultAddress,
+        putOptionTakerInfo: takerInfos[0].publicKey,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        vaultBaseAssetTreasury: privateVault.vaultBaseAssetTreasury,
+        takerBaseAssetAccount: putTakerwBTCATA.address,
+        initializer: putTakerKeypair.publicKey,
+        tokenProgram: token.TOKEN_PROGRAM_ID
+      }).signers([putTakerKeypair]).rpc(confirmOptions)
+      assert.equal(Number((await token.getAccount(conn, privateVault.vaultBaseAssetTreasury)).amount), maxBaseAsset)
+
+      rfqVault = { vaultFactory: vaultFactory, vault: privateVault.putOptionVaultAddress, collateral: collateral }
+    });
+
+    it("Unwrapping wSOL refunds only into accounts whose close authority is the maker", async () => {
+      const conn = anchor.getProvider().connection
+      const wSOL = token.NATIVE_MINT
+      await createAssetPairConfigIfNeeded(program, protocolFeesKeypair, wormholeBTCToken, wSOL, new AssetPairConfigParams({
+          enabled: true,
+          minLotSize: -8,
+          maxLotSize: 2,
+          minStrike: new anchor.BN(1),
+          maxStrike: new anchor.BN("18446744073709551615"),
+          maxMaturitySeconds: new anchor.BN(30*24*60*60),
+          priceSource: getOraclePubKey(),
+          protocolFeesBps: 100,
+          standardized: false,
+          strikeTick: new anchor.BN(0),
+          maturityGridSeconds: new anchor.BN(0),
+          maturityGridOffsetSeconds: new anchor.BN(0)
+        }))
+
+      // an empty wSOL associated token account, deposits wrap the missing lamports into it
+      const putMakerWSOLATA = await createTokenAccount(conn, minterKeypair, wSOL, putMakerKeypair.publicKey)
+      assert.isNull(putMakerWSOLATA.closeAuthority)
+
+      // a put on wBTC/SOL with strike 100 SOL, each lot of 0.001 wBTC is backed by 0.1 SOL
+      const strike = new anchor.BN(100*anchor.web3.LAMPORTS_PER_SOL)
+      const vaultParams = new MakerCreatePutOptionParams(
+        {
+          maturity: shortMaturity,
+          strike: strike,
+          lotSize: -3,
+          maxMakers: 100,
+          maxTakers: 100,
+          numLotsToSell: new anchor.BN(1),
+          premiumLimit: new anchor.BN(0),
+          cashSettled: false,
+          american: false,
+          barrier: new anchor.BN(0),
+          barrierUp: false,
+          knockIn: false,
+          auctionSeconds: new anchor.BN(0),
+          auctionStartMultipleBps: 0,
+          auctionFloorMultipleBps: 0,
+          makerAllocation: { takerChoice: {} },
+          initialMarginBps: 0,
+          maintenanceMarginBps: 0,
+          liquidationBonusBps: 0
+        })
+      const vaultFactory = await getPutOptionVaultFactoryPdaAddress(program, wormholeBTCToken, wSOL, shortMaturity, strike)
+      const vault = await getPutOptionVaultDerivedPdaAddresses(program, vaultFactory, wormholeBTCToken, wSOL, new anchor.BN(1))
+      await program.methods.makerOpenPutOptionVault(vaultParams).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
+        initializer: putMakerKeypair.publicKey,
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: vault.putOptionVaultAddress,
+        vaultBaseAssetTreasury: vault.vaultBaseAssetTreasury,
+        vaultQuoteAssetTreasury: vault.vaultQuoteAssetTreasury,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: wSOL,
+        makerQuoteAssetAccount: putMakerWSOLATA.address,
+      }).signers([putMakerKeypair]).rpc(confirmOptions)
+      const collateral = anchor.web3.LAMPORTS_PER_SOL/10
+      assert.equal(Number((await token.getAccount(conn, vault.vaultQuoteAssetTreasury)).amount), collateral)
+
+      const rfqInfo = getPutOptionRfqPdaAddress(program, vaultFactory, putTakerKeypair.publicKey)
+      await program.methods.takerPostPutOptionRfq(new anchor.BN(1), -3, shortMaturity.subn(FREEZE_SECONDS)).accounts({
+        vaultFactoryInfo: vaultFactory,
+        assetPairConfig: getAssetPairConfigPdaAddress(program, wormholeBTCToken, wSOL),
+        rfqInfo: rfqInfo,
+        initializer: putTakerKeypair.publicKey
+      }).signers([putTakerKeypair]).rpc(confirmOptions)
+
+      const quoteInfo = getPutOptionRfqQuotePdaAddress(program, rfqInfo, putMakerKeypair.publicKey)
+      const quoteCollateralEscrow = token.getAssociatedTokenAddressSync(wSOL, quoteInfo, true)
+      const quoteAndWithdraw = async (makerQuoteAssetAccount: anchor.web3.PublicKey) => {
+        await program.methods.makerQuotePutOptionRfq(new anchor.BN(anchor.web3.LAMPORTS_PER_SOL/100)).accounts({
+          rfqInfo: rfqInfo,
+          vaultFactoryInfo: vaultFactory,
+          quoteInfo: quoteInfo,
+          quoteCollateralEscrow: quoteCollateralEscrow,
+          quoteAssetMint: wSOL,
+          makerQuoteAssetAccount: makerQuoteAssetAccount,
+          initializer: putMakerKeypair.publicKey,
+          tokenProgram: token.TOKEN_PROGRAM_ID
+        }).signers([putMakerKeypair]).rpc(confirmOptions)
+        assert.equal(Number((await token.getAccount(conn, quoteCollateralEscrow)).amount), collateral)
+        await program.methods.makerWithdrawPutOptionRfqQuote().accounts({
+          quoteInfo: quoteInfo,
+          quoteCollateralEscrow: quoteCollateralEscrow,
+          quoteAssetMint: wSOL,
+          makerQuoteAssetAccount: makerQuoteAssetAccount,
+          initializer: putMakerKeypair.publicKey,
+          tokenProgram: token.TOKEN_PROGRAM_ID
+        }).signers([putMakerKeypair]).rpc(confirmOptions)
+      }
+
+      // without a close authority the refund stays wrapped in the associated token account
+      await quoteAndWithdraw(putMakerWSOLATA.address)
+      const ataAfterRefund = await token.getAccount(conn, putMakerWSOLATA.address)
+      assert.equal(Number(ataAfterRefund.amount), collateral)
+      assert.isNull(ataAfterRefund.closeAuthority)
+
+      // a wSOL account whose close authority the maker set to herself is closed on the refund,
+      // returning its balance and rent as native SOL
+      const optInAccount = await token.createWrappedNativeAccount(conn, minterKeypair, putMakerKeypair.publicKey, 0, anchor.web3.Keypair.generate(), confirmOptions)
+      await token.setAuthority(conn, minterKeypair, optInAccount, putMakerKeypair, token.AuthorityType.CloseAccount, putMakerKeypair.publicKey, [], confirmOptions)
+      const makerLamportsBefore = await conn.getBalance(putMakerKeypair.publicKey)
+      await quoteAndWithdraw(optInAccount)
+      assert.isNull(await conn.getAccountInfo(optInAccount))
+      // the collateral is wrapped from her lamports on the quote and comes back with the account rent
+      // on the withdrawal, less transaction fees
+      const makerLamportsAfter = await conn.getBalance(putMakerKeypair.publicKey)
+      assert.isAbove(makerLamportsAfter, makerLamportsBefore)
+      assert.equal(Number((await token.getAccount(conn, putMakerWSOLATA.address)).amount), collateral)
+    });
+
+    it("Placing, filling and cancelling resting put option bids", async () => {
+      const conn = anchor.getProvider().connection
+      const mintInfoWBTC = await token.getMint(conn, wormholeBTCToken)
+      const putMakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putMakerKeypair.publicKey)
+      const putMaker2USDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putMaker2Keypair.publicKey)
+      const putTakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putTakerKeypair.publicKey)
+      const callTakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, callTakerKeypair.publicKey)
+      await mintTokens(conn, minterKeypair, usdcToken, callTakerUSDCATA.address, minterKeypair, 100)
+
+      let currEpoch = Math.floor(Date.now()/1000)
+      const strike = new anchor.BN(25500*(10**6))
+      const vaultParams = new MakerCreatePutOptionParams(
+        {
+          maturity: new anchor.BN(currEpoch + (7*24*60*60) + 1200),
+          strike: strike,
+          lotSize: -3,
+          maxMakers: 100,
+          maxTakers: 100,
+          numLotsToSell: new anchor.BN(2),
+          premiumLimit: new anchor.BN(0),
+          cashSettled: false,
+          american: false,
+          barrier: new anchor.BN(0),
+          barrierUp: false,
+          knockIn: false,
+          auctionSeconds: new anchor.BN(0),
+          auctionStartMultipleBps: 0,
+          auctionFloorMultipleBps: 0,
+          makerAllocation: { takerChoice: {} },
+          initialMarginBps: 0,
+          maintenanceMarginBps: 0,
+          liquidationBonusBps: 0
+        })
+      const vaultFactory = await getPutOptionVaultFactoryPdaAddress(program, wormholeBTCToken, usdcToken, vaultParams.maturity, strike)
+      const vaultId = new anchor.BN(1)
+      const { putOptionVaultAddress, vaultBaseAssetTreasury, vaultQuoteAssetTreasury } = await getPutOptionVaultDerivedPdaAddresses(program, vaultFactory, wormholeBTCToken, usdcToken, vaultId)
+      await program.methods.makerOpenPutOptionVault(vaultParams).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
+        initializer: putMaker2Keypair.publicKey,
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: putOptionVaultAddress,
+        vaultBaseAssetTreasury: vaultBaseAssetTreasury,
+        vaultQuoteAssetTreasury: vaultQuoteAssetTreasury,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        makerQuoteAssetAccount: putMaker2USDCATA.address,
+      }).signers([putMaker2Keypair]).rpc(confirmOptions)
+
+      const placeBid = async (taker: anchor.web3.Keypair, takerQuoteAssetAccount: anchor.web3.PublicKey, numLots: number, maxFairPrice: number, expiry: number) => {
+        const takerBidInfo = getPutOptionTakerBidPdaAddress(program, putOptionVaultAddress, taker.publicKey)
+        const bidQuoteAssetEscrow = token.getAssociatedTokenAddressSync(usdcToken, takerBidInfo, true)
+        const putOptionTakerInfo = await getPutTakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, taker.publicKey)
+        await program.methods.takerPlacePutOptionBid(new anchor.BN(numLots), new anchor.BN(maxFairPrice), new anchor.BN(expiry)).accounts({
+          vaultFactoryInfo: vaultFactory,
+          vaultInfo: putOptionVaultAddress,
+          takerBidInfo: takerBidInfo,
+          bidQuoteAssetEscrow: bidQuoteAssetEscrow,
+          putOptionTakerInfo: putOptionTakerInfo,
+          baseAssetMint: wormholeBTCToken,
+          quoteAssetMint: usdcToken,
+          takerQuoteAssetAccount: takerQuoteAssetAccount,
+          initializer: taker.publicKey,
+          tokenProgram: token.TOKEN_PROGRAM_ID
+        }).signers([taker]).rpc(confirmOptions)
+        return { takerBidInfo, bidQuoteAssetEscrow, putOptionTakerInfo }
+      }
+      const bidAsRemainingAccounts = (bid: { takerBidInfo: anchor.web3.PublicKey, bidQuoteAssetEscrow: anchor.web3.PublicKey, putOptionTakerInfo: anchor.web3.PublicKey }) => [
+        { pubkey: bid.takerBidInfo, isSigner: false, isWritable: true },
+        { pubkey: bid.bidQuoteAssetEscrow, isSigner: false, isWritable: true },
+        { pubkey: bid.putOptionTakerInfo, isSigner: false, isWritable: true }
+      ]
+      const tokenBalance = async (address: anchor.web3.PublicKey) => Number((await token.getAccount(conn, address)).amount)
+
+      // 5 lots bid at up to 600 USDC per BTC, escrowing whole lamports per lot
+      const bidLots = 5
+      const maxFairPrice = 600*(10**6)
+      const escrowPerLot = Math.ceil(maxFairPrice*(10**-3))
+      const takerUSDCBeforeBid = await tokenBalance(putTakerUSDCATA.address)
+      currEpoch = Math.floor(Date.now()/1000)
+      const bid = await placeBid(putTakerKeypair, putTakerUSDCATA.address, bidLots, maxFairPrice, currEpoch + 10*60)
+      assert.equal(await tokenBalance(bid.bidQuoteAssetEscrow), escrowPerLot*bidLots)
+      assert.equal(await tokenBalance(putTakerUSDCATA.address), takerUSDCBeforeBid - escrowPerLot*bidLots)
+      let bidInfo = await program.account.putOptionTakerBidInfo.fetch(bid.takerBidInfo)
+      assert.equal(bidInfo.lotsRemaining.toNumber(), bidLots)
+      assert.equal(bidInfo.escrowedPremium.toNumber(), escrowPerLot*bidLots)
+
+      // a second bid expires before any maker comes
+      const expiredBid = await placeBid(callTakerKeypair, callTakerUSDCATA.address, 1, maxFairPrice, Math.floor(Date.now()/1000) + 15)
+      const expiredEscrow = await tokenBalance(expiredBid.bidQuoteAssetEscrow)
+      await new Promise(resolve => setTimeout(resolve, 30*1000))
+
+      // premiums and fees of a fill at fairPrice, computed as fill_put_option_taker_bids does
+      const fairPrice = 500*(10**6)
+      const fillPremium = (lots: number) => Math.round(fairPrice*(10**-3)*lots)
+      const fillFees = (lots: number) => Math.ceil(fairPrice*(10**-3)*lots*100/10000)
+      const lotMaxBaseAsset = Math.ceil(1*(10**-3)*(10**mintInfoWBTC.decimals))
+
+      // the crank sells the 2 lots of the first maker to the live bid, skipping the expired one
+      await setPutFairPrice(vaultFactory, fairPrice)
+      const maker2Info = await getPutMakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, putMaker2Keypair.publicKey)
+      const maker2USDCBeforeCrank = await tokenBalance(putMaker2USDCATA.address)
+      const crankTx = await program.methods.crankMatchPutOptionTakerBids(2).accounts({
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: putOptionVaultAddress,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        feeVaultInfo: getFeeVaultPdaAddress(program, usdcToken),
+        feeVaultQuoteAssetTreasury: token.getAssociatedTokenAddressSync(usdcToken, getFeeVaultPdaAddress(program, usdcToken), true),
+        insuranceFundInfo: getInsuranceFundPdaAddress(program, usdcToken),
+        initializer: putTakerKeypair.publicKey,
+        tokenProgram: token.TOKEN_PROGRAM_ID
+      }).remainingAccounts([
+        ...bidAsRemainingAccounts(expiredBid),
+        ...bidAsRemainingAccounts(bid),
+        { pubkey: maker2Info, isSigner: false, isWritable: true },
+        { pubkey: putMaker2USDCATA.address, isSigner: false, isWritable: true }
+      ]).signers([putTakerKeypair]).rpc(confirmOptions)
+      let t = await conn.getTransaction(crankTx, { maxSupportedTransactionVersion: 0, commitment: "confirmed" })
+      const [, , crankBuffer] = getReturnLog(t)
+      assert.equal(new borsh.BinaryReader(crankBuffer).readU64().toNumber(), 2)
+
+      bidInfo = await program.account.putOptionTakerBidInfo.fetch(bid.takerBidInfo)
+      assert.equal(bidInfo.lotsRemaining.toNumber(), bidLots - 2)
+      assert.equal(bidInfo.lotsFilled.toNumber(), 2)
+      assert.equal(bidInfo.escrowedPremium.toNumber(), escrowPerLot*bidLots - fillPremium(2))
+      assert.equal(await tokenBalance(bid.bidQuoteAssetEscrow), escrowPerLot*bidLots - fillPremium(2))
+      assert.equal(await tokenBalance(putMaker2USDCATA.address), maker2USDCBeforeCrank + fillPremium(2) - fillFees(2))
+      let takerInfo = await program.account.putOptionTakerInfo.fetch(bid.putOptionTakerInfo)
+      assert.equal(takerInfo.maxBaseAsset.toNumber(), 2*lotMaxBaseAsset)
+
+      const expiredBidInfo = await program.account.putOptionTakerBidInfo.fetch(expiredBid.takerBidInfo)
+      assert.equal(expiredBidInfo.lotsFilled.toNumber(), 0)
+      assert.equal(await tokenBalance(expiredBid.bidQuoteAssetEscrow), expiredEscrow)
+      const expiredTakerInfo = await program.account.putOptionTakerInfo.fetch(expiredBid.putOptionTakerInfo)
+      assert.equal(expiredTakerInfo.maxBaseAsset.toNumber(), 0)
+
+      // a second maker entering the vault sells her 2 lots to the bid right away
+      await setPutFairPrice(vaultFactory, fairPrice)
+      const feeVault = getFeeVaultPdaAddress(program, usdcToken)
+      const makerUSDCBeforeEnter = await tokenBalance(putMakerUSDCATA.address)
+      const lotValue = Math.ceil((10**-3)*strike.toNumber())
+      await program.methods.makerEnterPutOptionVault(new anchor.BN(2), new anchor.BN(0)).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
+        initializer: putMakerKeypair.publicKey,
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: putOptionVaultAddress,
+        vaultQuoteAssetTreasury: vaultQuoteAssetTreasury,
+        putOptionMakerInfo: await getPutMakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, putMakerKeypair.publicKey),
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        makerQuoteAssetAccount: putMakerUSDCATA.address,
+      }).remainingAccounts([
+        { pubkey: feeVault, isSigner: false, isWritable: true },
+        { pubkey: token.getAssociatedTokenAddressSync(usdcToken, feeVault, true), isSigner: false, isWritable: true },
+        { pubkey: getInsuranceFundPdaAddress(program, usdcToken), isSigner: false, isWritable: false },
+        ...bidAsRemainingAccounts(bid)
+      ]).signers([putMakerKeypair]).rpc(confirmOptions)
+
+      bidInfo = await program.account.putOptionTakerBidInfo.fetch(bid.takerBidInfo)
+      assert.equal(bidInfo.lotsRemaining.toNumber(), bidLots - 4)
+      assert.equal(bidInfo.lotsFilled.toNumber(), 4)
+      const escrowLeft = escrowPerLot*bidLots - fillPremium(2) - fillPremium(2)
+      assert.equal(await tokenBalance(bid.bidQuoteAssetEscrow), escrowLeft)
+      assert.equal(await tokenBalance(putMakerUSDCATA.address), makerUSDCBeforeEnter - 2*lotValue + fillPremium(2) - fillFees(2))
+      takerInfo = await program.account.putOptionTakerInfo.fetch(bid.putOptionTakerInfo)
+      assert.equal(takerInfo.maxBaseAsset.toNumber(), 4*lotMaxBaseAsset)
+
+      // cancelling refunds whatever is left in escrow, the lots bought stay with the taker
+      const cancelBid = async (taker: anchor.web3.Keypair, takerQuoteAssetAccount: anchor.web3.PublicKey, bidToCancel: { takerBidInfo: anchor.web3.PublicKey, bidQuoteAssetEscrow: anchor.web3.PublicKey }) => {
+        await program.methods.takerCancelPutOptionBid().accounts({
+          takerBidInfo: bidToCancel.takerBidInfo,
+          bidQuoteAssetEscrow: bidToCancel.bidQuoteAssetEscrow,
+          quoteAssetMint: usdcToken,
+          takerQuoteAssetAccount: takerQuoteAssetAccount,
+          initializer: taker.publicKey,
+          tokenProgram: token.TOKEN_PROGRAM_ID
+        }).signers([taker]).rpc(confirmOptions)
+        assert.isNull(await program.account.putOptionTakerBidInfo.fetchNullable(bidToCancel.takerBidInfo))
+        assert.isNull(await conn.getAccountInfo(bidToCancel.bidQuoteAssetEscrow))
+      }
+      const takerUSDCBeforeCancel = await tokenBalance(putTakerUSDCATA.address)
+      await cancelBid(putTakerKeypair, putTakerUSDCATA.address, bid)
+      assert.equal(await tokenBalance(putTakerUSDCATA.address), takerUSDCBeforeCancel + escrowLeft)
+      takerInfo = await program.account.putOptionTakerInfo.fetch(bid.putOptionTakerInfo)
+      assert.equal(takerInfo.maxBaseAsset.toNumber(), 4*lotMaxBaseAsset)
+
+      const callTakerUSDCBeforeCancel = await tokenBalance(callTakerUSDCATA.address)
+      await cancelBid(callTakerKeypair, callTakerUSDCATA.address, expiredBid)
+      assert.equal(await tokenBalance(callTakerUSDCATA.address), callTakerUSDCBeforeCancel + expiredEscrow)
+    });
+
+    it("Buying a put spread against resting bids of the lower strike vault", async () => {
+      const conn = anchor.getProvider().connection
+      const mintInfoWBTC = await token.getMint(conn, wormholeBTCToken)
+      const putMakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putMakerKeypair.publicKey)
+      const putMaker2USDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putMaker2Keypair.publicKey)
+      const putTakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putTakerKeypair.publicKey)
+      const callTakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, callTakerKeypair.publicKey)
+      await mintTokens(conn, minterKeypair, usdcToken, putTakerUSDCATA.address, minterKeypair, 100)
+      await mintTokens(conn, minterKeypair, usdcToken, callTakerUSDCATA.address, minterKeypair, 10)
+      const tokenBalance = async (address: anchor.web3.PublicKey) => Number((await token.getAccount(conn, address)).amount)
+
+      // both legs share pair, maturity, lot size and exercise style, and sell to takers of their choice
+      const maturity = new anchor.BN(Math.floor(Date.now()/1000) + (7*24*60*60) + 2400)
+      const openVault = async (maker: anchor.web3.Keypair, makerQuoteAssetAccount: anchor.web3.PublicKey, strike: anchor.BN, lots: number) => {
+        const vaultParams = new MakerCreatePutOptionParams(
+          {
+            maturity: maturity,
+            strike: strike,
+            lotSize: -3,
+            maxMakers: 100,
+            maxTakers: 100,
+            numLotsToSell: new anchor.BN(lots),
+            premiumLimit: new anchor.BN(0),
+            cashSettled: false,
+            american: false,
+            barrier: new anchor.BN(0),
+            barrierUp: false,
+            knockIn: false,
+            auctionSeconds: new anchor.BN(0),
+            auctionStartMultipleBps: 0,
+            auctionFloorMultipleBps: 0,
+            makerAllocation: { takerChoice: {} },
+            initialMarginBps: 0,
+            maintenanceMarginBps: 0,
+            liquidationBonusBps: 0
+          })
+        const vaultFactory = await getPutOptionVaultFactoryPdaAddress(program, wormholeBTCToken, usdcToken, maturity, strike)
+        const vaultId = new anchor.BN(1)
+        const { putOptionVaultAddress, vaultBaseAssetTreasury, vaultQuoteAssetTreasury } = await getPutOptionVaultDerivedPdaAddresses(program, vaultFactory, wormholeBTCToken, usdcToken, vaultId)
+        await program.methods.makerOpenPutOptionVault(vaultParams).accounts({
+          tokenProgram: token.TOKEN_PROGRAM_ID,
+          initializer: maker.publicKey,
+          vaultFactoryInfo: vaultFactory,
+          vaultInfo: putOptionVaultAddress,
+          vaultBaseAssetTreasury: vaultBaseAssetTreasury,
+          vaultQuoteAssetTreasury: vaultQuoteAssetTreasury,
+          baseAssetMint: wormholeBTCToken,
+          quoteAssetMint: usdcToken,
+          makerQuoteAssetAccount: makerQuoteAssetAccount,
+        }).signers([maker]).rpc(confirmOptions)
+        return { vaultFactory, vaultId, putOptionVaultAddress, vaultQuoteAssetTreasury }
+      }
+      const longStrike = new anchor.BN(27000*(10**6))
+      const shortStrike = new anchor.BN(26500*(10**6))
+      const longVault = await openVault(putMakerKeypair, putMakerUSDCATA.address, longStrike, 3)
+      const shortVault = await openVault(putMaker2Keypair, putMaker2USDCATA.address, shortStrike, 1)
+      const longMakerInfo = await getPutMakerVaultAssociatedAccountAddress(program, longVault.vaultFactory, longVault.vaultId, putMakerKeypair.publicKey)
+
+      // the only buyer of the lower strike puts bids for 2 lots
+      const bidLots = 2
+      const maxFairPrice = 400*(10**6)
+      const takerBidInfo = getPutOptionTakerBidPdaAddress(program, shortVault.putOptionVaultAddress, callTakerKeypair.publicKey)
+      const bidQuoteAssetEscrow = token.getAssociatedTokenAddressSync(usdcToken, takerBidInfo, true)
+      const bidderTakerInfo = await getPutTakerVaultAssociatedAccountAddress(program, shortVault.vaultFactory, shortVault.vaultId, callTakerKeypair.publicKey)
+      await program.methods.takerPlacePutOptionBid(new anchor.BN(bidLots), new anchor.BN(maxFairPrice), new anchor.BN(Math.floor(Date.now()/1000) + 10*60)).accounts({
+        vaultFactoryInfo: shortVault.vaultFactory,
+        vaultInfo: shortVault.putOptionVaultAddress,
+        takerBidInfo: takerBidInfo,
+        bidQuoteAssetEscrow: bidQuoteAssetEscrow,
+        putOptionTakerInfo: bidderTakerInfo,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        takerQuoteAssetAccount: callTakerUSDCATA.address,
+        initializer: callTakerKeypair.publicKey,
+        tokenProgram: token.TOKEN_PROGRAM_ID
+      }).signers([callTakerKeypair]).rpc(confirmOptions)
+      const bidEscrow = await tokenBalance(bidQuoteAssetEscrow)
+
+      const longFairPrice = 500*(10**6)
+      const shortFairPrice = 300*(10**6)
+      const spreadMakerInfo = await getPutMakerVaultAssociatedAccountAddress(program, shortVault.vaultFactory, shortVault.vaultId, putTakerKeypair.publicKey)
+      const spreadTakerInfo = await getPutTakerVaultAssociatedAccountAddress(program, longVault.vaultFactory, longVault.vaultId, putTakerKeypair.publicKey)
+      const buySpread = async (maxNetPremium: number, numLots: number) => {
+        await setPutFairPrice(longVault.vaultFactory, longFairPrice)
+        await setPutFairPrice(shortVault.vaultFactory, shortFairPrice)
+        return await program.methods.takerBuyPutSpread(new anchor.BN(maxNetPremium), new anchor.BN(numLots), 1).accounts({
+          longVaultFactoryInfo: longVault.vaultFactory,
+          longVaultInfo: longVault.putOptionVaultAddress,
+          putOptionTakerInfo: spreadTakerInfo,
+          shortVaultFactoryInfo: shortVault.vaultFactory,
+          shortVaultInfo: shortVault.putOptionVaultAddress,
+          shortVaultQuoteAssetTreasury: shortVault.vaultQuoteAssetTreasury,
+          putOptionMakerInfo: spreadMakerInfo,
+          baseAssetMint: wormholeBTCToken,
+          quoteAssetMint: usdcToken,
+          takerQuoteAssetAccount: putTakerUSDCATA.address,
+          feeVaultInfo: getFeeVaultPdaAddress(program, usdcToken),
+          feeVaultQuoteAssetTreasury: token.getAssociatedTokenAddressSync(usdcToken, getFeeVaultPdaAddress(program, usdcToken), true),
+          insuranceFundInfo: getInsuranceFundPdaAddress(program, usdcToken),
+          frontendInfo: null,
+          feeTiersInfo: getFeeTiersPdaAddress(program, usdcToken),
+          takerStatsInfo: getTakerStatsPdaAddress(program, putTakerKeypair.publicKey, usdcToken),
+          initializer: putTakerKeypair.publicKey,
+          tokenProgram: token.TOKEN_PROGRAM_ID
+        }).remainingAccounts([
+          { pubkey: takerBidInfo, isSigner: false, isWritable: true },
+          { pubkey: bidQuoteAssetEscrow, isSigner: false, isWritable: true },
+          { pubkey: bidderTakerInfo, isSigner: false, isWritable: true },
+          { pubkey: longMakerInfo, isSigner: false, isWritable: true },
+          { pubkey: putMakerUSDCATA.address, isSigner: false, isWritable: true }
+        ]).signers([putTakerKeypair]).rpc(confirmOptions)
+      }
+      const takerUSDCBefore = await tokenBalance(putTakerUSDCATA.address)
+      const assertNothingHappened = async () => {
+        assert.equal(await tokenBalance(putTakerUSDCATA.address), takerUSDCBefore)
+        assert.isNull(await program.account.putOptionMakerInfo.fetchNullable(spreadMakerInfo))
+        const bidInfo = await program.account.putOptionTakerBidInfo.fetch(takerBidInfo)
+        assert.equal(bidInfo.lotsRemaining.toNumber(), bidLots)
+        assert.equal(await tokenBalance(bidQuoteAssetEscrow), bidEscrow)
+        const longMaker = await program.account.putOptionMakerInfo.fetch(longMakerInfo)
+        assert.equal(longMaker.volumeSold.toNumber(), 0)
+      }
+
+      // 3 lots can be bought but only 2 can be sold, so neither leg is filled
+      try {
+        await buySpread(10**12, 3)
+        assert.fail("Spread was bought with its sold leg partially filled")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "SpreadNotFilled")
+      }
+      await assertNothingHappened()
+
+      // the premium received for the sold leg does not cover the one paid for the bought leg
+      try {
+        await buySpread(0, 2)
+        assert.fail("Spread was bought above its net premium limit")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "MaxNetPremiumTooLow")
+      }
+      await assertNothingHappened()
+
+      const tx = await buySpread(10**12, 2)
+      const t = await conn.getTransaction(tx, { maxSupportedTransactionVersion: 0, commitment: "confirmed" })
+      const [, , buffer] = getReturnLog(t)
+      const reader = new borsh.BinaryReader(buffer)
+      assert.equal(reader.readU64().toNumber(), 2)
+      const longPremium = reader.readU64().toNumber()
+      const shortPremium = reader.readU64().toNumber()
+
+      // the sold leg is paid from the escrow of the bid, net of the 100 bps of protocol fees of the pair
+      const shortPremiumF64 = shortFairPrice*(10**-3)*2
+      assert.equal(shortPremium, Math.round(shortPremiumF64) - Math.ceil(shortPremiumF64*100/10000))
+      // the bought leg costs at least its premium at fair price, fees on top
+      assert.isAtLeast(longPremium, Math.round(longFairPrice*(10**-3)*2))
+      const shortLotValue = Math.ceil((10**-3)*shortStrike.toNumber())
+      assert.equal(await tokenBalance(putTakerUSDCATA.address), takerUSDCBefore - longPremium - 2*shortLotValue + shortPremium)
+
+      // she holds the bought puts and backs the sold ones, all of them sold
+      const lotMaxBaseAsset = Math.ceil(1*(10**-3)*(10**mintInfoWBTC.decimals))
+      const spreadTaker = await program.account.putOptionTakerInfo.fetch(spreadTakerInfo)
+      assert.equal(spreadTaker.maxBaseAsset.toNumber(), 2*lotMaxBaseAsset)
+      const spreadMaker = await program.account.putOptionMakerInfo.fetch(spreadMakerInfo)
+      assert.equal(spreadMaker.quoteAssetQty.toNumber(), 2*shortLotValue)
+      assert.equal(spreadMaker.volumeSold.toNumber(), 2*shortLotValue)
+      assert.isTrue(spreadMaker.isAllSold)
+      const bidInfo = await program.account.putOptionTakerBidInfo.fetch(takerBidInfo)
+      assert.equal(bidInfo.lotsRemaining.toNumber(), 0)
+      assert.equal(bidInfo.lotsFilled.toNumber(), bidLots)
+      const bidderTaker = await program.account.putOptionTakerInfo.fetch(bidderTakerInfo)
+      assert.equal(bidderTaker.maxBaseAsset.toNumber(), 2*lotMaxBaseAsset)
+      const longMaker = await program.account.putOptionMakerInfo.fetch(longMakerInfo)
+      assert.equal(longMaker.volumeSold.toNumber(), 2*Math.ceil((10**-3)*longStrike.toNumber()))
+    });
+
+    it("Liquidating an undermargined maker of a margined put option vault", async () => {
+      const conn = anchor.getProvider().connection
+      const mintInfoWBTC = await token.getMint(conn, wormholeBTCToken)
+      const putMakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putMakerKeypair.publicKey)
+      const putMaker2USDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putMaker2Keypair.publicKey)
+      const putTakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putTakerKeypair.publicKey)
+      const putTakerwBTCATA = await createTokenAccount(conn, minterKeypair, wormholeBTCToken, putTakerKeypair.publicKey)
+      const callTakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, callTakerKeypair.publicKey)
+      await mintTokens(conn, minterKeypair, usdcToken, callTakerUSDCATA.address, minterKeypair, 500)
+      const tokenBalance = async (address: anchor.web3.PublicKey) => Number((await token.getAccount(conn, address)).amount)
+
+      // makers post 20% of what they offer, and must keep the liability of what they sold plus 10% of it
+      const strike = marginedStrike.toNumber()
+      const initialMarginBps = 2000
+      const maintenanceMarginBps = 1000
+      const liquidationBonusBps = 500
+      const vaultParams = new MakerCreatePutOptionParams(
+        {
+          maturity: shortMaturity,
+          strike: marginedStrike,
+          lotSize: -3,
+          maxMakers: 100,
+          maxTakers: 100,
+          numLotsToSell: new anchor.BN(10),
+          premiumLimit: new anchor.BN(0),
+          cashSettled: true,
+          american: false,
+          barrier: new anchor.BN(0),
+          barrierUp: false,
+          knockIn: false,
+          auctionSeconds: new anchor.BN(0),
+          auctionStartMultipleBps: 0,
+          auctionFloorMultipleBps: 0,
+          makerAllocation: { takerChoice: {} },
+          initialMarginBps: initialMarginBps,
+          maintenanceMarginBps: maintenanceMarginBps,
+          liquidationBonusBps: liquidationBonusBps
+        })
+      const vaultFactory = await getPutOptionVaultFactoryPdaAddress(program, wormholeBTCToken, usdcToken, shortMaturity, marginedStrike)
+      const vaultId = new anchor.BN(1)
+      const { putOptionVaultAddress, vaultBaseAssetTreasury, vaultQuoteAssetTreasury } = await getPutOptionVaultDerivedPdaAddresses(program, vaultFactory, wormholeBTCToken, usdcToken, vaultId)
+      await program.methods.makerOpenPutOptionVault(vaultParams).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
+        initializer: putMakerKeypair.publicKey,
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: putOptionVaultAddress,
+        vaultBaseAssetTreasury: vaultBaseAssetTreasury,
+        vaultQuoteAssetTreasury: vaultQuoteAssetTreasury,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        makerQuoteAssetAccount: putMakerUSDCATA.address,
+      }).signers([putMakerKeypair]).rpc(confirmOptions)
+      const makerInfo = await getPutMakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, putMakerKeypair.publicKey)
+      const maker2Info = await getPutMakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, putMaker2Keypair.publicKey)
+      await program.methods.makerEnterPutOptionVault(new anchor.BN(10), new anchor.BN(0)).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
+        initializer: putMaker2Keypair.publicKey,
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: putOptionVaultAddress,
+        vaultQuoteAssetTreasury: vaultQuoteAssetTreasury,
+        putOptionMakerInfo: maker2Info,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        makerQuoteAssetAccount: putMaker2USDCATA.address,
+      }).signers([putMaker2Keypair]).rpc(confirmOptions)
+
+      const marginFor = (notional: number) => Math.floor((notional*initialMarginBps + 9999)/10000)
+      let maker = await program.account.putOptionMakerInfo.fetch(makerInfo)
+      let maker2 = await program.account.putOptionMakerInfo.fetch(maker2Info)
+      assert.equal(maker.marginPosted.toNumber(), marginFor(maker.quoteAssetQty.toNumber()))
+      assert.equal(maker2.marginPosted.toNumber(), marginFor(maker2.quoteAssetQty.toNumber()))
+      assert.equal(await tokenBalance(vaultQuoteAssetTreasury), maker.marginPosted.toNumber() + maker2.marginPosted.toNumber())
+
+      // the taker buys everything both makers offer
+      const fairPrice = 100*(10**6)
+      await setPutFairPrice(vaultFactory, fairPrice)
+      const takerLots = 20
+      await program.methods.takerBuyLotsPutOptionVault(new anchor.BN(fairPrice), new anchor.BN(takerLots), new anchor.BN(0)).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        initializer: putTakerKeypair.publicKey,
+        feeVaultInfo: getFeeVaultPdaAddress(program, usdcToken),
+        feeVaultQuoteAssetTreasury: token.getAssociatedTokenAddressSync(usdcToken, getFeeVaultPdaAddress(program, usdcToken), true),
+        insuranceFundInfo: getInsuranceFundPdaAddress(program, usdcToken),
+        frontendInfo: null,
+        feeTiersInfo: getFeeTiersPdaAddress(program, usdcToken),
+        takerStatsInfo: getTakerStatsPdaAddress(program, putTakerKeypair.publicKey, usdcToken),
+        takerBaseAssetAccount: putTakerwBTCATA.address,
+        takerQuoteAssetAccount: putTakerUSDCATA.address,
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: putOptionVaultAddress,
+        vaultBaseAssetTreasury: vaultBaseAssetTreasury,
+        putOptionTakerInfo: await getPutTakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, putTakerKeypair.publicKey)
+      }).remainingAccounts([
+        { pubkey: makerInfo, isSigner: false, isWritable: true },
+        { pubkey: putMakerUSDCATA.address, isSigner: false, isWritable: true },
+        { pubkey: maker2Info, isSigner: false, isWritable: true },
+        { pubkey: putMaker2USDCATA.address, isSigner: false, isWritable: true }
+      ]).signers([putTakerKeypair]).rpc(confirmOptions)
+      maker = await program.account.putOptionMakerInfo.fetch(makerInfo)
+      maker2 = await program.account.putOptionMakerInfo.fetch(maker2Info)
+      assert.equal(maker.volumeSold.toNumber(), maker.quoteAssetQty.toNumber())
+      assert.equal(maker2.volumeSold.toNumber(), maker2.quoteAssetQty.toNumber())
+      const takerInfo = (await getUserTakerInfoForPutVault(program, putOptionVaultAddress, putTakerKeypair.publicKey))[0]
+      assert.equal(takerInfo.account.maxBaseAsset.toNumber(), Math.ceil(takerLots*(10**-3)*(10**mintInfoWBTC.decimals)))
+
+      // the first maker adds margin, then spot drops well below the strike
+      const addedMargin = 20*(10**6)
+      await program.methods.makerAddPutOptionMargin(new anchor.BN(addedMargin)).accounts({
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: putOptionVaultAddress,
+        quoteAssetMint: usdcToken,
+        vaultQuoteAssetTreasury: vaultQuoteAssetTreasury,
+        putOptionMakerInfo: makerInfo,
+        makerQuoteAssetAccount: putMakerUSDCATA.address,
+        initializer: putMakerKeypair.publicKey,
+        tokenProgram: token.TOKEN_PROGRAM_ID
+      }).signers([putMakerKeypair]).rpc(confirmOptions)
+      const spotPrice = 17000*(10**6)
+      await setPutFairPrice(vaultFactory, fairPrice, spotPrice)
+
+      // computed as compute_put_option_maker_margin_requirement does
+      const unitLiability = Math.max(strike - spotPrice, fairPrice)
+      const liabilityOf = (volumeSold: number) => Math.ceil(volumeSold/strike*unitLiability)
+      const requirementOf = (volumeSold: number) => liabilityOf(volumeSold) + Math.ceil(volumeSold*maintenanceMarginBps/10000)
+
+      // exiting only releases what exceeds both her initial margin and her maintenance requirement
+      const makerPosted = maker.marginPosted.toNumber() + addedMargin
+      const makerKept = Math.max(marginFor(maker.volumeSold.toNumber()), requirementOf(maker.volumeSold.toNumber()))
+      assert.isAbove(makerKept, marginFor(maker.volumeSold.toNumber()))
+      const makerUSDCBeforeExit = await tokenBalance(putMakerUSDCATA.address)
+      await program.methods.makerExitPutOptionVault().accounts({
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: putOptionVaultAddress,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        vaultQuoteAssetTreasury: vaultQuoteAssetTreasury,
+        putOptionMakerInfo: makerInfo,
+        makerQuoteAssetAccount: putMakerUSDCATA.address,
+        initializer: putMakerKeypair.publicKey,
+        tokenProgram: token.TOKEN_PROGRAM_ID
+      }).signers([putMakerKeypair]).rpc(confirmOptions)
+      assert.equal(await tokenBalance(putMakerUSDCATA.address), makerUSDCBeforeExit + makerPosted - makerKept)
+      maker = await program.account.putOptionMakerInfo.fetch(makerInfo)
+      assert.equal(maker.marginPosted.toNumber(), makerKept)
+
+      const liquidatorInfo = await getPutMakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, callTakerKeypair.publicKey)
+      const liquidate = (makerInfoToLiquidate: anchor.web3.PublicKey) => program.methods.liquidatePutOptionMaker().accounts({
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: putOptionVaultAddress,
+        quoteAssetMint: usdcToken,
+        vaultQuoteAssetTreasury: vaultQuoteAssetTreasury,
+        putOptionMakerInfo: makerInfoToLiquidate,
+        liquidatorMakerInfo: liquidatorInfo,
+        liquidatorQuoteAssetAccount: callTakerUSDCATA.address,
+        initializer: callTakerKeypair.publicKey,
+        tokenProgram: token.TOKEN_PROGRAM_ID
+      }).signers([callTakerKeypair]).rpc(confirmOptions)
+
+      // she kept her requirement posted, so she cannot be liquidated
+      try {
+        await liquidate(makerInfo)
+        assert.fail("Maker with enough margin was liquidated")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "MakerNotLiquidatable")
+      }
+
+      // the second maker did not, the liquidator takes over her options fully collateralized
+      const volume2 = maker2.volumeSold.toNumber()
+      const posted2 = maker2.marginPosted.toNumber()
+      assert.isBelow(posted2, requirementOf(volume2))
+      const compensation = Math.ceil(liabilityOf(volume2)*(10000 + liquidationBonusBps)/10000)
+      const fromMargin = Math.min(compensation, posted2)
+      const liquidatorUSDCBefore = await tokenBalance(callTakerUSDCATA.address)
+      const treasuryBeforeLiquidation = await tokenBalance(vaultQuoteAssetTreasury)
+      await liquidate(maker2Info)
+      assert.equal(await tokenBalance(callTakerUSDCATA.address), liquidatorUSDCBefore - (volume2 - fromMargin))
+      assert.equal(await tokenBalance(vaultQuoteAssetTreasury), treasuryBeforeLiquidation + volume2 - fromMargin)
+      maker2 = await program.account.putOptionMakerInfo.fetch(maker2Info)
+      assert.equal(maker2.volumeSold.toNumber(), 0)
+      assert.equal(maker2.marginPosted.toNumber(), posted2 - fromMargin)
+      const liquidator = await program.account.putOptionMakerInfo.fetch(liquidatorInfo)
+      assert.equal(liquidator.volumeSold.toNumber(), volume2)
+      assert.equal(liquidator.marginPosted.toNumber(), volume2)
+
+      // the liquidator also funds the safety fund, which covers part of the shortfall at settlement
+      const safetyFund = 10*(10**6)
+      await program.methods.fundPutOptionVaultSafetyFund(new anchor.BN(safetyFund)).accounts({
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: putOptionVaultAddress,
+        quoteAssetMint: usdcToken,
+        vaultQuoteAssetTreasury: vaultQuoteAssetTreasury,
+        funderQuoteAssetAccount: callTakerUSDCATA.address,
+        initializer: callTakerKeypair.publicKey,
+        tokenProgram: token.TOKEN_PROGRAM_ID
+      }).signers([callTakerKeypair]).rpc(confirmOptions)
+      const vault = await program.account.putOptionVaultInfo.fetch(putOptionVaultAddress)
+      assert.equal(vault.safetyFund.toNumber(), safetyFund)
+      assert.equal(vault.makersNum, 3)
+
+      marginedVault = { vaultFactory: vaultFactory, vault: putOptionVaultAddress, vaultId: vaultId }
+    });
+
+    it("Drawing the insurance fund to the safety fund of a margined put option vault", async () => {
+      const conn = anchor.getProvider().connection
+      const insuranceFund = getInsuranceFundPdaAddress(program, usdcToken)
+      const insuranceFundTreasury = token.getAssociatedTokenAddressSync(usdcToken, insuranceFund, true)
+      // the fund only gets fees when protocol fees are claimed, we top it up directly
+      await mintTokens(conn, minterKeypair, usdcToken, insuranceFundTreasury, minterKeypair, 5)
+      const amount = 5*(10**6)
+      const draw = async (vaultFactory: anchor.web3.PublicKey, vault: anchor.web3.PublicKey) => {
+        const fund = await program.account.insuranceFundInfo.fetch(insuranceFund)
+        const [drawInfo] = anchor.web3.PublicKey.findProgramAddressSync(
+          [
+            Buffer.from(anchor.utils.bytes.utf8.encode("InsuranceFundDrawInfo")),
+            insuranceFund.toBuffer(),
+            fund.numDraws.addn(1).toArrayLike(Buffer, "le", 8)
+          ],
+          program.programId
+        )
+        await program.methods.adminDrawInsuranceFundToPutOptionVault(new anchor.BN(amount), { marginShortfall: {} }).accounts({
+          insuranceFundInfo: insuranceFund,
+          insuranceFundQuoteAssetTreasury: insuranceFundTreasury,
+          insuranceFundDrawInfo: drawInfo,
+          vaultFactoryInfo: vaultFactory,
+          vaultInfo: vault,
+          vaultQuoteAssetTreasury: token.getAssociatedTokenAddressSync(usdcToken, vault, true),
+          quoteAssetMint: usdcToken,
+          initializer: protocolFeesKeypair.publicKey,
+          tokenProgram: token.TOKEN_PROGRAM_ID
+        }).signers([protocolFeesKeypair]).rpc(confirmOptions)
+        return drawInfo
+      }
+
+      // nothing would ever pay a draw out of a vault that is not margined
+      try {
+        await draw(rfqVault.vaultFactory, rfqVault.vault)
+        assert.fail("Draw to a vault that is not margined was accepted")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "VaultNotMargined")
+      }
+
+      const { vaultFactory, vault } = marginedVault
+      const safetyFundBefore = (await program.account.putOptionVaultInfo.fetch(vault)).safetyFund.toNumber()
+      const treasuryBefore = Number((await token.getAccount(conn, token.getAssociatedTokenAddressSync(usdcToken, vault, true))).amount)
+      const drawInfo = await draw(vaultFactory, vault)
+      assert.equal((await program.account.putOptionVaultInfo.fetch(vault)).safetyFund.toNumber(), safetyFundBefore + amount)
+      assert.equal(Number((await token.getAccount(conn, token.getAssociatedTokenAddressSync(usdcToken, vault, true))).amount), treasuryBefore + amount)
+      const recorded = await program.account.insuranceFundDrawInfo.fetch(drawInfo)
+      assert.equal(recorded.vault.toString(), vault.toString())
+      assert.equal(recorded.amount.toNumber(), amount)
+    });
+
+    it("Charging tiered fees with maker rebates and claiming protocol and frontend fees", async () => {
+      const conn = anchor.getProvider().connection
+      const putMakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putMakerKeypair.publicKey)
+      const putTakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putTakerKeypair.publicKey)
+      const putTakerwBTCATA = await createTokenAccount(conn, minterKeypair, wormholeBTCToken, putTakerKeypair.publicKey)
+      await mintTokens(conn, minterKeypair, usdcToken, putMakerUSDCATA.address, minterKeypair, 10)
+      const tokenBalance = async (address: anchor.web3.PublicKey) => Number((await token.getAccount(conn, address)).amount)
+      const feeVault = getFeeVaultPdaAddress(program, usdcToken)
+      const feeVaultTreasury = token.getAssociatedTokenAddressSync(usdcToken, feeVault, true)
+      const insuranceFund = getInsuranceFundPdaAddress(program, usdcToken)
+      const insuranceFundTreasury = token.getAssociatedTokenAddressSync(usdcToken, insuranceFund, true)
+      const feeTiers = getFeeTiersPdaAddress(program, usdcToken)
+      const takerStats = getTakerStatsPdaAddress(program, putTakerKeypair.publicKey, usdcToken)
+
+      // a frontend getting 30% of the fees of the takers coming from it
+      const frontendKeypair = anchor.web3.Keypair.generate()
+      const frontendUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, frontendKeypair.publicKey)
+      const frontend = getFrontendPdaAddress(program, frontendKeypair.publicKey, usdcToken)
+      const frontendFeeShareBps = 3000
+      await program.methods.adminRegisterFrontend(frontendFeeShareBps).accounts({
+        frontendInfo: frontend,
+        frontendOwner: frontendKeypair.publicKey,
+        frontendPayoutAccount: frontendUSDCATA.address,
+        quoteAssetMint: usdcToken,
+        initializer: protocolFeesKeypair.publicKey
+      }).signers([protocolFeesKeypair]).rpc(confirmOptions)
+
+      // 10 lots of 1 USDC of collateral each, fully collateralized
+      const strike = new anchor.BN(1000*(10**6))
+      const vaultParams = new MakerCreatePutOptionParams(
+        {
+          maturity: shortMaturity,
+          strike: strike,
+          lotSize: -3,
+          maxMakers: 100,
+          maxTakers: 100,
+          numLotsToSell: new anchor.BN(10),
+          premiumLimit: new anchor.BN(0),
+          cashSettled: true,
+          american: false,
+          barrier: new anchor.BN(0),
+          barrierUp: false,
+          knockIn: false,
+          auctionSeconds: new anchor.BN(0),
+          auctionStartMultipleBps: 0,
+          auctionFloorMultipleBps: 0,
+          makerAllocation: { takerChoice: {} },
+          initialMarginBps: 0,
+          maintenanceMarginBps: 0,
+          liquidationBonusBps: 0
+        })
+      const vaultFactory = await getPutOptionVaultFactoryPdaAddress(program, wormholeBTCToken, usdcToken, shortMaturity, strike)
+      const vaultId = new anchor.BN(1)
+      const { putOptionVaultAddress, vaultBaseAssetTreasury, vaultQuoteAssetTreasury } = await getPutOptionVaultDerivedPdaAddresses(program, vaultFactory, wormholeBTCToken, usdcToken, vaultId)
+      await program.methods.makerOpenPutOptionVault(vaultParams).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
+        initializer: putMakerKeypair.publicKey,
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: putOptionVaultAddress,
+        vaultBaseAssetTreasury: vaultBaseAssetTreasury,
+        vaultQuoteAssetTreasury: vaultQuoteAssetTreasury,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        makerQuoteAssetAccount: putMakerUSDCATA.address,
+      }).signers([putMakerKeypair]).rpc(confirmOptions)
+      const makerInfo = await getPutMakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, putMakerKeypair.publicKey)
+
+      // computed as TakerStatsInfo::rolling_volume does
+      const secondsPerDay = 24*60*60
+      const rollingVolume = (stats, today: number) => {
+        let volume = 0
+        for (let day = Math.max(today - (FEE_TIER_VOLUME_WINDOW_DAYS - 1), 0); day <= Math.min(today, stats.lastDay.toNumber()); day++) {
+          volume += stats.dailyVolume[day % FEE_TIER_VOLUME_WINDOW_DAYS].toNumber()
+        }
+        return volume
+      }
+      const today = Math.floor((await conn.getBlockTime(await conn.getSlot("confirmed")))/secondsPerDay)
+      const statsBefore = await program.account.takerStatsInfo.fetch(takerStats)
+      const volumeBefore = rollingVolume(statsBefore, today)
+
+      // the taker reaches the first tier with the volume she already has but not the second one,
+      // and makers get a rebate however long they have been resting
+      const tiersBefore = await program.account.feeTiersInfo.fetch(feeTiers)
+      const discountBps = 5000
+      const makerRebateBps = 1000
+      await program.methods.adminUpdateFeeTiers(new FeeTiersParams({
+        tiers: [
+          { minVolume: new anchor.BN(volumeBefore), discountBps: discountBps },
+          { minVolume: new anchor.BN(volumeBefore + 1), discountBps: 9000 },
+          { minVolume: new anchor.BN(0), discountBps: 0 },
+          { minVolume: new anchor.BN(0), discountBps: 0 }
+        ],
+        makerRebateBps: makerRebateBps,
+        makerRebateMinSeconds: new anchor.BN(0)
+      })).accounts({
+        feeTiersInfo: feeTiers,
+        initializer: protocolFeesKeypair.publicKey
+      }).signers([protocolFeesKeypair]).rpc(confirmOptions)
+
+      const fairPrice = 100*(10**6)
+      await setPutFairPrice(vaultFactory, fairPrice)
+      const lots = 10
+      const makerUSDCBefore = await tokenBalance(putMakerUSDCATA.address)
+      const takerUSDCBefore = await tokenBalance(putTakerUSDCATA.address)
+      const feeVaultTreasuryBefore = await tokenBalance(feeVaultTreasury)
+      const feeVaultBefore = await program.account.feeVaultInfo.fetch(feeVault)
+      await program.methods.takerBuyLotsPutOptionVault(new anchor.BN(fairPrice), new anchor.BN(lots), new anchor.BN(0)).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        initializer: putTakerKeypair.publicKey,
+        feeVaultInfo: feeVault,
+        feeVaultQuoteAssetTreasury: feeVaultTreasury,
+        insuranceFundInfo: insuranceFund,
+        frontendInfo: frontend,
+        feeTiersInfo: feeTiers,
+        takerStatsInfo: takerStats,
+        takerBaseAssetAccount: putTakerwBTCATA.address,
+        takerQuoteAssetAccount: putTakerUSDCATA.address,
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: putOptionVaultAddress,
+        vaultBaseAssetTreasury: vaultBaseAssetTreasury,
+        putOptionTakerInfo: await getPutTakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, putTakerKeypair.publicKey)
+      }).remainingAccounts([
+        { pubkey: makerInfo, isSigner: false, isWritable: true },
+        { pubkey: putMakerUSDCATA.address, isSigner: false, isWritable: true }
+      ]).signers([putTakerKeypair]).rpc(confirmOptions)
+      await program.methods.adminUpdateFeeTiers(new FeeTiersParams({
+        tiers: tiersBefore.tiers,
+        makerRebateBps: tiersBefore.makerRebateBps,
+        makerRebateMinSeconds: tiersBefore.makerRebateMinSeconds
+      })).accounts({
+        feeTiersInfo: feeTiers,
+        initializer: protocolFeesKeypair.publicKey
+      }).signers([protocolFeesKeypair]).rpc(confirmOptions)
+
+      // computed as buy_put_option_lots_from_makers does, protocol fees of the pair are 100 bps
+      const premiumF64 = fairPrice*(10**-3)*lots
+      const premium = Math.round(premiumF64)
+      const totalFees = premiumF64*100/10000*(10000 - discountBps)/10000
+      const frontendFeeShare = frontendFeeShareBps/10000
+      const backendShare = Math.ceil(totalFees*(1.0 - frontendFeeShare))
+      const frontendShare = Math.ceil(totalFees*frontendFeeShare)
+      const makerRebate = Math.floor(backendShare*makerRebateBps/10000)
+      assert.isAbove(makerRebate, 0)
+      const protocolShare = backendShare - makerRebate
+      const insuranceShare = Math.floor(protocolShare*2000/10000)
+      assert.isAbove(insuranceShare, 0)
+      assert.equal(await tokenBalance(putMakerUSDCATA.address), makerUSDCBefore + premium - backendShare - frontendShare + makerRebate)
+      assert.equal(await tokenBalance(putTakerUSDCATA.address), takerUSDCBefore - premium)
+      assert.equal(await tokenBalance(feeVaultTreasury), feeVaultTreasuryBefore + protocolShare + frontendShare)
+      let feeVaultAfter = await program.account.feeVaultInfo.fetch(feeVault)
+      assert.equal(feeVaultAfter.protocolFees.toNumber(), feeVaultBefore.protocolFees.toNumber() + protocolShare - insuranceShare)
+      assert.equal(feeVaultAfter.insuranceFees.toNumber(), feeVaultBefore.insuranceFees.toNumber() + insuranceShare)
+      assert.equal(feeVaultAfter.frontendFees.toNumber(), feeVaultBefore.frontendFees.toNumber() + frontendShare)
+      let frontendInfo = await program.account.frontendInfo.fetch(frontend)
+      assert.equal(frontendInfo.volume.toNumber(), premium)
+      assert.equal(frontendInfo.numTrades.toNumber(), 1)
+      assert.equal(frontendInfo.feesAccrued.toNumber(), frontendShare)
+
+      // the premium goes to the bucket of today, which restarts from zero on the first purchase of the day
+      const statsAfter = await program.account.takerStatsInfo.fetch(takerStats)
+      const bucket = today % FEE_TIER_VOLUME_WINDOW_DAYS
+      const bucketBefore = statsBefore.lastDay.toNumber() == today ? statsBefore.dailyVolume[bucket].toNumber() : 0
+      assert.equal(statsAfter.lastDay.toNumber(), today)
+      assert.equal(statsAfter.dailyVolume[bucket].toNumber(), bucketBefore + premium)
+      assert.equal(statsAfter.totalVolume.toNumber(), statsBefore.totalVolume.toNumber() + premium)
+      assert.equal(rollingVolume(statsAfter, today), volumeBefore + premium)
+
+      // the admin claims the protocol fees, the insurance share goes to the insurance fund
+      const protocolUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, protocolFeesKeypair.publicKey)
+      const protocolUSDCBefore = await tokenBalance(protocolUSDCATA.address)
+      const insuranceFundTreasuryBefore = await tokenBalance(insuranceFundTreasury)
+      const feeVaultTreasuryBeforeClaim = await tokenBalance(feeVaultTreasury)
+      const claimTx = await program.methods.claimProtocolFees().accounts({
+        feeVaultInfo: feeVault,
+        feeVaultQuoteAssetTreasury: feeVaultTreasury,
+        insuranceFundInfo: insuranceFund,
+        insuranceFundQuoteAssetTreasury: insuranceFundTreasury,
+        protocolQuoteAssetTreasury: protocolUSDCATA.address,
+        quoteAssetMint: usdcToken,
+        initializer: protocolFeesKeypair.publicKey,
+        tokenProgram: token.TOKEN_PROGRAM_ID
+      }).signers([protocolFeesKeypair]).rpc(confirmOptions)
+      let t = await conn.getTransaction(claimTx, { maxSupportedTransactionVersion: 0, commitment: "confirmed" })
+      let [key, , buffer] = getReturnLog(t)
+      assert.equal(key, program.programId)
+      assert.equal(new borsh.BinaryReader(buffer).readU64().toNumber(), feeVaultAfter.protocolFees.toNumber())
+      assert.equal(await tokenBalance(protocolUSDCATA.address), protocolUSDCBefore + feeVaultAfter.protocolFees.toNumber())
+      assert.equal(await tokenBalance(insuranceFundTreasury), insuranceFundTreasuryBefore + feeVaultAfter.insuranceFees.toNumber())
+      assert.equal(await tokenBalance(feeVaultTreasury), feeVaultTreasuryBeforeClaim - feeVaultAfter.protocolFees.toNumber() - feeVaultAfter.insuranceFees.toNumber())
+      let feeVaultClaimed = await program.account.feeVaultInfo.fetch(feeVault)
+      assert.equal(feeVaultClaimed.protocolFees.toNumber(), 0)
+      assert.equal(feeVaultClaimed.insuranceFees.toNumber(), 0)
+      assert.equal(feeVaultClaimed.frontendFees.toNumber(), feeVaultAfter.frontendFees.toNumber())
+
+      // the frontend claims its share to its payout account
+      const frontendClaimTx = await program.methods.claimFrontendFees().accounts({
+        frontendInfo: frontend,
+        feeVaultInfo: feeVault,
+        feeVaultQuoteAssetTreasury: feeVaultTreasury,
+        frontendPayoutAccount: frontendUSDCATA.address,
+        quoteAssetMint: usdcToken,
+        initializer: frontendKeypair.publicKey,
+        tokenProgram: token.TOKEN_PROGRAM_ID
+      }).signers([frontendKeypair]).rpc(confirmOptions)
+      t = await conn.getTransaction(frontendClaimTx, { maxSupportedTransactionVersion: 0, commitment: "confirmed" });
+      [key, , buffer] = getReturnLog(t)
+      assert.equal(key, program.programId)
+      assert.equal(new borsh.BinaryReader(buffer).readU64().toNumber(), frontendShare)
+      assert.equal(await tokenBalance(frontendUSDCATA.address), frontendShare)
+      frontendInfo = await program.account.frontendInfo.fetch(frontend)
+      assert.equal(frontendInfo.feesAccrued.toNumber(), 0)
+      feeVaultClaimed = await program.account.feeVaultInfo.fetch(feeVault)
+      assert.equal(feeVaultClaimed.frontendFees.toNumber(), feeVaultAfter.frontendFees.toNumber() - frontendShare)
+
+      // nothing left to claim
+      try {
+        await program.methods.claimFrontendFees().accounts({
+          frontendInfo: frontend,
+          feeVaultInfo: feeVault,
+          feeVaultQuoteAssetTreasury: feeVaultTreasury,
+          frontendPayoutAccount: frontendUSDCATA.address,
+          quoteAssetMint: usdcToken,
+          initializer: frontendKeypair.publicKey,
+          tokenProgram: token.TOKEN_PROGRAM_ID
+        }).signers([frontendKeypair]).rpc(confirmOptions)
+        assert.fail("Frontend claimed fees twice")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "NothingToClaim")
+      }
+    });
+
+    it("Depositing, rolling and withdrawing a covered call roll vault", async () => {
+      const conn = anchor.getProvider().connection
+      const mintInfoWBTC = await token.getMint(conn, wormholeBTCToken)
+      const tokenBalance = async (address: anchor.web3.PublicKey) => Number((await token.getAccount(conn, address)).amount)
+      const callMakerwBTCATA = await createTokenAccount(conn, minterKeypair, wormholeBTCToken, callMakerKeypair.publicKey)
+      const callMaker2wBTCATA = await createTokenAccount(conn, minterKeypair, wormholeBTCToken, callMaker2Keypair.publicKey)
+      const callMakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, callMakerKeypair.publicKey)
+      const callMaker2USDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, callMaker2Keypair.publicKey)
+      const callTakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, callTakerKeypair.publicKey)
+      await mintTokens(conn, minterKeypair, wormholeBTCToken, callMakerwBTCATA.address, minterKeypair, 0.004)
+      await mintTokens(conn, minterKeypair, wormholeBTCToken, callMaker2wBTCATA.address, minterKeypair, 0.0016)
+      await mintTokens(conn, minterKeypair, usdcToken, callTakerUSDCATA.address, minterKeypair, 10)
+
+      const strikeOffsetBps = 500
+      const tenorSeconds = new anchor.BN(24*60*60)
+      const strike = new anchor.BN(30000*(10**6))
+      const rollVault = getCoveredCallRollVaultPdaAddress(program, wormholeBTCToken, usdcToken, strikeOffsetBps, tenorSeconds)
+      const rollBaseAssetTreasury = token.getAssociatedTokenAddressSync(wormholeBTCToken, rollVault, true)
+      const rollQuoteAssetTreasury = token.getAssociatedTokenAddressSync(usdcToken, rollVault, true)
+      await program.methods.createCoveredCallRollVault({
+        strikeOffsetBps: strikeOffsetBps,
+        tenorSeconds: tenorSeconds,
+        lotSize: -3,
+        maxTakers: 10,
+        firstMaturity: shortMaturity,
+        firstStrike: strike
+      }).accounts({
+        rollVaultInfo: rollVault,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        rollBaseAssetTreasury: rollBaseAssetTreasury,
+        rollQuoteAssetTreasury: rollQuoteAssetTreasury,
+        initializer: callMakerKeypair.publicKey,
+        tokenProgram: token.TOKEN_PROGRAM_ID
+      }).signers([callMakerKeypair]).rpc(confirmOptions)
+
+      const deposit = async (depositor: anchor.web3.Keypair, baseAssetAccount: anchor.web3.PublicKey, quoteAssetAccount: anchor.web3.PublicKey, amount: number) => {
+        return await program.methods.depositCoveredCallRollVault(new anchor.BN(amount)).accounts({
+          rollVaultInfo: rollVault,
+          shareInfo: getCoveredCallRollSharePdaAddress(program, rollVault, depositor.publicKey),
+          baseAssetMint: wormholeBTCToken,
+          quoteAssetMint: usdcToken,
+          rollBaseAssetTreasury: rollBaseAssetTreasury,
+          rollQuoteAssetTreasury: rollQuoteAssetTreasury,
+          depositorBaseAssetAccount: baseAssetAccount,
+          depositorQuoteAssetAccount: quoteAssetAccount,
+          initializer: depositor.publicKey,
+          tokenProgram: token.TOKEN_PROGRAM_ID
+        }).signers([depositor]).rpc(confirmOptions)
+      }
+      const withdraw = async (depositor: anchor.web3.Keypair, baseAssetAccount: anchor.web3.PublicKey, quoteAssetAccount: anchor.web3.PublicKey, shares: number) => {
+        return await program.methods.withdrawCoveredCallRollVault(new anchor.BN(shares)).accounts({
+          rollVaultInfo: rollVault,
+          shareInfo: getCoveredCallRollSharePdaAddress(program, rollVault, depositor.publicKey),
+          baseAssetMint: wormholeBTCToken,
+          quoteAssetMint: usdcToken,
+          rollBaseAssetTreasury: rollBaseAssetTreasury,
+          rollQuoteAssetTreasury: rollQuoteAssetTreasury,
+          depositorBaseAssetAccount: baseAssetAccount,
+          depositorQuoteAssetAccount: quoteAssetAccount,
+          initializer: depositor.publicKey,
+          tokenProgram: token.TOKEN_PROGRAM_ID
+        }).signers([depositor]).rpc(confirmOptions)
+      }
+
+      // with no premiums accrued yet, shares are priced one to one by the idle base asset
+      const deposit1 = 0.004*(10**mintInfoWBTC.decimals)
+      const deposit2 = 0.0016*(10**mintInfoWBTC.decimals)
+      await deposit(callMakerKeypair, callMakerwBTCATA.address, callMakerUSDCATA.address, deposit1)
+      await deposit(callMaker2Keypair, callMaker2wBTCATA.address, callMaker2USDCATA.address, deposit2)
+      assert.equal((await program.account.coveredCallRollShareInfo.fetch(getCoveredCallRollSharePdaAddress(program, rollVault, callMakerKeypair.publicKey))).shares.toNumber(), deposit1)
+      assert.equal((await program.account.coveredCallRollShareInfo.fetch(getCoveredCallRollSharePdaAddress(program, rollVault, callMaker2Keypair.publicKey))).shares.toNumber(), deposit2)
+
+      // between series, shares are withdrawn for their part of the idle base asset
+      const withdrawnShares = 0.0004*(10**mintInfoWBTC.decimals)
+      const maker2wBTCBefore = await tokenBalance(callMaker2wBTCATA.address)
+      const withdrawTx = await withdraw(callMaker2Keypair, callMaker2wBTCATA.address, callMaker2USDCATA.address, withdrawnShares)
+      let t = await conn.getTransaction(withdrawTx, { maxSupportedTransactionVersion: 0, commitment: "confirmed" })
+      let [, , buffer] = getReturnLog(t)
+      let reader = new borsh.BinaryReader(buffer)
+      assert.equal(reader.readU64().toNumber(), withdrawnShares)
+      assert.equal(reader.readU64().toNumber(), 0)
+      assert.equal(await tokenBalance(callMaker2wBTCATA.address), maker2wBTCBefore + withdrawnShares)
+      const idle = deposit1 + deposit2 - withdrawnShares
+      let roll = await program.account.coveredCallRollVaultInfo.fetch(rollVault)
+      assert.equal(roll.totalShares.toNumber(), idle)
+      assert.equal(roll.baseAssetIdle.toNumber(), idle)
+      assert.equal(await tokenBalance(rollBaseAssetTreasury), idle)
+
+      // the crank commits the idle base asset in whole lots to a new call option vault, the rest stays idle
+      const vaultFactory = await getCallOptionVaultFactoryPdaAddress(program, wormholeBTCToken, usdcToken, shortMaturity, strike)
+      const vaultId = new anchor.BN(1)
+      const { callOptionVaultAddress, vaultBaseAssetTreasury, vaultQuoteAssetTreasury } = await getCallOptionVaultDerivedPdaAddresses(program, vaultFactory, wormholeBTCToken, usdcToken, vaultId)
+      const makerInfo = await getCallMakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, rollVault)
+      const rollTx = await program.methods.crankRollCoveredCallVault(shortMaturity, vaultId).accounts({
+        rollVaultInfo: rollVault,
+        rollBaseAssetTreasury: rollBaseAssetTreasury,
+        vaultFactoryInfo: vaultFactory,
+        assetPairConfig: getAssetPairConfigPdaAddress(program, wormholeBTCToken, usdcToken),
+        seriesIndex: getSeriesIndexPdaAddress(program, wormholeBTCToken, usdcToken),
+        vaultInfo: callOptionVaultAddress,
+        vaultBaseAssetTreasury: vaultBaseAssetTreasury,
+        vaultQuoteAssetTreasury: vaultQuoteAssetTreasury,
+        callOptionMakerInfo: makerInfo,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        initializer: callTakerKeypair.publicKey,
+        tokenProgram: token.TOKEN_PROGRAM_ID
+      }).signers([callTakerKeypair]).rpc(confirmOptions)
+      const lotLamports = Math.ceil(1*(10**-3)*(10**mintInfoWBTC.decimals))
+      const committed = Math.floor(idle/lotLamports)*lotLamports
+      assert.isAbove(idle, committed)
+      t = await conn.getTransaction(rollTx, { maxSupportedTransactionVersion: 0, commitment: "confirmed" })
+      ;[, , buffer] = getReturnLog(t)
+      assert.equal(new borsh.BinaryReader(buffer).readU64().toNumber(), committed)
+      roll = await program.account.coveredCallRollVaultInfo.fetch(rollVault)
+      assert.isTrue(roll.isInSeries)
+      assert.equal(roll.baseAssetIdle.toNumber(), idle - committed)
+      assert.equal(roll.baseAssetCommitted.toNumber(), committed)
+      assert.equal(roll.currentCallOptionVault.toString(), callOptionVaultAddress.toString())
+      assert.equal(await tokenBalance(vaultBaseAssetTreasury), committed)
+      let maker = await program.account.callOptionMakerInfo.fetch(makerInfo)
+      assert.equal(maker.owner.toString(), rollVault.toString())
+      assert.equal(maker.baseAssetQty.toNumber(), committed)
+
+      // while the series runs, nobody can get in or out
+      try {
+        await deposit(callMaker2Keypair, callMaker2wBTCATA.address, callMaker2USDCATA.address, withdrawnShares)
+        assert.fail("Deposit was accepted during a series")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "SeriesInProgress")
+      }
+      try {
+        await withdraw(callMakerKeypair, callMakerwBTCATA.address, callMakerUSDCATA.address, 1)
+        assert.fail("Withdrawal was accepted during a series")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "SeriesInProgress")
+      }
+
+      // a taker buys 2 lots, paying the premium straight to the roll vault
+      await program.methods.genUpdateCallOptionFairPriceTicket().accounts({
+        vaultFactoryInfo: vaultFactory,
+        initializer: callTakerKeypair.publicKey,
+        oracleWallet: getOraclePubKey(),
+        callOptionFairPriceTicket: await getUserTicketAccountAddressForCallVaultFactory(program, vaultFactory, callTakerKeypair.publicKey)
+      }).signers([callTakerKeypair]).rpc(confirmOptions)
+      const fairPrice = 200*(10**6)
+      await _testUpdateCallOptionFairPrice(program, vaultFactory, callTakerKeypair.publicKey, new anchor.BN(fairPrice))
+      const rollQuoteBefore = await tokenBalance(rollQuoteAssetTreasury)
+      await program.methods.takerBuyLotsCallOptionVault(new anchor.BN(fairPrice), new anchor.BN(2), new anchor.BN(0)).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        initializer: callTakerKeypair.publicKey,
+        feeVaultInfo: getFeeVaultPdaAddress(program, usdcToken),
+        feeVaultQuoteAssetTreasury: token.getAssociatedTokenAddressSync(usdcToken, getFeeVaultPdaAddress(program, usdcToken), true),
+        insuranceFundInfo: getInsuranceFundPdaAddress(program, usdcToken),
+        frontendInfo: null,
+        feeTiersInfo: getFeeTiersPdaAddress(program, usdcToken),
+        takerStatsInfo: getTakerStatsPdaAddress(program, callTakerKeypair.publicKey, usdcToken),
+        takerQuoteAssetAccount: callTakerUSDCATA.address,
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: callOptionVaultAddress,
+        vaultQuoteAssetTreasury: vaultQuoteAssetTreasury,
+        callOptionTakerInfo: await getCallTakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, callTakerKeypair.publicKey)
+      }).remainingAccounts([
+        { pubkey: makerInfo, isSigner: false, isWritable: true },
+        { pubkey: rollQuoteAssetTreasury, isSigner: false, isWritable: true }
+      ]).signers([callTakerKeypair]).rpc(confirmOptions)
+      const premium = await tokenBalance(rollQuoteAssetTreasury) - rollQuoteBefore
+      assert.isAbove(premium, 0)
+      maker = await program.account.callOptionMakerInfo.fetch(makerInfo)
+      assert.equal(maker.volumeSold.toNumber(), 2*lotLamports)
+
+      // the crank taking the series position back only works once the series is in emergency mode,
+      // which cannot happen before the grace period after maturity is over
+      try {
+        await program.methods.crankCoveredCallRollVaultEmergencyExit().accounts({
+          rollVaultInfo: rollVault,
+          rollBaseAssetTreasury: rollBaseAssetTreasury,
+          rollQuoteAssetTreasury: rollQuoteAssetTreasury,
+          vaultFactoryInfo: vaultFactory,
+          vaultInfo: callOptionVaultAddress,
+          callOptionMakerInfo: makerInfo,
+          baseAssetMint: wormholeBTCToken,
+          quoteAssetMint: usdcToken,
+          vaultBaseAssetTreasury: vaultBaseAssetTreasury,
+          initializer: callTakerKeypair.publicKey,
+          tokenProgram: token.TOKEN_PROGRAM_ID
+        }).signers([callTakerKeypair]).rpc(confirmOptions)
+        assert.fail("Series position was taken back before the emergency grace period")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "EmergencyModeTooEarly")
+      }
+      assert.equal(await tokenBalance(vaultBaseAssetTreasury), committed)
+      roll = await program.account.coveredCallRollVaultInfo.fetch(rollVault)
+      assert.isTrue(roll.isInSeries)
+
+      coveredCallRoll = { rollVault, vaultFactory, vault: callOptionVaultAddress, vaultId, idle: idle - committed, committed, premium }
+    });
+
+    it("Queuing deposits and withdrawals of a cash-secured put roll vault and rolling it", async () => {
+      const conn = anchor.getProvider().connection
+      const mintInfoUSDC = await token.getMint(conn, usdcToken)
+      const tokenBalance = async (address: anchor.web3.PublicKey) => Number((await token.getAccount(conn, address)).amount)
+      const putMakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putMakerKeypair.publicKey)
+      const putMaker2USDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putMaker2Keypair.publicKey)
+      await mintTokens(conn, minterKeypair, usdcToken, putMakerUSDCATA.address, minterKeypair, 130)
+      await mintTokens(conn, minterKeypair, usdcToken, putMaker2USDCATA.address, minterKeypair, 60)
+
+      const strikeOffsetBps = 1000
+      const tenorSeconds = new anchor.BN(24*60*60)
+      const { rollVaultInfo: rollVault, shareMint } = getCashSecuredPutRollVaultPdaAddresses(program, wormholeBTCToken, usdcToken, strikeOffsetBps, tenorSeconds)
+      const rollQuoteAssetTreasury = token.getAssociatedTokenAddressSync(usdcToken, rollVault, true)
+      const rollShareEscrow = token.getAssociatedTokenAddressSync(shareMint, rollVault, true)
+      await program.methods.createCashSecuredPutRollVault({
+        strikeOffsetBps: strikeOffsetBps,
+        tenorSeconds: tenorSeconds,
+        lotSize: -3,
+        maxTakers: 10,
+        firstMaturity: shortMaturity
+      }).accounts({
+        rollVaultInfo: rollVault,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        shareMint: shareMint,
+        rollQuoteAssetTreasury: rollQuoteAssetTreasury,
+        rollShareEscrow: rollShareEscrow,
+        initializer: putMakerKeypair.publicKey,
+        tokenProgram: token.TOKEN_PROGRAM_ID
+      }).signers([putMakerKeypair]).rpc(confirmOptions)
+      const putMakerShareATA = await createTokenAccount(conn, minterKeypair, shareMint, putMakerKeypair.publicKey)
+      const putMaker2ShareATA = await createTokenAccount(conn, minterKeypair, shareMint, putMaker2Keypair.publicKey)
+
+      const requestDeposit = async (depositor: anchor.web3.Keypair, quoteAssetAccount: anchor.web3.PublicKey, amount: number) => {
+        await program.methods.requestDepositCashSecuredPutRollVault(new anchor.BN(amount)).accounts({
+          rollVaultInfo: rollVault,
+          ticketInfo: getCashSecuredPutRollTicketPdaAddress(program, rollVault, depositor.publicKey),
+          baseAssetMint: wormholeBTCToken,
+          quoteAssetMint: usdcToken,
+          rollQuoteAssetTreasury: rollQuoteAssetTreasury,
+          depositorQuoteAssetAccount: quoteAssetAccount,
+          initializer: depositor.publicKey,
+          tokenProgram: token.TOKEN_PROGRAM_ID
+        }).signers([depositor]).rpc(confirmOptions)
+      }
+      const requestWithdraw = async (depositor: anchor.web3.Keypair, shareAccount: anchor.web3.PublicKey, shares: number) => {
+        await program.methods.requestWithdrawCashSecuredPutRollVault(new anchor.BN(shares)).accounts({
+          rollVaultInfo: rollVault,
+          ticketInfo: getCashSecuredPutRollTicketPdaAddress(program, rollVault, depositor.publicKey),
+          baseAssetMint: wormholeBTCToken,
+          quoteAssetMint: usdcToken,
+          shareMint: shareMint,
+          rollShareEscrow: rollShareEscrow,
+          depositorShareAccount: shareAccount,
+          initializer: depositor.publicKey,
+          tokenProgram: token.TOKEN_PROGRAM_ID
+        }).signers([depositor]).rpc(confirmOptions)
+      }
+      const processQueues = async () => {
+        const epoch = (await program.account.cashSecuredPutRollVaultInfo.fetch(rollVault)).epoch
+        await program.methods.crankProcessCashSecuredPutRollQueues().accounts({
+          rollVaultInfo: rollVault,
+          epochInfo: getCashSecuredPutRollEpochPdaAddress(program, rollVault, epoch),
+          baseAssetMint: wormholeBTCToken,
+          quoteAssetMint: usdcToken,
+          shareMint: shareMint,
+          rollShareEscrow: rollShareEscrow,
+          initializer: putTakerKeypair.publicKey,
+          tokenProgram: token.TOKEN_PROGRAM_ID
+        }).signers([putTakerKeypair]).rpc(confirmOptions)
+        return await program.account.cashSecuredPutRollEpochInfo.fetch(getCashSecuredPutRollEpochPdaAddress(program, rollVault, epoch))
+      }
+      const claim = async (depositor: anchor.web3.Keypair, shareAccount: anchor.web3.PublicKey, quoteAssetAccount: anchor.web3.PublicKey) => {
+        const ticket = getCashSecuredPutRollTicketPdaAddress(program, rollVault, depositor.publicKey)
+        const epoch = (await program.account.cashSecuredPutRollTicketInfo.fetch(ticket)).epoch
+        const tx = await program.methods.claimCashSecuredPutRollTicket().accounts({
+          rollVaultInfo: rollVault,
+          ticketInfo: ticket,
+          epochInfo: getCashSecuredPutRollEpochPdaAddress(program, rollVault, epoch),
+          baseAssetMint: wormholeBTCToken,
+          quoteAssetMint: usdcToken,
+          shareMint: shareMint,
+          rollQuoteAssetTreasury: rollQuoteAssetTreasury,
+          depositorShareAccount: shareAccount,
+          depositorQuoteAssetAccount: quoteAssetAccount,
+          initializer: depositor.publicKey,
+          tokenProgram: token.TOKEN_PROGRAM_ID
+        }).signers([depositor]).rpc(confirmOptions)
+        const t = await conn.getTransaction(tx, { maxSupportedTransactionVersion: 0, commitment: "confirmed" })
+        const [, , buffer] = getReturnLog(t)
+        const reader = new borsh.BinaryReader(buffer)
+        return { sharesMinted: reader.readU64().toNumber(), quoteAssetTransfer: reader.readU64().toNumber() }
+      }
+      const usdc = (amount: number) => amount*(10**mintInfoUSDC.decimals)
+
+      // epoch 0: both deposits get their shares one to one, as the vault is empty
+      await requestDeposit(putMakerKeypair, putMakerUSDCATA.address, usdc(100))
+      await requestDeposit(putMaker2Keypair, putMaker2USDCATA.address, usdc(50))
+      try {
+        await claim(putMakerKeypair, putMakerShareATA.address, putMakerUSDCATA.address)
+        assert.fail("Ticket was claimed before its epoch was closed")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "AccountNotInitialized")
+      }
+      let epochInfo = await processQueues()
+      assert.equal(epochInfo.deposits.toNumber(), usdc(150))
+      assert.equal(epochInfo.sharesMinted.toNumber(), usdc(150))
+      assert.deepEqual(await claim(putMakerKeypair, putMakerShareATA.address, putMakerUSDCATA.address), { sharesMinted: usdc(100), quoteAssetTransfer: 0 })
+      assert.deepEqual(await claim(putMaker2Keypair, putMaker2ShareATA.address, putMaker2USDCATA.address), { sharesMinted: usdc(50), quoteAssetTransfer: 0 })
+      assert.equal(await tokenBalance(putMakerShareATA.address), usdc(100))
+
+      // epoch 1: a withdrawal waits in escrow and a deposit in the treasury, both priced when the epoch is closed
+      await requestWithdraw(putMaker2Keypair, putMaker2ShareATA.address, usdc(20))
+      await requestDeposit(putMakerKeypair, putMakerUSDCATA.address, usdc(30))
+      assert.equal(await tokenBalance(rollShareEscrow), usdc(20))
+      let roll = await program.account.cashSecuredPutRollVaultInfo.fetch(rollVault)
+      assert.equal(roll.pendingDeposits.toNumber(), usdc(30))
+      assert.equal(roll.pendingWithdrawShares.toNumber(), usdc(20))
+      epochInfo = await processQueues()
+      assert.equal(epochInfo.sharesMinted.toNumber(), usdc(30))
+      assert.equal(epochInfo.quoteForWithdrawals.toNumber(), usdc(20))
+      assert.equal(await tokenBalance(rollShareEscrow), 0)
+      const maker2USDCBefore = await tokenBalance(putMaker2USDCATA.address)
+      assert.deepEqual(await claim(putMaker2Keypair, putMaker2ShareATA.address, putMaker2USDCATA.address), { sharesMinted: 0, quoteAssetTransfer: usdc(20) })
+      assert.equal(await tokenBalance(putMaker2USDCATA.address), maker2USDCBefore + usdc(20))
+      assert.deepEqual(await claim(putMakerKeypair, putMakerShareATA.address, putMakerUSDCATA.address), { sharesMinted: usdc(30), quoteAssetTransfer: 0 })
+      roll = await program.account.cashSecuredPutRollVaultInfo.fetch(rollVault)
+      assert.equal(roll.epoch.toNumber(), 2)
+      assert.equal(roll.totalShares.toNumber(), usdc(160))
+      assert.equal(roll.quoteAssetIdle.toNumber(), usdc(160))
+      assert.equal(roll.quoteReservedForWithdrawals.toNumber(), 0)
+      assert.equal(await tokenBalance(rollQuoteAssetTreasury), usdc(160))
+
+      // the strike of the series is the spot price of the oracle minus the offset
+      const spotPrice = 25000*(10**6)
+      const strike = new anchor.BN(spotPrice*(10000 - strikeOffsetBps)/10000)
+      await _testUpdateCashSecuredPutRollSpotPrice(program, rollVault, new anchor.BN(spotPrice))
+      const vaultFactory = await getPutOptionVaultFactoryPdaAddress(program, wormholeBTCToken, usdcToken, shortMaturity, strike)
+      const vaultId = new anchor.BN(1)
+      const { putOptionVaultAddress, vaultBaseAssetTreasury, vaultQuoteAssetTreasury } = await getPutOptionVaultDerivedPdaAddresses(program, vaultFactory, wormholeBTCToken, usdcToken, vaultId)
+      const makerInfo = await getPutMakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, rollVault)
+      const crankRoll = async (rollStrike: anchor.BN) => {
+        const rollFactory = await getPutOptionVaultFactoryPdaAddress(program, wormholeBTCToken, usdcToken, shortMaturity, rollStrike)
+        const rollVaultAddresses = await getPutOptionVaultDerivedPdaAddresses(program, rollFactory, wormholeBTCToken, usdcToken, vaultId)
+        return await program.methods.crankRollCashSecuredPutVault(shortMaturity, rollStrike, vaultId).accounts({
+          rollVaultInfo: rollVault,
+          rollQuoteAssetTreasury: rollQuoteAssetTreasury,
+          vaultFactoryInfo: rollFactory,
+          assetPairConfig: getAssetPairConfigPdaAddress(program, wormholeBTCToken, usdcToken),
+          seriesIndex: getSeriesIndexPdaAddress(program, wormholeBTCToken, usdcToken),
+          vaultInfo: rollVaultAddresses.putOptionVaultAddress,
+          vaultBaseAssetTreasury: rollVaultAddresses.vaultBaseAssetTreasury,
+          vaultQuoteAssetTreasury: rollVaultAddresses.vaultQuoteAssetTreasury,
+          putOptionMakerInfo: await getPutMakerVaultAssociatedAccountAddress(program, rollFactory, vaultId, rollVault),
+          baseAssetMint: wormholeBTCToken,
+          quoteAssetMint: usdcToken,
+          initializer: putTakerKeypair.publicKey,
+          tokenProgram: token.TOKEN_PROGRAM_ID
+        }).signers([putTakerKeypair]).rpc(confirmOptions)
+      }
+      // the strike must follow the spot price of the oracle
+      try {
+        await crankRoll(new anchor.BN(spotPrice))
+        assert.fail("Series was rolled at a strike off the schedule")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "InvalidRollSchedule")
+      }
+      const rollTx = await crankRoll(strike)
+      const lotValue = Math.ceil((10**-3)*strike.toNumber())
+      const committed = Math.floor(usdc(160)/lotValue)*lotValue
+      let t = await conn.getTransaction(rollTx, { maxSupportedTransactionVersion: 0, commitment: "confirmed" })
+      let [, , buffer] = getReturnLog(t)
+      assert.equal(new borsh.BinaryReader(buffer).readU64().toNumber(), committed)
+      roll = await program.account.cashSecuredPutRollVaultInfo.fetch(rollVault)
+      assert.isTrue(roll.isInSeries)
+      assert.equal(roll.quoteAssetIdle.toNumber(), usdc(160) - committed)
+      assert.equal(roll.quoteAssetCommitted.toNumber(), committed)
+      assert.equal(roll.currentPutOptionVault.toString(), putOptionVaultAddress.toString())
+      assert.equal(await tokenBalance(vaultQuoteAssetTreasury), committed)
+      const factory = await program.account.putOptionVaultFactoryInfo.fetch(vaultFactory)
+      assert.isTrue(factory.cashSettled)
+
+      // requests can still be queued during the series, but the epoch is only closed between series
+      await requestDeposit(putMaker2Keypair, putMaker2USDCATA.address, usdc(10))
+      try {
+        await processQueues()
+        assert.fail("Epoch was closed during a series")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "ConstraintRaw")
+      }
+
+      // a taker buys 1 lot, paying the premium straight to the roll vault
+      const putTakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putTakerKeypair.publicKey)
+      const putTakerwBTCATA = await createTokenAccount(conn, minterKeypair, wormholeBTCToken, putTakerKeypair.publicKey)
+      const fairPrice = 100*(10**6)
+      await setPutFairPrice(vaultFactory, fairPrice)
+      const rollQuoteBefore = await tokenBalance(rollQuoteAssetTreasury)
+      await program.methods.takerBuyLotsPutOptionVault(new anchor.BN(fairPrice), new anchor.BN(1), new anchor.BN(0)).accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        initializer: putTakerKeypair.publicKey,
+        feeVaultInfo: getFeeVaultPdaAddress(program, usdcToken),
+        feeVaultQuoteAssetTreasury: token.getAssociatedTokenAddressSync(usdcToken, getFeeVaultPdaAddress(program, usdcToken), true),
+        insuranceFundInfo: getInsuranceFundPdaAddress(program, usdcToken),
+        frontendInfo: null,
+        feeTiersInfo: getFeeTiersPdaAddress(program, usdcToken),
+        takerStatsInfo: getTakerStatsPdaAddress(program, putTakerKeypair.publicKey, usdcToken),
+        takerBaseAssetAccount: putTakerwBTCATA.address,
+        takerQuoteAssetAccount: putTakerUSDCATA.address,
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: putOptionVaultAddress,
+        vaultBaseAssetTreasury: vaultBaseAssetTreasury,
+        putOptionTakerInfo: await getPutTakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, putTakerKeypair.publicKey)
+      }).remainingAccounts([
+        { pubkey: makerInfo, isSigner: false, isWritable: true },
+        { pubkey: rollQuoteAssetTreasury, isSigner: false, isWritable: true }
+      ]).signers([putTakerKeypair]).rpc(confirmOptions)
+      const premium = await tokenBalance(rollQuoteAssetTreasury) - rollQuoteBefore
+      assert.isAbove(premium, 0)
+
+      putRoll = { rollVault, shareMint, vaultFactory, vault: putOptionVaultAddress, vaultId, idle: usdc(160) - committed, committed, premium, pendingDeposit: usdc(10) }
+    });
+
+    it("Rejecting invalid binary option makers in remaining accounts", async () => {
+      const conn = anchor.getProvider().connection
+      const tokenBalance = async (address: anchor.web3.PublicKey) => Number((await token.getAccount(conn, address)).amount)
+      const putMakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putMakerKeypair.publicKey)
+      const putMaker2USDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putMaker2Keypair.publicKey)
+      const putTakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putTakerKeypair.publicKey)
+      await mintTokens(conn, minterKeypair, usdcToken, putMakerUSDCATA.address, minterKeypair, 10)
+      await mintTokens(conn, minterKeypair, usdcToken, putMaker2USDCATA.address, minterKeypair, 10)
+      await mintTokens(conn, minterKeypair, usdcToken, putTakerUSDCATA.address, minterKeypair, 10)
+
+      // a binary call paying 1 USDC per lot, each maker opens her own vault in the same series
+      const params = {
+        maturity: shortMaturity,
+        strike: new anchor.BN(25000*(10**6)),
+        payoutPerLot: new anchor.BN(10**6),
+        isPut: false,
+        maxMakers: 10,
+        maxTakers: 10,
+        numLotsToSell: new anchor.BN(5),
+        premiumLimit: new anchor.BN(0)
+      }
+      const vaultFactory = getBinaryOptionVaultFactoryPdaAddress(program, wormholeBTCToken, usdcToken, params.maturity, params.strike, params.payoutPerLot, params.isPut)
+      const createVault = async (maker: anchor.web3.Keypair, makerQuoteAssetAccount: anchor.web3.PublicKey) => {
+        const nextIdTx = await program.methods.makerNextBinaryOptionVaultId(params).accounts({
+          vaultFactoryInfo: vaultFactory,
+          assetPairConfig: getAssetPairConfigPdaAddress(program, wormholeBTCToken, usdcToken),
+          seriesIndex: getSeriesIndexPdaAddress(program, wormholeBTCToken, usdcToken),
+          baseAssetMint: wormholeBTCToken,
+          quoteAssetMint: usdcToken,
+          initializer: maker.publicKey
+        }).signers([maker]).rpc(confirmOptions)
+        const t = await conn.getTransaction(nextIdTx, { maxSupportedTransactionVersion: 0, commitment: "confirmed" })
+        const [, , buffer] = getReturnLog(t)
+        const vaultId = new anchor.BN(new borsh.BinaryReader(buffer).readU64().toNumber())
+        const { binaryOptionVaultAddress, vaultQuoteAssetTreasury } = getBinaryOptionVaultDerivedPdaAddresses(program, vaultFactory, usdcToken, vaultId)
+        const makerInfo = getBinaryMakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, maker.publicKey)
+        await program.methods.makerCreateBinaryOptionVault(params, vaultId).accounts({
+          vaultFactoryInfo: vaultFactory,
+          vaultInfo: binaryOptionVaultAddress,
+          baseAssetMint: wormholeBTCToken,
+          quoteAssetMint: usdcToken,
+          vaultQuoteAssetTreasury: vaultQuoteAssetTreasury,
+          binaryOptionMakerInfo: makerInfo,
+          makerQuoteAssetAccount: makerQuoteAssetAccount,
+          initializer: maker.publicKey,
+          tokenProgram: token.TOKEN_PROGRAM_ID
+        }).signers([maker]).rpc(confirmOptions)
+        return { vaultId, vault: binaryOptionVaultAddress, vaultQuoteAssetTreasury, makerInfo }
+      }
+      const { vaultId, vault, vaultQuoteAssetTreasury, makerInfo } = await createVault(putMakerKeypair, putMakerUSDCATA.address)
+      const { makerInfo: otherVaultMakerInfo } = await createVault(putMaker2Keypair, putMaker2USDCATA.address)
+      assert.equal(await tokenBalance(vaultQuoteAssetTreasury), 5*(10**6))
+
+      const fairPrice = 300000
+      await program.methods.genUpdateBinaryOptionFairPriceTicket().accounts({
+        vaultFactoryInfo: vaultFactory,
+        initializer: putTakerKeypair.publicKey,
+        oracleWallet: getOraclePubKey(),
+        binaryOptionFairPriceTicket: getUserTicketAccountAddressForBinaryVaultFactory(program, vaultFactory, putTakerKeypair.publicKey)
+      }).signers([putTakerKeypair]).rpc(confirmOptions)
+      await _testUpdateBinaryOptionFairPrice(program, vaultFactory, putTakerKeypair.publicKey, new anchor.BN(fairPrice))
+
+      const buyLots = async (remainingAccounts: anchor.web3.AccountMeta[]) => {
+        return await program.methods.takerBuyLotsBinaryOptionVault(new anchor.BN(fairPrice), new anchor.BN(2)).accounts({
+          vaultFactoryInfo: vaultFactory,
+          vaultInfo: vault,
+          binaryOptionTakerInfo: getBinaryTakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, putTakerKeypair.publicKey),
+          baseAssetMint: wormholeBTCToken,
+          quoteAssetMint: usdcToken,
+          takerQuoteAssetAccount: putTakerUSDCATA.address,
+          feeVaultInfo: getFeeVaultPdaAddress(program, usdcToken),
+          feeVaultQuoteAssetTreasury: token.getAssociatedTokenAddressSync(usdcToken, getFeeVaultPdaAddress(program, usdcToken), true),
+          insuranceFundInfo: getInsuranceFundPdaAddress(program, usdcToken),
+          frontendInfo: null,
+          feeTiersInfo: getFeeTiersPdaAddress(program, usdcToken),
+          takerStatsInfo: getTakerStatsPdaAddress(program, putTakerKeypair.publicKey, usdcToken),
+          initializer: putTakerKeypair.publicKey,
+          tokenProgram: token.TOKEN_PROGRAM_ID
+        }).remainingAccounts(remainingAccounts).signers([putTakerKeypair]).rpc(confirmOptions)
+      }
+      const invalidRemainingAccounts = {
+        "a premium account that is not the maker's": [
+          { pubkey: makerInfo, isSigner: false, isWritable: true },
+          { pubkey: putTakerUSDCATA.address, isSigner: false, isWritable: true }
+        ],
+        "a maker of another vault": [
+          { pubkey: otherVaultMakerInfo, isSigner: false, isWritable: true },
+          { pubkey: putMaker2USDCATA.address, isSigner: false, isWritable: true }
+        ],
+        "a maker account not owned by the program": [
+          { pubkey: putMakerUSDCATA.address, isSigner: false, isWritable: true },
+          { pubkey: putMakerUSDCATA.address, isSigner: false, isWritable: true }
+        ],
+        "a read-only maker account": [
+          { pubkey: makerInfo, isSigner: false, isWritable: false },
+          { pubkey: putMakerUSDCATA.address, isSigner: false, isWritable: true }
+        ],
+        "the same maker twice": [
+          { pubkey: makerInfo, isSigner: false, isWritable: true },
+          { pubkey: putMakerUSDCATA.address, isSigner: false, isWritable: true },
+          { pubkey: makerInfo, isSigner: false, isWritable: true },
+          { pubkey: putMakerUSDCATA.address, isSigner: false, isWritable: true }
+        ]
+      }
+      for (const [description, remainingAccounts] of Object.entries(invalidRemainingAccounts)) {
+        try {
+          await buyLots(remainingAccounts)
+          assert.fail(`Lots were bought from ${description}`)
+        } catch (e) {
+          assert.equal(e.error?.errorCode?.code, "AccountValidationError", description)
+        }
+      }
+
+      // the taker pays the price of 2 lots, the maker gets it net of fees
+      const makerUSDCBefore = await tokenBalance(putMakerUSDCATA.address)
+      const takerUSDCBefore = await tokenBalance(putTakerUSDCATA.address)
+      await buyLots([
+        { pubkey: makerInfo, isSigner: false, isWritable: true },
+        { pubkey: putMakerUSDCATA.address, isSigner: false, isWritable: true }
+      ])
+      assert.equal(await tokenBalance(putTakerUSDCATA.address), takerUSDCBefore - 2*fairPrice)
+      const makerPremium = await tokenBalance(putMakerUSDCATA.address) - makerUSDCBefore
+      assert.isAbove(makerPremium, 0)
+      assert.isBelow(makerPremium, 2*fairPrice)
+      assert.equal((await program.account.binaryOptionMakerInfo.fetch(makerInfo)).volumeSold.toNumber(), 2*(10**6))
+      assert.equal((await program.account.binaryOptionTakerInfo.fetch(getBinaryTakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, putTakerKeypair.publicKey))).numLots.toNumber(), 2)
+
+      // emergency mode cannot be activated before the grace period after maturity is over,
+      // and makers cannot take their collateral back without it
+      try {
+        await program.methods.makerActivateBinaryOptionEmergencyMode().accounts({
+          vaultFactoryInfo: vaultFactory,
+          vaultInfo: vault,
+          binaryOptionMakerInfo: makerInfo,
+          initializer: putMakerKeypair.publicKey
+        }).signers([putMakerKeypair]).rpc(confirmOptions)
+        assert.fail("Maker activated emergency mode before the grace period")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "EmergencyModeTooEarly")
+      }
+      try {
+        await program.methods.takerActivateBinaryOptionEmergencyMode().accounts({
+          vaultFactoryInfo: vaultFactory,
+          vaultInfo: vault,
+          binaryOptionTakerInfo: getBinaryTakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, putTakerKeypair.publicKey),
+          initializer: putTakerKeypair.publicKey
+        }).signers([putTakerKeypair]).rpc(confirmOptions)
+        assert.fail("Taker activated emergency mode before the grace period")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "EmergencyModeTooEarly")
+      }
+      try {
+        await program.methods.makerBinaryOptionEmergencyExit().accounts({
+          vaultFactoryInfo: vaultFactory,
+          vaultInfo: vault,
+          binaryOptionMakerInfo: makerInfo,
+          quoteAssetMint: usdcToken,
+          vaultQuoteAssetTreasury: vaultQuoteAssetTreasury,
+          makerQuoteAssetAccount: putMakerUSDCATA.address,
+          initializer: putMakerKeypair.publicKey,
+          tokenProgram: token.TOKEN_PROGRAM_ID
+        }).signers([putMakerKeypair]).rpc(confirmOptions)
+        assert.fail("Maker exited a binary option vault that is not in emergency mode")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "ConstraintRaw")
+      }
+      assert.equal(await tokenBalance(vaultQuoteAssetTreasury), 5*(10**6))
+    });
+
+    it("Waiting for the short maturity", async function () {
+      const waitSeconds = shortMaturity.toNumber() - Math.floor(Date.now()/1000) + 30
+      this.timeout((waitSeconds + 60)*1000)
+      if (waitSeconds > 0) {
+        console.log(`Waiting ${waitSeconds} seconds for the vaults of the short series to mature`)
+        await new Promise(resolve => setTimeout(resolve, waitSeconds*1000))
+      }
+    });
+
+    it("Settling the private put option vault of the accepted RFQ quote", async () => {
+      const conn = anchor.getProvider().connection
+      const { vaultFactory, vault, collateral } = rfqVault
+      const mintInfoWBTC = await token.getMint(conn, wormholeBTCToken)
+
+      await program.methods.genSettlePutOptionPriceTicket().accounts({
+        vaultFactoryInfo: vaultFactory,
+        initializer: putTakerKeypair.publicKey,
+        oracleWallet: getOraclePubKey(),
+        putOptionSettlePriceTicket: await getUserSettleTicketAccountAddressForPutVaultFactory(program, vaultFactory, putTakerKeypair.publicKey)
+      }).signers([putTakerKeypair]).rpc(confirmOptions)
+      // below the strike, the options are exercised
+      await _testSettlePutOptionPrice(program, vaultFactory, putTakerKeypair.publicKey, new anchor.BN(20000*(10**6)))
+      const factory = await program.account.putOptionVaultFactoryInfo.fetch(vaultFactory)
+      assert.isTrue(factory.matured)
+
+      const vaultBaseAssetTreasury = token.getAssociatedTokenAddressSync(wormholeBTCToken, vault, true)
+      const vaultQuoteAssetTreasury = token.getAssociatedTokenAddressSync(usdcToken, vault, true)
+      const deposited = Number((await token.getAccount(conn, vaultBaseAssetTreasury)).amount)
+
+      const takerInfo = (await getUserTakerInfoForPutVault(program, vault, putTakerKeypair.publicKey))[0]
+      const takerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putTakerKeypair.publicKey)
+      const takerUSDCBefore = Number(takerUSDCATA.amount)
+      await program.methods.takerSettlePutOption().accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        initializer: putTakerKeypair.publicKey,
+        putOptionTakerInfo: takerInfo.publicKey,
+        takerBaseAssetAccount: token.getAssociatedTokenAddressSync(wormholeBTCToken, putTakerKeypair.publicKey, false),
+        takerQuoteAssetAccount: takerUSDCATA.address,
+        vaultBaseAssetTreasury: vaultBaseAssetTreasury,
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: vault,
+        vaultQuoteAssetTreasury: vaultQuoteAssetTreasury
+      }).signers([putTakerKeypair]).rpc(confirmOptions)
+      // the taker sells all her deposited base asset at strike
+      const takerPayout = Math.floor(deposited/(10**mintInfoWBTC.decimals)*shortStrike.toNumber())
+      assert.equal(Number((await token.getAccount(conn, takerUSDCATA.address)).amount), takerUSDCBefore + takerPayout)
+
+      const makerInfo = (await getUserMakerInfoForPutVault(program, vault, putMakerKeypair.publicKey))[0]
+      const makerwBTCATA = await createTokenAccount(conn, minterKeypair, wormholeBTCToken, putMakerKeypair.publicKey)
+      const makerwBTCBefore = Number(makerwBTCATA.amount)
+      await program.methods.makerSettlePutOption().accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        initializer: putMakerKeypair.publicKey,
+        putOptionMakerInfo: makerInfo.publicKey,
+        makerBaseAssetAccount: makerwBTCATA.address,
+        makerQuoteAssetAccount: token.getAssociatedTokenAddressSync(usdcToken, putMakerKeypair.publicKey, false),
+        vaultBaseAssetTreasury: vaultBaseAssetTreasury,
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: vault,
+        vaultQuoteAssetTreasury: vaultQuoteAssetTreasury
+      }).signers([putMakerKeypair]).rpc(confirmOptions)
+      // the maker buys the deposited base asset with her collateral, the vault is left empty
+      const makerBought = Math.floor(collateral/shortStrike.toNumber()*(10**mintInfoWBTC.decimals))
+      assert.equal(makerBought, deposited)
+      assert.equal(Number((await token.getAccount(conn, makerwBTCATA.address)).amount), makerwBTCBefore + makerBought)
+      assert.equal(Number((await token.getAccount(conn, vaultBaseAssetTreasury)).amount), 0)
+    });
+
+
+    it("Settling a margined put option vault with a margin shortfall", async () => {
+      const conn = anchor.getProvider().connection
+      const { vaultFactory, vault, vaultId } = marginedVault
+      const mintInfoWBTC = await token.getMint(conn, wormholeBTCToken)
+      const tokenBalance = async (address: anchor.web3.PublicKey) => Number((await token.getAccount(conn, address)).amount)
+      const strike = marginedStrike.toNumber()
+
+      await program.methods.genSettlePutOptionPriceTicket().accounts({
+        vaultFactoryInfo: vaultFactory,
+        initializer: putTakerKeypair.publicKey,
+        oracleWallet: getOraclePubKey(),
+        putOptionSettlePriceTicket: await getUserSettleTicketAccountAddressForPutVaultFactory(program, vaultFactory, putTakerKeypair.publicKey)
+      }).signers([putTakerKeypair]).rpc(confirmOptions)
+      // far enough below the strike that the margin of the first maker falls short
+      const settlePrice = 12000*(10**6)
+      await _testSettlePutOptionPrice(program, vaultFactory, putTakerKeypair.publicKey, new anchor.BN(settlePrice))
+
+      const vaultBaseAssetTreasury = token.getAssociatedTokenAddressSync(wormholeBTCToken, vault, true)
+      const vaultQuoteAssetTreasury = token.getAssociatedTokenAddressSync(usdcToken, vault, true)
+      const takerInfo = (await getUserTakerInfoForPutVault(program, vault, putTakerKeypair.publicKey))[0]
+      const takerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, putTakerKeypair.publicKey)
+      const takerSettle = () => program.methods.takerSettlePutOption().accounts({
+        tokenProgram: token.TOKEN_PROGRAM_ID,
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        initializer: putTakerKeypair.publicKey,
+        putOptionTakerInfo: takerInfo.publicKey,
+        takerBaseAssetAccount: token.getAssociatedTokenAddressSync(wormholeBTCToken, putTakerKeypair.publicKey, false),
+        takerQuoteAssetAccount: takerUSDCATA.address,
+        vaultBaseAssetTreasury: vaultBaseAssetTreasury,
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: vault,
+        vaultQuoteAssetTreasury: vaultQuoteAssetTreasury
+      }).signers([putTakerKeypair]).rpc(confirmOptions)
+
+      // takers of margined vaults wait for every maker
+      try {
+        await takerSettle()
+        assert.fail("Taker settled before the makers")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "MakersNotSettled")
+      }
+
+      // what each maker owes, computed as compute_put_option_maker_cash_payout does
+      const priceDifference = strike - settlePrice
+      const payoutOf = (volumeSold: number) => Math.min(Math.ceil(volumeSold/strike*priceDifference), volumeSold)
+      const initialSafetyFund = (await program.account.putOptionVaultInfo.fetch(vault)).safetyFund.toNumber()
+      let safetyFund = initialSafetyFund
+      let totalPayout = 0
+      let uncovered = 0
+      const settleMaker = async (settler: anchor.web3.Keypair, owner: anchor.web3.PublicKey) => {
+        const makerInfo = await getPutMakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, owner)
+        const maker = await program.account.putOptionMakerInfo.fetch(makerInfo)
+        const makerwBTCATA = await createTokenAccount(conn, minterKeypair, wormholeBTCToken, owner)
+        const makerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, owner)
+        const makerUSDCBefore = Number(makerUSDCATA.amount)
+        await program.methods.makerSettlePutOption().accounts({
+          tokenProgram: token.TOKEN_PROGRAM_ID,
+          baseAssetMint: wormholeBTCToken,
+          quoteAssetMint: usdcToken,
+          initializer: settler.publicKey,
+          putOptionMakerInfo: makerInfo,
+          makerBaseAssetAccount: makerwBTCATA.address,
+          makerQuoteAssetAccount: makerUSDCATA.address,
+          vaultBaseAssetTreasury: vaultBaseAssetTreasury,
+          vaultFactoryInfo: vaultFactory,
+          vaultInfo: vault,
+          vaultQuoteAssetTreasury: vaultQuoteAssetTreasury
+        }).signers([settler]).rpc(confirmOptions)
+        const payout = payoutOf(maker.volumeSold.toNumber())
+        const margin = maker.marginPosted.toNumber()
+        const shortfall = Math.max(payout - margin, 0)
+        const covered = Math.min(shortfall, safetyFund)
+        safetyFund -= covered
+        uncovered += shortfall - covered
+        totalPayout += payout
+        // she gets back whatever margin her payout leaves
+        assert.equal(await tokenBalance(makerUSDCATA.address), makerUSDCBefore + Math.max(margin - payout, 0))
+        return shortfall
+      }
+
+      // the first maker owes more than her margin, the safety fund only covers part of it
+      const firstShortfall = await settleMaker(putMakerKeypair, putMakerKeypair.publicKey)
+      assert.isAbove(firstShortfall, initialSafetyFund)
+      // anyone can settle makers of a margined vault, the liquidator settles the liquidated maker too
+      await settleMaker(callTakerKeypair, putMaker2Keypair.publicKey)
+      await settleMaker(callTakerKeypair, callTakerKeypair.publicKey)
+
+      const settledVault = await program.account.putOptionVaultInfo.fetch(vault)
+      assert.equal(settledVault.makersSettled, 3)
+      assert.equal(settledVault.makersTotalPayout.toNumber(), totalPayout)
+      assert.equal(settledVault.marginShortfall.toNumber(), uncovered)
+      assert.equal(settledVault.safetyFund.toNumber(), 0)
+      assert.isAbove(uncovered, 0)
+      assert.equal(await tokenBalance(vaultQuoteAssetTreasury), totalPayout - uncovered)
+
+      // funding the safety fund after the makers have settled covers part of what they left uncovered
+      const funderUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, callTakerKeypair.publicKey)
+      const fundSafetyFund = (amount: number) => program.methods.fundPutOptionVaultSafetyFund(new anchor.BN(amount)).accounts({
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: vault,
+        quoteAssetMint: usdcToken,
+        vaultQuoteAssetTreasury: vaultQuoteAssetTreasury,
+        funderQuoteAssetAccount: funderUSDCATA.address,
+        initializer: callTakerKeypair.publicKey,
+        tokenProgram: token.TOKEN_PROGRAM_ID
+      }).signers([callTakerKeypair]).rpc(confirmOptions)
+      const lateFunding = Math.floor(uncovered/2)
+      await mintTokens(conn, minterKeypair, usdcToken, funderUSDCATA.address, minterKeypair, Math.ceil(2*lateFunding/(10**6)))
+      await fundSafetyFund(lateFunding)
+      uncovered -= lateFunding
+      let fundedVault = await program.account.putOptionVaultInfo.fetch(vault)
+      assert.equal(fundedVault.marginShortfall.toNumber(), uncovered)
+      assert.equal(fundedVault.safetyFund.toNumber(), 0)
+
+      // and so does a draw from the insurance fund
+      const insuranceFund = getInsuranceFundPdaAddress(program, usdcToken)
+      const insuranceFundTreasury = token.getAssociatedTokenAddressSync(usdcToken, insuranceFund, true)
+      const lateDraw = Math.floor(uncovered/2)
+      await mintTokens(conn, minterKeypair, usdcToken, insuranceFundTreasury, minterKeypair, Math.ceil(lateDraw/(10**6)))
+      const fund = await program.account.insuranceFundInfo.fetch(insuranceFund)
+      const [drawInfo] = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(anchor.utils.bytes.utf8.encode("InsuranceFundDrawInfo")),
+          insuranceFund.toBuffer(),
+          fund.numDraws.addn(1).toArrayLike(Buffer, "le", 8)
+        ],
+        program.programId
+      )
+      await program.methods.adminDrawInsuranceFundToPutOptionVault(new anchor.BN(lateDraw), { marginShortfall: {} }).accounts({
+        insuranceFundInfo: insuranceFund,
+        insuranceFundQuoteAssetTreasury: insuranceFundTreasury,
+        insuranceFundDrawInfo: drawInfo,
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: vault,
+        vaultQuoteAssetTreasury: vaultQuoteAssetTreasury,
+        quoteAssetMint: usdcToken,
+        initializer: protocolFeesKeypair.publicKey,
+        tokenProgram: token.TOKEN_PROGRAM_ID
+      }).signers([protocolFeesKeypair]).rpc(confirmOptions)
+      uncovered -= lateDraw
+      fundedVault = await program.account.putOptionVaultInfo.fetch(vault)
+      assert.equal(fundedVault.marginShortfall.toNumber(), uncovered)
+      assert.equal(fundedVault.safetyFund.toNumber(), 0)
+      const treasuryBeforeTaker = await tokenBalance(vaultQuoteAssetTreasury)
+      assert.equal(treasuryBeforeTaker, totalPayout - uncovered)
+
+      // what is left of the safety fund only goes back to the insurance fund once everybody has settled
+      const returnSafetyFund = () => program.methods.crankReturnPutOptionSafetyFund().accounts({
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: vault,
+        quoteAssetMint: usdcToken,
+        vaultQuoteAssetTreasury: vaultQuoteAssetTreasury,
+        insuranceFundInfo: insuranceFund,
+        insuranceFundQuoteAssetTreasury: token.getAssociatedTokenAddressSync(usdcToken, insuranceFund, true),
+        initializer: callTakerKeypair.publicKey,
+        tokenProgram: token.TOKEN_PROGRAM_ID
+      }).signers([callTakerKeypair]).rpc(confirmOptions)
+      try {
+        await returnSafetyFund()
+        assert.fail("Safety fund was returned before the taker settled")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "VaultNotFullySettled")
+      }
+
+      // the taker is paid pro-rata what the makers and the safety fund actually paid
+      const payoff = Math.floor(takerInfo.account.maxBaseAsset.toNumber()/(10**mintInfoWBTC.decimals)*priceDifference)
+      const paid = Number(BigInt(payoff)*BigInt(totalPayout - uncovered)/BigInt(totalPayout))
+      assert.isBelow(paid, payoff)
+      const takerUSDCBefore = Number(takerUSDCATA.amount)
+      await takerSettle()
+      assert.equal(await tokenBalance(takerUSDCATA.address), takerUSDCBefore + paid)
+      assert.equal(await tokenBalance(vaultQuoteAssetTreasury), treasuryBeforeTaker - paid)
+
+      // the share of takers is fixed once they start settling
+      try {
+        await fundSafetyFund(lateFunding)
+        assert.fail("Safety fund was funded after a taker settled")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "TakersSettling")
+      }
+      const takerSettledVault = await program.account.putOptionVaultInfo.fetch(vault)
+      assert.equal(takerSettledVault.takersSettled, takerSettledVault.takersNum)
+      await returnSafetyFund()
+      assert.equal((await program.account.putOptionVaultInfo.fetch(vault)).safetyFund.toNumber(), 0)
+    });
+
+    it("Settling a covered call roll vault and withdrawing its premiums", async () => {
+      const conn = anchor.getProvider().connection
+      const { rollVault, vaultFactory, vault, vaultId, idle, committed, premium } = coveredCallRoll
+      const tokenBalance = async (address: anchor.web3.PublicKey) => Number((await token.getAccount(conn, address)).amount)
+      const rollBaseAssetTreasury = token.getAssociatedTokenAddressSync(wormholeBTCToken, rollVault, true)
+      const rollQuoteAssetTreasury = token.getAssociatedTokenAddressSync(usdcToken, rollVault, true)
+
+      await program.methods.genSettleCallOptionPriceTicket().accounts({
+        vaultFactoryInfo: vaultFactory,
+        initializer: callTakerKeypair.publicKey,
+        oracleWallet: getOraclePubKey(),
+        callOptionSettlePriceTicket: await getUserSettleTicketAccountAddressForCallVaultFactory(program, vaultFactory, callTakerKeypair.publicKey)
+      }).signers([callTakerKeypair]).rpc(confirmOptions)
+      // below the strike, the options are not exercised
+      const settlePrice = 28000*(10**6)
+      await _testSettleCallOptionPrice(program, vaultFactory, callTakerKeypair.publicKey, new anchor.BN(settlePrice))
+
+      const settleTx = await program.methods.crankSettleCoveredCallRollVault().accounts({
+        rollVaultInfo: rollVault,
+        rollBaseAssetTreasury: rollBaseAssetTreasury,
+        rollQuoteAssetTreasury: rollQuoteAssetTreasury,
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: vault,
+        callOptionMakerInfo: await getCallMakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, rollVault),
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        vaultBaseAssetTreasury: token.getAssociatedTokenAddressSync(wormholeBTCToken, vault, true),
+        vaultQuoteAssetTreasury: token.getAssociatedTokenAddressSync(usdcToken, vault, true),
+        initializer: callTakerKeypair.publicKey,
+        tokenProgram: token.TOKEN_PROGRAM_ID
+      }).signers([callTakerKeypair]).rpc(confirmOptions)
+      const t = await conn.getTransaction(settleTx, { maxSupportedTransactionVersion: 0, commitment: "confirmed" })
+      const [, , buffer] = getReturnLog(t)
+      const reader = new borsh.BinaryReader(buffer)
+      assert.equal(reader.readU64().toNumber(), committed)
+      assert.equal(reader.readU64().toNumber(), 0)
+
+      // the whole position is back, and the next series is one tenor later, at the offset from the settled price
+      let roll = await program.account.coveredCallRollVaultInfo.fetch(rollVault)
+      assert.isFalse(roll.isInSeries)
+      assert.equal(roll.baseAssetIdle.toNumber(), idle + committed)
+      assert.equal(roll.baseAssetCommitted.toNumber(), 0)
+      assert.equal(roll.quoteAssetAccrued.toNumber(), premium)
+      assert.equal(roll.nextStrike.toNumber(), 29400*(10**6))
+      assert.equal(roll.nextMaturity.toNumber(), shortMaturity.toNumber() + roll.tenorSeconds.toNumber())
+      assert.equal(await tokenBalance(rollBaseAssetTreasury), idle + committed)
+
+      // shareholders get out with their part of the base asset and of the premiums
+      const shares = (await program.account.coveredCallRollShareInfo.fetch(getCoveredCallRollSharePdaAddress(program, rollVault, callMakerKeypair.publicKey))).shares.toNumber()
+      const totalShares = roll.totalShares.toNumber()
+      const callMakerwBTCATA = await createTokenAccount(conn, minterKeypair, wormholeBTCToken, callMakerKeypair.publicKey)
+      const callMakerUSDCATA = await createTokenAccount(conn, minterKeypair, usdcToken, callMakerKeypair.publicKey)
+      await program.methods.withdrawCoveredCallRollVault(new anchor.BN(shares)).accounts({
+        rollVaultInfo: rollVault,
+        shareInfo: getCoveredCallRollSharePdaAddress(program, rollVault, callMakerKeypair.publicKey),
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        rollBaseAssetTreasury: rollBaseAssetTreasury,
+        rollQuoteAssetTreasury: rollQuoteAssetTreasury,
+        depositorBaseAssetAccount: callMakerwBTCATA.address,
+        depositorQuoteAssetAccount: callMakerUSDCATA.address,
+        initializer: callMakerKeypair.publicKey,
+        tokenProgram: token.TOKEN_PROGRAM_ID
+      }).signers([callMakerKeypair]).rpc(confirmOptions)
+      const baseAssetOut = Math.floor(shares*(idle + committed)/totalShares)
+      const quoteAssetOut = Math.floor(shares*premium/totalShares)
+      assert.equal(await tokenBalance(callMakerwBTCATA.address), Number(callMakerwBTCATA.amount) + baseAssetOut)
+      assert.equal(await tokenBalance(callMakerUSDCATA.address), Number(callMakerUSDCATA.amount) + quoteAssetOut)
+      roll = await program.account.coveredCallRollVaultInfo.fetch(rollVault)
+      assert.equal(roll.totalShares.toNumber(), totalShares - shares)
+      assert.equal(roll.baseAssetIdle.toNumber(), idle + committed - baseAssetOut)
+      assert.equal(roll.quoteAssetAccrued.toNumber(), premium - quoteAssetOut)
+    });
+
+    it("Settling a cash-secured put roll vault and pricing the queued deposit", async () => {
+      const conn = anchor.getProvider().connection
+      const { rollVault, shareMint, vaultFactory, vault, vaultId, idle, committed, premium, pendingDeposit } = putRoll
+      const rollQuoteAssetTreasury = token.getAssociatedTokenAddressSync(usdcToken, rollVault, true)
+      const rollShareEscrow = token.getAssociatedTokenAddressSync(shareMint, rollVault, true)
+
+      await program.methods.genSettlePutOptionPriceTicket().accounts({
+        vaultFactoryInfo: vaultFactory,
+        initializer: putTakerKeypair.publicKey,
+        oracleWallet: getOraclePubKey(),
+        putOptionSettlePriceTicket: await getUserSettleTicketAccountAddressForPutVaultFactory(program, vaultFactory, putTakerKeypair.publicKey)
+      }).signers([putTakerKeypair]).rpc(confirmOptions)
+      // above the strike, the options are not exercised
+      await _testSettlePutOptionPrice(program, vaultFactory, putTakerKeypair.publicKey, new anchor.BN(30000*(10**6)))
+
+      const settleTx = await program.methods.crankSettleCashSecuredPutRollVault().accounts({
+        rollVaultInfo: rollVault,
+        rollQuoteAssetTreasury: rollQuoteAssetTreasury,
+        vaultFactoryInfo: vaultFactory,
+        vaultInfo: vault,
+        putOptionMakerInfo: await getPutMakerVaultAssociatedAccountAddress(program, vaultFactory, vaultId, rollVault),
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        vaultQuoteAssetTreasury: token.getAssociatedTokenAddressSync(usdcToken, vault, true),
+        initializer: putTakerKeypair.publicKey,
+        tokenProgram: token.TOKEN_PROGRAM_ID
+      }).signers([putTakerKeypair]).rpc(confirmOptions)
+      const t = await conn.getTransaction(settleTx, { maxSupportedTransactionVersion: 0, commitment: "confirmed" })
+      const [, , buffer] = getReturnLog(t)
+      assert.equal(new borsh.BinaryReader(buffer).readU64().toNumber(), committed)
+
+      // the premium is now owned by the shareholders, the deposit queued during the series is not
+      let roll = await program.account.cashSecuredPutRollVaultInfo.fetch(rollVault)
+      assert.isFalse(roll.isInSeries)
+      assert.equal(roll.quoteAssetCommitted.toNumber(), 0)
+      assert.equal(roll.quoteAssetIdle.toNumber(), idle + committed + premium)
+      assert.equal(roll.pendingDeposits.toNumber(), pendingDeposit)
+      assert.equal(roll.nextMaturity.toNumber(), shortMaturity.toNumber() + roll.tenorSeconds.toNumber())
+
+      // so the queued deposit gets fewer shares than quote asset lamports
+      const epoch = roll.epoch
+      const totalShares = roll.totalShares.toNumber()
+      await program.methods.crankProcessCashSecuredPutRollQueues().accounts({
+        rollVaultInfo: rollVault,
+        epochInfo: getCashSecuredPutRollEpochPdaAddress(program, rollVault, epoch),
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        shareMint: shareMint,
+        rollShareEscrow: rollShareEscrow,
+        initializer: putTakerKeypair.publicKey,
+        tokenProgram: token.TOKEN_PROGRAM_ID
+      }).signers([putTakerKeypair]).rpc(confirmOptions)
+      const sharesMinted = Math.floor(pendingDeposit*totalShares/(idle + committed + premium))
+      assert.isBelow(sharesMinted, pendingDeposit)
+      const epochInfo = await program.account.cashSecuredPutRollEpochInfo.fetch(getCashSecuredPutRollEpochPdaAddress(program, rollVault, epoch))
+      assert.equal(epochInfo.sharesMinted.toNumber(), sharesMinted)
+      roll = await program.account.cashSecuredPutRollVaultInfo.fetch(rollVault)
+      assert.equal(roll.totalShares.toNumber(), totalShares + sharesMinted)
+      assert.equal(roll.quoteAssetIdle.toNumber(), idle + committed + premium + pendingDeposit)
+
+      const putMaker2ShareATA = await createTokenAccount(conn, minterKeypair, shareMint, putMaker2Keypair.publicKey)
+      await program.methods.claimCashSecuredPutRollTicket().accounts({
+        rollVaultInfo: rollVault,
+        ticketInfo: getCashSecuredPutRollTicketPdaAddress(program, rollVault, putMaker2Keypair.publicKey),
+        epochInfo: getCashSecuredPutRollEpochPdaAddress(program, rollVault, epoch),
+        baseAssetMint: wormholeBTCToken,
+        quoteAssetMint: usdcToken,
+        shareMint: shareMint,
+        rollQuoteAssetTreasury: rollQuoteAssetTreasury,
+        depositorShareAccount: putMaker2ShareATA.address,
+        depositorQuoteAssetAccount: token.getAssociatedTokenAddressSync(usdcToken, putMaker2Keypair.publicKey, false),
+        initializer: putMaker2Keypair.publicKey,
+        tokenProgram: token.TOKEN_PROGRAM_ID
+      }).signers([putMaker2Keypair]).rpc(confirmOptions)
+      assert.equal(Number((await token.getAccount(conn, putMaker2ShareATA.address)).amount), Number(putMaker2ShareATA.amount) + sharesMinted)
+    });
+
   }
 
   
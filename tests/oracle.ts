@@ -1,6 +1,6 @@
 import * as anchor from "@coral-xyz/anchor";
 import { AnchorSolhedge } from "../target/types/anchor_solhedge";
-import { getUserSettleTicketAccountAddressForPutVaultFactory, getUserTicketAccountAddressForPutVaultFactory, getUserTicketAccountAddressForCallVaultFactory } from "./accounts";
+import { getSeriesIndexPdaAddress, getUserSettleTicketAccountAddressForPutVaultFactory, getUserTicketAccountAddressForPutVaultFactory, getUserTicketAccountAddressForCallVaultFactory, getUserSettleTicketAccountAddressForCallVaultFactory, getUserTicketAccountAddressForBinaryVaultFactory } from "./accounts";
 import axios from 'axios'
 import { cdfStdNormal, convertInterest, volatilitySquared } from "./stats";
 import * as token from "@solana/spl-token"
@@ -120,12 +120,137 @@ export const updatePutOptionSettlePrice = async (
     let tx = program.methods.oracleUpdatePutOptionSettlePrice(new anchor.BN(settlePrice)).accounts({
         vaultFactoryInfo: vaultFactoryInfo,
         updateTicket: settleTicketAddress,
+        seriesIndex: getSeriesIndexPdaAddress(program, vaultFactoryAccount.baseAsset, vaultFactoryAccount.quoteAsset),
         ticketOwner: user,
         initializer: oracleKeyPair.publicKey
     }).signers([oracleKeyPair]).rpc()
     return tx
 }
 
+// Localnet only: the oracle sets the fair price of a put option vault factory to a price chosen by the test,
+// consuming the fair price ticket of user
+export const _testUpdatePutOptionFairPrice = async (
+    program: anchor.Program<AnchorSolhedge>,
+    vaultFactoryInfo: anchor.web3.PublicKey,
+    user: anchor.web3.PublicKey,
+    fairPrice: anchor.BN
+): Promise<string> => {
+    const ticketAddress = await getUserTicketAccountAddressForPutVaultFactory(program, vaultFactoryInfo, user)
+    const oracleKeyPair = anchor.web3.Keypair.fromSecretKey(Uint8Array.from(ORACLE_KEY))
+    return program.methods.oracleUpdatePutOptionPrice(fairPrice).accounts({
+        vaultFactoryInfo: vaultFactoryInfo,
+        updateTicket: ticketAddress,
+        ticketOwner: user,
+        initializer: oracleKeyPair.publicKey
+    }).signers([oracleKeyPair]).rpc({ commitment: "confirmed" })
+}
+
+// Localnet only: like _testUpdatePutOptionFairPrice, also observing a spot price chosen by the test
+export const _testUpdatePutOptionFairPriceWithSpot = async (
+    program: anchor.Program<AnchorSolhedge>,
+    vaultFactoryInfo: anchor.web3.PublicKey,
+    user: anchor.web3.PublicKey,
+    fairPrice: anchor.BN,
+    spotPrice: anchor.BN
+): Promise<string> => {
+    const ticketAddress = await getUserTicketAccountAddressForPutVaultFactory(program, vaultFactoryInfo, user)
+    const oracleKeyPair = anchor.web3.Keypair.fromSecretKey(Uint8Array.from(ORACLE_KEY))
+    return program.methods.oracleUpdatePutOptionPriceWithSpot(fairPrice, spotPrice).accounts({
+        vaultFactoryInfo: vaultFactoryInfo,
+        updateTicket: ticketAddress,
+        ticketOwner: user,
+        initializer: oracleKeyPair.publicKey
+    }).signers([oracleKeyPair]).rpc({ commitment: "confirmed" })
+}
+
+// Localnet only: the oracle settles a matured put option vault factory at a price chosen by the test,
+// consuming the settle ticket of user
+export const _testSettlePutOptionPrice = async (
+    program: anchor.Program<AnchorSolhedge>,
+    vaultFactoryInfo: anchor.web3.PublicKey,
+    user: anchor.web3.PublicKey,
+    settlePrice: anchor.BN
+): Promise<string> => {
+    const settleTicketAddress = await getUserSettleTicketAccountAddressForPutVaultFactory(program, vaultFactoryInfo, user)
+    const vaultFactoryAccount = await program.account.putOptionVaultFactoryInfo.fetch(vaultFactoryInfo)
+    const oracleKeyPair = anchor.web3.Keypair.fromSecretKey(Uint8Array.from(ORACLE_KEY))
+    return program.methods.oracleUpdatePutOptionSettlePrice(settlePrice).accounts({
+        vaultFactoryInfo: vaultFactoryInfo,
+        updateTicket: settleTicketAddress,
+        seriesIndex: getSeriesIndexPdaAddress(program, vaultFactoryAccount.baseAsset, vaultFactoryAccount.quoteAsset),
+        ticketOwner: user,
+        initializer: oracleKeyPair.publicKey
+    }).signers([oracleKeyPair]).rpc({ commitment: "confirmed" })
+}
+
+// Localnet only: the oracle sets the fair price of a call option vault factory to a price chosen by the test,
+// consuming the fair price ticket of user
+export const _testUpdateCallOptionFairPrice = async (
+    program: anchor.Program<AnchorSolhedge>,
+    vaultFactoryInfo: anchor.web3.PublicKey,
+    user: anchor.web3.PublicKey,
+    fairPrice: anchor.BN
+): Promise<string> => {
+    const ticketAddress = await getUserTicketAccountAddressForCallVaultFactory(program, vaultFactoryInfo, user)
+    const oracleKeyPair = anchor.web3.Keypair.fromSecretKey(Uint8Array.from(ORACLE_KEY))
+    return program.methods.oracleUpdateCallOptionPrice(fairPrice).accounts({
+        vaultFactoryInfo: vaultFactoryInfo,
+        updateTicket: ticketAddress,
+        ticketOwner: user,
+        initializer: oracleKeyPair.publicKey
+    }).signers([oracleKeyPair]).rpc({ commitment: "confirmed" })
+}
+
+// Localnet only: the oracle settles a matured call option vault factory at a price chosen by the test,
+// consuming the settle ticket of user
+export const _testSettleCallOptionPrice = async (
+    program: anchor.Program<AnchorSolhedge>,
+    vaultFactoryInfo: anchor.web3.PublicKey,
+    user: anchor.web3.PublicKey,
+    settlePrice: anchor.BN
+): Promise<string> => {
+    const settleTicketAddress = await getUserSettleTicketAccountAddressForCallVaultFactory(program, vaultFactoryInfo, user)
+    const vaultFactoryAccount = await program.account.callOptionVaultFactoryInfo.fetch(vaultFactoryInfo)
+    const oracleKeyPair = anchor.web3.Keypair.fromSecretKey(Uint8Array.from(ORACLE_KEY))
+    return program.methods.oracleUpdateCallOptionSettlePrice(settlePrice).accounts({
+        vaultFactoryInfo: vaultFactoryInfo,
+        updateTicket: settleTicketAddress,
+        seriesIndex: getSeriesIndexPdaAddress(program, vaultFactoryAccount.baseAsset, vaultFactoryAccount.quoteAsset),
+        ticketOwner: user,
+        initializer: oracleKeyPair.publicKey
+    }).signers([oracleKeyPair]).rpc({ commitment: "confirmed" })
+}
+
+// Localnet only: the oracle sets the price of one lot of a binary option vault factory to a price chosen by the test,
+// consuming the fair price ticket of user
+export const _testUpdateBinaryOptionFairPrice = async (
+    program: anchor.Program<AnchorSolhedge>,
+    vaultFactoryInfo: anchor.web3.PublicKey,
+    user: anchor.web3.PublicKey,
+    fairPrice: anchor.BN
+): Promise<string> => {
+    const oracleKeyPair = anchor.web3.Keypair.fromSecretKey(Uint8Array.from(ORACLE_KEY))
+    return program.methods.oracleUpdateBinaryOptionPrice(fairPrice).accounts({
+        vaultFactoryInfo: vaultFactoryInfo,
+        updateTicket: getUserTicketAccountAddressForBinaryVaultFactory(program, vaultFactoryInfo, user),
+        ticketOwner: user,
+        initializer: oracleKeyPair.publicKey
+    }).signers([oracleKeyPair]).rpc({ commitment: "confirmed" })
+}
+
+// Localnet only: the oracle observes a spot price chosen by the test for a cash-secured put roll vault
+export const _testUpdateCashSecuredPutRollSpotPrice = async (
+    program: anchor.Program<AnchorSolhedge>,
+    rollVaultInfo: anchor.web3.PublicKey,
+    spotPrice: anchor.BN
+): Promise<string> => {
+    const oracleKeyPair = anchor.web3.Keypair.fromSecretKey(Uint8Array.from(ORACLE_KEY))
+    return program.methods.oracleUpdateCashSecuredPutRollSpotPrice(spotPrice).accounts({
+        rollVaultInfo: rollVaultInfo,
+        initializer: oracleKeyPair.publicKey
+    }).signers([oracleKeyPair]).rpc({ commitment: "confirmed" })
+}
+
 export const updateCallOptionFairPrice = async (
     program: anchor.Program<AnchorSolhedge>,
     vaultFactoryInfo: anchor.web3.PublicKey,